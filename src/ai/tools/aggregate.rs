@@ -5,8 +5,8 @@ use rig::tool::Tool;
 use serde::Deserialize;
 
 use super::{
-    MAX_FIND_LIMIT, MAX_OUTPUT_BYTES, MongoContext, ToolError, doc_to_json, parse_json_to_doc,
-    resolve_collection, truncate_output,
+    MAX_FIND_LIMIT, MAX_OUTPUT_BYTES, MongoContext, ToolError, doc_to_json, ensure_writable,
+    parse_json_to_doc, resolve_collection, truncate_output,
 };
 
 pub struct AggregateTool(MongoContext);
@@ -74,6 +74,10 @@ impl Tool for AggregateTool {
             })
             .collect::<Result<Vec<_>, _>>()?;
 
+        if pipeline.iter().any(|stage| stage.contains_key("$out") || stage.contains_key("$merge")) {
+            ensure_writable(&self.0, Self::NAME)?;
+        }
+
         // Force $limit if missing
         let has_limit = pipeline.iter().any(|stage| stage.keys().any(|k| k == "$limit"));
         if !has_limit {
@@ -89,13 +89,14 @@ pub fn build_tools(ctx: MongoContext) -> Vec<Box<dyn ToolDyn>> {
 /// Request user confirmation for a write operation via the event channel.
 ///
 /// Returns `Ok(())` if the operation should proceed, or an appropriate error
-/// if it was blocked or rejected.
+/// if it was blocked or rejected. Read-only connections are refused without asking.
 pub async fn require_confirmation(
     ctx: &MongoContext,
     tool_name: &str,
     args_json: &str,
     preview: OperationPreview,
 ) -> Result<(), ToolError> {
+    ensure_writable(ctx, tool_name)?;
     let classification = classify_tool_call(tool_name, args_json);
     match classification.tier {
         SafetyTier::AutoExecute => Ok(()),
@@ -126,6 +127,12 @@ pub async fn require_confirmation(
     }
 }
 
+/// Refuse write tools on read-only connections.
+pub fn ensure_writable(ctx: &MongoContext, tool_name: &str) -> Result<(), ToolError> {
+    crate::connection::ops::ensure_writable(&ctx.client, tool_name)
+        .map_err(|err| ToolError::Rejected(err.to_string()))
+}
+
 /// Truncate a JSON value's serialized form to `max_bytes`.
 ///
 /// For objects containing a large array (e.g. `"documents"`, `"results"`),
@@ -3,8 +3,9 @@ use uuid::Uuid;
 
 use crate::components::ConnectionDialog;
 use crate::components::action_bar::ActionExecution;
+use crate::components::saved_query_dialog::run_saved_query;
 use crate::state::settings::AppTheme;
-use crate::state::{ActiveTab, AppCommands, AppState, CollectionSubview, View};
+use crate::state::{ActiveTab, AppCommands, AppState, CollectionSubview, StatusMessage, View};
 use crate::views::CollectionView;
 
 use super::AppRoot;
@@ -199,6 +200,40 @@ impl AppRoot {
             return;
         }
 
+        // Saved queries (format: "saved:<uuid>")
+        if let Some(query_str) = id.strip_prefix("saved:") {
+            let query = Uuid::parse_str(query_str)
+                .ok()
+                .and_then(|query_id| state.read(cx).saved_queries.get(query_id).cloned());
+            if let Some(query) = query {
+                run_saved_query(state.clone(), query, window, cx);
+            }
+            return;
+        }
+
+        // Query history: open the entry's collection and run the query there
+        if let Some(entry_str) = id.strip_prefix("history:") {
+            let entry = Uuid::parse_str(entry_str)
+                .ok()
+                .and_then(|entry_id| state.read(cx).query_history.get(entry_id).cloned());
+            if let Some(entry) = entry {
+                let result = state.update(cx, |state, cx| {
+                    let result = state.restore_query_in_new_tab(&entry, cx);
+                    let message = match &result {
+                        Ok(_) => StatusMessage::info("Query restored from history"),
+                        Err(err) => StatusMessage::error(format!("Could not restore query: {err}")),
+                    };
+                    state.set_status_message(Some(message));
+                    cx.notify();
+                    result
+                });
+                if let Ok(session_key) = result {
+                    AppCommands::load_documents_for_session(state.clone(), session_key, cx);
+                }
+            }
+            return;
+        }
+
         // Theme actions
         if let Some(theme_id) = id.strip_prefix("theme:") {
             if let Some(theme) = AppTheme::from_theme_id(theme_id) {
@@ -376,6 +411,13 @@ impl AppRoot {
                     CollectionSubview::Schema => {
                         AppCommands::analyze_collection_schema(self.state.clone(), session_key, cx);
                     }
+                    CollectionSubview::Validation => {
+                        AppCommands::load_collection_validation(
+                            self.state.clone(),
+                            session_key,
+                            cx,
+                        );
+                    }
                 }
             }
             View::Database => {
@@ -0,0 +1,426 @@
+//! Create-collection dialog with options for capped, time series, clustered
+//! and view collections, a default collation and a validator.
+
+use gpui::prelude::FluentBuilder as _;
+use gpui::*;
+use gpui_component::ActiveTheme as _;
+use gpui_component::WindowExt as _;
+use gpui_component::dialog::Dialog;
+use gpui_component::input::{Input, InputState};
+use gpui_component::scroll::ScrollableElement as _;
+use mongodb::bson::Document;
+use mongodb::options::{TimeseriesGranularity, ValidationAction, ValidationLevel};
+
+use crate::bson::{parse_document_from_json, parse_documents_from_json};
+use crate::components::{Button, FormField, cancel_button};
+use crate::connection::collection_options::collation_from_document;
+use crate::connection::{CollectionKind, CreateCollectionOptions, TimeSeriesSpec};
+use crate::state::{AppCommands, AppState};
+use crate::theme::{fonts, spacing};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum KindChoice {
+    Standard,
+    Capped,
+    TimeSeries,
+    Clustered,
+    View,
+}
+
+impl KindChoice {
+    const ALL: [KindChoice; 5] = [
+        KindChoice::Standard,
+        KindChoice::Capped,
+        KindChoice::TimeSeries,
+        KindChoice::Clustered,
+        KindChoice::View,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            KindChoice::Standard => "Standard",
+            KindChoice::Capped => "Capped",
+            KindChoice::TimeSeries => "Time series",
+            KindChoice::Clustered => "Clustered",
+            KindChoice::View => "View",
+        }
+    }
+}
+
+const GRANULARITIES: [(Option<TimeseriesGranularity>, &str); 4] = [
+    (None, "Default"),
+    (Some(TimeseriesGranularity::Seconds), "Seconds"),
+    (Some(TimeseriesGranularity::Minutes), "Minutes"),
+    (Some(TimeseriesGranularity::Hours), "Hours"),
+];
+
+const VALIDATION_LEVELS: [(Option<ValidationLevel>, &str); 4] = [
+    (None, "Default"),
+    (Some(ValidationLevel::Strict), "Strict"),
+    (Some(ValidationLevel::Moderate), "Moderate"),
+    (Some(ValidationLevel::Off), "Off"),
+];
+
+const VALIDATION_ACTIONS: [(Option<ValidationAction>, &str); 3] = [
+    (None, "Default"),
+    (Some(ValidationAction::Error), "Error"),
+    (Some(ValidationAction::Warn), "Warn"),
+];
+
+pub struct CreateCollectionDialog {
+    state: Entity<AppState>,
+    database: String,
+    kind: KindChoice,
+    name_state: Entity<InputState>,
+    capped_size_state: Entity<InputState>,
+    capped_max_state: Entity<InputState>,
+    time_field_state: Entity<InputState>,
+    meta_field_state: Entity<InputState>,
+    granularity: Option<TimeseriesGranularity>,
+    expire_state: Entity<InputState>,
+    view_on_state: Entity<InputState>,
+    pipeline_state: Entity<InputState>,
+    collation_state: Entity<InputState>,
+    validator_state: Entity<InputState>,
+    validation_level: Option<ValidationLevel>,
+    validation_action: Option<ValidationAction>,
+    error_message: Option<String>,
+}
+
+impl CreateCollectionDialog {
+    pub fn open(state: Entity<AppState>, database: String, window: &mut Window, cx: &mut App) {
+        let title = format!("Create Collection in {database}");
+        let dialog_view = cx.new(|cx| Self::new(state, database, window, cx));
+        window.open_dialog(cx, move |dialog: Dialog, _window: &mut Window, _cx: &mut App| {
+            dialog.title(title.clone()).w(px(640.0)).child(dialog_view.clone())
+        });
+    }
+
+    fn new(
+        state: Entity<AppState>,
+        database: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let mut input = |placeholder: &'static str| {
+            cx.new(|cx| InputState::new(window, cx).placeholder(placeholder))
+        };
+        let name_state = input("collection_name");
+        let capped_size_state = input("Size in bytes, e.g. 1048576");
+        let capped_max_state = input("Max documents (optional)");
+        let time_field_state = input("timestamp");
+        let meta_field_state = input("metadata (optional)");
+        let expire_state = input("Expire after seconds (optional)");
+        let view_on_state = input("source_collection");
+        let mut editor = |placeholder: &'static str| {
+            cx.new(|cx| {
+                InputState::new(window, cx)
+                    .placeholder(placeholder)
+                    .code_editor("javascript")
+                    .soft_wrap(true)
+            })
+        };
+        let pipeline_state = editor("[ { \"$match\": { \"status\": \"active\" } } ]");
+        let collation_state = editor("Collation, e.g. { \"locale\": \"en\", \"strength\": 2 }");
+        let validator_state = editor("Validator, e.g. { \"$jsonSchema\": { ... } }");
+
+        Self {
+            state,
+            database,
+            kind: KindChoice::Standard,
+            name_state,
+            capped_size_state,
+            capped_max_state,
+            time_field_state,
+            meta_field_state,
+            granularity: None,
+            expire_state,
+            view_on_state,
+            pipeline_state,
+            collation_state,
+            validator_state,
+            validation_level: None,
+            validation_action: None,
+            error_message: None,
+        }
+    }
+
+    fn build_options(&self, cx: &App) -> Result<CreateCollectionOptions, String> {
+        let text = |state: &Entity<InputState>| state.read(cx).value().trim().to_string();
+        let optional = |state: &Entity<InputState>| Some(text(state)).filter(|s| !s.is_empty());
+        let number = |state: &Entity<InputState>, label: &str| {
+            optional(state)
+                .map(|value| {
+                    value
+                        .parse::<u64>()
+                        .map_err(|_| format!("{label} must be a whole number of 0 or more"))
+                })
+                .transpose()
+        };
+        let json = |state: &Entity<InputState>, label: &str| {
+            optional(state)
+                .map(|value| {
+                    parse_document_from_json(&value).map_err(|err| format!("{label}: {err}"))
+                })
+                .transpose()
+        };
+
+        let kind = match self.kind {
+            KindChoice::Standard => CollectionKind::Standard,
+            KindChoice::Capped => CollectionKind::Capped {
+                size_bytes: number(&self.capped_size_state, "Size")?
+                    .ok_or("Capped collections need a size in bytes")?,
+                max_documents: number(&self.capped_max_state, "Max documents")?,
+            },
+            KindChoice::TimeSeries => CollectionKind::TimeSeries(TimeSeriesSpec {
+                time_field: text(&self.time_field_state),
+                meta_field: optional(&self.meta_field_state),
+                granularity: self.granularity.clone(),
+                expire_after_seconds: number(&self.expire_state, "Expire after")?,
+            }),
+            KindChoice::Clustered => CollectionKind::Clustered {
+                expire_after_seconds: number(&self.expire_state, "Expire after")?,
+            },
+            KindChoice::View => CollectionKind::View {
+                view_on: text(&self.view_on_state),
+                pipeline: match optional(&self.pipeline_state) {
+                    Some(pipeline) => parse_documents_from_json(&pipeline)
+                        .map_err(|err| format!("Pipeline: {err}"))?,
+                    None => Vec::new(),
+                },
+            },
+        };
+        let collation =
+            json(&self.collation_state, "Collation")?.map(collation_from_document).transpose()?;
+        let validator: Option<Document> = if self.kind == KindChoice::View {
+            None
+        } else {
+            json(&self.validator_state, "Validator")?
+        };
+        let has_validator = validator.is_some();
+        let options = CreateCollectionOptions {
+            kind,
+            collation,
+            validator,
+            validation_level: self.validation_level.clone().filter(|_| has_validator),
+            validation_action: self.validation_action.clone().filter(|_| has_validator),
+        };
+        options.validate()?;
+        Ok(options)
+    }
+
+    fn create(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let name = self.name_state.read(cx).value().trim().to_string();
+        if name.is_empty() {
+            self.error_message = Some("Enter a collection name.".to_string());
+            cx.notify();
+            return;
+        }
+        match self.build_options(cx) {
+            Ok(options) => {
+                AppCommands::create_collection(
+                    self.state.clone(),
+                    self.database.clone(),
+                    name,
+                    options,
+                    cx,
+                );
+                window.close_dialog(cx);
+            }
+            Err(error) => {
+                self.error_message = Some(error);
+                cx.notify();
+            }
+        }
+    }
+}
+
+/// A row of compact buttons with the selected one highlighted.
+fn choice_row<T: Clone + PartialEq + 'static>(
+    id: &'static str,
+    choices: &[(T, &'static str)],
+    selected: &T,
+    view: &Entity<CreateCollectionDialog>,
+    apply: fn(&mut CreateCollectionDialog, T),
+) -> Div {
+    div().flex().flex_wrap().gap(spacing::xs()).children(choices.iter().enumerate().map(
+        |(index, (value, label))| {
+            let base = Button::new((id, index)).compact().label(*label).on_click({
+                let view = view.clone();
+                let value = value.clone();
+                move |_: &ClickEvent, _window: &mut Window, cx: &mut App| {
+                    view.update(cx, |this, cx| {
+                        apply(this, value.clone());
+                        this.error_message = None;
+                        cx.notify();
+                    });
+                }
+            });
+            if value == selected { base.primary() } else { base.ghost() }
+        },
+    ))
+}
+
+fn labeled(label: &'static str, child: impl IntoElement, cx: &App) -> Div {
+    div()
+        .flex()
+        .flex_col()
+        .gap(spacing::xs())
+        .child(div().text_sm().text_color(cx.theme().foreground).child(label))
+        .child(child)
+}
+
+fn editor(state: &Entity<InputState>, height: f32) -> Input {
+    Input::new(state).font_family(fonts::mono()).h(px(height)).w_full()
+}
+
+impl Render for CreateCollectionDialog {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let view = cx.entity();
+        let kinds: Vec<_> = KindChoice::ALL.iter().map(|kind| (*kind, kind.label())).collect();
+        let is_view = self.kind == KindChoice::View;
+
+        let field = |label: &'static str, state: &Entity<InputState>| {
+            div().flex_1().child(FormField::new(label, state).render(cx))
+        };
+        let kind_options = match self.kind {
+            KindChoice::Standard => None,
+            KindChoice::Capped => Some(
+                div()
+                    .flex()
+                    .gap(spacing::sm())
+                    .child(field("Size", &self.capped_size_state))
+                    .child(field("Max documents", &self.capped_max_state)),
+            ),
+            KindChoice::TimeSeries => Some(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap(spacing::sm())
+                    .child(
+                        div()
+                            .flex()
+                            .gap(spacing::sm())
+                            .child(field("Time field", &self.time_field_state))
+                            .child(field("Meta field", &self.meta_field_state)),
+                    )
+                    .child(labeled(
+                        "Granularity",
+                        choice_row(
+                            "granularity",
+                            &GRANULARITIES,
+                            &self.granularity,
+                            &view,
+                            |this, value| this.granularity = value,
+                        ),
+                        cx,
+                    ))
+                    .child(field("Expire after seconds", &self.expire_state)),
+            ),
+            KindChoice::Clustered => Some(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap(spacing::xs())
+                    .child(div().text_xs().text_color(cx.theme().muted_foreground).child(
+                        "Documents are stored in _id order under a unique { _id: 1 } index.",
+                    ))
+                    .child(field("Expire after seconds", &self.expire_state)),
+            ),
+            KindChoice::View => Some(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap(spacing::sm())
+                    .child(field("View on", &self.view_on_state))
+                    .child(labeled("Pipeline", editor(&self.pipeline_state, 120.0), cx)),
+            ),
+        };
+
+        let validation = div()
+            .flex()
+            .flex_col()
+            .gap(spacing::sm())
+            .child(labeled("Validator", editor(&self.validator_state, 120.0), cx))
+            .child(
+                div()
+                    .flex()
+                    .gap(spacing::md())
+                    .child(labeled(
+                        "Validation level",
+                        choice_row(
+                            "validation-level",
+                            &VALIDATION_LEVELS,
+                            &self.validation_level,
+                            &view,
+                            |this, value| this.validation_level = value,
+                        ),
+                        cx,
+                    ))
+                    .child(labeled(
+                        "Validation action",
+                        choice_row(
+                            "validation-action",
+                            &VALIDATION_ACTIONS,
+                            &self.validation_action,
+                            &view,
+                            |this, value| this.validation_action = value,
+                        ),
+                        cx,
+                    )),
+            );
+
+        let action_row = div()
+            .flex()
+            .items_center()
+            .justify_between()
+            .pt(spacing::xs())
+            .child(
+                div()
+                    .min_h(px(18.0))
+                    .text_sm()
+                    .text_color(cx.theme().danger_foreground)
+                    .children(self.error_message.clone()),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap(spacing::sm())
+                    .child(cancel_button("cancel-collection"))
+                    .child(Button::new("create-collection").primary().label("Create").on_click({
+                        let view = view.clone();
+                        move |_: &ClickEvent, window: &mut Window, cx: &mut App| {
+                            view.update(cx, |this, cx| this.create(window, cx));
+                        }
+                    })),
+            );
+
+        div()
+            .flex()
+            .flex_col()
+            .gap(spacing::sm())
+            .p(spacing::md())
+            .child(
+                div()
+                    .id("create-collection-form")
+                    .flex()
+                    .flex_col()
+                    .gap(spacing::md())
+                    .max_h(px(560.0))
+                    .overflow_y_scrollbar()
+                    .child(FormField::new("Collection name", &self.name_state).render(cx))
+                    .child(labeled(
+                        "Type",
+                        choice_row("collection-kind", &kinds, &self.kind, &view, |this, kind| {
+                            this.kind = kind;
+                        }),
+                        cx,
+                    ))
+                    .children(kind_options)
+                    .child(labeled("Collation", editor(&self.collation_state, 72.0), cx))
+                    .when(!is_view, |this| this.child(validation)),
+            )
+            .child(action_row)
+    }
+}
@@ -1,3 +1,5 @@
+mod create_collection;
+
 use gpui::*;
 use gpui_component::WindowExt as _;
 use gpui_component::dialog::Dialog;
@@ -7,6 +9,8 @@ use crate::components::{FormField, cancel_button, primary_button};
 use crate::state::{AppCommands, AppState};
 use crate::theme::spacing;
 
+use create_collection::CreateCollectionDialog;
+
 pub(crate) fn open_create_database_dialog(
     state: Entity<AppState>,
     window: &mut Window,
@@ -69,48 +73,7 @@ pub(crate) fn open_create_collection_dialog(
     window: &mut Window,
     cx: &mut App,
 ) {
-    let col_state =
-        cx.new(|cx| InputState::new(window, cx).placeholder("collection_name").default_value(""));
-    let col_state_save = col_state.clone();
-    window.open_dialog(cx, move |dialog: Dialog, _window: &mut Window, cx: &mut App| {
-        dialog
-            .title(format!("Create Collection in {database}"))
-            .min_w(px(420.0))
-            .child(
-                div()
-                    .flex()
-                    .flex_col()
-                    .gap(spacing::md())
-                    .p(spacing::md())
-                    .child(FormField::new("Collection name", &col_state).render(cx)),
-            )
-            .footer({
-                let state = state.clone();
-                let database = database.clone();
-                let col_state = col_state_save.clone();
-                move |_ok_fn, _cancel_fn, _window: &mut Window, _cx: &mut App| {
-                    let state = state.clone();
-                    let database = database.clone();
-                    let col_state = col_state.clone();
-                    vec![
-                        cancel_button("cancel-collection"),
-                        primary_button("create-collection", "Create", move |window, cx| {
-                            let col = col_state.read(cx).value().to_string();
-                            if col.trim().is_empty() {
-                                return;
-                            }
-                            AppCommands::create_collection(
-                                state.clone(),
-                                database.clone(),
-                                col.trim().to_string(),
-                                cx,
-                            );
-                            window.close_dialog(cx);
-                        }),
-                    ]
-                }
-            })
-    });
+    CreateCollectionDialog::open(state, database, window, cx);
 }
 
 pub(crate) fn open_rename_collection_dialog(
@@ -70,21 +70,39 @@ impl AppRoot {
 
         // Hydrate connection secrets from keychain (+ migrate legacy plaintext)
         {
-            use crate::state::app_state::write_conn_secrets;
+            use crate::state::app_state::{
+                jump_secret_keys, legacy_jump_secret_keys, write_conn_secrets,
+            };
 
             let conns: Vec<_> = state
                 .read(cx)
                 .connections
                 .iter()
-                .map(|c| (c.id, c.uri.clone(), c.ssh.clone(), c.proxy.clone()))
+                .map(|c| {
+                    (
+                        c.id,
+                        c.uri.clone(),
+                        c.ssh.clone(),
+                        c.proxy.clone(),
+                        c.tls.clone(),
+                        c.auth.clone(),
+                    )
+                })
                 .collect();
 
             // Check for legacy plaintext passwords still on disk
-            let has_legacy = conns.iter().any(|(_, uri, ssh, proxy)| {
+            let has_legacy = conns.iter().any(|(_, uri, ssh, proxy, tls, auth)| {
                 extract_uri_password(uri).is_some_and(|p| p != REDACTED_PASSWORD)
                     || ssh.as_ref().and_then(|s| s.password.as_ref()).is_some()
                     || ssh.as_ref().and_then(|s| s.identity_passphrase.as_ref()).is_some()
                     || proxy.as_ref().and_then(|p| p.password.as_ref()).is_some()
+                    || tls.as_ref().and_then(|t| t.cert_key_password.as_ref()).is_some()
+                    || auth.as_ref().and_then(|a| a.password.as_ref()).is_some()
+                    || ssh.as_ref().is_some_and(|s| {
+                        s.jump_hosts
+                            .iter()
+                            .any(|hop| hop.password.is_some() || hop.identity_passphrase.is_some())
+                    })
             });
 
             if has_legacy {
@@ -98,15 +116,31 @@ impl AppRoot {
 
             // Hydrate: read secrets from keychain into in-memory state
             let mut tasks = Vec::new();
-            for (id, _, _, _) in &conns {
-                for key in &["uri", "ssh", "ssh-passphrase", "proxy"] {
-                    tasks.push((*id, *key, KeyStore::read_conn(cx, *id, key)));
+            for (id, _, ssh, _, _, _) in &conns {
+                for key in ["uri", "ssh", "ssh-passphrase", "proxy", "tls", "auth"] {
+                    tasks.push((*id, key.to_string(), KeyStore::read_conn(cx, *id, key)));
+                }
+                // Legacy positional keys first so id-keyed secrets win when both exist.
+                let hops = ssh.as_ref().map(|s| s.jump_hosts.as_slice()).unwrap_or_default();
+                for index in 0..hops.len() {
+                    let (password_key, passphrase_key) = legacy_jump_secret_keys(index);
+                    for key in [password_key, passphrase_key] {
+                        let task = KeyStore::read_conn(cx, *id, &key);
+                        tasks.push((*id, key, task));
+                    }
+                }
+                for hop in hops {
+                    let (password_key, passphrase_key) = jump_secret_keys(hop.id);
+                    for key in [password_key, passphrase_key] {
+                        let task = KeyStore::read_conn(cx, *id, &key);
+                        tasks.push((*id, key, task));
+                    }
                 }
             }
 
             let state = state.clone();
             cx.spawn(async move |_this: WeakEntity<Self>, cx: &mut AsyncApp| {
-                let mut secrets: Vec<(uuid::Uuid, &str, String)> = Vec::new();
+                let mut secrets: Vec<(uuid::Uuid, String, String)> = Vec::new();
                 for (id, kind, task) in tasks {
                     if let Ok(Some(secret)) = task.await {
                         secrets.push((id, kind, secret));
@@ -116,13 +150,14 @@ impl AppRoot {
                     return;
                 }
                 let _ = cx.update(|cx| {
-                    state.update(cx, |s, _| {
+                    state.update(cx, |s, cx| {
+                        let mut migrated = Vec::new();
                         for conn in &mut s.connections {
                             for (id, kind, secret) in &secrets {
                                 if conn.id != *id {
                                     continue;
                                 }
-                                match *kind {
+                                match kind.as_str() {
                                     "uri" => {
                                         conn.uri = inject_uri_password(&conn.uri, Some(secret));
                                     }
@@ -141,10 +176,54 @@ impl AppRoot {
                                             proxy.password = Some(secret.clone());
                                         }
                                     }
-                                    _ => {}
+                                    "tls" => {
+                                        if let Some(tls) = &mut conn.tls {
+                                            tls.cert_key_password = Some(secret.clone());
+                                        }
+                                    }
+                                    "auth" => {
+                                        if let Some(auth) = &mut conn.auth {
+                                            auth.password = Some(secret.clone());
+                                        }
+                                    }
+                                    other => {
+                                        let Some(ssh) = &mut conn.ssh else { continue };
+                                        for (index, hop) in ssh.jump_hosts.iter_mut().enumerate() {
+                                            let (password_key, passphrase_key) =
+                                                jump_secret_keys(hop.id);
+                                            let (legacy_password, legacy_passphrase) =
+                                                legacy_jump_secret_keys(index);
+                                            if other == password_key {
+                                                hop.password = Some(secret.clone());
+                                            } else if other == passphrase_key {
+                                                hop.identity_passphrase = Some(secret.clone());
+                                            } else if other == legacy_password {
+                                                hop.password = Some(secret.clone());
+                                                migrated.push((conn.id, other.to_string()));
+                                            } else if other == legacy_passphrase {
+                                                hop.identity_passphrase = Some(secret.clone());
+                                                migrated.push((conn.id, other.to_string()));
+                                            }
+                                        }
+                                    }
                                 }
                             }
                         }
+
+                        // Re-key positional jump host secrets by hop id and persist the
+                        // ids, so later removals and reorders keep each secret on its hop.
+                        if migrated.is_empty() {
+                            return;
+                        }
+                        for conn in &s.connections {
+                            if migrated.iter().any(|(id, _)| *id == conn.id) {
+                                write_conn_secrets(cx, conn);
+                            }
+                        }
+                        for (id, key) in &migrated {
+                            KeyStore::delete_conn(cx, *id, key).detach();
+                        }
+                        s.save_connections();
                     });
                 });
             })
@@ -296,6 +375,8 @@ impl Render for AppRoot {
         let connection_name = active_conn.map(|c| c.config.name.clone());
         let status_message = state.status_message();
         let read_only = active_conn.map(|c| c.config.read_only).unwrap_or(false);
+        let environment = active_conn.and_then(|c| c.config.environment);
+        let health = active_conn.map(|c| c.health).unwrap_or_default();
         let show_status_bar = state.settings.appearance.show_status_bar;
         let appearance = state.settings.appearance.clone();
         let show_ai_island = state.ai_chat.panel_open && state.ai_assistant_available();
@@ -322,6 +403,7 @@ impl Render for AppRoot {
                     Some(CollectionSubview::Stats) => key_context.push_str(" Stats"),
                     Some(CollectionSubview::Aggregation) => key_context.push_str(" Aggregation"),
                     Some(CollectionSubview::Schema) => key_context.push_str(" Schema"),
+                    Some(CollectionSubview::Validation) => key_context.push_str(" Validation"),
                     _ => {}
                 }
             }
@@ -669,6 +751,8 @@ impl Render for AppRoot {
                     self.state.read(cx).ai_assistant_available(),
                     self.state.read(cx).ai_chat.panel_open,
                 )
+                .environment(environment)
+                .health(health)
                 .sidebar_collapsed(sidebar_collapsed)
                 .on_toggle_sidebar(move |_window: &mut Window, cx: &mut App| {
                     sidebar.update(cx, |sidebar, cx| {
@@ -109,6 +109,9 @@ impl Sidebar {
                 this.model.selected_tree_id = None;
                 this.refresh_tree(cx);
             }
+            AppEvent::ConnectionLost { .. }
+            | AppEvent::Reconnecting { .. }
+            | AppEvent::Reconnected(_) => cx.notify(),
             AppEvent::ConnectionFailed(_) => {
                 this.model.connecting_connection = None;
                 this.model.loading_databases.clear();
@@ -116,12 +119,17 @@ impl Sidebar {
                 cx.notify();
             }
             AppEvent::DocumentsLoaded { .. }
+            | AppEvent::DocumentsStreamed { .. }
+            | AppEvent::DocumentsStreamFailed { .. }
             | AppEvent::DocumentInserted
             | AppEvent::DocumentInsertFailed { .. }
             | AppEvent::DocumentsInserted { .. }
             | AppEvent::DocumentsInsertFailed { .. }
             | AppEvent::DocumentSaved { .. }
             | AppEvent::DocumentSaveFailed { .. }
+            | AppEvent::DocumentsSaved { .. }
+            | AppEvent::DocumentsSaveFailed { .. }
+            | AppEvent::DocumentSaveConflict { .. }
             | AppEvent::DocumentDeleted { .. }
             | AppEvent::DocumentDeleteFailed { .. }
             | AppEvent::DocumentsDeleted { .. }
@@ -132,6 +140,8 @@ impl Sidebar {
             | AppEvent::IndexDropFailed { .. }
             | AppEvent::IndexCreated { .. }
             | AppEvent::IndexCreateFailed { .. }
+            | AppEvent::IndexModified { .. }
+            | AppEvent::IndexModifyFailed { .. }
             | AppEvent::DocumentsUpdated { .. }
             | AppEvent::DocumentsUpdateFailed { .. }
             | AppEvent::AggregationCompleted { .. }
@@ -148,6 +158,9 @@ impl Sidebar {
             | AppEvent::CollectionProgressUpdate { .. }
             | AppEvent::SchemaAnalyzed { .. }
             | AppEvent::SchemaFailed { .. }
+            | AppEvent::ValidationLoaded { .. }
+            | AppEvent::ValidationLoadFailed { .. }
+            | AppEvent::ValidationApplied { .. }
             | AppEvent::UpdateAvailable { .. } => {}
             AppEvent::ViewChanged => {
                 this.sync_selection_from_state(cx);
@@ -178,6 +191,7 @@ impl Sidebar {
             state.update(cx, |state, cx| {
                 state.workspace_restore_pending = false;
                 state.update_workspace_from_state();
+                state.flush_query_history_now();
                 cx.notify();
             });
             async {}
@@ -14,9 +14,9 @@ use crate::keyboard::{
     DisconnectConnection, EditConnection, FindInSidebar, OpenSelection, OpenSelectionPreview,
     PasteTreeItem, RenameCollection, TransferCopy, TransferExport, TransferImport,
 };
-use crate::models::TreeNodeId;
+use crate::models::{ConnectionEnvironment, TreeNodeId};
 use crate::state::{AppCommands, TransferMode};
-use crate::theme::{borders, islands, sizing, spacing};
+use crate::theme::{borders, colors, islands, sizing, spacing};
 
 use super::super::menus::{build_collection_menu, build_connection_menu, build_database_menu};
 use super::super::sidebar_model::SidebarModel;
@@ -29,12 +29,14 @@ impl Render for Sidebar {
         let active_connections = self.cached_active.clone();
         let connecting_id = self.model.connecting_connection;
 
-        let disconnected_connections: Vec<_> = self
+        let mut disconnected_connections: Vec<_> = self
             .cached_connections
             .iter()
             .filter(|c| !active_connections.contains_key(&c.id))
             .cloned()
             .collect();
+        disconnected_connections
+            .sort_by_cached_key(|conn| conn.folder.as_ref().map(|f| f.to_lowercase()));
 
         let state = self.state.clone();
         let state_for_add = state.clone();
@@ -173,7 +175,16 @@ impl Render for Sidebar {
                                                 PopupMenuItem::new("All connected").disabled(true),
                                             );
                                         } else {
+                                            let mut current_folder = None;
                                             for conn in &disconnected_connections {
+                                                if conn.folder.is_some()
+                                                    && conn.folder != current_folder
+                                                {
+                                                    current_folder = conn.folder.clone();
+                                                    menu = menu.label(
+                                                        conn.folder.clone().unwrap_or_default(),
+                                                    );
+                                                }
                                                 let conn_id = conn.id;
                                                 let state = state_for_connect.clone();
                                                 let sidebar_entity = sidebar_entity.clone();
@@ -411,6 +422,9 @@ impl Render for Sidebar {
                         let theme_primary = cx.theme().primary;
                         let theme_info = cx.theme().info;
                         let theme_warning = cx.theme().warning;
+                        let theme_tab_bar = cx.theme().tab_bar;
+                        let environment_colors =
+                            ConnectionEnvironment::ALL.map(|env| (env, colors::environment(env, cx)));
                         uniform_list("sidebar-rows", self.model.entries.len(), {
                             let state_clone = state_for_tree.clone();
                             let sidebar_entity = sidebar_entity.clone();
@@ -440,6 +454,16 @@ impl Render for Sidebar {
                                         let is_collection = node_id.is_collection();
 
                                         let connection_id = node_id.connection_id();
+                                        let environment = active_connections
+                                            .get(&connection_id)
+                                            .and_then(|conn| conn.config.environment)
+                                            .filter(|_| is_connection);
+                                        let environment_color = environment.and_then(|env| {
+                                            environment_colors
+                                                .iter()
+                                                .find(|(candidate, _)| *candidate == env)
+                                                .map(|(_, color)| *color)
+                                        });
                                         let is_connected = is_connection
                                             && active_connections.contains_key(&connection_id);
                                         let is_connecting =
@@ -733,12 +757,14 @@ impl Render for Sidebar {
                                             .when(!is_folder, |this| {
                                                 this.child(div().w(sizing::icon_sm()))
                                             })
-                                            // Connection: server icon (green)
+                                            // Connection: server icon (environment color, else green)
                                             .when(is_connection, |this| {
                                                 this.child(
                                                     Icon::new(IconName::Globe)
                                                         .size(sizing::icon_md())
-                                                        .text_color(theme_primary),
+                                                        .text_color(
+                                                            environment_color.unwrap_or(theme_primary),
+                                                        ),
                                                 )
                                             })
                                             // Database: dashboard icon (blue)
@@ -771,6 +797,22 @@ impl Render for Sidebar {
                                                     .truncate()
                                                     .child(label.clone()),
                                             )
+                                            .when_some(
+                                                environment.zip(environment_color),
+                                                |this, (env, color)| {
+                                                    this.child(
+                                                        div()
+                                                            .flex_shrink_0()
+                                                            .mr(px(6.0))
+                                                            .px(px(4.0))
+                                                            .rounded(borders::radius_sm())
+                                                            .bg(color)
+                                                            .text_xs()
+                                                            .text_color(theme_tab_bar)
+                                                            .child(env.badge()),
+                                                    )
+                                                },
+                                            )
                                             .when(is_connecting || is_loading_db, |this| {
                                                 this.child(Spinner::new().xsmall())
                                             });
@@ -211,6 +211,9 @@ impl SidebarModel {
         expanded: &HashSet<TreeNodeId>,
     ) -> Vec<SidebarEntry> {
         let mut items = Vec::new();
+        // Keep folder members together, matching the connection manager list.
+        let mut connections: Vec<&SavedConnection> = connections.iter().collect();
+        connections.sort_by_cached_key(|conn| conn.folder.as_ref().map(|f| f.to_lowercase()));
         for conn in connections {
             let active_conn = active.get(&conn.id);
             if active_conn.is_none() {
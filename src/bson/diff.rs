@@ -0,0 +1,144 @@
+//! Structural field-by-field diff between two documents.
+
+use mongodb::bson::{Bson, Document};
+
+use super::PathSegment;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffKind {
+    /// Only the right document has the field.
+    Added,
+    /// Only the left document has the field.
+    Removed,
+    /// Same type, different value.
+    Changed,
+    /// The BSON type differs, e.g. Int32 to Int64.
+    TypeChanged,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldDiff {
+    pub path: Vec<PathSegment>,
+    pub kind: DiffKind,
+    pub left: Option<Bson>,
+    pub right: Option<Bson>,
+}
+
+impl FieldDiff {
+    /// Dotted path as written in queries, e.g. `items.0.qty`.
+    pub fn path_label(&self) -> String {
+        path_label(&self.path)
+    }
+}
+
+/// Dotted form of `path`; array indexes become numeric segments.
+pub fn path_label(path: &[PathSegment]) -> String {
+    path.iter()
+        .map(|segment| match segment {
+            PathSegment::Key(key) => key.clone(),
+            PathSegment::Index(index) => index.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Differences from `left` to `right`, in the right document's field order
+/// followed by fields only `left` has. Documents and arrays present on both
+/// sides are compared element by element.
+pub fn diff_documents(left: &Document, right: &Document) -> Vec<FieldDiff> {
+    let mut diffs = Vec::new();
+    diff_into(&mut Vec::new(), left, right, &mut diffs);
+    diffs
+}
+
+fn diff_into(
+    path: &mut Vec<PathSegment>,
+    left: &Document,
+    right: &Document,
+    out: &mut Vec<FieldDiff>,
+) {
+    let keys = right.keys().chain(left.keys().filter(|key| !right.contains_key(key.as_str())));
+    for key in keys {
+        path.push(PathSegment::Key(key.clone()));
+        diff_values(path, left.get(key), right.get(key), out);
+        path.pop();
+    }
+}
+
+fn diff_values(
+    path: &mut Vec<PathSegment>,
+    left: Option<&Bson>,
+    right: Option<&Bson>,
+    out: &mut Vec<FieldDiff>,
+) {
+    let kind = match (left, right) {
+        (Some(Bson::Document(old)), Some(Bson::Document(new))) => {
+            return diff_into(path, old, new, out);
+        }
+        (Some(Bson::Array(old)), Some(Bson::Array(new))) => {
+            for index in 0..old.len().max(new.len()) {
+                path.push(PathSegment::Index(index));
+                diff_values(path, old.get(index), new.get(index), out);
+                path.pop();
+            }
+            return;
+        }
+        (None, None) => return,
+        (None, Some(_)) => DiffKind::Added,
+        (Some(_), None) => DiffKind::Removed,
+        (Some(old), Some(new)) if old.element_type() != new.element_type() => DiffKind::TypeChanged,
+        (Some(old), Some(new)) if old != new => DiffKind::Changed,
+        _ => return,
+    };
+    out.push(FieldDiff { path: path.clone(), kind, left: left.cloned(), right: right.cloned() });
+}
+
+#[cfg(test)]
+mod tests {
+    use mongodb::bson::doc;
+
+    use super::*;
+
+    #[test]
+    fn diff_reports_each_kind_by_path() {
+        let left = doc! {
+            "_id": 1,
+            "name": "a",
+            "count": 1_i32,
+            "meta": { "tags": ["x", "y"], "gone": true },
+            "same": { "v": 1 },
+        };
+        let right = doc! {
+            "_id": 1,
+            "name": "b",
+            "count": 1_i64,
+            "meta": { "tags": ["x", "z", "w"] },
+            "same": { "v": 1 },
+            "added": null,
+        };
+        let diffs: Vec<(String, DiffKind)> = diff_documents(&left, &right)
+            .into_iter()
+            .map(|diff| (diff.path_label(), diff.kind))
+            .collect();
+        assert_eq!(
+            diffs,
+            vec![
+                ("name".to_string(), DiffKind::Changed),
+                ("count".to_string(), DiffKind::TypeChanged),
+                ("meta.tags.1".to_string(), DiffKind::Changed),
+                ("meta.tags.2".to_string(), DiffKind::Added),
+                ("meta.gone".to_string(), DiffKind::Removed),
+                ("added".to_string(), DiffKind::Added),
+            ]
+        );
+    }
+
+    #[test]
+    fn replacing_a_subdocument_is_a_type_change() {
+        let diffs = diff_documents(&doc! { "a": { "b": 1 } }, &doc! { "a": "flat" });
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].path, vec![PathSegment::Key("a".into())]);
+        assert_eq!(diffs[0].kind, DiffKind::TypeChanged);
+        assert!(diff_documents(&doc! { "a": [1] }, &doc! { "a": [1] }).is_empty());
+    }
+}
@@ -1,11 +1,15 @@
 //! BSON utilities for document manipulation, formatting, and parsing.
 
+mod diff;
 mod formatter;
 mod key;
 mod parser;
 mod path;
+mod three_way;
 
+pub use diff::*;
 pub use formatter::*;
 pub use key::*;
 pub use parser::*;
 pub use path::*;
+pub use three_way::*;
@@ -0,0 +1,123 @@
+//! Field-level three-way comparison of a loaded document, a local draft and
+//! the current server copy, used to resolve save conflicts.
+
+use mongodb::bson::{Bson, Document};
+
+/// Which side changed a top-level field relative to the loaded document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldChange {
+    Unchanged,
+    Draft,
+    Server,
+    /// Both sides made the same change.
+    Both,
+    /// Both sides changed the field to different values.
+    Conflict,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThreeWayRow {
+    pub key: String,
+    pub loaded: Option<Bson>,
+    pub draft: Option<Bson>,
+    pub current: Option<Bson>,
+    pub change: FieldChange,
+}
+
+/// One row per top-level field of any of the three documents, in server
+/// order followed by fields only the draft or the loaded copy has.
+pub fn three_way_rows(loaded: &Document, draft: &Document, current: &Document) -> Vec<ThreeWayRow> {
+    let mut keys: Vec<&String> = current.keys().collect();
+    for key in draft.keys().chain(loaded.keys()) {
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+
+    keys.into_iter()
+        .map(|key| {
+            let base = loaded.get(key);
+            let mine = draft.get(key);
+            let theirs = current.get(key);
+            let change = match (mine != base, theirs != base) {
+                (false, false) => FieldChange::Unchanged,
+                (true, false) => FieldChange::Draft,
+                (false, true) => FieldChange::Server,
+                (true, true) if mine == theirs => FieldChange::Both,
+                (true, true) => FieldChange::Conflict,
+            };
+            ThreeWayRow {
+                key: key.clone(),
+                loaded: base.cloned(),
+                draft: mine.cloned(),
+                current: theirs.cloned(),
+                change,
+            }
+        })
+        .collect()
+}
+
+/// Apply the draft's changes on top of the server copy. Fields both sides
+/// changed differently keep the draft's value; their names are returned so
+/// the user can review them.
+pub fn three_way_merge(
+    loaded: &Document,
+    draft: &Document,
+    current: &Document,
+) -> (Document, Vec<String>) {
+    let mut merged = Document::new();
+    let mut conflicts = Vec::new();
+    for row in three_way_rows(loaded, draft, current) {
+        let value = match row.change {
+            FieldChange::Unchanged | FieldChange::Server => row.current,
+            FieldChange::Draft | FieldChange::Both => row.draft,
+            FieldChange::Conflict => {
+                conflicts.push(row.key.clone());
+                row.draft
+            }
+        };
+        if let Some(value) = value {
+            merged.insert(row.key, value);
+        }
+    }
+    (merged, conflicts)
+}
+
+#[cfg(test)]
+mod tests {
+    use mongodb::bson::doc;
+
+    use super::*;
+
+    #[test]
+    fn rows_classify_each_side() {
+        let loaded = doc! { "_id": 1, "a": 1, "b": 1, "c": 1, "d": 1 };
+        let draft = doc! { "_id": 1, "a": 2, "b": 1, "c": 3, "d": 5, "new": true };
+        let current = doc! { "_id": 1, "a": 1, "b": 2, "c": 4, "d": 5 };
+        let changes: Vec<(String, FieldChange)> = three_way_rows(&loaded, &draft, &current)
+            .into_iter()
+            .map(|row| (row.key, row.change))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                ("_id".to_string(), FieldChange::Unchanged),
+                ("a".to_string(), FieldChange::Draft),
+                ("b".to_string(), FieldChange::Server),
+                ("c".to_string(), FieldChange::Conflict),
+                ("d".to_string(), FieldChange::Both),
+                ("new".to_string(), FieldChange::Draft),
+            ]
+        );
+    }
+
+    #[test]
+    fn merge_keeps_both_sides_and_reports_conflicts() {
+        let loaded = doc! { "_id": 1, "a": 1, "b": 1, "c": 1, "gone": 1 };
+        let draft = doc! { "_id": 1, "a": 2, "b": 1, "c": 3 };
+        let current = doc! { "_id": 1, "a": 1, "b": 2, "c": 4, "gone": 1, "added": "x" };
+        let (merged, conflicts) = three_way_merge(&loaded, &draft, &current);
+        assert_eq!(merged, doc! { "_id": 1, "a": 2, "b": 2, "c": 3, "added": "x" });
+        assert_eq!(conflicts, vec!["c".to_string()]);
+    }
+}
@@ -13,8 +13,8 @@ use crate::state::settings::AppTheme;
 use crate::theme::{borders, fonts, islands, spacing};
 
 use providers::{
-    command_actions, connection_actions, disconnect_actions, navigation_actions, tab_actions,
-    theme_actions, view_actions,
+    command_actions, connection_actions, disconnect_actions, navigation_actions,
+    query_history_actions, saved_query_actions, tab_actions, theme_actions, view_actions,
 };
 use types::{FilteredAction, PaletteMode};
 
@@ -69,6 +69,7 @@ impl ActionBar {
 
     fn open(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         self.open = true;
+        self.state.update(cx, |state, _cx| state.reload_saved_queries());
         self.selected_index = 0;
         self.rebuild_actions(cx);
         self.filter_actions("");
@@ -174,6 +175,21 @@ impl ActionBar {
         cx.notify();
     }
 
+    fn switch_to_history(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.mode = PaletteMode::History;
+        self.rebuild_actions(cx);
+        self.filter_actions("");
+        self.selected_index = 0;
+        self.scroll_offset = 0;
+        if let Some(input) = self.input_state.clone() {
+            input.update(cx, |state, cx| {
+                state.set_placeholder("Search Query History...", window, cx);
+                state.set_value("", window, cx);
+            });
+        }
+        cx.notify();
+    }
+
     fn close(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         // Revert to original theme if we were previewing
         if let Some(original) = self.original_theme.take() {
@@ -199,11 +215,13 @@ impl ActionBar {
                 actions.extend(command_actions(state));
                 actions.extend(navigation_actions(state));
                 actions.extend(view_actions(state));
+                actions.extend(saved_query_actions(state));
                 actions
             }
             PaletteMode::Theme => theme_actions(state),
             PaletteMode::Connect => connection_actions(state),
             PaletteMode::Disconnect => disconnect_actions(state),
+            PaletteMode::History => query_history_actions(state),
         };
     }
 
@@ -279,6 +297,12 @@ impl ActionBar {
             return;
         }
 
+        // Intercept "Query History" — switch to history mode instead of closing
+        if action.item.id.as_ref() == "cmd:query-history" {
+            self.switch_to_history(window, cx);
+            return;
+        }
+
         // Theme confirm: clear original so close doesn't revert, then fire handler
         if self.mode == PaletteMode::Theme {
             self.original_theme = None;
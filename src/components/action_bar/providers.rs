@@ -247,6 +247,15 @@ pub fn command_actions(state: &AppState) -> Vec<ActionItem> {
             priority: 4,
             ..Default::default()
         },
+        ActionItem {
+            id: SharedString::from("cmd:query-history"),
+            label: SharedString::from("Query History"),
+            detail: Some(SharedString::from("Search and restore recent queries")),
+            category: ActionCategory::Command,
+            available: !state.query_history.entries.is_empty(),
+            priority: 6,
+            ..Default::default()
+        },
         ActionItem {
             id: SharedString::from("cmd:change-theme"),
             label: SharedString::from("Theme Selector: Toggle"),
@@ -356,3 +365,52 @@ pub fn view_actions(state: &AppState) -> Vec<ActionItem> {
         },
     ]
 }
+
+/// Query history: recent queries on connected connections, newest first.
+pub fn query_history_actions(state: &AppState) -> Vec<ActionItem> {
+    state
+        .query_history
+        .entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| ActionItem {
+            id: SharedString::from(format!("history:{}", entry.id)),
+            label: SharedString::from(entry.summary()),
+            detail: Some(SharedString::from(entry.details())),
+            category: ActionCategory::Command,
+            available: state.is_connected(entry.connection_id),
+            priority: i as i32,
+            ..Default::default()
+        })
+        .collect()
+}
+
+/// Saved queries offered for the selected collection, global ones included.
+pub fn saved_query_actions(state: &AppState) -> Vec<ActionItem> {
+    let connected = state.selected_connection_id().is_some_and(|id| state.is_connected(id));
+    let database = state.selected_database_name();
+    let collection = state.selected_collection_name();
+    state
+        .saved_queries
+        .queries
+        .iter()
+        .filter(|query| query.applies_to(database.as_deref(), collection.as_deref()))
+        .enumerate()
+        .map(|(i, query)| {
+            let mut detail = query.query.kind_label().to_string();
+            if !query.description.is_empty() {
+                detail.push_str(" · ");
+                detail.push_str(&query.description);
+            }
+            ActionItem {
+                id: SharedString::from(format!("saved:{}", query.id)),
+                label: SharedString::from(query.name.clone()),
+                detail: Some(SharedString::from(detail)),
+                category: ActionCategory::SavedQuery,
+                available: connected,
+                priority: i as i32,
+                ..Default::default()
+            }
+        })
+        .collect()
+}
@@ -22,6 +22,7 @@ pub enum ActionCategory {
     Command,
     Tab,
     View,
+    SavedQuery,
 }
 
 impl ActionCategory {
@@ -31,6 +32,7 @@ impl ActionCategory {
             Self::Command => "Commands",
             Self::Tab => "Tabs",
             Self::View => "View",
+            Self::SavedQuery => "Saved Queries",
         }
     }
 
@@ -40,6 +42,7 @@ impl ActionCategory {
             Self::Command => 1,
             Self::Navigation => 2,
             Self::View => 3,
+            Self::SavedQuery => 4,
         }
     }
 }
@@ -59,6 +62,7 @@ pub enum PaletteMode {
     Theme,
     Connect,
     Disconnect,
+    History,
 }
 
 /// Payload returned when user executes an action.
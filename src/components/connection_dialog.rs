@@ -435,6 +435,11 @@ impl Render for ConnectionDialog {
                                                 read_only,
                                                 ssh: existing.ssh.clone(),
                                                 proxy: existing.proxy.clone(),
+                                                tls: existing.tls.clone(),
+                                                auth: existing.auth.clone(),
+                                                folder: existing.folder.clone(),
+                                                tags: existing.tags.clone(),
+                                                environment: existing.environment,
                                             };
                                             state.update_connection(connection, cx);
                                         } else {
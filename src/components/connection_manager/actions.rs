@@ -10,19 +10,31 @@ use tokio::sync::mpsc;
 use uuid::Uuid;
 
 use crate::components::{Button, cancel_button, open_confirm_dialog};
+use crate::connection::diagnostics::DiagnosticReport;
 use crate::helpers::{
     REDACTED_PASSWORD, extract_host_from_uri, extract_uri_password, inject_uri_password,
-    redact_uri_password, validate_mongodb_uri,
+    redact_uri_password, ssh_config, validate_mongodb_uri,
+};
+use crate::models::{
+    AuthConfig, AuthMechanism, ProxyConfig, ProxyKind, SavedConnection, SshAuth, SshConfig,
+    SshJumpHost, TlsConfig,
 };
-use crate::models::{ProxyConfig, ProxyKind, SavedConnection, SshAuth, SshConfig};
 use crate::state::AppState;
 use crate::theme::spacing;
 
 use super::uri::{bool_to_query, parse_bool, parse_uri, value_or_none};
-use super::{ConnectionManager, TestStatus};
+use super::{ConnectionManager, JumpHopDraft, TestStatus};
 
 const TEST_CONNECTION_TIMEOUT_SECS: u64 = 30;
 
+/// Typed per-connection settings collected from the draft (saved outside the URI).
+struct TransportSettings {
+    ssh: Option<SshConfig>,
+    proxy: Option<ProxyConfig>,
+    tls: Option<TlsConfig>,
+    auth: Option<AuthConfig>,
+}
+
 impl ConnectionManager {
     pub fn open(state: Entity<AppState>, window: &mut Window, cx: &mut App) {
         Self::open_with_selected(state, None, window, cx);
@@ -68,6 +80,7 @@ impl ConnectionManager {
         self.last_tested_uri = None;
         self.pending_test_uri = None;
         self.testing_step = None;
+        self.diagnostics = None;
         self.parse_error = None;
         self.creating_new = connection.is_none();
 
@@ -81,6 +94,13 @@ impl ConnectionManager {
                 state.set_cursor_position(Position::new(0, 0), window, cx);
             });
             self.draft.read_only = connection.read_only;
+            self.draft.environment = connection.environment;
+            self.draft.folder_state.update(cx, |state, cx| {
+                state.set_value(connection.folder.clone().unwrap_or_default(), window, cx)
+            });
+            self.draft
+                .tags_state
+                .update(cx, |state, cx| state.set_value(connection.tags.join(", "), window, cx));
             self.load_transport_settings(&connection, window, cx);
             self.import_from_uri(window, cx);
         } else {
@@ -96,33 +116,11 @@ impl ConnectionManager {
         cx: &mut Context<Self>,
     ) {
         if let Some(ssh) = &connection.ssh {
-            self.draft.ssh_enabled = ssh.enabled;
-            self.draft.ssh_use_identity_file = matches!(ssh.auth, SshAuth::IdentityFile);
-            self.draft.ssh_strict_host_key_checking = ssh.strict_host_key_checking;
-            self.draft
-                .ssh_host_state
-                .update(cx, |state, cx| state.set_value(ssh.host.clone(), window, cx));
-            self.draft
-                .ssh_port_state
-                .update(cx, |state, cx| state.set_value(ssh.port.to_string(), window, cx));
-            self.draft
-                .ssh_username_state
-                .update(cx, |state, cx| state.set_value(ssh.username.clone(), window, cx));
-            self.draft.ssh_password_state.update(cx, |state, cx| {
-                state.set_value(ssh.password.clone().unwrap_or_default(), window, cx)
-            });
-            self.draft.ssh_identity_file_state.update(cx, |state, cx| {
-                state.set_value(ssh.identity_file.clone().unwrap_or_default(), window, cx)
-            });
-            self.draft.ssh_identity_passphrase_state.update(cx, |state, cx| {
-                state.set_value(ssh.identity_passphrase.clone().unwrap_or_default(), window, cx)
-            });
-            self.draft
-                .ssh_local_bind_host_state
-                .update(cx, |state, cx| state.set_value(ssh.local_bind_host.clone(), window, cx));
+            self.load_ssh_settings(ssh, window, cx);
         } else {
             self.draft.ssh_enabled = false;
             self.draft.ssh_use_identity_file = false;
+            self.draft.ssh_use_agent = false;
             self.draft.ssh_strict_host_key_checking = true;
             self.draft
                 .ssh_host_state
@@ -145,6 +143,10 @@ impl ConnectionManager {
             self.draft
                 .ssh_local_bind_host_state
                 .update(cx, |state, cx| state.set_value("127.0.0.1".to_string(), window, cx));
+            self.draft.ssh_jump_hosts.clear();
+            self.draft
+                .ssh_jump_hosts_state
+                .update(cx, |state, cx| state.set_value(String::new(), window, cx));
         }
 
         if let Some(proxy) = &connection.proxy {
@@ -176,12 +178,137 @@ impl ConnectionManager {
                 .proxy_password_state
                 .update(cx, |state, cx| state.set_value(String::new(), window, cx));
         }
+
+        let tls = connection.tls.clone().unwrap_or_default();
+        self.draft.tls = tls.enabled;
+        self.draft.tls_insecure = tls.insecure;
+        self.draft.tls_allow_invalid_hostnames = tls.allow_invalid_hostnames;
+        self.draft.tls_ca_file_state.update(cx, |state, cx| {
+            state.set_value(tls.ca_file.clone().unwrap_or_default(), window, cx)
+        });
+        self.draft.tls_cert_key_file_state.update(cx, |state, cx| {
+            state.set_value(tls.cert_key_file.clone().unwrap_or_default(), window, cx)
+        });
+        self.draft.tls_cert_key_password_state.update(cx, |state, cx| {
+            state.set_value(tls.cert_key_password.clone().unwrap_or_default(), window, cx)
+        });
+
+        let auth = connection.auth.clone().unwrap_or_default();
+        self.draft.auth_mechanism = auth.mechanism;
+        self.draft
+            .username_state
+            .update(cx, |state, cx| state.set_value(auth.username.clone(), window, cx));
+        self.draft.password_state.update(cx, |state, cx| {
+            state.set_value(auth.password.clone().unwrap_or_default(), window, cx)
+        });
+        self.draft.auth_source_state.update(cx, |state, cx| {
+            state.set_value(auth.auth_source.clone().unwrap_or_default(), window, cx)
+        });
     }
 
-    fn build_transport_settings(
-        &self,
-        cx: &App,
-    ) -> std::result::Result<(Option<SshConfig>, Option<ProxyConfig>), String> {
+    fn load_ssh_settings(&mut self, ssh: &SshConfig, window: &mut Window, cx: &mut Context<Self>) {
+        self.draft.ssh_enabled = ssh.enabled;
+        self.draft.ssh_use_identity_file = matches!(ssh.auth, SshAuth::IdentityFile);
+        self.draft.ssh_use_agent = matches!(ssh.auth, SshAuth::Agent);
+        self.draft.ssh_strict_host_key_checking = ssh.strict_host_key_checking;
+        self.draft
+            .ssh_host_state
+            .update(cx, |state, cx| state.set_value(ssh.host.clone(), window, cx));
+        self.draft
+            .ssh_port_state
+            .update(cx, |state, cx| state.set_value(ssh.port.to_string(), window, cx));
+        self.draft
+            .ssh_username_state
+            .update(cx, |state, cx| state.set_value(ssh.username.clone(), window, cx));
+        self.draft.ssh_password_state.update(cx, |state, cx| {
+            state.set_value(ssh.password.clone().unwrap_or_default(), window, cx)
+        });
+        self.draft.ssh_identity_file_state.update(cx, |state, cx| {
+            state.set_value(ssh.identity_file.clone().unwrap_or_default(), window, cx)
+        });
+        self.draft.ssh_identity_passphrase_state.update(cx, |state, cx| {
+            state.set_value(ssh.identity_passphrase.clone().unwrap_or_default(), window, cx)
+        });
+        self.draft
+            .ssh_local_bind_host_state
+            .update(cx, |state, cx| state.set_value(ssh.local_bind_host.clone(), window, cx));
+        self.draft.ssh_jump_hosts =
+            ssh.jump_hosts.iter().map(|hop| JumpHopDraft::new(hop.clone(), window, cx)).collect();
+        self.draft.ssh_jump_hosts_state.update(cx, |state, cx| {
+            state.set_value(SshJumpHost::format_proxy_jump(&ssh.jump_hosts), window, cx)
+        });
+    }
+
+    /// Rebuild the per-hop editors after the ProxyJump field changed. Hops keep
+    /// their id and inputs when their address is unchanged, or when they were
+    /// edited in place (same position, old address gone).
+    pub(super) fn sync_jump_hop_drafts(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let spec = self.draft.ssh_jump_hosts_state.read(cx).value().to_string();
+        // Keep the current editors while the field does not parse mid-edit.
+        let Ok(parsed) = SshJumpHost::parse_proxy_jump(&spec) else {
+            return;
+        };
+        let addresses: Vec<String> = parsed.iter().map(SshJumpHost::address).collect();
+        let mut previous: Vec<Option<JumpHopDraft>> =
+            std::mem::take(&mut self.draft.ssh_jump_hosts).into_iter().map(Some).collect();
+
+        let mut hops = Vec::with_capacity(parsed.len());
+        for (index, parsed_hop) in parsed.into_iter().enumerate() {
+            let same_address = previous.iter().position(|slot| {
+                slot.as_ref().is_some_and(|draft| draft.hop.address() == addresses[index])
+            });
+            let edited_in_place = || {
+                previous
+                    .get(index)
+                    .and_then(Option::as_ref)
+                    .filter(|draft| !addresses.contains(&draft.hop.address()))
+                    .map(|_| index)
+            };
+            let reused = same_address.or_else(edited_in_place).and_then(|i| previous[i].take());
+            hops.push(match reused {
+                Some(mut draft) => {
+                    draft.hop.host = parsed_hop.host;
+                    draft.hop.port = parsed_hop.port;
+                    draft.hop.username = parsed_hop.username;
+                    draft
+                }
+                None => JumpHopDraft::new(parsed_hop, window, cx),
+            });
+        }
+        self.draft.ssh_jump_hosts = hops;
+        cx.notify();
+    }
+
+    /// Fill the SSH section from a `Host` alias in `~/.ssh/config`. The config
+    /// (and its `Include`s) is read off the UI thread.
+    pub(super) fn import_ssh_config_alias(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let alias = self.draft.ssh_config_alias_state.read(cx).value().trim().to_string();
+        let window_handle = window.window_handle();
+        let view = cx.entity();
+        let task = cx.background_spawn(async move { ssh_config::import_host_alias(&alias) });
+        cx.spawn(async move |_this, cx: &mut gpui::AsyncApp| {
+            let result = task.await;
+            let _ = cx.update_window(window_handle, |_root, window, cx| {
+                view.update(cx, |this, cx| {
+                    match result {
+                        Ok(ssh) => {
+                            this.load_ssh_settings(&ssh, window, cx);
+                            this.parse_error = None;
+                        }
+                        Err(err) => {
+                            let message = err.to_string();
+                            this.parse_error = Some(message.clone());
+                            this.status = TestStatus::Error(message);
+                        }
+                    }
+                    cx.notify();
+                });
+            });
+        })
+        .detach();
+    }
+
+    fn build_transport_settings(&self, cx: &App) -> std::result::Result<TransportSettings, String> {
         let read_trim = |state: &Entity<InputState>| state.read(cx).value().trim().to_string();
         let read_opt = |state: &Entity<InputState>| {
             let value = state.read(cx).value().trim().to_string();
@@ -204,7 +331,9 @@ impl ConnectionManager {
                 if value.is_empty() { "127.0.0.1".to_string() } else { value }
             };
 
-            let auth = if self.draft.ssh_use_identity_file {
+            let auth = if self.draft.ssh_use_agent {
+                SshAuth::Agent
+            } else if self.draft.ssh_use_identity_file {
                 SshAuth::IdentityFile
             } else {
                 SshAuth::Password
@@ -224,6 +353,32 @@ impl ConnectionManager {
                 _ => {}
             }
 
+            let jump_hosts =
+                SshJumpHost::parse_proxy_jump(&read_trim(&self.draft.ssh_jump_hosts_state))?
+                    .into_iter()
+                    .map(|parsed| {
+                        let address = parsed.address();
+                        let mut hop = self
+                            .draft
+                            .ssh_jump_hosts
+                            .iter()
+                            .find(|draft| draft.hop.address() == address)
+                            .map_or(parsed, |draft| draft.to_hop(cx));
+                        if hop.username.is_empty() {
+                            hop.username = username.clone();
+                        }
+                        match hop.auth {
+                            SshAuth::Password if hop.password.is_none() => {
+                                Err(format!("Jump host {address}: password is required"))
+                            }
+                            SshAuth::IdentityFile if hop.identity_file.is_none() => {
+                                Err(format!("Jump host {address}: identity file is required"))
+                            }
+                            _ => Ok(hop),
+                        }
+                    })
+                    .collect::<std::result::Result<Vec<_>, String>>()?;
+
             Some(SshConfig {
                 enabled: true,
                 host,
@@ -235,6 +390,7 @@ impl ConnectionManager {
                 identity_passphrase,
                 strict_host_key_checking: self.draft.ssh_strict_host_key_checking,
                 local_bind_host,
+                jump_hosts,
             })
         } else {
             None
@@ -260,13 +416,49 @@ impl ConnectionManager {
             None
         };
 
-        if ssh.as_ref().is_some_and(|cfg| cfg.enabled)
-            && proxy.as_ref().is_some_and(|cfg| cfg.enabled)
-        {
-            return Err("SSH tunnel and SOCKS5 proxy cannot be enabled together yet".to_string());
-        }
+        let tls = if self.draft.tls {
+            if self.draft.tls_allow_invalid_hostnames && !self.draft.tls_insecure {
+                return Err(
+                    "Allow invalid hostnames requires TLS insecure; enable both or neither"
+                        .to_string(),
+                );
+            }
+            Some(TlsConfig {
+                enabled: true,
+                ca_file: read_opt(&self.draft.tls_ca_file_state),
+                cert_key_file: read_opt(&self.draft.tls_cert_key_file_state),
+                cert_key_password: read_opt(&self.draft.tls_cert_key_password_state),
+                allow_invalid_hostnames: self.draft.tls_allow_invalid_hostnames,
+                insecure: self.draft.tls_insecure,
+            })
+        } else {
+            None
+        };
+
+        let mechanism = self.draft.auth_mechanism;
+        let username = read_trim(&self.draft.username_state);
+        let auth = if mechanism == AuthMechanism::Scram && username.is_empty() {
+            None
+        } else {
+            if mechanism.uses_password() && username.is_empty() {
+                return Err(format!("Username is required for {}", mechanism.label()));
+            }
+            if mechanism == AuthMechanism::X509
+                && tls.as_ref().is_none_or(|tls| tls.cert_key_file.is_none())
+            {
+                return Err("X.509 authentication requires a TLS certificate key file".to_string());
+            }
+            // Passwords may legitimately start or end with spaces.
+            let password = self.draft.password_state.read(cx).value().to_string();
+            Some(AuthConfig {
+                mechanism,
+                username,
+                auth_source: read_opt(&self.draft.auth_source_state),
+                password: (mechanism.uses_password() && !password.is_empty()).then_some(password),
+            })
+        };
 
-        Ok((ssh, proxy))
+        Ok(TransportSettings { ssh, proxy, tls, auth })
     }
 
     pub(super) fn real_uri(&self, cx: &App) -> String {
@@ -287,22 +479,36 @@ impl ConnectionManager {
         let uri = self.draft.uri_state.read(cx).value().to_string();
         match parse_uri(&uri) {
             Ok(parts) => {
+                // Credentials live in `AuthConfig`; URI credentials only seed it.
                 let (user, password) = parts.userinfo();
-                self.draft
-                    .username_state
-                    .update(cx, |state, cx| state.set_value(user.unwrap_or_default(), window, cx));
-                self.draft.password_state.update(cx, |state, cx| {
-                    state.set_value(password.unwrap_or_default(), window, cx)
-                });
+                if let Some(user) = user {
+                    self.draft
+                        .username_state
+                        .update(cx, |state, cx| state.set_value(user, window, cx));
+                    self.draft.password_state.update(cx, |state, cx| {
+                        state.set_value(password.unwrap_or_default(), window, cx)
+                    });
+                }
+                let auth_source = parts.get_query("authSource");
+                if !auth_source.is_empty() {
+                    self.draft
+                        .auth_source_state
+                        .update(cx, |state, cx| state.set_value(auth_source, window, cx));
+                }
+                let mechanism = parts.get_query("authMechanism");
+                if !mechanism.is_empty() {
+                    match AuthMechanism::from_uri_name(&mechanism) {
+                        Some(mechanism) => self.draft.auth_mechanism = mechanism,
+                        None => {
+                            self.parse_error =
+                                Some(format!("Unsupported auth mechanism: {mechanism}"));
+                            return;
+                        }
+                    }
+                }
                 self.draft.app_name_state.update(cx, |state, cx| {
                     state.set_value(parts.get_query("appName"), window, cx)
                 });
-                self.draft.auth_source_state.update(cx, |state, cx| {
-                    state.set_value(parts.get_query("authSource"), window, cx)
-                });
-                self.draft.auth_mechanism_state.update(cx, |state, cx| {
-                    state.set_value(parts.get_query("authMechanism"), window, cx)
-                });
                 self.draft.auth_mechanism_props_state.update(cx, |state, cx| {
                     state.set_value(parts.get_query("authMechanismProperties"), window, cx)
                 });
@@ -339,18 +545,34 @@ impl ConnectionManager {
                 self.draft.zlib_level_state.update(cx, |state, cx| {
                     state.set_value(parts.get_query("zlibCompressionLevel"), window, cx)
                 });
-                self.draft.tls_ca_file_state.update(cx, |state, cx| {
-                    state.set_value(parts.get_query("tlsCAFile"), window, cx)
-                });
-                self.draft.tls_cert_key_file_state.update(cx, |state, cx| {
-                    state.set_value(parts.get_query("tlsCertificateKeyFile"), window, cx)
-                });
-                self.draft.tls_cert_key_password_state.update(cx, |state, cx| {
-                    state.set_value(parts.get_query("tlsCertificateKeyFilePassword"), window, cx)
-                });
                 self.draft.direct_connection = parse_bool(parts.get_query("directConnection"));
-                self.draft.tls = parse_bool(parts.get_query("tls"));
-                self.draft.tls_insecure = parse_bool(parts.get_query("tlsInsecure"));
+                // TLS lives in `TlsConfig`; URI options only seed it (e.g. a pasted URI).
+                for (key, input) in [
+                    ("tlsCAFile", &self.draft.tls_ca_file_state),
+                    ("tlsCertificateKeyFile", &self.draft.tls_cert_key_file_state),
+                    ("tlsCertificateKeyFilePassword", &self.draft.tls_cert_key_password_state),
+                ] {
+                    let value = parts.get_query(key);
+                    if !value.is_empty() {
+                        input.update(cx, |state, cx| state.set_value(value, window, cx));
+                    }
+                }
+                let tls = match parts.get_query("tls") {
+                    value if value.is_empty() => parts.get_query("ssl"),
+                    value => value,
+                };
+                if !tls.is_empty() {
+                    self.draft.tls = parse_bool(tls);
+                }
+                for (key, flag) in [
+                    ("tlsInsecure", &mut self.draft.tls_insecure),
+                    ("tlsAllowInvalidHostnames", &mut self.draft.tls_allow_invalid_hostnames),
+                ] {
+                    let value = parts.get_query(key);
+                    if !value.is_empty() {
+                        *flag = parse_bool(value);
+                    }
+                }
                 self.parse_error = None;
 
                 let redacted = redact_uri_password(&uri);
@@ -379,12 +601,12 @@ impl ConnectionManager {
             }
         };
 
-        let username = value_or_none(&self.draft.username_state, cx);
-        let password = value_or_none(&self.draft.password_state, cx);
-        parts.set_userinfo(username, password);
+        // Credentials are saved in `AuthConfig` and applied at connect time, which
+        // keeps the URI free of secrets and shareable.
+        parts.set_userinfo(None, None);
+        parts.set_query("authSource", None);
+        parts.set_query("authMechanism", None);
         parts.set_query("appName", value_or_none(&self.draft.app_name_state, cx));
-        parts.set_query("authSource", value_or_none(&self.draft.auth_source_state, cx));
-        parts.set_query("authMechanism", value_or_none(&self.draft.auth_mechanism_state, cx));
         parts.set_query(
             "authMechanismProperties",
             value_or_none(&self.draft.auth_mechanism_props_state, cx),
@@ -406,18 +628,20 @@ impl ConnectionManager {
         );
         parts.set_query("compressors", value_or_none(&self.draft.compressors_state, cx));
         parts.set_query("zlibCompressionLevel", value_or_none(&self.draft.zlib_level_state, cx));
-        parts.set_query("tlsCAFile", value_or_none(&self.draft.tls_ca_file_state, cx));
-        parts.set_query(
+        parts.set_query("directConnection", bool_to_query(self.draft.direct_connection));
+        // TLS options are saved in `TlsConfig` and applied at connect time, which
+        // keeps the key password out of the URI.
+        for key in [
+            "tls",
+            "ssl",
+            "tlsInsecure",
+            "tlsAllowInvalidHostnames",
+            "tlsCAFile",
             "tlsCertificateKeyFile",
-            value_or_none(&self.draft.tls_cert_key_file_state, cx),
-        );
-        parts.set_query(
             "tlsCertificateKeyFilePassword",
-            value_or_none(&self.draft.tls_cert_key_password_state, cx),
-        );
-        parts.set_query("directConnection", bool_to_query(self.draft.direct_connection));
-        parts.set_query("tls", bool_to_query(self.draft.tls));
-        parts.set_query("tlsInsecure", bool_to_query(self.draft.tls_insecure));
+        ] {
+            parts.set_query(key, None);
+        }
 
         let updated = redact_uri_password(&parts.to_uri());
         self.draft.uri_state.update(cx, |state, cx| {
@@ -431,20 +655,21 @@ impl ConnectionManager {
     pub(super) fn start_test(view: Entity<ConnectionManager>, cx: &mut App) {
         let display_uri = view.read(cx).draft.uri_state.read(cx).value().to_string();
         let real_uri = view.read(cx).real_uri(cx);
-        let (ssh, proxy) = match view.read(cx).build_transport_settings(cx) {
-            Ok(settings) => settings,
-            Err(err) => {
-                view.update(cx, |this, cx| {
-                    this.parse_error = Some(err.clone());
-                    this.status = TestStatus::Error(err);
-                    this.last_tested_uri = None;
-                    this.pending_test_uri = None;
-                    this.testing_step = None;
-                    cx.notify();
-                });
-                return;
-            }
-        };
+        let TransportSettings { ssh, proxy, tls, auth } =
+            match view.read(cx).build_transport_settings(cx) {
+                Ok(settings) => settings,
+                Err(err) => {
+                    view.update(cx, |this, cx| {
+                        this.parse_error = Some(err.clone());
+                        this.status = TestStatus::Error(err);
+                        this.last_tested_uri = None;
+                        this.pending_test_uri = None;
+                        this.testing_step = None;
+                        cx.notify();
+                    });
+                    return;
+                }
+            };
         if let Err(err) = validate_mongodb_uri(&real_uri) {
             view.update(cx, |this, cx| {
                 let message = err.to_string();
@@ -466,6 +691,7 @@ impl ConnectionManager {
             this.last_tested_uri = None;
             this.parse_error = None;
             this.testing_step = Some("Preparing transport settings".to_string());
+            this.diagnostics = None;
             cx.notify();
         });
 
@@ -475,14 +701,15 @@ impl ConnectionManager {
                 let mut temp = SavedConnection::new("Test".to_string(), real_uri);
                 temp.ssh = ssh;
                 temp.proxy = proxy;
-                manager.test_connection_with_progress(
+                temp.tls = tls;
+                temp.auth = auth;
+                manager.diagnose_connection(
                     &temp,
                     std::time::Duration::from_secs(TEST_CONNECTION_TIMEOUT_SECS),
                     move |step| {
                         let _ = progress_tx.send(step);
                     },
-                )?;
-                Ok::<(), crate::error::Error>(())
+                )
             }
         });
 
@@ -508,7 +735,7 @@ impl ConnectionManager {
                             });
                         }
                         result = &mut task => {
-                            let result: Result<(), crate::error::Error> = result;
+                            let report: DiagnosticReport = result;
                             let _ = cx.update(|cx| {
                                 view.update(cx, |this, cx| {
                                     let current_uri = this.draft.uri_state.read(cx).value().to_string();
@@ -522,16 +749,17 @@ impl ConnectionManager {
                                         return;
                                     }
 
-                                    match result {
-                                        Ok(()) => {
+                                    match report.error.clone() {
+                                        None => {
                                             this.status = TestStatus::Success;
                                             this.last_tested_uri = Some(current_uri);
                                         }
-                                        Err(err) => {
-                                            this.status = TestStatus::Error(err.to_string());
+                                        Some(err) => {
+                                            this.status = TestStatus::Error(err);
                                             this.last_tested_uri = None;
                                         }
                                     }
+                                    this.diagnostics = Some(report);
                                     this.pending_test_uri = None;
                                     this.testing_step = None;
                                     cx.notify();
@@ -573,7 +801,10 @@ impl ConnectionManager {
         };
 
         let read_only = self.draft.read_only;
-        let (ssh, proxy) = match self.build_transport_settings(cx) {
+        let environment = self.draft.environment;
+        let folder = SavedConnection::normalize_folder(&self.draft.folder_state.read(cx).value());
+        let tags = SavedConnection::parse_tags(&self.draft.tags_state.read(cx).value());
+        let TransportSettings { ssh, proxy, tls, auth } = match self.build_transport_settings(cx) {
             Ok(settings) => settings,
             Err(err) => {
                 self.parse_error = Some(err.clone());
@@ -595,6 +826,11 @@ impl ConnectionManager {
                         read_only,
                         ssh: ssh.clone(),
                         proxy: proxy.clone(),
+                        tls: tls.clone(),
+                        auth: auth.clone(),
+                        folder: folder.clone(),
+                        tags: tags.clone(),
+                        environment,
                     };
                     state.update_connection(connection.clone(), cx);
                     saved_connection = Some(connection);
@@ -604,6 +840,11 @@ impl ConnectionManager {
                 connection.read_only = read_only;
                 connection.ssh = ssh.clone();
                 connection.proxy = proxy.clone();
+                connection.tls = tls.clone();
+                connection.auth = auth.clone();
+                connection.folder = folder.clone();
+                connection.tags = tags.clone();
+                connection.environment = environment;
                 state.add_connection(connection.clone(), cx);
                 saved_connection = Some(connection);
             }
@@ -12,7 +12,7 @@ use uuid::Uuid;
 use crate::components::Button;
 use crate::helpers::extract_host_from_uri;
 use crate::models::SavedConnection;
-use crate::theme::{borders, sizing, spacing};
+use crate::theme::{borders, colors, sizing, spacing};
 
 use super::export_dialog::open_export_dialog;
 use super::import::open_import_flow;
@@ -126,13 +126,16 @@ impl ConnectionManager {
             .into_any_element()
     }
 
-    /// Renders the list of connections or an empty state.
+    /// Renders the list of connections, grouped by folder, or an empty state.
     fn render_list_content(
         view: Entity<Self>,
-        connections: Vec<SavedConnection>,
+        mut connections: Vec<SavedConnection>,
         selected_id: Option<Uuid>,
         cx: &App,
     ) -> AnyElement {
+        // Stable sort: ungrouped connections first, saved order kept within a folder.
+        connections.sort_by_cached_key(|conn| conn.folder.as_ref().map(|f| f.to_lowercase()));
+
         if connections.is_empty() {
             div()
                 .p(spacing::md())
@@ -146,13 +149,46 @@ impl ConnectionManager {
                 .flex_col()
                 .gap(spacing::xs())
                 .p(spacing::xs())
-                .child(div().flex().flex_col().gap(px(2.0)).children(connections.into_iter().map(
-                    move |conn| Self::render_connection_item(view.clone(), conn, selected_id, cx),
-                )))
+                .child(div().flex().flex_col().gap(px(2.0)).children({
+                    let mut items = Vec::with_capacity(connections.len());
+                    let mut current_folder: Option<String> = None;
+                    for conn in connections {
+                        if conn.folder.is_some() && conn.folder != current_folder {
+                            current_folder = conn.folder.clone();
+                            items.push(Self::render_folder_header(
+                                conn.folder.clone().unwrap_or_default(),
+                                cx,
+                            ));
+                        }
+                        items.push(Self::render_connection_item(
+                            view.clone(),
+                            conn,
+                            selected_id,
+                            cx,
+                        ));
+                    }
+                    items
+                }))
                 .into_any_element()
         }
     }
 
+    /// Renders a folder heading above its connections.
+    fn render_folder_header(folder: String, cx: &App) -> AnyElement {
+        div()
+            .flex()
+            .items_center()
+            .gap(spacing::xs())
+            .px(spacing::sm())
+            .pt(spacing::sm())
+            .pb(px(2.0))
+            .text_xs()
+            .text_color(cx.theme().muted_foreground)
+            .child(Icon::new(IconName::FolderOpen).xsmall())
+            .child(div().truncate().child(folder))
+            .into_any_element()
+    }
+
     /// Renders a single connection item in the list.
     fn render_connection_item(
         view: Entity<Self>,
@@ -167,6 +203,8 @@ impl ConnectionManager {
             .map(|dt| dt.format("%Y-%m-%d").to_string())
             .unwrap_or_else(|| "Never".to_string());
         let read_only = conn.read_only;
+        let environment = conn.environment;
+        let tags = conn.tags.clone();
 
         div()
             .flex()
@@ -186,20 +224,45 @@ impl ConnectionManager {
                     .items_center()
                     .justify_between()
                     .child(
-                        div().text_sm().text_color(cx.theme().foreground).child(conn.name.clone()),
+                        div()
+                            .flex_1()
+                            .min_w(px(0.0))
+                            .truncate()
+                            .text_sm()
+                            .text_color(cx.theme().foreground)
+                            .child(conn.name.clone()),
                     )
-                    .when(read_only, |s| {
-                        s.child(
-                            div()
-                                .px(spacing::xs())
-                                .py(px(1.0))
-                                .rounded(borders::radius_sm())
-                                .bg(cx.theme().warning)
-                                .text_xs()
-                                .text_color(cx.theme().tab_bar)
-                                .child("RO"),
-                        )
-                    }),
+                    .child(
+                        div()
+                            .flex()
+                            .flex_shrink_0()
+                            .items_center()
+                            .gap(px(2.0))
+                            .when_some(environment, |s, env| {
+                                s.child(
+                                    div()
+                                        .px(spacing::xs())
+                                        .py(px(1.0))
+                                        .rounded(borders::radius_sm())
+                                        .bg(colors::environment(env, cx))
+                                        .text_xs()
+                                        .text_color(cx.theme().tab_bar)
+                                        .child(env.badge()),
+                                )
+                            })
+                            .when(read_only, |s| {
+                                s.child(
+                                    div()
+                                        .px(spacing::xs())
+                                        .py(px(1.0))
+                                        .rounded(borders::radius_sm())
+                                        .bg(cx.theme().warning)
+                                        .text_xs()
+                                        .text_color(cx.theme().tab_bar)
+                                        .child("RO"),
+                                )
+                            }),
+                    ),
             )
             .child(div().text_xs().text_color(cx.theme().secondary_foreground).child(host))
             .child(
@@ -208,6 +271,19 @@ impl ConnectionManager {
                     .text_color(cx.theme().muted_foreground)
                     .child(format!("Last: {last_connected}")),
             )
+            .when(!tags.is_empty(), |s| {
+                s.child(div().flex().flex_wrap().gap(px(2.0)).children(tags.into_iter().map(
+                    |tag| {
+                        div()
+                            .px(spacing::xs())
+                            .rounded(borders::radius_sm())
+                            .bg(cx.theme().secondary)
+                            .text_xs()
+                            .text_color(cx.theme().secondary_foreground)
+                            .child(tag)
+                    },
+                )))
+            })
             .on_mouse_down(MouseButton::Left, {
                 move |_, window, cx| {
                     view.update(cx, |this, cx| {
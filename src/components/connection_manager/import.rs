@@ -19,7 +19,7 @@ pub fn open_import_flow(state: Entity<AppState>, window: &mut Window, cx: &mut A
     cx.spawn(async move |cx: &mut AsyncApp| {
         let path = open_file_dialog_async(
             FilePickerMode::Open,
-            vec![FileFilter::connections_json(), FileFilter::all()],
+            vec![FileFilter::connections_import(), FileFilter::all()],
             None,
         )
         .await;
@@ -48,7 +48,7 @@ pub fn open_import_flow(state: Entity<AppState>, window: &mut Window, cx: &mut A
                 let _ = cx.update(|cx| {
                     state_clone.update(cx, |state, _cx| {
                         state.set_status_message(Some(StatusMessage::error(format!(
-                            "Invalid export file: {e}"
+                            "Unrecognized connections file: {e}"
                         ))));
                     });
                 });
@@ -2,6 +2,8 @@ use gpui::{Entity, Subscription};
 use gpui_component::input::InputState;
 use uuid::Uuid;
 
+use crate::connection::diagnostics::DiagnosticReport;
+use crate::models::{AuthMechanism, ConnectionEnvironment, SshJumpHost};
 use crate::state::AppState;
 
 mod actions;
@@ -32,11 +34,12 @@ enum TestStatus {
 struct ConnectionDraft {
     name_state: Entity<InputState>,
     uri_state: Entity<InputState>,
+    folder_state: Entity<InputState>,
+    tags_state: Entity<InputState>,
     username_state: Entity<InputState>,
     password_state: Entity<InputState>,
     app_name_state: Entity<InputState>,
     auth_source_state: Entity<InputState>,
-    auth_mechanism_state: Entity<InputState>,
     auth_mechanism_props_state: Entity<InputState>,
     read_preference_state: Entity<InputState>,
     read_concern_state: Entity<InputState>,
@@ -59,27 +62,47 @@ struct ConnectionDraft {
     ssh_identity_file_state: Entity<InputState>,
     ssh_identity_passphrase_state: Entity<InputState>,
     ssh_local_bind_host_state: Entity<InputState>,
+    ssh_jump_hosts_state: Entity<InputState>,
+    ssh_config_alias_state: Entity<InputState>,
     proxy_host_state: Entity<InputState>,
     proxy_port_state: Entity<InputState>,
     proxy_username_state: Entity<InputState>,
     proxy_password_state: Entity<InputState>,
     read_only: bool,
+    environment: Option<ConnectionEnvironment>,
+    /// Saved as `AuthConfig`; the username, password and auth source inputs feed it.
+    auth_mechanism: AuthMechanism,
     direct_connection: bool,
     tls: bool,
     tls_insecure: bool,
+    /// Only valid together with `tls_insecure` (see `validate_tls_config`).
+    tls_allow_invalid_hostnames: bool,
     ssh_enabled: bool,
     ssh_use_identity_file: bool,
+    ssh_use_agent: bool,
     ssh_strict_host_key_checking: bool,
+    /// One editor per ProxyJump hop, kept in step with `ssh_jump_hosts_state`.
+    ssh_jump_hosts: Vec<JumpHopDraft>,
     proxy_enabled: bool,
     pool_expanded: bool,
     compression_expanded: bool,
 }
 
+/// Auth inputs for one jump host; `hop` carries its id, address, auth choice and
+/// host-key policy, the inputs its secrets and identity file.
+struct JumpHopDraft {
+    hop: SshJumpHost,
+    password_state: Entity<InputState>,
+    identity_file_state: Entity<InputState>,
+    identity_passphrase_state: Entity<InputState>,
+}
+
 pub struct ConnectionManager {
     state: Entity<AppState>,
     selected_id: Option<Uuid>,
     draft: ConnectionDraft,
     testing_step: Option<String>,
+    diagnostics: Option<DiagnosticReport>,
     active_tab: ManagerTab,
     creating_new: bool,
     status: TestStatus,
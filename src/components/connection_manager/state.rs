@@ -2,9 +2,10 @@ use gpui::{AppContext as _, Context, Entity, Window};
 use gpui_component::input::{InputEvent, InputState};
 use uuid::Uuid;
 
+use crate::models::{AuthMechanism, SshJumpHost};
 use crate::state::AppState;
 
-use super::{ConnectionDraft, ConnectionManager, ManagerTab, TestStatus};
+use super::{ConnectionDraft, ConnectionManager, JumpHopDraft, ManagerTab, TestStatus};
 
 impl ManagerTab {
     pub(super) fn all() -> [ManagerTab; 4] {
@@ -38,12 +39,13 @@ impl ConnectionDraft {
                     .placeholder("mongodb://localhost:27017")
                     .default_value("mongodb://localhost:27017")
             }),
+            folder_state: cx.new(|cx| InputState::new(window, cx).placeholder("Clients/Acme")),
+            tags_state: cx.new(|cx| InputState::new(window, cx).placeholder("billing, eu-west")),
             username_state: cx.new(|cx| InputState::new(window, cx).placeholder("username")),
             password_state: cx
                 .new(|cx| InputState::new(window, cx).placeholder("password").masked(true)),
             app_name_state: cx.new(|cx| InputState::new(window, cx).placeholder("MyApp")),
             auth_source_state: cx.new(|cx| InputState::new(window, cx).placeholder("admin")),
-            auth_mechanism_state: cx.new(|cx| InputState::new(window, cx)),
             auth_mechanism_props_state: cx.new(|cx| InputState::new(window, cx)),
             read_preference_state: cx.new(|cx| InputState::new(window, cx).placeholder("primary")),
             read_concern_state: cx.new(|cx| InputState::new(window, cx).placeholder("local")),
@@ -74,18 +76,27 @@ impl ConnectionDraft {
                 .new(|cx| InputState::new(window, cx).placeholder("passphrase").masked(true)),
             ssh_local_bind_host_state: cx
                 .new(|cx| InputState::new(window, cx).default_value("127.0.0.1")),
+            ssh_jump_hosts_state: cx
+                .new(|cx| InputState::new(window, cx).placeholder("user@jump1:22, user@jump2")),
+            ssh_config_alias_state: cx
+                .new(|cx| InputState::new(window, cx).placeholder("prod-bastion")),
             proxy_host_state: cx.new(|cx| InputState::new(window, cx).placeholder("127.0.0.1")),
             proxy_port_state: cx.new(|cx| InputState::new(window, cx).placeholder("1080")),
             proxy_username_state: cx.new(|cx| InputState::new(window, cx).placeholder("username")),
             proxy_password_state: cx
                 .new(|cx| InputState::new(window, cx).placeholder("password").masked(true)),
             read_only: false,
+            environment: None,
+            auth_mechanism: AuthMechanism::default(),
             direct_connection: false,
             tls: false,
             tls_insecure: false,
+            tls_allow_invalid_hostnames: false,
             ssh_enabled: false,
             ssh_use_identity_file: false,
+            ssh_use_agent: false,
             ssh_strict_host_key_checking: true,
+            ssh_jump_hosts: Vec::new(),
             proxy_enabled: false,
             pool_expanded: false,
             compression_expanded: false,
@@ -97,12 +108,12 @@ impl ConnectionDraft {
         self.uri_state.update(cx, |state, cx| {
             state.set_value("mongodb://localhost:27017".to_string(), window, cx)
         });
+        self.folder_state.update(cx, |state, cx| state.set_value(String::new(), window, cx));
+        self.tags_state.update(cx, |state, cx| state.set_value(String::new(), window, cx));
         self.username_state.update(cx, |state, cx| state.set_value(String::new(), window, cx));
         self.password_state.update(cx, |state, cx| state.set_value(String::new(), window, cx));
         self.app_name_state.update(cx, |state, cx| state.set_value(String::new(), window, cx));
         self.auth_source_state.update(cx, |state, cx| state.set_value(String::new(), window, cx));
-        self.auth_mechanism_state
-            .update(cx, |state, cx| state.set_value(String::new(), window, cx));
         self.auth_mechanism_props_state
             .update(cx, |state, cx| state.set_value(String::new(), window, cx));
         self.read_preference_state
@@ -135,6 +146,10 @@ impl ConnectionDraft {
             .update(cx, |state, cx| state.set_value(String::new(), window, cx));
         self.ssh_local_bind_host_state
             .update(cx, |state, cx| state.set_value("127.0.0.1".to_string(), window, cx));
+        self.ssh_jump_hosts_state
+            .update(cx, |state, cx| state.set_value(String::new(), window, cx));
+        self.ssh_config_alias_state
+            .update(cx, |state, cx| state.set_value(String::new(), window, cx));
         self.proxy_host_state.update(cx, |state, cx| state.set_value(String::new(), window, cx));
         self.proxy_port_state.update(cx, |state, cx| state.set_value(String::new(), window, cx));
         self.proxy_username_state
@@ -142,18 +157,69 @@ impl ConnectionDraft {
         self.proxy_password_state
             .update(cx, |state, cx| state.set_value(String::new(), window, cx));
         self.read_only = false;
+        self.environment = None;
+        self.auth_mechanism = AuthMechanism::default();
         self.direct_connection = false;
         self.tls = false;
         self.tls_insecure = false;
+        self.tls_allow_invalid_hostnames = false;
         self.ssh_enabled = false;
         self.ssh_use_identity_file = false;
+        self.ssh_use_agent = false;
         self.ssh_strict_host_key_checking = true;
+        self.ssh_jump_hosts.clear();
         self.proxy_enabled = false;
         self.pool_expanded = false;
         self.compression_expanded = false;
     }
 }
 
+impl JumpHopDraft {
+    pub(super) fn new(
+        hop: SshJumpHost,
+        window: &mut Window,
+        cx: &mut Context<ConnectionManager>,
+    ) -> Self {
+        let password = hop.password.clone().unwrap_or_default();
+        let identity_file = hop.identity_file.clone().unwrap_or_default();
+        let identity_passphrase = hop.identity_passphrase.clone().unwrap_or_default();
+        Self {
+            password_state: cx.new(|cx| {
+                InputState::new(window, cx)
+                    .placeholder("password")
+                    .masked(true)
+                    .default_value(password)
+            }),
+            identity_file_state: cx.new(|cx| {
+                InputState::new(window, cx)
+                    .placeholder("~/.ssh/id_ed25519")
+                    .default_value(identity_file)
+            }),
+            identity_passphrase_state: cx.new(|cx| {
+                InputState::new(window, cx)
+                    .placeholder("passphrase")
+                    .masked(true)
+                    .default_value(identity_passphrase)
+            }),
+            hop,
+        }
+    }
+
+    /// The hop as it will be saved, with secrets read from the inputs.
+    pub(super) fn to_hop(&self, cx: &gpui::App) -> SshJumpHost {
+        let read_opt = |state: &Entity<InputState>| {
+            let value = state.read(cx).value().trim().to_string();
+            if value.is_empty() { None } else { Some(value) }
+        };
+        SshJumpHost {
+            password: read_opt(&self.password_state),
+            identity_file: read_opt(&self.identity_file_state),
+            identity_passphrase: read_opt(&self.identity_passphrase_state),
+            ..self.hop.clone()
+        }
+    }
+}
+
 impl ConnectionManager {
     pub fn new(
         state: Entity<AppState>,
@@ -174,17 +240,30 @@ impl ConnectionManager {
                     view.last_tested_uri = None;
                     view.pending_test_uri = None;
                     view.testing_step = None;
+                    view.diagnostics = None;
                     view.parse_error = None;
                     cx.notify();
                 }
             },
         ));
 
+        let jump_hosts_state = draft.ssh_jump_hosts_state.clone();
+        subscriptions.push(cx.subscribe_in(
+            &jump_hosts_state,
+            window,
+            move |view, _state, event, window, cx| {
+                if matches!(event, InputEvent::Change) {
+                    view.sync_jump_hop_drafts(window, cx);
+                }
+            },
+        ));
+
         let mut view = Self {
             state,
             selected_id,
             draft,
             testing_step: None,
+            diagnostics: None,
             active_tab: ManagerTab::General,
             creating_new: false,
             status: TestStatus::Idle,
@@ -6,17 +6,21 @@ use gpui::prelude::FluentBuilder as _;
 use gpui::*;
 use gpui_component::ActiveTheme as _;
 use gpui_component::Sizable as _;
+use gpui_component::button::Button as MenuButton;
 use gpui_component::collapsible::Collapsible;
 use gpui_component::input::Input;
+use gpui_component::menu::{DropdownMenu as _, PopupMenuItem};
 use gpui_component::switch::Switch;
 
 use crate::components::Button;
-use crate::theme::spacing;
+use crate::models::{AuthMechanism, ConnectionEnvironment, SshAuth};
+use crate::theme::{borders, colors, sizing, spacing};
 
-use super::ConnectionManager;
+use super::{ConnectionManager, JumpHopDraft};
 
 impl ConnectionManager {
-    /// General tab: Name, URI, Read-only, Username/Password, App name, Auth fields.
+    /// General tab: Name, Folder/Tags/Environment, URI, Read-only, Authentication
+    /// (saved as `AuthConfig`), App name.
     pub(super) fn render_general_tab(
         &mut self,
         parse_error: Option<String>,
@@ -37,6 +41,90 @@ impl ConnectionManager {
                     .child(div().text_sm().text_color(cx.theme().foreground).child("Name"))
                     .child(Input::new(&self.draft.name_state)),
             )
+            // Folder / Tags
+            .child(
+                div()
+                    .grid()
+                    .grid_cols(2)
+                    .gap(spacing::md())
+                    .child(
+                        div()
+                            .flex()
+                            .flex_col()
+                            .gap(spacing::xs())
+                            .child(
+                                div().text_sm().text_color(cx.theme().foreground).child("Folder"),
+                            )
+                            .child(Input::new(&self.draft.folder_state)),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .flex_col()
+                            .gap(spacing::xs())
+                            .child(div().text_sm().text_color(cx.theme().foreground).child("Tags"))
+                            .child(Input::new(&self.draft.tags_state)),
+                    ),
+            )
+            // Environment
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap(spacing::xs())
+                    .child(div().text_sm().text_color(cx.theme().foreground).child("Environment"))
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap(spacing::sm())
+                            .when_some(self.draft.environment, |this, env| {
+                                this.child(
+                                    div()
+                                        .w(sizing::status_dot())
+                                        .h(sizing::status_dot())
+                                        .rounded_full()
+                                        .bg(colors::environment(env, cx)),
+                                )
+                            })
+                            .child(
+                                MenuButton::new("connection-environment")
+                                    .compact()
+                                    .label(
+                                        self.draft
+                                            .environment
+                                            .map_or("None", ConnectionEnvironment::label),
+                                    )
+                                    .dropdown_caret(true)
+                                    .rounded(borders::radius_sm())
+                                    .with_size(gpui_component::Size::Small)
+                                    .dropdown_menu_with_anchor(Corner::BottomLeft, {
+                                        let view = view.clone();
+                                        move |mut menu, _window, _cx| {
+                                            let options = std::iter::once(None).chain(
+                                                ConnectionEnvironment::ALL.into_iter().map(Some),
+                                            );
+                                            for environment in options {
+                                                let view = view.clone();
+                                                let label = environment
+                                                    .map_or("None", ConnectionEnvironment::label);
+                                                menu =
+                                                    menu.item(PopupMenuItem::new(label).on_click(
+                                                        move |_, _, cx| {
+                                                            view.update(cx, |this, cx| {
+                                                                this.draft.environment =
+                                                                    environment;
+                                                                cx.notify();
+                                                            });
+                                                        },
+                                                    ));
+                                            }
+                                            menu
+                                        }
+                                    }),
+                            ),
+                    ),
+            )
             // URI
             .child(
                 div()
@@ -134,6 +222,43 @@ impl ConnectionManager {
                             ),
                     ),
             )
+            // Authentication mechanism
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap(spacing::xs())
+                    .child(
+                        div().text_sm().text_color(cx.theme().foreground).child("Authentication"),
+                    )
+                    .child(
+                        MenuButton::new("auth-mechanism")
+                            .compact()
+                            .label(self.draft.auth_mechanism.label())
+                            .dropdown_caret(true)
+                            .rounded(borders::radius_sm())
+                            .with_size(gpui_component::Size::Small)
+                            .dropdown_menu_with_anchor(Corner::BottomLeft, {
+                                let view = view.clone();
+                                move |mut menu, _window, _cx| {
+                                    for mechanism in AuthMechanism::ALL {
+                                        let view = view.clone();
+                                        menu = menu.item(
+                                            PopupMenuItem::new(mechanism.label()).on_click(
+                                                move |_, _, cx| {
+                                                    view.update(cx, |this, cx| {
+                                                        this.draft.auth_mechanism = mechanism;
+                                                        cx.notify();
+                                                    });
+                                                },
+                                            ),
+                                        );
+                                    }
+                                    menu
+                                }
+                            }),
+                    ),
+            )
             // Username / Password
             .child(
                 div()
@@ -158,7 +283,11 @@ impl ConnectionManager {
                             .child(
                                 div().text_sm().text_color(cx.theme().foreground).child("Password"),
                             )
-                            .child(Input::new(&self.draft.password_state).mask_toggle()),
+                            .child(
+                                Input::new(&self.draft.password_state)
+                                    .mask_toggle()
+                                    .disabled(!self.draft.auth_mechanism.uses_password()),
+                            ),
                     ),
             )
             // App name
@@ -177,17 +306,12 @@ impl ConnectionManager {
                     .flex_col()
                     .gap(spacing::xs())
                     .child(div().text_sm().text_color(cx.theme().foreground).child("Auth source"))
-                    .child(Input::new(&self.draft.auth_source_state)),
-            )
-            .child(
-                div()
-                    .flex()
-                    .flex_col()
-                    .gap(spacing::xs())
-                    .child(
-                        div().text_sm().text_color(cx.theme().foreground).child("Auth mechanism"),
-                    )
-                    .child(Input::new(&self.draft.auth_mechanism_state)),
+                    .child(Input::new(&self.draft.auth_source_state))
+                    .child(div().text_xs().text_color(cx.theme().muted_foreground).child(format!(
+                        "Defaults to {} for {}",
+                        self.draft.auth_mechanism.default_source(),
+                        self.draft.auth_mechanism.label()
+                    ))),
             )
             .child(
                 div()
@@ -205,7 +329,7 @@ impl ConnectionManager {
             .into_any_element()
     }
 
-    /// TLS tab: unchanged.
+    /// TLS tab: CA and client certificate (saved as `TlsConfig`).
     pub(super) fn render_tls_tab(
         &mut self,
         _window: &mut Window,
@@ -252,6 +376,49 @@ impl ConnectionManager {
                     )
                     .child(div().text_sm().text_color(cx.theme().foreground).child("TLS insecure")),
             )
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap(spacing::xs())
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap(spacing::sm())
+                            .child(
+                                Switch::new("tls-allow-invalid-hostnames")
+                                    .checked(self.draft.tls_allow_invalid_hostnames)
+                                    .small()
+                                    .on_click({
+                                        let view = cx.entity();
+                                        move |checked, _window, cx| {
+                                            view.update(cx, |this, cx| {
+                                                this.draft.tls_allow_invalid_hostnames = *checked;
+                                                cx.notify();
+                                            });
+                                        }
+                                    }),
+                            )
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .text_color(cx.theme().foreground)
+                                    .child("Allow invalid hostnames"),
+                            ),
+                    )
+                    .when(
+                        self.draft.tls_allow_invalid_hostnames && !self.draft.tls_insecure,
+                        |this| {
+                            this.child(
+                                div()
+                                    .text_xs()
+                                    .text_color(cx.theme().danger)
+                                    .child("Only supported together with TLS insecure"),
+                            )
+                        },
+                    ),
+            )
             .child(
                 div()
                     .flex()
@@ -289,6 +456,122 @@ impl ConnectionManager {
             .into_any_element()
     }
 
+    /// Auth editor for one ProxyJump hop.
+    fn render_jump_hop(
+        index: usize,
+        draft: &JumpHopDraft,
+        view: Entity<Self>,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let field = |label: &'static str, input: Input| {
+            div()
+                .flex()
+                .flex_col()
+                .gap(spacing::xs())
+                .child(div().text_sm().text_color(cx.theme().foreground).child(label))
+                .child(input)
+        };
+        let auth_fields = match draft.hop.auth {
+            SshAuth::Password => div()
+                .child(field("Password", Input::new(&draft.password_state).mask_toggle()))
+                .into_any_element(),
+            SshAuth::IdentityFile => div()
+                .grid()
+                .grid_cols(2)
+                .gap(spacing::md())
+                .child(field("Identity file", Input::new(&draft.identity_file_state)))
+                .child(field(
+                    "Identity passphrase",
+                    Input::new(&draft.identity_passphrase_state).mask_toggle(),
+                ))
+                .into_any_element(),
+            SshAuth::Agent => div()
+                .text_xs()
+                .text_color(cx.theme().muted_foreground)
+                .child("Keys are offered by the running SSH agent (SSH_AUTH_SOCK).")
+                .into_any_element(),
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap(spacing::sm())
+            .mt(spacing::xs())
+            .p(spacing::sm())
+            .rounded(borders::radius_sm())
+            .border_1()
+            .border_color(cx.theme().border)
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap(spacing::sm())
+                    .child(div().text_sm().text_color(cx.theme().foreground).child(format!(
+                        "Hop {}: {}",
+                        index + 1,
+                        draft.hop.address()
+                    )))
+                    .child(
+                        MenuButton::new(("jump-hop-auth", index))
+                            .compact()
+                            .label(draft.hop.auth.label())
+                            .dropdown_caret(true)
+                            .rounded(borders::radius_sm())
+                            .with_size(gpui_component::Size::Small)
+                            .dropdown_menu_with_anchor(Corner::BottomRight, {
+                                let view = view.clone();
+                                move |mut menu, _window, _cx| {
+                                    for auth in SshAuth::ALL {
+                                        let view = view.clone();
+                                        menu =
+                                            menu.item(PopupMenuItem::new(auth.label()).on_click(
+                                                move |_, _, cx| {
+                                                    view.update(cx, |this, cx| {
+                                                        if let Some(draft) =
+                                                            this.draft.ssh_jump_hosts.get_mut(index)
+                                                        {
+                                                            draft.hop.auth = auth.clone();
+                                                        }
+                                                        cx.notify();
+                                                    });
+                                                },
+                                            ));
+                                    }
+                                    menu
+                                }
+                            }),
+                    ),
+            )
+            .child(auth_fields)
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap(spacing::sm())
+                    .child(
+                        Switch::new(("jump-hop-strict-host-key", index))
+                            .checked(draft.hop.strict_host_key_checking)
+                            .small()
+                            .on_click(move |checked, _window, cx| {
+                                view.update(cx, |this, cx| {
+                                    if let Some(draft) = this.draft.ssh_jump_hosts.get_mut(index) {
+                                        draft.hop.strict_host_key_checking = *checked;
+                                    }
+                                    cx.notify();
+                                });
+                            }),
+                    )
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(cx.theme().foreground)
+                            .child("Strict host key checking"),
+                    ),
+            )
+            .into_any_element()
+    }
+
     /// Network tab: SSH Tunnel + SOCKS5 Proxy with spacing-only section labels.
     pub(super) fn render_network_tab(
         &mut self,
@@ -297,7 +580,13 @@ impl ConnectionManager {
     ) -> AnyElement {
         let view = cx.entity();
 
-        let ssh_auth_block = if self.draft.ssh_use_identity_file {
+        let ssh_auth_block = if self.draft.ssh_use_agent {
+            div()
+                .text_xs()
+                .text_color(cx.theme().muted_foreground)
+                .child("Keys are offered by the running SSH agent (SSH_AUTH_SOCK).")
+                .into_any_element()
+        } else if self.draft.ssh_use_identity_file {
             div()
                 .grid()
                 .grid_cols(2)
@@ -344,18 +633,18 @@ impl ConnectionManager {
         div()
             .flex()
             .flex_col()
-            // Mutual-exclusion warning
+            // Chained transport note
             .when(both_enabled, |this| {
                 this.child(
                     div()
                         .mb(spacing::md())
                         .rounded_md()
-                        .bg(cx.theme().danger.opacity(0.08))
+                        .bg(cx.theme().info.opacity(0.08))
                         .px(spacing::sm())
                         .py(spacing::xs())
                         .text_xs()
-                        .text_color(cx.theme().danger_foreground)
-                        .child("SSH tunnel and SOCKS5 proxy cannot be enabled together."),
+                        .text_color(cx.theme().muted_foreground)
+                        .child("The SSH connection will be opened through the SOCKS5 proxy."),
                 )
             })
             // SSH Tunnel section
@@ -398,6 +687,42 @@ impl ConnectionManager {
                                     .child("Enable SSH tunnel"),
                             ),
                     )
+                    .child(
+                        div()
+                            .flex()
+                            .flex_col()
+                            .gap(spacing::xs())
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .text_color(cx.theme().foreground)
+                                    .child("Import from ~/.ssh/config"),
+                            )
+                            .child(
+                                div()
+                                    .flex()
+                                    .items_center()
+                                    .gap(spacing::sm())
+                                    .child(
+                                        div()
+                                            .flex_1()
+                                            .child(Input::new(&self.draft.ssh_config_alias_state)),
+                                    )
+                                    .child(
+                                        Button::new("import-ssh-config")
+                                            .compact()
+                                            .label("Import")
+                                            .on_click({
+                                                let view = view.clone();
+                                                move |_, window, cx| {
+                                                    view.update(cx, |this, cx| {
+                                                        this.import_ssh_config_alias(window, cx);
+                                                    });
+                                                }
+                                            }),
+                                    ),
+                            ),
+                    )
                     .child(
                         div()
                             .grid()
@@ -456,20 +781,42 @@ impl ConnectionManager {
                                     .child(Input::new(&self.draft.ssh_local_bind_host_state)),
                             ),
                     )
+                    .child(
+                        div()
+                            .flex()
+                            .flex_col()
+                            .gap(spacing::xs())
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .text_color(cx.theme().foreground)
+                                    .child("Jump hosts (ProxyJump)"),
+                            )
+                            .child(Input::new(&self.draft.ssh_jump_hosts_state))
+                            .child(div().text_xs().text_color(cx.theme().muted_foreground).child(
+                                "Comma-separated, in connection order. Each hop has its own \
+                                 credentials and host key setting below.",
+                            ))
+                            .children(self.draft.ssh_jump_hosts.iter().enumerate().map(
+                                |(index, draft)| {
+                                    Self::render_jump_hop(index, draft, view.clone(), cx)
+                                },
+                            )),
+                    )
                     .child(
                         div()
                             .flex()
                             .items_center()
                             .gap(spacing::sm())
                             .child(
-                                Switch::new("ssh-use-identity")
-                                    .checked(self.draft.ssh_use_identity_file)
+                                Switch::new("ssh-use-agent")
+                                    .checked(self.draft.ssh_use_agent)
                                     .small()
                                     .on_click({
                                         let view = view.clone();
                                         move |checked, _window, cx| {
                                             view.update(cx, |this, cx| {
-                                                this.draft.ssh_use_identity_file = *checked;
+                                                this.draft.ssh_use_agent = *checked;
                                                 cx.notify();
                                             });
                                         }
@@ -479,9 +826,37 @@ impl ConnectionManager {
                                 div()
                                     .text_sm()
                                     .text_color(cx.theme().foreground)
-                                    .child("Use identity file auth"),
+                                    .child("Use SSH agent"),
                             ),
                     )
+                    .when(!self.draft.ssh_use_agent, |this| {
+                        this.child(
+                            div()
+                                .flex()
+                                .items_center()
+                                .gap(spacing::sm())
+                                .child(
+                                    Switch::new("ssh-use-identity")
+                                        .checked(self.draft.ssh_use_identity_file)
+                                        .small()
+                                        .on_click({
+                                            let view = view.clone();
+                                            move |checked, _window, cx| {
+                                                view.update(cx, |this, cx| {
+                                                    this.draft.ssh_use_identity_file = *checked;
+                                                    cx.notify();
+                                                });
+                                            }
+                                        }),
+                                )
+                                .child(
+                                    div()
+                                        .text_sm()
+                                        .text_color(cx.theme().foreground)
+                                        .child("Use identity file auth"),
+                                ),
+                        )
+                    })
                     .child(ssh_auth_block)
                     .child(
                         div()
@@ -19,6 +19,14 @@ use crate::theme::{islands, sizing, spacing};
 
 use super::{ConnectionManager, ManagerTab, TestStatus};
 
+/// Contents of the test "Details" dialog.
+#[derive(Clone)]
+struct TestDetails {
+    text: String,
+    /// Structured report, when the test got far enough to produce one.
+    json: Option<String>,
+}
+
 impl Render for ConnectionManager {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let connections = self.state.read(cx).connections_snapshot();
@@ -146,19 +154,19 @@ impl ConnectionManager {
         let status = self.status.clone();
         let testing_step = self.testing_step.clone();
         let is_testing = matches!(status, TestStatus::Testing);
-        let error_details = match &status {
-            TestStatus::Error(err) => Some(err.clone()),
+        let details = match (&self.diagnostics, &status) {
+            (_, TestStatus::Testing) => None,
+            (Some(report), _) => Some(TestDetails {
+                text: report.to_text(),
+                json: serde_json::to_string_pretty(report).ok(),
+            }),
+            (None, TestStatus::Error(err)) => Some(TestDetails { text: err.clone(), json: None }),
             _ => None,
         };
 
         let (status_text, status_color) = Self::format_status(&status, testing_step.as_deref(), cx);
-        let actions = Self::render_action_buttons(
-            view,
-            state,
-            is_testing,
-            is_active_selection,
-            error_details,
-        );
+        let actions =
+            Self::render_action_buttons(view, state, is_testing, is_active_selection, details);
 
         div()
             .flex()
@@ -207,7 +215,7 @@ impl ConnectionManager {
         state: Entity<crate::state::AppState>,
         is_testing: bool,
         is_active_selection: bool,
-        error_details: Option<String>,
+        details: Option<TestDetails>,
     ) -> AnyElement {
         let row = div()
             .flex()
@@ -251,10 +259,10 @@ impl ConnectionManager {
                 },
             ));
 
-        let row = if let Some(error_details) = error_details {
+        let row = if let Some(details) = details {
             row.child(Button::new("test-details").compact().label("Details").on_click(
                 move |_, window, cx| {
-                    let details = error_details.clone();
+                    let details = details.clone();
                     window.open_dialog(cx, move |dialog: Dialog, _window, _cx| {
                         dialog
                             .title("Connection Test Details")
@@ -270,8 +278,47 @@ impl ConnectionManager {
                                     .p(spacing::md())
                                     .text_xs()
                                     .font_family(crate::theme::fonts::mono())
-                                    .child(details.clone()),
+                                    .child(details.text.clone()),
                             )
+                            .footer({
+                                let details = details.clone();
+                                move |_ok_fn, _cancel_fn, _window, _cx| {
+                                    let mut buttons = vec![
+                                        Button::new("copy-test-report")
+                                            .label("Copy report")
+                                            .on_click({
+                                                let text = details.text.clone();
+                                                move |_, _window, cx| {
+                                                    cx.write_to_clipboard(
+                                                        ClipboardItem::new_string(text.clone()),
+                                                    );
+                                                }
+                                            })
+                                            .into_any_element(),
+                                    ];
+                                    if let Some(json) = details.json.clone() {
+                                        buttons.push(
+                                            Button::new("copy-test-json")
+                                                .label("Copy JSON")
+                                                .on_click(move |_, _window, cx| {
+                                                    cx.write_to_clipboard(
+                                                        ClipboardItem::new_string(json.clone()),
+                                                    );
+                                                })
+                                                .into_any_element(),
+                                        );
+                                    }
+                                    buttons.push(
+                                        Button::new("close-test-details")
+                                            .label("Close")
+                                            .on_click(|_, window, cx| {
+                                                window.close_dialog(cx);
+                                            })
+                                            .into_any_element(),
+                                    );
+                                    buttons
+                                }
+                            })
                     });
                 },
             ))
@@ -7,7 +7,7 @@ use gpui_component::tab::{Tab, TabBar};
 use gpui_component::{ActiveTheme as _, Icon, IconName, Sizable as _};
 
 use crate::state::{ActiveTab, AppState, IslandsTabStyle, SessionKey, TabKey, View};
-use crate::theme::{borders, islands, spacing};
+use crate::theme::{borders, colors, islands, spacing};
 use crate::views::{
     ChangelogView, CollectionView, DatabaseView, ForgeView, SettingsView, TransferView,
 };
@@ -155,6 +155,16 @@ pub(crate) fn render_tabs_host(host: TabsHost<'_>, cx: &App) -> AnyElement {
                         TabKey::Settings => IconName::Settings,
                         TabKey::Changelog => IconName::BookOpen,
                     };
+                    let connection_id = match tab {
+                        TabKey::Collection(tab) => Some(tab.connection_id),
+                        TabKey::Database(tab) => Some(tab.connection_id),
+                        TabKey::Transfer(tab) => tab.connection_id,
+                        TabKey::Forge(tab) => Some(tab.connection_id),
+                        TabKey::Settings | TabKey::Changelog => None,
+                    };
+                    let environment_color = connection_id
+                        .and_then(|id| host.state.read(cx).connection_environment(id))
+                        .map(|env| colors::environment(env, cx));
                     let is_selected = selected_index == index;
                     let state = host.state.clone();
                     let close_button = if islands_tab_variant {
@@ -217,21 +227,24 @@ pub(crate) fn render_tabs_host(host: TabsHost<'_>, cx: &App) -> AnyElement {
                         dirty_dot = dirty_dot.mr(px(2.0));
                     }
 
-                    let icon_color =
-                        if is_selected { cx.theme().primary } else { cx.theme().muted_foreground };
-                    let icon_el = Icon::new(icon_name).with_size(px(14.0)).text_color(icon_color);
-                    let prefix: AnyElement = if is_dirty {
-                        div()
-                            .flex()
-                            .items_center()
-                            .gap(px(4.0))
-                            .ml(px(6.0))
-                            .child(dirty_dot)
-                            .child(icon_el)
-                            .into_any_element()
+                    // Tabs on a connection with an environment carry its color.
+                    let icon_color = environment_color.unwrap_or(if is_selected {
+                        cx.theme().primary
                     } else {
-                        div().flex().items_center().ml(px(6.0)).child(icon_el).into_any_element()
-                    };
+                        cx.theme().muted_foreground
+                    });
+                    let icon_el = Icon::new(icon_name).with_size(px(14.0)).text_color(icon_color);
+                    let environment_bar = environment_color
+                        .map(|color| div().w(px(3.0)).h(px(14.0)).rounded_full().bg(color));
+                    let prefix: AnyElement = div()
+                        .flex()
+                        .items_center()
+                        .gap(px(4.0))
+                        .ml(px(6.0))
+                        .children(environment_bar)
+                        .when(is_dirty, |s| s.child(dirty_dot))
+                        .child(icon_el)
+                        .into_any_element();
 
                     let drag_label: SharedString = label.clone().into();
                     let tab_view = Tab::new()
@@ -61,6 +61,11 @@ impl FileFilter {
         Self::new("OpenMango Connections", vec!["json"])
     }
 
+    /// Connection files from OpenMango, Compass, Studio 3T or mongosh
+    pub fn connections_import() -> Self {
+        Self::new("Connection Files", vec!["json", "txt", "uri", "sh"])
+    }
+
     /// All files
     pub fn all() -> Self {
         Self::new("All Files", vec!["*"])
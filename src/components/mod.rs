@@ -11,6 +11,7 @@ pub mod dialog_helpers;
 pub mod file_picker;
 pub mod filter_builder;
 pub mod form_field;
+pub mod saved_query_dialog;
 mod status_bar;
 pub use button::Button;
 pub use confirm::open_confirm_dialog;
@@ -0,0 +1,228 @@
+//! Dialogs for saving a query to the library and for filling in its
+//! `{{param}}` values before it runs.
+
+use std::collections::HashMap;
+
+use gpui::*;
+use gpui_component::ActiveTheme as _;
+use gpui_component::WindowExt as _;
+use gpui_component::checkbox::Checkbox;
+use gpui_component::dialog::Dialog;
+use gpui_component::input::InputState;
+
+use crate::components::{FormField, cancel_button, primary_button};
+use crate::state::{
+    AppCommands, AppState, SavedQuery, SavedQueryBody, SavedQueryRun, SavedQueryScope,
+    StatusMessage,
+};
+use crate::theme::spacing;
+
+struct SaveQueryDialogState {
+    body: SavedQueryBody,
+    /// Collection the query was saved from; offered as its scope.
+    collection: Option<(String, String)>,
+    scoped: bool,
+    name_state: Entity<InputState>,
+    description_state: Entity<InputState>,
+}
+
+impl Render for SaveQueryDialogState {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let view = cx.entity();
+        let parameters = self.body.parameters();
+        let parameters_text = if parameters.is_empty() {
+            "No parameters. Write {{name}} in the query to prompt for a value on run.".to_string()
+        } else {
+            format!("Parameters: {}", parameters.join(", "))
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap(spacing::md())
+            .p(spacing::md())
+            .child(FormField::new("Name", &self.name_state).required(true).render(cx))
+            .child(FormField::new("Description", &self.description_state).render(cx))
+            .children(self.collection.as_ref().map(|(database, collection)| {
+                Checkbox::new("saved-query-scoped")
+                    .checked(self.scoped)
+                    .label(format!("Only offer in {database}.{collection}"))
+                    .on_click(move |_, _, cx| {
+                        view.update(cx, |this, cx| {
+                            this.scoped = !this.scoped;
+                            cx.notify();
+                        });
+                    })
+            }))
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(format!("{} · {parameters_text}", self.body.kind_label())),
+            )
+    }
+}
+
+/// Ask for a name and scope, then add `body` to the saved query library.
+pub fn open_save_query_dialog(
+    state: Entity<AppState>,
+    body: SavedQueryBody,
+    collection: Option<(String, String)>,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let dialog_state = cx.new(|cx| SaveQueryDialogState {
+        body,
+        scoped: collection.is_some(),
+        collection,
+        name_state: cx.new(|cx| InputState::new(window, cx).placeholder("Late orders")),
+        description_state: cx.new(|cx| InputState::new(window, cx).placeholder("Optional")),
+    });
+
+    window.open_dialog(cx, move |dialog: Dialog, _window: &mut Window, _cx: &mut App| {
+        dialog.title("Save Query").w(px(480.0)).child(dialog_state.clone()).footer({
+            let state = state.clone();
+            let dialog_state = dialog_state.clone();
+            move |_ok, _cancel, _window, _cx| {
+                let state = state.clone();
+                let dialog_state = dialog_state.clone();
+                vec![
+                    cancel_button("cancel-save-query"),
+                    primary_button("save-query", "Save", move |window, cx| {
+                        let ds = dialog_state.read(cx);
+                        let name = ds.name_state.read(cx).value().trim().to_string();
+                        if name.is_empty() {
+                            return;
+                        }
+                        let scope = match (&ds.collection, ds.scoped) {
+                            (Some((database, collection)), true) => SavedQueryScope::Collection {
+                                database: database.clone(),
+                                collection: collection.clone(),
+                            },
+                            _ => SavedQueryScope::Global,
+                        };
+                        let mut query = SavedQuery::new(name.clone(), scope, ds.body.clone());
+                        query.description =
+                            ds.description_state.read(cx).value().trim().to_string();
+
+                        state.update(cx, |state, cx| {
+                            let message = match state.save_query(query) {
+                                Ok(()) => StatusMessage::info(format!("Saved query \"{name}\"")),
+                                Err(e) => {
+                                    StatusMessage::error(format!("Failed to save query: {e}"))
+                                }
+                            };
+                            state.set_status_message(Some(message));
+                            cx.notify();
+                        });
+                        window.close_dialog(cx);
+                    }),
+                ]
+            }
+        })
+    });
+}
+
+struct RunQueryDialogState {
+    parameters: Vec<(String, Entity<InputState>)>,
+}
+
+impl Render for RunQueryDialogState {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .flex()
+            .flex_col()
+            .gap(spacing::md())
+            .p(spacing::md())
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(cx.theme().muted_foreground)
+                    .child("Values are inserted as written; quote strings, e.g. \"shipped\"."),
+            )
+            .children(
+                self.parameters
+                    .iter()
+                    .map(|(name, input)| FormField::new(name.clone(), input).render(cx)),
+            )
+    }
+}
+
+/// Run a saved query, asking for its parameter values first if it has any.
+pub fn run_saved_query(
+    state: Entity<AppState>,
+    query: SavedQuery,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let parameters = query.query.parameters();
+    if parameters.is_empty() {
+        let body = query.query.clone();
+        open_saved_query(state, &query, &body, cx);
+        return;
+    }
+
+    let dialog_state = cx.new(|cx| RunQueryDialogState {
+        parameters: parameters
+            .into_iter()
+            .map(|name| {
+                let input = cx.new(|cx| InputState::new(window, cx).placeholder(name.clone()));
+                (name, input)
+            })
+            .collect(),
+    });
+
+    window.open_dialog(cx, move |dialog: Dialog, _window: &mut Window, _cx: &mut App| {
+        dialog.title(query.name.clone()).w(px(420.0)).child(dialog_state.clone()).footer({
+            let state = state.clone();
+            let query = query.clone();
+            let dialog_state = dialog_state.clone();
+            move |_ok, _cancel, _window, _cx| {
+                let state = state.clone();
+                let query = query.clone();
+                let dialog_state = dialog_state.clone();
+                vec![
+                    cancel_button("cancel-run-query"),
+                    primary_button("run-query", "Run", move |window, cx| {
+                        let values: HashMap<String, String> = dialog_state
+                            .read(cx)
+                            .parameters
+                            .iter()
+                            .map(|(name, input)| (name.clone(), input.read(cx).value().to_string()))
+                            .collect();
+                        let body = query.query.with_parameters(&values);
+                        window.close_dialog(cx);
+                        open_saved_query(state.clone(), &query, &body, cx);
+                    }),
+                ]
+            }
+        })
+    });
+}
+
+fn open_saved_query(
+    state: Entity<AppState>,
+    query: &SavedQuery,
+    body: &SavedQueryBody,
+    cx: &mut App,
+) {
+    let result = state.update(cx, |state, cx| {
+        let result = state.open_saved_query(query, body, cx);
+        let message = match &result {
+            Ok(_) => StatusMessage::info(format!("Running \"{}\"", query.name)),
+            Err(e) => StatusMessage::error(format!("Could not run \"{}\": {e}", query.name)),
+        };
+        state.set_status_message(Some(message));
+        cx.notify();
+        result
+    });
+    match result {
+        Ok(SavedQueryRun::Documents(session_key)) => {
+            AppCommands::load_documents_for_session(state, session_key, cx);
+        }
+        Ok(SavedQueryRun::Aggregation(session_key)) => {
+            AppCommands::run_aggregation(state, session_key, false, cx);
+        }
+        Ok(SavedQueryRun::Forge) | Err(_) => {}
+    }
+}
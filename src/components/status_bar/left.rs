@@ -2,18 +2,29 @@ use gpui::prelude::FluentBuilder as _;
 use gpui::*;
 use gpui_component::{ActiveTheme as _, Icon, IconName, Sizable as _};
 
-use crate::theme::{borders, sizing, spacing};
+use crate::models::{ConnectionEnvironment, ConnectionHealth};
+use crate::theme::{borders, colors, sizing, spacing};
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn render_status_left(
     is_connected: bool,
     connection_name: Option<String>,
     read_only: bool,
+    environment: Option<ConnectionEnvironment>,
+    health: ConnectionHealth,
     sidebar_collapsed: bool,
     on_toggle_sidebar: super::ToggleSidebarHandler,
     cx: &App,
 ) -> AnyElement {
+    let name = connection_name.as_deref().unwrap_or("Connected");
     let (status_color, status_text) = if is_connected {
-        (cx.theme().success, connection_name.as_deref().unwrap_or("Connected").to_string())
+        match health {
+            ConnectionHealth::Healthy => (cx.theme().success, name.to_string()),
+            ConnectionHealth::Lost => (cx.theme().danger, format!("{name} — connection lost")),
+            ConnectionHealth::Reconnecting { attempt } => {
+                (cx.theme().warning, format!("{name} — reconnecting (attempt {attempt})"))
+            }
+        }
     } else {
         (cx.theme().muted_foreground, "Not connected".to_string())
     };
@@ -51,6 +62,18 @@ pub(crate) fn render_status_left(
             div().w(sizing::status_dot()).h(sizing::status_dot()).rounded_full().bg(status_color),
         )
         .child(div().text_xs().text_color(cx.theme().foreground).child(status_text))
+        .when_some(environment, |s: Div, env| {
+            s.child(
+                div()
+                    .px(spacing::xs())
+                    .py(px(1.0))
+                    .rounded(borders::radius_sm())
+                    .bg(colors::environment(env, cx))
+                    .text_xs()
+                    .text_color(cx.theme().tab_bar)
+                    .child(env.label().to_uppercase()),
+            )
+        })
         .when(read_only && is_connected, |s: Div| {
             s.child(
                 div()
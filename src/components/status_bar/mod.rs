@@ -1,8 +1,9 @@
 use gpui::*;
 
+use crate::models::{ConnectionEnvironment, ConnectionHealth};
 use crate::state::app_state::updater::UpdateStatus;
 use crate::state::{AppState, StatusMessage};
-use crate::theme::{islands, sizing, spacing};
+use crate::theme::{colors, islands, sizing, spacing};
 
 mod left;
 mod right;
@@ -18,6 +19,8 @@ pub struct StatusBar {
     connection_name: Option<String>,
     status_message: Option<StatusMessage>,
     read_only: bool,
+    environment: Option<ConnectionEnvironment>,
+    health: ConnectionHealth,
     update_status: UpdateStatus,
     state: Entity<AppState>,
     sidebar_collapsed: bool,
@@ -40,6 +43,8 @@ impl StatusBar {
             connection_name,
             status_message,
             read_only,
+            environment: None,
+            health: ConnectionHealth::Healthy,
             update_status,
             state,
             sidebar_collapsed: false,
@@ -55,6 +60,18 @@ impl StatusBar {
         self
     }
 
+    /// Tint the bar with the active connection's environment color.
+    pub fn environment(mut self, environment: Option<ConnectionEnvironment>) -> Self {
+        self.environment = environment;
+        self
+    }
+
+    /// Show the health monitor's view of the active connection.
+    pub fn health(mut self, health: ConnectionHealth) -> Self {
+        self.health = health;
+        self
+    }
+
     pub fn sidebar_collapsed(mut self, collapsed: bool) -> Self {
         self.sidebar_collapsed = collapsed;
         self
@@ -69,6 +86,15 @@ impl StatusBar {
 impl RenderOnce for StatusBar {
     fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
         let appearance = self.state.read(cx).settings.appearance.clone();
+        let environment = self.environment.filter(|_| self.is_connected);
+        let border_color = environment.map_or_else(
+            || islands::panel_border(&appearance, cx),
+            |env| colors::environment(env, cx),
+        );
+        let background = environment.map_or_else(
+            || islands::tool_bg(&appearance, cx),
+            |env| colors::environment(env, cx).opacity(0.12),
+        );
         div()
             .flex()
             .flex_shrink_0()
@@ -77,8 +103,8 @@ impl RenderOnce for StatusBar {
             .min_w(px(0.0))
             .h(sizing::status_bar_height())
             .px(spacing::md())
-            .bg(islands::tool_bg(&appearance, cx))
-            .border_color(islands::panel_border(&appearance, cx))
+            .bg(background)
+            .border_color(border_color)
             .mx(spacing::xs())
             .mb(spacing::xs())
             .rounded(islands::radius_sm(&appearance))
@@ -87,6 +113,8 @@ impl RenderOnce for StatusBar {
                 self.is_connected,
                 self.connection_name,
                 self.read_only,
+                environment,
+                self.health,
                 self.sidebar_collapsed,
                 self.on_toggle_sidebar,
                 cx,
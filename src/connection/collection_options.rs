@@ -0,0 +1,211 @@
+//! Typed options for creating a collection: capped, time series, clustered
+//! and view collections, plus a default collation and a validator.
+
+use std::time::Duration;
+
+use mongodb::bson::Document;
+use mongodb::options::{
+    ClusteredIndex, Collation, TimeseriesGranularity, TimeseriesOptions, ValidationAction,
+    ValidationLevel,
+};
+
+/// What kind of collection to create. Each kind carries the options only it
+/// accepts, so combinations the server rejects cannot be expressed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum CollectionKind {
+    #[default]
+    Standard,
+    /// Fixed-size collection that overwrites its oldest documents.
+    Capped {
+        size_bytes: u64,
+        max_documents: Option<u64>,
+    },
+    TimeSeries(TimeSeriesSpec),
+    /// Collection stored in `_id` order, optionally expiring documents by
+    /// their `_id` timestamp.
+    Clustered {
+        expire_after_seconds: Option<u64>,
+    },
+    /// Read-only view over `view_on` through an aggregation pipeline.
+    View {
+        view_on: String,
+        pipeline: Vec<Document>,
+    },
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TimeSeriesSpec {
+    pub time_field: String,
+    pub meta_field: Option<String>,
+    /// Unset keeps the server default (seconds).
+    pub granularity: Option<TimeseriesGranularity>,
+    pub expire_after_seconds: Option<u64>,
+}
+
+/// Options for creating a collection; the default creates a plain
+/// collection.
+#[derive(Debug, Clone, Default)]
+pub struct CreateCollectionOptions {
+    pub kind: CollectionKind,
+    pub collation: Option<Collation>,
+    pub validator: Option<Document>,
+    pub validation_level: Option<ValidationLevel>,
+    pub validation_action: Option<ValidationAction>,
+}
+
+impl CreateCollectionOptions {
+    /// Check the options before they reach the server, which reports most of
+    /// these mistakes with less helpful messages.
+    pub fn validate(&self) -> Result<(), String> {
+        match &self.kind {
+            CollectionKind::Standard | CollectionKind::Clustered { .. } => {}
+            CollectionKind::Capped { size_bytes, .. } => {
+                if *size_bytes == 0 {
+                    return Err("Capped collections need a size greater than 0".into());
+                }
+            }
+            CollectionKind::TimeSeries(spec) => {
+                let time_field = spec.time_field.trim();
+                if time_field.is_empty() {
+                    return Err("Time series collections need a time field".into());
+                }
+                if let Some(meta_field) = spec.meta_field.as_deref()
+                    && (meta_field == time_field || meta_field == "_id")
+                {
+                    return Err("The meta field must differ from the time field and _id".into());
+                }
+            }
+            CollectionKind::View { view_on, .. } => {
+                if view_on.trim().is_empty() {
+                    return Err("Views need a source collection".into());
+                }
+                if self.validator.is_some() {
+                    return Err("Views cannot have a validator".into());
+                }
+            }
+        }
+        if self.validator.is_none()
+            && (self.validation_level.is_some() || self.validation_action.is_some())
+        {
+            return Err("Validation level and action require a validator".into());
+        }
+        Ok(())
+    }
+
+    /// The driver options for `createCollection`.
+    pub fn to_driver(&self) -> mongodb::options::CreateCollectionOptions {
+        let mut options = mongodb::options::CreateCollectionOptions::default();
+        match &self.kind {
+            CollectionKind::Standard => {}
+            CollectionKind::Capped { size_bytes, max_documents } => {
+                options.capped = Some(true);
+                options.size = Some(*size_bytes);
+                options.max = *max_documents;
+            }
+            CollectionKind::TimeSeries(spec) => {
+                options.timeseries = Some(
+                    TimeseriesOptions::builder()
+                        .time_field(spec.time_field.trim().to_string())
+                        .meta_field(spec.meta_field.clone())
+                        .granularity(spec.granularity.clone())
+                        .build(),
+                );
+                options.expire_after_seconds = spec.expire_after_seconds.map(Duration::from_secs);
+            }
+            CollectionKind::Clustered { expire_after_seconds } => {
+                options.clustered_index = Some(ClusteredIndex::default());
+                options.expire_after_seconds = expire_after_seconds.map(Duration::from_secs);
+            }
+            CollectionKind::View { view_on, pipeline } => {
+                options.view_on = Some(view_on.trim().to_string());
+                options.pipeline = Some(pipeline.clone());
+            }
+        }
+        options.collation = self.collation.clone();
+        options.validator = self.validator.clone();
+        options.validation_level = self.validation_level.clone();
+        options.validation_action = self.validation_action.clone();
+        options
+    }
+}
+
+/// Parse a collation document such as `{ locale: "fr", strength: 2 }`.
+pub fn collation_from_document(collation: Document) -> Result<Collation, String> {
+    mongodb::bson::from_document(collation).map_err(|err| format!("Invalid collation: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use mongodb::bson::doc;
+
+    use super::*;
+
+    #[test]
+    fn maps_each_kind_to_driver_options() {
+        let capped = CreateCollectionOptions {
+            kind: CollectionKind::Capped { size_bytes: 4096, max_documents: Some(10) },
+            ..Default::default()
+        }
+        .to_driver();
+        assert_eq!((capped.capped, capped.size, capped.max), (Some(true), Some(4096), Some(10)));
+
+        let series = CreateCollectionOptions {
+            kind: CollectionKind::TimeSeries(TimeSeriesSpec {
+                time_field: " ts ".into(),
+                meta_field: Some("sensor".into()),
+                granularity: Some(TimeseriesGranularity::Minutes),
+                expire_after_seconds: Some(60),
+            }),
+            ..Default::default()
+        }
+        .to_driver();
+        let timeseries = series.timeseries.expect("timeseries options");
+        assert_eq!(timeseries.time_field, "ts");
+        assert_eq!(timeseries.meta_field.as_deref(), Some("sensor"));
+        assert_eq!(series.expire_after_seconds, Some(Duration::from_secs(60)));
+
+        let clustered = CreateCollectionOptions {
+            kind: CollectionKind::Clustered { expire_after_seconds: None },
+            ..Default::default()
+        }
+        .to_driver();
+        assert_eq!(clustered.clustered_index.map(|index| index.key), Some(doc! { "_id": 1 }));
+
+        let view = CreateCollectionOptions {
+            kind: CollectionKind::View {
+                view_on: "orders".into(),
+                pipeline: vec![doc! { "$match": { "open": true } }],
+            },
+            ..Default::default()
+        }
+        .to_driver();
+        assert_eq!(view.view_on.as_deref(), Some("orders"));
+        assert_eq!(view.pipeline.map(|pipeline| pipeline.len()), Some(1));
+    }
+
+    #[test]
+    fn rejects_options_the_server_would_refuse() {
+        let mut options = CreateCollectionOptions {
+            kind: CollectionKind::Capped { size_bytes: 0, max_documents: None },
+            ..Default::default()
+        };
+        assert!(options.validate().is_err());
+
+        options.kind = CollectionKind::TimeSeries(TimeSeriesSpec {
+            time_field: "ts".into(),
+            meta_field: Some("ts".into()),
+            ..Default::default()
+        });
+        assert!(options.validate().is_err());
+
+        options.kind = CollectionKind::View { view_on: "orders".into(), pipeline: Vec::new() };
+        options.validator = Some(doc! { "a": { "$exists": true } });
+        assert!(options.validate().is_err());
+
+        options.kind = CollectionKind::Standard;
+        assert!(options.validate().is_ok());
+        options.validator = None;
+        options.validation_action = Some(ValidationAction::Warn);
+        assert!(options.validate().is_err());
+    }
+}
@@ -0,0 +1,164 @@
+//! Optimistic concurrency for writes to edited documents.
+//!
+//! A save only matches the document while the server copy is still the one
+//! that was loaded, so a teammate's change in between is reported as a
+//! conflict instead of being overwritten.
+
+use mongodb::bson::{Bson, DateTime, Document, doc};
+
+use crate::bson::{PathSegment, get_bson_at_path, set_bson_at_path};
+
+/// What must still hold on the server for a write to go through.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConcurrencyCheck {
+    /// The whole document must equal the loaded one.
+    Document(Document),
+    /// Only these top-level fields must be unchanged; used when the document
+    /// was loaded through a projection and other fields are unknown.
+    Fields(Document),
+    /// A version field (dotted path) must still hold its loaded value.
+    Version { field: String, value: Bson },
+}
+
+impl ConcurrencyCheck {
+    /// Check for a document as loaded. The version field is used when set and
+    /// present in `loaded`; otherwise the loaded content is compared.
+    pub fn for_loaded(loaded: &Document, projected: bool, version_field: &str) -> Self {
+        let version_field = version_field.trim();
+        if !version_field.is_empty()
+            && let Some(value) = get_bson_at_path(loaded, &version_path(version_field))
+        {
+            return Self::Version { field: version_field.to_string(), value: value.clone() };
+        }
+        if projected { Self::Fields(loaded.clone()) } else { Self::Document(loaded.clone()) }
+    }
+
+    /// Move the version field of `replacement` past the loaded value, so an
+    /// editor still holding that version no longer matches: numbers go up by
+    /// one, dates become the current time. Other checks change nothing.
+    pub fn advance(&self, replacement: &mut Document) {
+        let Self::Version { field, value } = self else {
+            return;
+        };
+        let next = match value {
+            Bson::Int32(n) => Bson::Int32(n.wrapping_add(1)),
+            Bson::Int64(n) => Bson::Int64(n.wrapping_add(1)),
+            Bson::Double(n) => Bson::Double(n + 1.0),
+            Bson::DateTime(loaded) => {
+                let now = DateTime::now().timestamp_millis();
+                Bson::DateTime(DateTime::from_millis(now.max(loaded.timestamp_millis() + 1)))
+            }
+            other => {
+                log::debug!("Version field {field} holds {other}; left unchanged on save");
+                return;
+            }
+        };
+        set_bson_at_path(replacement, &version_path(field), next);
+    }
+
+    /// Filter matching the document `id` only while the check holds.
+    pub fn filter(&self, id: &Bson) -> Document {
+        let mut filter = doc! { "_id": id.clone() };
+        match self {
+            Self::Document(expected) => {
+                filter
+                    .insert("$expr", doc! { "$eq": ["$$ROOT", { "$literal": expected.clone() }] });
+            }
+            Self::Fields(expected) => {
+                let clauses: Vec<Bson> = expected
+                    .iter()
+                    .filter(|(key, _)| key.as_str() != "_id")
+                    .map(|(key, value)| {
+                        Bson::Document(doc! {
+                            "$eq": [field_expression(key), { "$literal": value.clone() }]
+                        })
+                    })
+                    .collect();
+                if !clauses.is_empty() {
+                    filter.insert("$expr", doc! { "$and": clauses });
+                }
+            }
+            Self::Version { field, value } => {
+                filter.insert(field.clone(), value.clone());
+            }
+        }
+        filter
+    }
+}
+
+fn version_path(field: &str) -> Vec<PathSegment> {
+    field.split('.').map(|part| PathSegment::Key(part.to_string())).collect()
+}
+
+/// `$field`, or `$getField` for names an expression path cannot spell.
+fn field_expression(key: &str) -> Bson {
+    if key.contains('.') || key.starts_with('$') || key.is_empty() {
+        Bson::Document(doc! { "$getField": { "field": { "$literal": key }, "input": "$$ROOT" } })
+    } else {
+        Bson::String(format!("${key}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_field_is_preferred_when_present() {
+        let loaded = doc! { "_id": 1, "meta": { "rev": 4 }, "name": "a" };
+        let check = ConcurrencyCheck::for_loaded(&loaded, false, " meta.rev ");
+        assert_eq!(check, ConcurrencyCheck::Version { field: "meta.rev".into(), value: 4.into() });
+        assert_eq!(check.filter(&Bson::Int32(1)), doc! { "_id": 1, "meta.rev": 4 });
+
+        let missing = ConcurrencyCheck::for_loaded(&loaded, false, "version");
+        assert_eq!(missing, ConcurrencyCheck::Document(loaded.clone()));
+        assert!(matches!(
+            ConcurrencyCheck::for_loaded(&loaded, true, ""),
+            ConcurrencyCheck::Fields(_)
+        ));
+    }
+
+    #[test]
+    fn advance_moves_the_version_past_the_loaded_value() {
+        let loaded = doc! { "_id": 1, "meta": { "rev": 4 }, "n": Bson::Int64(7) };
+        let mut draft = doc! { "_id": 1, "meta": { "rev": 4 }, "n": Bson::Int64(7), "x": 1 };
+        ConcurrencyCheck::for_loaded(&loaded, false, "meta.rev").advance(&mut draft);
+        assert_eq!(draft, doc! { "_id": 1, "meta": { "rev": 5 }, "n": Bson::Int64(7), "x": 1 });
+        ConcurrencyCheck::for_loaded(&loaded, false, "n").advance(&mut draft);
+        assert_eq!(draft.get_i64("n"), Ok(8));
+
+        let future = DateTime::from_millis(DateTime::now().timestamp_millis() + 60_000);
+        let mut dated = doc! { "_id": 1, "at": future };
+        ConcurrencyCheck::for_loaded(&dated.clone(), false, "at").advance(&mut dated);
+        assert!(dated.get_datetime("at").unwrap() > &future);
+
+        let mut unchanged = draft.clone();
+        ConcurrencyCheck::Document(loaded).advance(&mut unchanged);
+        assert_eq!(unchanged, draft);
+    }
+
+    #[test]
+    fn filters_compare_literal_values() {
+        let loaded = doc! { "_id": 1, "price": "$5", "a.b": 2 };
+        let whole = ConcurrencyCheck::Document(loaded.clone()).filter(&Bson::Int32(1));
+        assert_eq!(
+            whole,
+            doc! { "_id": 1, "$expr": { "$eq": ["$$ROOT", { "$literal": loaded.clone() }] } }
+        );
+
+        let fields = ConcurrencyCheck::Fields(loaded).filter(&Bson::Int32(1));
+        assert_eq!(
+            fields,
+            doc! {
+                "_id": 1,
+                "$expr": { "$and": [
+                    { "$eq": ["$price", { "$literal": "$5" }] },
+                    { "$eq": [
+                        { "$getField": { "field": { "$literal": "a.b" }, "input": "$$ROOT" } },
+                        { "$literal": 2 },
+                    ] },
+                ] },
+            }
+        );
+    }
+}
@@ -0,0 +1,286 @@
+//! Dry-run previews of bulk writes.
+//!
+//! An update document is translated into aggregation stages so its effect on
+//! a sample of matching documents can be computed without writing anything.
+
+use mongodb::bson::{Bson, Document, doc};
+
+use crate::bson::{FieldDiff, diff_documents};
+use crate::error::{Error, Result};
+
+/// Documents shown in a preview.
+pub const PREVIEW_SAMPLE_SIZE: i64 = 5;
+
+/// What a bulk update or delete would do.
+#[derive(Debug, Clone, Default)]
+pub struct WritePreview {
+    /// Documents matching the filter.
+    pub matched: u64,
+    pub samples: Vec<PreviewSample>,
+    /// Why an update's effect could not be computed; the samples then only
+    /// show the matched documents.
+    pub unsupported: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PreviewSample {
+    pub before: Document,
+    /// The document after the update; `None` for deletes.
+    pub after: Option<Document>,
+}
+
+impl PreviewSample {
+    /// Fields the write changes; see [`diff_documents`].
+    pub fn changed_fields(&self) -> Vec<FieldDiff> {
+        match &self.after {
+            Some(after) => diff_documents(&self.before, after),
+            None => Vec::new(),
+        }
+    }
+}
+
+/// Aggregation stages with the same effect as `update` on a document.
+///
+/// Replacement documents keep the `_id`. Operators are applied in the order
+/// written, which matches the server as long as they touch different fields.
+/// Positional and array-index paths, `$pull` conditions and `$push`
+/// modifiers other than `$each` cannot be previewed.
+pub fn update_pipeline(update: &Document) -> Result<Vec<Document>> {
+    let operators = update.keys().filter(|key| key.starts_with('$')).count();
+    if operators == 0 {
+        return Ok(vec![doc! {
+            "$replaceWith": { "$mergeObjects": [{ "_id": "$_id" }, { "$literal": update.clone() }] }
+        }]);
+    }
+    if operators != update.len() {
+        return Err(unsupported("mixing update operators and plain fields"));
+    }
+
+    let mut stages = Vec::new();
+    for (operator, fields) in update {
+        let Bson::Document(fields) = fields else {
+            return Err(unsupported(&format!("a non-document value for {operator}")));
+        };
+        if let Some(path) = fields.keys().find(|path| path.contains(".$") || path.starts_with('$'))
+        {
+            return Err(unsupported(&format!("positional path {path}")));
+        }
+        // Aggregation `$set` would create a field named after the index
+        // instead of updating the array element.
+        if let Some(path) = fields.keys().find(|path| {
+            path.split('.').any(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()))
+        }) {
+            return Err(unsupported(&format!("array index path {path}")));
+        }
+        match operator.as_str() {
+            "$set" => stages.push(set_stage(fields, |_, value| literal(value))),
+            "$unset" => {
+                let paths: Vec<Bson> =
+                    fields.keys().map(|path| Bson::String(path.clone())).collect();
+                stages.push(doc! { "$unset": paths });
+            }
+            "$inc" => stages.push(set_stage(fields, |path, value| {
+                doc! { "$add": [{ "$ifNull": [field(path), 0] }, literal(value)] }.into()
+            })),
+            "$mul" => stages.push(set_stage(fields, |path, value| {
+                doc! { "$multiply": [{ "$ifNull": [field(path), 0] }, literal(value)] }.into()
+            })),
+            "$min" | "$max" => stages.push(set_stage(fields, |path, value| {
+                let mut compare = Document::new();
+                compare.insert(operator.clone(), vec![field(path), literal(value)]);
+                doc! {
+                    "$cond": [
+                        { "$eq": [{ "$type": field(path) }, "missing"] },
+                        literal(value),
+                        compare,
+                    ]
+                }
+                .into()
+            })),
+            "$currentDate" => stages.push(set_stage(fields, |_, _| Bson::String("$$NOW".into()))),
+            "$rename" => {
+                let mut renamed = Document::new();
+                let mut removed = Vec::new();
+                for (from, to) in fields {
+                    let Bson::String(to) = to else {
+                        return Err(unsupported("a non-string $rename target"));
+                    };
+                    renamed.insert(to.clone(), field(from));
+                    removed.push(Bson::String(from.clone()));
+                }
+                stages.push(doc! { "$set": renamed });
+                stages.push(doc! { "$unset": removed });
+            }
+            "$push" | "$addToSet" => {
+                let mut set = Document::new();
+                for (path, value) in fields {
+                    let items = push_items(value)?;
+                    let current = doc! { "$ifNull": [field(path), []] };
+                    let expression = if operator == "$push" {
+                        doc! { "$concatArrays": [current, { "$literal": items }] }
+                    } else {
+                        doc! { "$reduce": {
+                            "input": { "$literal": items },
+                            "initialValue": current,
+                            "in": { "$cond": [
+                                { "$in": ["$$this", "$$value"] },
+                                "$$value",
+                                { "$concatArrays": ["$$value", ["$$this"]] },
+                            ] },
+                        } }
+                    };
+                    set.insert(path.clone(), expression);
+                }
+                stages.push(doc! { "$set": set });
+            }
+            "$pull" => {
+                if fields.values().any(|value| matches!(value, Bson::Document(_))) {
+                    return Err(unsupported("$pull with a condition"));
+                }
+                stages.push(set_stage(fields, |path, value| {
+                    unless_missing(
+                        path,
+                        doc! { "$filter": {
+                            "input": field(path),
+                            "cond": { "$ne": ["$$this", literal(value)] },
+                        } },
+                    )
+                }));
+            }
+            "$pop" => stages.push(set_stage(fields, |path, value| {
+                let array = field(path);
+                let first = matches!(value, Bson::Int32(-1) | Bson::Int64(-1))
+                    || value.as_f64() == Some(-1.0);
+                let start = if first { 1 } else { 0 };
+                unless_missing(
+                    path,
+                    doc! { "$cond": [
+                        { "$lte": [{ "$size": array.clone() }, 1] },
+                        [],
+                        { "$slice": [array.clone(), start, { "$subtract": [{ "$size": array }, 1] }] },
+                    ] },
+                )
+            })),
+            // Only applies when an upsert inserts, which a preview never does.
+            "$setOnInsert" => {}
+            other => return Err(unsupported(other)),
+        }
+    }
+    Ok(stages)
+}
+
+fn set_stage(fields: &Document, expression: impl Fn(&str, &Bson) -> Bson) -> Document {
+    let set: Document =
+        fields.iter().map(|(path, value)| (path.clone(), expression(path, value))).collect();
+    doc! { "$set": set }
+}
+
+fn push_items(value: &Bson) -> Result<Vec<Bson>> {
+    match value {
+        Bson::Document(spec) if spec.keys().any(|key| key.starts_with('$')) => {
+            match (spec.get("$each"), spec.len()) {
+                (Some(Bson::Array(items)), 1) => Ok(items.clone()),
+                _ => Err(unsupported("$push modifiers other than $each")),
+            }
+        }
+        value => Ok(vec![value.clone()]),
+    }
+}
+
+/// `$pull` and `$pop` leave a missing field absent rather than creating it.
+fn unless_missing(path: &str, expression: Document) -> Bson {
+    doc! { "$cond": [
+        { "$eq": [{ "$type": field(path) }, "missing"] },
+        "$$REMOVE",
+        expression,
+    ] }
+    .into()
+}
+
+fn field(path: &str) -> Bson {
+    Bson::String(format!("${path}"))
+}
+
+fn literal(value: &Bson) -> Bson {
+    doc! { "$literal": value.clone() }.into()
+}
+
+fn unsupported(what: &str) -> Error {
+    Error::Parse(format!("Preview does not support {what}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn operators_become_stages() {
+        let update = doc! {
+            "$set": { "status": "$done" },
+            "$inc": { "stats.views": 1 },
+            "$rename": { "old": "new" },
+        };
+        let stages = update_pipeline(&update).unwrap();
+        assert_eq!(
+            stages,
+            vec![
+                doc! { "$set": { "status": { "$literal": "$done" } } },
+                doc! { "$set": { "stats.views": {
+                    "$add": [{ "$ifNull": ["$stats.views", 0] }, { "$literal": 1 }]
+                } } },
+                doc! { "$set": { "new": "$old" } },
+                doc! { "$unset": ["old"] },
+            ]
+        );
+
+        let replace = update_pipeline(&doc! { "name": "x" }).unwrap();
+        assert_eq!(
+            replace,
+            vec![doc! { "$replaceWith": {
+                "$mergeObjects": [{ "_id": "$_id" }, { "$literal": { "name": "x" } }]
+            } }]
+        );
+    }
+
+    #[test]
+    fn unsupported_updates_are_refused() {
+        assert!(update_pipeline(&doc! { "$set": { "items.$.qty": 1 } }).is_err());
+        assert!(update_pipeline(&doc! { "$pull": { "items": { "qty": 0 } } }).is_err());
+        assert!(
+            update_pipeline(&doc! { "$push": { "tags": { "$each": [1], "$slice": 3 } } }).is_err()
+        );
+        assert!(update_pipeline(&doc! { "$set": { "a": 1 }, "b": 2 }).is_err());
+        assert!(update_pipeline(&doc! { "$bit": { "flags": { "and": 1 } } }).is_err());
+        assert!(update_pipeline(&doc! { "$push": { "tags": { "$each": ["a"] } } }).is_ok());
+        assert!(update_pipeline(&doc! { "$set": { "tags.0": "x" } }).is_err());
+        assert!(update_pipeline(&doc! { "$inc": { "items.1.qty": 1 } }).is_err());
+        assert!(update_pipeline(&doc! { "$set": { "v2.a1": 1 } }).is_ok());
+    }
+
+    #[test]
+    fn pull_and_pop_keep_missing_fields_absent() {
+        let stages = update_pipeline(&doc! { "$pop": { "tags": 1 } }).unwrap();
+        let Some(Bson::Document(set)) = stages[0].get("$set") else {
+            panic!("expected a $set stage, got {stages:?}");
+        };
+        let Some(Bson::Array(cond)) = set.get_document("tags").unwrap().get("$cond") else {
+            panic!("expected a $cond expression, got {set:?}");
+        };
+        assert_eq!(cond[0], Bson::Document(doc! { "$eq": [{ "$type": "$tags" }, "missing"] }));
+        assert_eq!(cond[1], Bson::String("$$REMOVE".into()));
+    }
+
+    #[test]
+    fn changed_fields_descend_into_subdocuments() {
+        let sample = PreviewSample {
+            before: doc! { "_id": 1, "a": { "b": 1, "c": 2 }, "gone": true, "same": 1 },
+            after: Some(doc! { "_id": 1, "a": { "b": 5, "c": 2 }, "same": 1, "added": "x" }),
+        };
+        let paths: Vec<String> =
+            sample.changed_fields().iter().map(FieldDiff::path_label).collect();
+        assert_eq!(paths, vec!["a.b", "added", "gone"]);
+
+        let delete = PreviewSample { before: doc! { "_id": 1 }, after: None };
+        assert!(delete.changed_fields().is_empty());
+    }
+}
@@ -0,0 +1,265 @@
+//! Keyset (cursor-based) pagination helpers.
+//!
+//! Pages are ordered by the active sort plus `_id` as a tiebreaker, and the next
+//! page starts strictly after the last document's values for those keys. Unlike
+//! `skip`, the server can seek straight to the boundary through an index.
+//!
+//! Documents missing a sort field compare as `null`, the lowest bracket. Values of mixed BSON types
+//! follow MongoDB's comparison order only within the same type bracket, so a
+//! field holding mixed types can cause documents to be skipped.
+
+use mongodb::bson::{Bson, Document, doc};
+
+use crate::bson::{PathSegment, get_bson_at_path};
+
+/// Which side of the anchor the requested page lies on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeysetDirection {
+    After,
+    Before,
+}
+
+/// Boundary of a keyset page: the sort-key values of the first or last document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeysetCursor {
+    pub direction: KeysetDirection,
+    /// Sort key (as in [`keyset_sort`]) to the anchor document's value.
+    pub values: Document,
+}
+
+impl KeysetCursor {
+    /// Cursor for the page following `last`.
+    pub fn after(sort: &Document, last: &Document) -> Self {
+        Self { direction: KeysetDirection::After, values: anchor_values(sort, last) }
+    }
+
+    /// Cursor for the page preceding `first`.
+    pub fn before(sort: &Document, first: &Document) -> Self {
+        Self { direction: KeysetDirection::Before, values: anchor_values(sort, first) }
+    }
+
+    /// Filter matching documents strictly past the anchor in `sort` order:
+    /// `(k1 > v1) OR (k1 = v1 AND k2 > v2) OR ...`, with `<` for descending keys.
+    ///
+    /// `$gt`/`$lt` never match null, so the null bracket is spelled out: past a
+    /// null anchor is `$ne: null` ascending, and below a value includes null.
+    pub fn predicate(&self, sort: &Document) -> Document {
+        let mut clauses = Vec::new();
+        let mut equal_prefix = Document::new();
+        for (key, direction) in sort {
+            let value = self.values.get(key).cloned().unwrap_or(Bson::Null);
+            let ascending = is_ascending(direction) != (self.direction == KeysetDirection::Before);
+            let mut clause = equal_prefix.clone();
+            match (ascending, value == Bson::Null) {
+                // Null and missing sort first, so everything non-null follows them.
+                (true, true) => {
+                    clause.insert(key.clone(), doc! { "$ne": Bson::Null });
+                }
+                (true, false) => {
+                    clause.insert(key.clone(), doc! { "$gt": value.clone() });
+                }
+                // Nothing sorts below null.
+                (false, true) => {}
+                (false, false) => {
+                    clause.insert(
+                        "$or",
+                        vec![
+                            Bson::Document(doc! { key.clone(): { "$lt": value.clone() } }),
+                            Bson::Document(doc! { key.clone(): Bson::Null }),
+                        ],
+                    );
+                }
+            }
+            if clause.len() > equal_prefix.len() {
+                clauses.push(Bson::Document(clause));
+            }
+            equal_prefix.insert(key.clone(), value);
+        }
+        match clauses.len() {
+            1 => match clauses.pop() {
+                Some(Bson::Document(clause)) => clause,
+                _ => Document::new(),
+            },
+            _ => doc! { "$or": clauses },
+        }
+    }
+}
+
+/// Sort order used for keyset paging: the user's numeric sort keys plus `_id`.
+///
+/// `$natural` and `$meta` sorts cannot be used as a keyset and are dropped, which
+/// leaves `_id` order.
+pub fn keyset_sort(sort: Option<&Document>) -> Document {
+    let mut keyset = Document::new();
+    if let Some(sort) = sort {
+        for (key, direction) in sort {
+            if key.starts_with('$') || direction_value(direction).is_none() {
+                continue;
+            }
+            let direction = if is_ascending(direction) { 1 } else { -1 };
+            keyset.insert(key.clone(), direction);
+        }
+    }
+    if !keyset.contains_key("_id") {
+        keyset.insert("_id", 1);
+    }
+    keyset
+}
+
+/// Same order, every key flipped; used to fetch the page before an anchor.
+pub fn reverse_sort(sort: &Document) -> Document {
+    sort.iter()
+        .map(|(key, direction)| {
+            (key.clone(), Bson::Int32(if is_ascending(direction) { -1 } else { 1 }))
+        })
+        .collect()
+}
+
+/// Combine the user filter with a keyset predicate.
+pub fn keyset_filter(filter: Document, cursor: &KeysetCursor, sort: &Document) -> Document {
+    let predicate = cursor.predicate(sort);
+    if filter.is_empty() {
+        predicate
+    } else {
+        doc! { "$and": [filter, predicate] }
+    }
+}
+
+/// Make sure a projection returns the sort keys, which the next cursor is built from.
+pub fn projection_with_keys(projection: Option<Document>, sort: &Document) -> Option<Document> {
+    let mut projection = projection?;
+    let inclusive = projection
+        .iter()
+        .any(|(key, value)| key != "_id" && !matches!(direction_value(value), Some(0.0)));
+    for key in sort.keys() {
+        if inclusive {
+            projection.insert(key.clone(), 1);
+        } else {
+            projection.remove(key);
+        }
+    }
+    Some(projection)
+}
+
+fn anchor_values(sort: &Document, anchor: &Document) -> Document {
+    sort.keys()
+        .map(|key| {
+            let path: Vec<PathSegment> =
+                key.split('.').map(|part| PathSegment::Key(part.to_string())).collect();
+            let value = get_bson_at_path(anchor, &path).cloned().unwrap_or(Bson::Null);
+            (key.clone(), value)
+        })
+        .collect()
+}
+
+fn direction_value(value: &Bson) -> Option<f64> {
+    match value {
+        Bson::Int32(n) => Some(f64::from(*n)),
+        Bson::Int64(n) => Some(*n as f64),
+        Bson::Double(n) => Some(*n),
+        Bson::Boolean(b) => Some(if *b { 1.0 } else { 0.0 }),
+        _ => None,
+    }
+}
+
+fn is_ascending(direction: &Bson) -> bool {
+    direction_value(direction).is_none_or(|n| n >= 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keyset_sort_appends_id_and_drops_natural() {
+        assert_eq!(keyset_sort(None), doc! { "_id": 1 });
+        assert_eq!(keyset_sort(Some(&doc! { "$natural": 1 })), doc! { "_id": 1 });
+        assert_eq!(
+            keyset_sort(Some(&doc! { "age": -1_i64, "name": 1.0 })),
+            doc! { "age": -1, "name": 1, "_id": 1 }
+        );
+        assert_eq!(keyset_sort(Some(&doc! { "_id": -1 })), doc! { "_id": -1 });
+    }
+
+    #[test]
+    fn predicate_expands_compound_sort() {
+        let sort = keyset_sort(Some(&doc! { "age": -1, "address.city": 1 }));
+        let last = doc! { "_id": 7, "age": 30, "address": { "city": "Oslo" } };
+
+        let after = KeysetCursor::after(&sort, &last);
+        assert_eq!(
+            after.predicate(&sort),
+            doc! { "$or": [
+                { "$or": [{ "age": { "$lt": 30 } }, { "age": null }] },
+                { "age": 30, "address.city": { "$gt": "Oslo" } },
+                { "age": 30, "address.city": "Oslo", "_id": { "$gt": 7 } },
+            ] }
+        );
+
+        let before = KeysetCursor::before(&sort, &last);
+        assert_eq!(
+            before.predicate(&sort),
+            doc! { "$or": [
+                { "age": { "$gt": 30 } },
+                { "age": 30, "$or": [{ "address.city": { "$lt": "Oslo" } }, { "address.city": null }] },
+                { "age": 30, "address.city": "Oslo", "_id": { "$lt": 7 } },
+            ] }
+        );
+    }
+
+    #[test]
+    fn predicate_handles_null_anchor() {
+        let ascending = keyset_sort(Some(&doc! { "age": 1 }));
+        let missing = doc! { "_id": 7 };
+        assert_eq!(
+            KeysetCursor::after(&ascending, &missing).predicate(&ascending),
+            doc! { "$or": [
+                { "age": { "$ne": null } },
+                { "age": null, "_id": { "$gt": 7 } },
+            ] }
+        );
+        assert_eq!(
+            KeysetCursor::before(&ascending, &missing).predicate(&ascending),
+            doc! { "age": null, "_id": { "$lt": 7 } }
+        );
+
+        let descending = keyset_sort(Some(&doc! { "age": -1 }));
+        assert_eq!(
+            KeysetCursor::after(&descending, &missing).predicate(&descending),
+            doc! { "age": null, "_id": { "$gt": 7 } }
+        );
+        assert_eq!(
+            KeysetCursor::before(&descending, &missing).predicate(&descending),
+            doc! { "$or": [
+                { "age": { "$ne": null } },
+                { "age": null, "_id": { "$lt": 7 } },
+            ] }
+        );
+    }
+
+    #[test]
+    fn id_only_cursor_and_filter_combination() {
+        let sort = keyset_sort(None);
+        let cursor = KeysetCursor::after(&sort, &doc! { "_id": 41 });
+        assert_eq!(keyset_filter(Document::new(), &cursor, &sort), doc! { "_id": { "$gt": 41 } });
+        assert_eq!(
+            keyset_filter(doc! { "status": "a" }, &cursor, &sort),
+            doc! { "$and": [{ "status": "a" }, { "_id": { "$gt": 41 } }] }
+        );
+        assert_eq!(reverse_sort(&doc! { "a": 1, "_id": -1 }), doc! { "a": -1, "_id": 1 });
+    }
+
+    #[test]
+    fn projection_keeps_sort_keys() {
+        let sort = doc! { "age": 1, "_id": 1 };
+        assert_eq!(
+            projection_with_keys(Some(doc! { "name": 1 }), &sort),
+            Some(doc! { "name": 1, "age": 1, "_id": 1 })
+        );
+        assert_eq!(
+            projection_with_keys(Some(doc! { "age": 0, "bio": 0 }), &sort),
+            Some(doc! { "bio": 0 })
+        );
+        assert_eq!(projection_with_keys(None, &sort), None);
+    }
+}
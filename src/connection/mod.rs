@@ -2,24 +2,42 @@
 //!
 //! This module provides:
 //! - `ConnectionManager`: Core connection management and basic operations
+//! - `oidc`: OIDC device-flow login used by typed `MONGODB-OIDC` auth
 //! - `ops`: Database operations (documents, export, import, indexes, stats, aggregation, copy, bson_tools)
 //! - `tools`: MongoDB tools (mongodump/mongorestore) path detection
 //! - `types`: Shared types for all operations
 //! - `csv_utils`: CSV flattening/unflattening utilities
 
+pub mod collection_options;
+pub mod concurrency;
 pub mod csv_utils;
+pub mod diagnostics;
+pub mod dry_run;
+pub mod keyset;
 pub mod manager;
+pub mod oidc;
 pub mod ops;
+pub mod query_options;
 pub mod tools;
 pub mod tunnel;
 pub mod types;
 
 // Re-export commonly used items at the crate level
+pub use collection_options::{CollectionKind, CreateCollectionOptions, TimeSeriesSpec};
+pub use concurrency::ConcurrencyCheck;
+pub use dry_run::{PREVIEW_SAMPLE_SIZE, WritePreview};
 pub use manager::ConnectionManager;
 pub use ops::export::generate_export_preview;
+pub use ops::index_usage::{IndexFlag, IndexUsage};
+pub use ops::indexes::{IndexBuild, IndexChange};
+pub use ops::stream::DocumentStream;
+pub use ops::transactions::DocumentReplacement;
+pub use ops::validation::{ValidationCheck, ValidationRules};
+pub use query_options::QueryOptions;
 pub use tools::tools_available;
 pub use types::{
-    AggregatePipelineError, BsonOutputFormat, BsonToolProgress, CopyOptions, CsvImportOptions,
-    Encoding, ExportQueryOptions, ExtendedJsonMode, FindDocumentsOptions, InsertMode,
-    JsonExportOptions, JsonImportOptions, JsonTransferFormat, ProgressCallback,
+    AggregatePipelineError, BsonOutputFormat, BsonToolProgress, CopyOptions, CountStrategy,
+    CsvImportOptions, DocumentChange, DocumentCount, DocumentPage, Encoding, ExportQueryOptions,
+    ExtendedJsonMode, FindDocumentsOptions, InsertMode, JsonExportOptions, JsonImportOptions,
+    JsonTransferFormat, PaginationMode, ProgressCallback, ReplayOutcome, SaveOutcome,
 };
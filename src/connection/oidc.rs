@@ -0,0 +1,250 @@
+//! OIDC human login using the OAuth 2.0 device authorization grant (RFC 8628).
+//!
+//! The driver calls [`device_flow_callback`] whenever it needs a fresh token. The
+//! identity provider comes from the server's `IdpServerInfo`; the user finishes the
+//! login in a browser while the callback polls the token endpoint.
+
+use std::time::{Duration, Instant};
+
+use futures::FutureExt as _;
+use mongodb::options::oidc::{Callback, CallbackContext, IdpServerInfo, IdpServerResponse};
+use serde::Deserialize;
+
+/// Poll interval when the device authorization response does not specify one.
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Added to the interval when the provider answers `slow_down`.
+const SLOW_DOWN_STEP: Duration = Duration::from_secs(5);
+const DEVICE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:device_code";
+
+#[derive(Debug, Deserialize)]
+struct ProviderMetadata {
+    device_authorization_endpoint: Option<String>,
+    token_endpoint: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeviceAuthorization {
+    device_code: String,
+    user_code: String,
+    verification_uri: String,
+    #[serde(default)]
+    verification_uri_complete: Option<String>,
+    #[serde(default)]
+    expires_in: Option<u64>,
+    #[serde(default)]
+    interval: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default)]
+    expires_in: Option<u64>,
+    #[serde(default)]
+    refresh_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenError {
+    error: String,
+    #[serde(default)]
+    error_description: Option<String>,
+}
+
+/// Driver callback running the device flow (or a refresh) for each token request.
+pub fn device_flow_callback() -> Callback {
+    Callback::human(|context: CallbackContext| {
+        async move { request_token(context).await.map_err(auth_error) }.boxed()
+    })
+}
+
+async fn request_token(context: CallbackContext) -> Result<IdpServerResponse, String> {
+    let idp = context
+        .idp_info
+        .ok_or_else(|| "Server did not send OIDC identity provider information".to_string())?;
+    let client_id = idp
+        .client_id
+        .clone()
+        .ok_or_else(|| "OIDC identity provider has no client id configured".to_string())?;
+    let http = reqwest::Client::new();
+    let metadata = discover(&http, &idp.issuer).await?;
+
+    if let Some(refresh_token) = context.refresh_token.as_deref() {
+        let params = [
+            ("grant_type", "refresh_token"),
+            ("refresh_token", refresh_token),
+            ("client_id", client_id.as_str()),
+        ];
+        match post_token(&http, &metadata.token_endpoint, &params).await {
+            Ok(Ok(token)) => return Ok(token.into_response()),
+            Ok(Err(err)) => {
+                log::debug!("OIDC refresh rejected ({}), starting device login", err.error)
+            }
+            Err(err) => log::debug!("OIDC refresh failed ({err}), starting device login"),
+        }
+    }
+
+    let endpoint = metadata.device_authorization_endpoint.as_deref().ok_or_else(|| {
+        format!("OIDC issuer {} does not support the device authorization flow", idp.issuer)
+    })?;
+    let scope = scopes(&idp);
+    let authorization: DeviceAuthorization = http
+        .post(endpoint)
+        .form(&[("client_id", client_id.as_str()), ("scope", scope.as_str())])
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|err| format!("OIDC device authorization failed: {err}"))?
+        .json()
+        .await
+        .map_err(|err| format!("Invalid OIDC device authorization response: {err}"))?;
+
+    prompt_user(&authorization);
+
+    let mut interval = authorization.interval.map_or(DEFAULT_POLL_INTERVAL, Duration::from_secs);
+    let expires_at =
+        authorization.expires_in.map(|secs| Instant::now() + Duration::from_secs(secs));
+    let deadline = match (context.timeout, expires_at) {
+        (Some(timeout), Some(expires)) => Some(timeout.min(expires)),
+        (timeout, expires) => timeout.or(expires),
+    };
+    let params = [
+        ("grant_type", DEVICE_GRANT_TYPE),
+        ("device_code", authorization.device_code.as_str()),
+        ("client_id", client_id.as_str()),
+    ];
+
+    loop {
+        if deadline.is_some_and(|deadline| Instant::now() + interval >= deadline) {
+            return Err("OIDC device login timed out before it was approved".to_string());
+        }
+        tokio::time::sleep(interval).await;
+        match post_token(&http, &metadata.token_endpoint, &params).await? {
+            Ok(token) => return Ok(token.into_response()),
+            Err(err) => match err.error.as_str() {
+                "authorization_pending" => {}
+                "slow_down" => interval += SLOW_DOWN_STEP,
+                _ => {
+                    return Err(format!(
+                        "OIDC device login failed: {}",
+                        err.error_description.unwrap_or(err.error)
+                    ));
+                }
+            },
+        }
+    }
+}
+
+async fn discover(http: &reqwest::Client, issuer: &str) -> Result<ProviderMetadata, String> {
+    let url = format!("{}/.well-known/openid-configuration", issuer.trim_end_matches('/'));
+    http.get(&url)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|err| format!("OIDC discovery failed for {issuer}: {err}"))?
+        .json()
+        .await
+        .map_err(|err| format!("Invalid OIDC discovery document from {issuer}: {err}"))
+}
+
+/// `Ok(Err(..))` carries an OAuth error body (e.g. `authorization_pending`).
+async fn post_token(
+    http: &reqwest::Client,
+    endpoint: &str,
+    params: &[(&str, &str)],
+) -> Result<Result<TokenResponse, TokenError>, String> {
+    let response = http
+        .post(endpoint)
+        .form(params)
+        .send()
+        .await
+        .map_err(|err| format!("OIDC token request failed: {err}"))?;
+    let success = response.status().is_success();
+    let body = response.text().await.map_err(|err| format!("OIDC token request failed: {err}"))?;
+    if success {
+        serde_json::from_str(&body)
+            .map(Ok)
+            .map_err(|err| format!("Invalid OIDC token response: {err}"))
+    } else {
+        serde_json::from_str(&body)
+            .map(Err)
+            .map_err(|_| format!("OIDC token endpoint returned an error: {body}"))
+    }
+}
+
+fn scopes(idp: &IdpServerInfo) -> String {
+    let mut scopes = vec!["openid".to_string(), "offline_access".to_string()];
+    for scope in idp.request_scopes.iter().flatten() {
+        if !scopes.contains(scope) {
+            scopes.push(scope.clone());
+        }
+    }
+    scopes.join(" ")
+}
+
+/// Open the verification page and show the user code until the login completes.
+fn prompt_user(authorization: &DeviceAuthorization) {
+    let url = authorization
+        .verification_uri_complete
+        .clone()
+        .unwrap_or_else(|| authorization.verification_uri.clone());
+    log::info!(
+        "OIDC device login: visit {} and enter code {}",
+        authorization.verification_uri,
+        authorization.user_code
+    );
+    open_browser(&url);
+
+    let description = format!(
+        "Finish signing in at {}\n\nCode: {}",
+        authorization.verification_uri, authorization.user_code
+    );
+    std::thread::spawn(move || {
+        rfd::MessageDialog::new()
+            .set_title("MongoDB OIDC login")
+            .set_description(description)
+            .set_buttons(rfd::MessageButtons::Ok)
+            .show();
+    });
+}
+
+fn open_browser(url: &str) {
+    #[cfg(target_os = "macos")]
+    let result = std::process::Command::new("open").arg(url).spawn();
+    #[cfg(target_os = "windows")]
+    let result = std::process::Command::new("cmd").args(["/C", "start", "", url]).spawn();
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let result = std::process::Command::new("xdg-open").arg(url).spawn();
+
+    if let Err(err) = result {
+        log::warn!("Failed to open browser for OIDC login: {err}");
+    }
+}
+
+fn auth_error(message: String) -> mongodb::error::Error {
+    mongodb::error::Error::custom(message)
+}
+
+impl TokenResponse {
+    fn into_response(self) -> IdpServerResponse {
+        IdpServerResponse::builder()
+            .access_token(self.access_token)
+            .expires(self.expires_in.map(|secs| Instant::now() + Duration::from_secs(secs)))
+            .refresh_token(self.refresh_token)
+            .build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scopes_include_openid_and_server_scopes() {
+        let idp = IdpServerInfo::builder()
+            .issuer("https://issuer.example.com".to_string())
+            .request_scopes(Some(vec!["openid".to_string(), "mongodb".to_string()]))
+            .build();
+        assert_eq!(scopes(&idp), "openid offline_access mongodb");
+    }
+}
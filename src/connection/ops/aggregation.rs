@@ -4,6 +4,7 @@ use mongodb::Client;
 use mongodb::bson::{Document, doc};
 
 use crate::connection::ConnectionManager;
+use crate::connection::ops::ensure_writable;
 use crate::connection::types::AggregatePipelineError;
 
 impl ConnectionManager {
@@ -22,6 +23,11 @@ impl ConnectionManager {
         use futures::TryStreamExt;
         use futures::future::Abortable;
 
+        if pipeline.iter().any(|stage| stage.contains_key("$out") || stage.contains_key("$merge")) {
+            ensure_writable(client, "aggregation with $out/$merge")
+                .map_err(AggregatePipelineError::from)?;
+        }
+
         if append_limit
             && let Some(limit) = limit
             && limit > 0
@@ -4,6 +4,7 @@ use std::path::Path;
 use std::process::Command;
 
 use crate::connection::ConnectionManager;
+use crate::connection::ops::ensure_writable_uri;
 use crate::connection::tools::{mongodump_path, mongorestore_path};
 use crate::connection::types::{BsonOutputFormat, BsonToolProgress};
 use crate::error::{Error, Result};
@@ -109,6 +110,7 @@ impl ConnectionManager {
         path: &Path,
         drop_before: bool,
     ) -> Result<()> {
+        ensure_writable_uri(connection_string, "restore")?;
         let mongorestore = mongorestore_path().ok_or_else(|| {
             Error::ToolNotFound(
                 "mongorestore not found. Run 'just download-tools' or install MongoDB Database Tools."
@@ -260,6 +262,7 @@ impl ConnectionManager {
     {
         use std::process::Stdio;
 
+        ensure_writable_uri(connection_string, "restore")?;
         let mongorestore = mongorestore_path().ok_or_else(|| {
             Error::ToolNotFound(
                 "mongorestore not found. Run 'just download-tools' or install MongoDB Database Tools."
@@ -4,6 +4,7 @@ use mongodb::Client;
 use mongodb::bson::{Document, doc};
 
 use crate::connection::ConnectionManager;
+use crate::connection::ops::ensure_writable;
 use crate::connection::types::CopyOptions;
 use crate::error::{Error, Result};
 
@@ -48,6 +49,7 @@ impl ConnectionManager {
         use crate::connection::ops::import::import_batch_by_mode;
         use futures::TryStreamExt;
 
+        ensure_writable(dest_client, "copy")?;
         let src_client = src_client.clone();
         let dest_client = dest_client.clone();
         let src_database = src_database.to_string();
@@ -169,6 +171,7 @@ impl ConnectionManager {
     ) -> Result<u64> {
         use std::collections::HashSet;
 
+        ensure_writable(dest_client, "copy")?;
         let src_client = src_client.clone();
         let dest_client = dest_client.clone();
         let src_database = src_database.to_string();
@@ -1,22 +1,36 @@
 //! Document CRUD operations for MongoDB collections.
 
-use mongodb::Client;
-use mongodb::bson::{Document, doc};
+use mongodb::bson::{Bson, Document, doc};
+use mongodb::options::{CountOptions, EstimatedDocumentCountOptions};
 use mongodb::results::UpdateResult;
+use mongodb::{Client, ClientSession};
 
-use crate::connection::ConnectionManager;
-use crate::connection::types::FindDocumentsOptions;
-use crate::error::Result;
+use crate::connection::dry_run::{PreviewSample, WritePreview, update_pipeline};
+use crate::connection::keyset::{
+    KeysetDirection, keyset_filter, keyset_sort, projection_with_keys, reverse_sort,
+};
+use crate::connection::ops::ensure_writable;
+use crate::connection::ops::transactions::with_transaction;
+use crate::connection::types::{
+    CountStrategy, DocumentChange, DocumentCount, DocumentPage, ESTIMATED_FILTER_COUNT_CAP,
+    FindDocumentsOptions, ReplayOutcome, SaveOutcome,
+};
+use crate::connection::{ConcurrencyCheck, ConnectionManager, QueryOptions};
+use crate::error::{Error, Result};
 
 impl ConnectionManager {
     /// Find documents in a collection with pagination (runs in Tokio runtime)
+    ///
+    /// With a keyset cursor the page is fetched relative to the cursor and
+    /// `skip` is ignored; `opts.sort` must already be a [`keyset_sort`]. The
+    /// count runs alongside the query according to `opts.count`.
     pub fn find_documents(
         &self,
         client: &Client,
         database: &str,
         collection: &str,
         opts: FindDocumentsOptions,
-    ) -> Result<(Vec<Document>, u64)> {
+    ) -> Result<DocumentPage> {
         use futures::TryStreamExt;
 
         let client = client.clone();
@@ -27,20 +41,68 @@ impl ConnectionManager {
         self.runtime.block_on(async {
             let coll = client.database(&database).collection::<Document>(&collection);
 
-            // Get total count (with filter)
-            let total = coll.count_documents(filter.clone()).await?;
-
-            // Fetch documents with pagination
+            // Fetch one extra document to learn whether another page follows.
             let mut options = mongodb::options::FindOptions::default();
-            options.skip = Some(opts.skip);
-            options.limit = Some(opts.limit);
-            options.sort = opts.sort;
-            options.projection = opts.projection;
+            opts.options.apply_to_find(&mut options);
+            options.limit = Some(opts.limit.saturating_add(1));
+            let (query, backwards) = match &opts.keyset {
+                Some(cursor) => {
+                    let sort = opts.sort.clone().unwrap_or_else(|| keyset_sort(None));
+                    let backwards = cursor.direction == KeysetDirection::Before;
+                    options.sort = Some(if backwards { reverse_sort(&sort) } else { sort.clone() });
+                    options.projection = projection_with_keys(opts.projection, &sort);
+                    (keyset_filter(filter.clone(), cursor, &sort), backwards)
+                }
+                None => {
+                    options.skip = Some(opts.skip);
+                    options.sort = opts.sort;
+                    options.projection = opts.projection;
+                    (filter.clone(), false)
+                }
+            };
+
+            let find = async {
+                let cursor = coll.find(query).with_options(options).await?;
+                cursor.try_collect::<Vec<Document>>().await
+            };
+            let (mut documents, count) = futures::try_join!(
+                find,
+                count_with_strategy(&coll, filter, opts.count, &opts.options)
+            )?;
+
+            let limit = usize::try_from(opts.limit.max(0)).unwrap_or(usize::MAX);
+            let overflow = documents.len() > limit;
+            documents.truncate(limit);
+            if backwards {
+                documents.reverse();
+            }
+            // Paging back from an anchor always leaves the anchor's page ahead.
+            let has_more = overflow || backwards;
+
+            Ok(DocumentPage { documents, count, has_more })
+        })
+    }
 
-            let cursor = coll.find(filter).with_options(options).await?;
-            let documents: Vec<Document> = cursor.try_collect().await?;
+    /// Count documents matching `filter` (runs in Tokio runtime)
+    pub fn count_documents(
+        &self,
+        client: &Client,
+        database: &str,
+        collection: &str,
+        filter: Option<Document>,
+        query_options: &QueryOptions,
+    ) -> Result<u64> {
+        let client = client.clone();
+        let database = database.to_string();
+        let collection = collection.to_string();
+        let mut options = CountOptions::default();
+        query_options.apply_to_count(&mut options);
 
-            Ok((documents, total))
+        self.runtime.block_on(async {
+            let coll = client.database(&database).collection::<Document>(&collection);
+            let count =
+                coll.count_documents(filter.unwrap_or_default()).with_options(options).await?;
+            Ok(count)
         })
     }
 
@@ -52,6 +114,7 @@ impl ConnectionManager {
         collection: &str,
         document: Document,
     ) -> Result<()> {
+        ensure_writable(client, "insert")?;
         let client = client.clone();
         let database = database.to_string();
         let collection = collection.to_string();
@@ -71,6 +134,7 @@ impl ConnectionManager {
         collection: &str,
         documents: Vec<Document>,
     ) -> Result<usize> {
+        ensure_writable(client, "insert")?;
         let count = documents.len();
         let client = client.clone();
         let database = database.to_string();
@@ -91,6 +155,7 @@ impl ConnectionManager {
         collection: &str,
         filter: Document,
     ) -> Result<u64> {
+        ensure_writable(client, "delete")?;
         let client = client.clone();
         let database = database.to_string();
         let collection = collection.to_string();
@@ -157,6 +222,7 @@ impl ConnectionManager {
         filter: Document,
         update: Document,
     ) -> Result<UpdateResult> {
+        ensure_writable(client, "update")?;
         let client = client.clone();
         let database = database.to_string();
         let collection = collection.to_string();
@@ -177,6 +243,7 @@ impl ConnectionManager {
         filter: Document,
         update: Document,
     ) -> Result<UpdateResult> {
+        ensure_writable(client, "update")?;
         let client = client.clone();
         let database = database.to_string();
         let collection = collection.to_string();
@@ -188,7 +255,60 @@ impl ConnectionManager {
         })
     }
 
+    /// Preview a bulk update (or, without `update`, a delete) of the
+    /// documents matching `filter` without writing (runs in Tokio runtime)
+    pub fn preview_write(
+        &self,
+        client: &Client,
+        database: &str,
+        collection: &str,
+        filter: Document,
+        update: Option<&Document>,
+        sample_size: i64,
+    ) -> Result<WritePreview> {
+        use futures::TryStreamExt;
+
+        let (stages, unsupported) = match update.map(update_pipeline).transpose() {
+            Ok(stages) => (stages, None),
+            Err(err) => (None, Some(err.to_string())),
+        };
+        let client = client.clone();
+        let database = database.to_string();
+        let collection = collection.to_string();
+
+        self.runtime.block_on(async {
+            let coll = client.database(&database).collection::<Document>(&collection);
+            let matched = coll.count_documents(filter.clone()).await?;
+            let before: Vec<Document> =
+                coll.find(filter).limit(sample_size).await?.try_collect().await?;
+
+            let Some(stages) = stages else {
+                let samples = before
+                    .into_iter()
+                    .map(|before| PreviewSample { before, after: None })
+                    .collect();
+                return Ok(WritePreview { matched, samples, unsupported });
+            };
+
+            let ids: Vec<Bson> = before.iter().filter_map(|doc| doc.get("_id").cloned()).collect();
+            let mut pipeline = vec![doc! { "$match": { "_id": { "$in": ids } } }];
+            pipeline.extend(stages);
+            let mut after: Vec<Document> = coll.aggregate(pipeline).await?.try_collect().await?;
+            let samples = before
+                .into_iter()
+                .map(|before| {
+                    let position = after.iter().position(|doc| doc.get("_id") == before.get("_id"));
+                    PreviewSample { after: position.map(|index| after.swap_remove(index)), before }
+                })
+                .collect();
+            Ok(WritePreview { matched, samples, unsupported: None })
+        })
+    }
+
     /// Replace a document by _id in a collection (runs in Tokio runtime)
+    ///
+    /// With a `check`, the document is only replaced while the check still
+    /// holds; otherwise the current server copy is returned as a conflict.
     pub fn replace_document(
         &self,
         client: &Client,
@@ -196,16 +316,26 @@ impl ConnectionManager {
         collection: &str,
         id: &mongodb::bson::Bson,
         replacement: Document,
-    ) -> Result<()> {
+        check: Option<&ConcurrencyCheck>,
+    ) -> Result<SaveOutcome> {
+        ensure_writable(client, "replace")?;
         let client = client.clone();
         let database = database.to_string();
         let collection = collection.to_string();
         let id = id.clone();
+        let filter = match check {
+            Some(check) => check.filter(&id),
+            None => doc! { "_id": id.clone() },
+        };
 
         self.runtime.block_on(async {
             let coll = client.database(&database).collection::<Document>(&collection);
-            coll.replace_one(doc! { "_id": id }, replacement).await?;
-            Ok(())
+            let result = coll.replace_one(filter, replacement).await?;
+            if result.matched_count > 0 || check.is_none() {
+                return Ok(SaveOutcome::Saved);
+            }
+            let current = coll.find_one(doc! { "_id": id }).await?;
+            Ok(SaveOutcome::Conflict(current))
         })
     }
 
@@ -229,6 +359,114 @@ impl ConnectionManager {
         })
     }
 
+    /// Find documents by _id (runs in Tokio runtime)
+    pub fn find_documents_by_ids(
+        &self,
+        client: &Client,
+        database: &str,
+        collection: &str,
+        ids: Vec<Bson>,
+    ) -> Result<Vec<Document>> {
+        self.find_documents_limited(client, database, collection, doc! { "_id": { "$in": ids } }, 0)
+    }
+
+    /// Find documents matching `filter`, at most `limit` of them when it is
+    /// positive (runs in Tokio runtime)
+    pub fn find_documents_limited(
+        &self,
+        client: &Client,
+        database: &str,
+        collection: &str,
+        filter: Document,
+        limit: i64,
+    ) -> Result<Vec<Document>> {
+        use futures::TryStreamExt;
+
+        let client = client.clone();
+        let database = database.to_string();
+        let collection = collection.to_string();
+
+        self.runtime.block_on(async {
+            let coll = client.database(&database).collection::<Document>(&collection);
+            let cursor = coll.find(filter).limit(limit).await?;
+            let docs: Vec<Document> = cursor.try_collect().await?;
+            Ok(docs)
+        })
+    }
+
+    /// Move documents from their `before` to their `after` state (runs in
+    /// Tokio runtime)
+    ///
+    /// All documents are checked against their `before` image first, and each
+    /// write only matches a document still in that state, so a document that
+    /// changed on the server since is never overwritten. On a replica set or
+    /// sharded cluster the check and the writes share one transaction, so an
+    /// error means nothing was written. On a standalone server a write can
+    /// fail after others went through, which is reported as
+    /// [`ReplayOutcome::Partial`].
+    pub fn apply_document_changes(
+        &self,
+        client: &Client,
+        database: &str,
+        collection: &str,
+        changes: Vec<DocumentChange>,
+    ) -> Result<ReplayOutcome> {
+        ensure_writable(client, "write")?;
+        let transactional = self.supports_transactions(client)?;
+        let client = client.clone();
+        let database = database.to_string();
+        let collection = collection.to_string();
+
+        self.runtime.block_on(async {
+            let coll = client.database(&database).collection::<Document>(&collection);
+            if transactional {
+                with_transaction(&client, async |session| {
+                    for change in &changes {
+                        let current = coll
+                            .find_one(doc! { "_id": change.id.clone() })
+                            .session(&mut *session)
+                            .await?;
+                        if current != change.before {
+                            return Err(changed_on_server(change));
+                        }
+                    }
+                    for change in &changes {
+                        if !apply_change(&coll, change, Some(&mut *session)).await? {
+                            return Err(changed_on_server(change));
+                        }
+                    }
+                    Ok(())
+                })
+                .await?;
+                return Ok(ReplayOutcome::Applied);
+            }
+
+            for change in &changes {
+                let current = coll.find_one(doc! { "_id": change.id.clone() }).await?;
+                if current != change.before {
+                    return Err(changed_on_server(change));
+                }
+            }
+
+            let mut applied = Vec::new();
+            for change in &changes {
+                let error = match apply_change(&coll, change, None).await {
+                    Ok(true) => {
+                        applied.push(change.id.clone());
+                        continue;
+                    }
+                    Ok(false) => changed_on_server(change),
+                    Err(err) => err,
+                };
+                if applied.is_empty() {
+                    return Err(error);
+                }
+                return Ok(ReplayOutcome::Partial { applied, error });
+            }
+            Ok(ReplayOutcome::Applied)
+        })
+    }
+
     /// Delete a document by _id in a collection (runs in Tokio runtime)
     pub fn delete_document(
         &self,
@@ -237,6 +475,7 @@ impl ConnectionManager {
         collection: &str,
         id: &mongodb::bson::Bson,
     ) -> Result<()> {
+        ensure_writable(client, "delete")?;
         let client = client.clone();
         let database = database.to_string();
         let collection = collection.to_string();
@@ -249,3 +488,88 @@ impl ConnectionManager {
         })
     }
 }
+
+/// Write one change, matching only a document still in its `before` state.
+/// `false` means the document no longer matched.
+async fn apply_change(
+    coll: &mongodb::Collection<Document>,
+    change: &DocumentChange,
+    session: Option<&mut ClientSession>,
+) -> Result<bool> {
+    let applied = match (&change.before, &change.after) {
+        (Some(before), Some(after)) => {
+            let filter = ConcurrencyCheck::Document(before.clone()).filter(&change.id);
+            let action = coll.replace_one(filter, after.clone());
+            let outcome = match session {
+                Some(session) => action.session(session).await?,
+                None => action.await?,
+            };
+            outcome.matched_count > 0
+        }
+        (Some(before), None) => {
+            let filter = ConcurrencyCheck::Document(before.clone()).filter(&change.id);
+            let action = coll.delete_one(filter);
+            let outcome = match session {
+                Some(session) => action.session(session).await?,
+                None => action.await?,
+            };
+            outcome.deleted_count > 0
+        }
+        (None, Some(after)) => {
+            // Fails with a duplicate key error if the _id was reused.
+            let action = coll.insert_one(after.clone());
+            match session {
+                Some(session) => action.session(session).await?,
+                None => action.await?,
+            };
+            true
+        }
+        (None, None) => true,
+    };
+    Ok(applied)
+}
+
+fn changed_on_server(change: &DocumentChange) -> Error {
+    Error::Conflict(format!(
+        "Document {} was changed on the server since this operation",
+        crate::bson::bson_value_for_edit(&change.id)
+    ))
+}
+
+pub(super) async fn count_with_strategy(
+    coll: &mongodb::Collection<Document>,
+    filter: Document,
+    strategy: CountStrategy,
+    query_options: &QueryOptions,
+) -> mongodb::error::Result<DocumentCount> {
+    let mut options = CountOptions::default();
+    query_options.apply_to_count(&mut options);
+    match strategy {
+        CountStrategy::Exact => {
+            Ok(DocumentCount::Exact(coll.count_documents(filter).with_options(options).await?))
+        }
+        CountStrategy::Estimated if filter.is_empty() => {
+            let mut options = EstimatedDocumentCountOptions::default();
+            query_options.apply_to_estimated_count(&mut options);
+            Ok(DocumentCount::Estimated(
+                coll.estimated_document_count().with_options(options).await?,
+            ))
+        }
+        CountStrategy::Estimated => {
+            capped_count(coll, filter, ESTIMATED_FILTER_COUNT_CAP, options).await
+        }
+        CountStrategy::Capped(cap) => capped_count(coll, filter, cap, options).await,
+        CountStrategy::Lazy => Ok(DocumentCount::Unknown),
+    }
+}
+
+async fn capped_count(
+    coll: &mongodb::Collection<Document>,
+    filter: Document,
+    cap: u64,
+    mut options: CountOptions,
+) -> mongodb::error::Result<DocumentCount> {
+    options.limit = Some(cap);
+    let count = coll.count_documents(filter).with_options(options).await?;
+    Ok(if count >= cap { DocumentCount::AtLeast(count) } else { DocumentCount::Exact(count) })
+}
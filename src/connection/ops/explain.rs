@@ -1,9 +1,11 @@
 //! Explain command operations for find and aggregation.
 
+use futures::TryStreamExt;
 use mongodb::Client;
 use mongodb::bson::{Document, doc};
 
-use crate::connection::ConnectionManager;
+use crate::connection::ops::facet_count;
+use crate::connection::{ConnectionManager, QueryOptions};
 use crate::error::Result;
 
 pub struct ExplainFindRequest {
@@ -12,15 +14,69 @@ pub struct ExplainFindRequest {
     pub filter: Option<Document>,
     pub sort: Option<Document>,
     pub projection: Option<Document>,
+    pub options: QueryOptions,
     pub verbosity: String,
 }
 
+/// How many sampled documents a filter matched.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SampleSelectivity {
+    pub sampled: u64,
+    pub matched: u64,
+}
+
+impl SampleSelectivity {
+    /// Matched share of the sample; `None` for an empty collection.
+    pub fn fraction(&self) -> Option<f64> {
+        (self.sampled > 0).then(|| self.matched as f64 / self.sampled as f64)
+    }
+}
+
 impl ConnectionManager {
+    /// Estimate the share of documents matching `filter` from a random sample
+    /// (runs in Tokio runtime)
+    pub fn sample_selectivity(
+        &self,
+        client: &Client,
+        database: &str,
+        collection: &str,
+        filter: &Document,
+        sample_size: u64,
+    ) -> Result<SampleSelectivity> {
+        let client = client.clone();
+        let database = database.to_string();
+        let collection = collection.to_string();
+        let pipeline = vec![
+            doc! { "$sample": { "size": sample_size as i64 } },
+            doc! { "$facet": {
+                "sampled": [{ "$count": "n" }],
+                "matched": [{ "$match": filter.clone() }, { "$count": "n" }],
+            } },
+        ];
+
+        self.runtime.block_on(async {
+            let coll = client.database(&database).collection::<Document>(&collection);
+            let facets: Vec<Document> = coll.aggregate(pipeline).await?.try_collect().await?;
+            let facets = facets.into_iter().next().unwrap_or_default();
+            Ok(SampleSelectivity {
+                sampled: facet_count(&facets, "sampled"),
+                matched: facet_count(&facets, "matched"),
+            })
+        })
+    }
+
     /// Run explain for a `find` command using selected verbosity.
     pub fn explain_find(&self, client: &Client, request: ExplainFindRequest) -> Result<Document> {
         let client = client.clone();
-        let ExplainFindRequest { database, collection, filter, sort, projection, verbosity } =
-            request;
+        let ExplainFindRequest {
+            database,
+            collection,
+            filter,
+            sort,
+            projection,
+            options,
+            verbosity,
+        } = request;
         let filter = filter.unwrap_or_default();
         let sort = sort.unwrap_or_default();
         let projection = projection.unwrap_or_default();
@@ -37,12 +93,17 @@ impl ConnectionManager {
             if !projection.is_empty() {
                 find_cmd.insert("projection", projection);
             }
+            options.apply_to_command(&mut find_cmd);
 
             let command = doc! {
                 "explain": find_cmd,
                 "verbosity": verbosity,
             };
-            let explain = db.run_command(command).await?;
+            let mut run = db.run_command(command);
+            if let Some(criteria) = options.selection_criteria() {
+                run = run.selection_criteria(criteria);
+            }
+            let explain = run.await?;
             Ok(explain)
         })
     }
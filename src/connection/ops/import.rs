@@ -8,6 +8,7 @@ use mongodb::Client;
 use mongodb::bson::{Document, doc};
 
 use crate::connection::ConnectionManager;
+use crate::connection::ops::ensure_writable;
 use crate::connection::types::{
     CsvImportOptions, Encoding, InsertMode, JsonImportOptions, JsonTransferFormat,
 };
@@ -44,6 +45,7 @@ impl ConnectionManager {
         path: &Path,
         options: JsonImportOptions,
     ) -> Result<u64> {
+        ensure_writable(client, "import")?;
         let client = client.clone();
         let database = database.to_string();
         let collection = collection.to_string();
@@ -206,6 +208,7 @@ impl ConnectionManager {
         use crate::connection::csv_utils::unflatten_row;
         use std::collections::HashMap;
 
+        ensure_writable(client, "import")?;
         let client = client.clone();
         let database = database.to_string();
         let collection = collection.to_string();
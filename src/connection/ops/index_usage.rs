@@ -0,0 +1,282 @@
+//! Index usage from `$indexStats` and `collStats.indexSizes`, and flags for
+//! indexes that are unused, covered by another index, or duplicated.
+
+use std::collections::HashMap;
+
+use futures::TryStreamExt;
+use mongodb::Client;
+use mongodb::IndexModel;
+use mongodb::bson::{Bson, DateTime, Document, doc};
+
+use crate::connection::ConnectionManager;
+use crate::error::Result;
+
+/// Usage of one index. Counts are summed across shards and `since` is the
+/// earliest counter reset.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IndexUsage {
+    /// Operations that used the index; `None` when `$indexStats` had no entry.
+    pub ops: Option<u64>,
+    pub since: Option<DateTime>,
+    pub size_bytes: Option<u64>,
+}
+
+/// Why an index may be worth dropping.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IndexFlag {
+    /// No operations since the counters were reset.
+    Unused,
+    /// The keys are a prefix of `covered_by`, which serves the same queries.
+    Redundant { covered_by: String },
+    /// Same key pattern as `of`.
+    Duplicate { of: String },
+}
+
+impl IndexFlag {
+    pub fn label(&self) -> String {
+        match self {
+            Self::Unused => "unused".to_string(),
+            Self::Redundant { covered_by } => format!("covered by {covered_by}"),
+            Self::Duplicate { of } => format!("duplicate of {of}"),
+        }
+    }
+}
+
+impl ConnectionManager {
+    /// Read per-index operation counts and sizes, keyed by index name (runs in
+    /// Tokio runtime)
+    pub fn index_usage(
+        &self,
+        client: &Client,
+        database: &str,
+        collection: &str,
+    ) -> Result<HashMap<String, IndexUsage>> {
+        let client = client.clone();
+        let database = database.to_string();
+        let collection = collection.to_string();
+
+        self.runtime.block_on(async {
+            let db = client.database(&database);
+            let stats: Vec<Document> = db
+                .collection::<Document>(&collection)
+                .aggregate(vec![doc! { "$indexStats": {} }])
+                .await?
+                .try_collect()
+                .await?;
+            let coll_stats = db.run_command(doc! { "collStats": &collection }).await?;
+            Ok(merge_index_usage(&stats, &coll_stats))
+        })
+    }
+}
+
+/// Combine `$indexStats` entries with the `indexSizes` of `collStats`.
+pub fn merge_index_usage(
+    index_stats: &[Document],
+    coll_stats: &Document,
+) -> HashMap<String, IndexUsage> {
+    let mut usage: HashMap<String, IndexUsage> = HashMap::new();
+    for entry in index_stats {
+        let Ok(name) = entry.get_str("name") else {
+            continue;
+        };
+        let accesses = entry.get_document("accesses").ok();
+        let ops = accesses.and_then(|accesses| accesses.get("ops")).and_then(bson_u64);
+        let since = accesses.and_then(|accesses| accesses.get_datetime("since").ok()).copied();
+        let item = usage.entry(name.to_string()).or_default();
+        item.ops = Some(item.ops.unwrap_or(0) + ops.unwrap_or(0));
+        item.since = match (item.since, since) {
+            (Some(current), Some(since)) => Some(current.min(since)),
+            (current, since) => current.or(since),
+        };
+    }
+    if let Ok(sizes) = coll_stats.get_document("indexSizes") {
+        for (name, size) in sizes {
+            usage.entry(name.clone()).or_default().size_bytes = bson_u64(size);
+        }
+    }
+    usage
+}
+
+/// Flag indexes by name. Unique and TTL indexes are never flagged as unused or
+/// redundant since they enforce behavior beyond serving queries.
+pub fn flag_indexes(
+    indexes: &[IndexModel],
+    usage: Option<&HashMap<String, IndexUsage>>,
+) -> HashMap<String, Vec<IndexFlag>> {
+    let mut flags: HashMap<String, Vec<IndexFlag>> = HashMap::new();
+    for (position, index) in indexes.iter().enumerate() {
+        let Some(name) = index_name(index) else {
+            continue;
+        };
+        if name == "_id_" {
+            continue;
+        }
+        let mut found = Vec::new();
+        let enforces = enforces_behavior(index);
+        if !enforces
+            && usage.and_then(|usage| usage.get(name)).and_then(|usage| usage.ops) == Some(0)
+        {
+            found.push(IndexFlag::Unused);
+        }
+        let duplicate = indexes[..position]
+            .iter()
+            .find(|other| {
+                same_keys(&other.keys, &index.keys) && selection(other) == selection(index)
+            })
+            .and_then(index_name);
+        if let Some(of) = duplicate {
+            found.push(IndexFlag::Duplicate { of: of.to_string() });
+        } else if !enforces
+            && let Some(covered_by) =
+                indexes.iter().find(|other| covers(other, index)).and_then(index_name)
+        {
+            found.push(IndexFlag::Redundant { covered_by: covered_by.to_string() });
+        }
+        if !found.is_empty() {
+            flags.insert(name.to_string(), found);
+        }
+    }
+    flags
+}
+
+fn index_name(index: &IndexModel) -> Option<&str> {
+    index.options.as_ref().and_then(|options| options.name.as_deref())
+}
+
+fn enforces_behavior(index: &IndexModel) -> bool {
+    index
+        .options
+        .as_ref()
+        .is_some_and(|options| options.unique.unwrap_or(false) || options.expire_after.is_some())
+}
+
+/// Whether `wider` serves every query `narrower` does: its keys start with all
+/// of `narrower`'s and it holds the same documents.
+fn covers(wider: &IndexModel, narrower: &IndexModel) -> bool {
+    let hidden = wider.options.as_ref().and_then(|options| options.hidden).unwrap_or(false);
+    wider.keys.len() > narrower.keys.len()
+        && keys_start_with(&wider.keys, &narrower.keys)
+        && !hidden
+        && selection(wider) == selection(narrower)
+}
+
+/// Sparseness, partial filter and collation locale: what an index holds and
+/// how it compares strings.
+fn selection(index: &IndexModel) -> (bool, Option<Document>, Option<String>) {
+    let Some(options) = index.options.as_ref() else {
+        return Default::default();
+    };
+    (
+        options.sparse.unwrap_or(false),
+        options.partial_filter_expression.clone(),
+        options.collation.as_ref().map(|collation| collation.locale.clone()),
+    )
+}
+
+fn same_keys(left: &Document, right: &Document) -> bool {
+    left.len() == right.len() && keys_start_with(left, right)
+}
+
+/// Whether `keys` begins with the fields and directions of `prefix`.
+pub fn keys_start_with(keys: &Document, prefix: &Document) -> bool {
+    prefix.len() <= keys.len()
+        && keys.iter().zip(prefix.iter()).all(|((key, value), (prefix_key, prefix_value))| {
+            key == prefix_key && key_value_eq(value, prefix_value)
+        })
+}
+
+/// Key directions compare numerically so `1`, `1.0` and `Int64(1)` match.
+fn key_value_eq(left: &Bson, right: &Bson) -> bool {
+    match (bson_f64(left), bson_f64(right)) {
+        (Some(left), Some(right)) => left == right,
+        _ => left == right,
+    }
+}
+
+fn bson_f64(value: &Bson) -> Option<f64> {
+    match value {
+        Bson::Int32(n) => Some(f64::from(*n)),
+        Bson::Int64(n) => Some(*n as f64),
+        Bson::Double(n) => Some(*n),
+        _ => None,
+    }
+}
+
+fn bson_u64(value: &Bson) -> Option<u64> {
+    match value {
+        Bson::Int32(n) => u64::try_from(*n).ok(),
+        Bson::Int64(n) => u64::try_from(*n).ok(),
+        Bson::Double(n) if *n >= 0.0 => Some(*n as u64),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mongodb::options::IndexOptions;
+
+    use super::*;
+
+    fn index(name: &str, keys: Document, unique: bool) -> IndexModel {
+        IndexModel::builder()
+            .keys(keys)
+            .options(IndexOptions::builder().name(name.to_string()).unique(unique).build())
+            .build()
+    }
+
+    #[test]
+    fn merges_shard_counts_and_sizes() {
+        let early = DateTime::from_millis(1_000);
+        let late = DateTime::from_millis(2_000);
+        let stats = vec![
+            doc! { "name": "a_1", "accesses": { "ops": 3_i64, "since": late } },
+            doc! { "name": "a_1", "accesses": { "ops": 4_i64, "since": early } },
+            doc! { "name": "b_1", "accesses": { "ops": 0_i64, "since": late } },
+        ];
+        let coll_stats = doc! { "indexSizes": { "a_1": 4096, "b_1": 8192.0, "_id_": 20480_i64 } };
+        let usage = merge_index_usage(&stats, &coll_stats);
+        assert_eq!(
+            usage["a_1"],
+            IndexUsage { ops: Some(7), since: Some(early), size_bytes: Some(4096) }
+        );
+        assert_eq!(usage["b_1"].ops, Some(0));
+        assert_eq!(usage["_id_"], IndexUsage { ops: None, since: None, size_bytes: Some(20480) });
+    }
+
+    #[test]
+    fn flags_unused_prefix_and_duplicate_indexes() {
+        let indexes = vec![
+            index("_id_", doc! { "_id": 1 }, false),
+            index("a_1", doc! { "a": 1 }, false),
+            index("a_1_b_1", doc! { "a": 1, "b": 1 }, false),
+            index("a_1_copy", doc! { "a": 1.0 }, false),
+            index("a_-1", doc! { "a": -1 }, false),
+            index("email_1", doc! { "email": 1 }, true),
+            index("email_1_name_1", doc! { "email": 1, "name": 1 }, false),
+        ];
+        let usage: HashMap<String, IndexUsage> = ["a_1", "a_1_b_1", "a_-1", "email_1"]
+            .into_iter()
+            .map(|name| {
+                let ops = if name == "a_1_b_1" { 5 } else { 0 };
+                (name.to_string(), IndexUsage { ops: Some(ops), ..Default::default() })
+            })
+            .collect();
+
+        let flags = flag_indexes(&indexes, Some(&usage));
+        assert_eq!(
+            flags["a_1"],
+            vec![IndexFlag::Unused, IndexFlag::Redundant { covered_by: "a_1_b_1".into() }]
+        );
+        assert_eq!(flags["a_1_copy"], vec![IndexFlag::Duplicate { of: "a_1".into() }]);
+        assert_eq!(flags["a_-1"], vec![IndexFlag::Unused]);
+        assert!(!flags.contains_key("a_1_b_1"));
+        assert!(!flags.contains_key("email_1"), "unique indexes enforce a constraint");
+        assert!(!flags.contains_key("_id_"));
+
+        let without_usage = flag_indexes(&indexes, None);
+        assert_eq!(
+            without_usage["a_1"],
+            vec![IndexFlag::Redundant { covered_by: "a_1_b_1".into() }]
+        );
+    }
+}
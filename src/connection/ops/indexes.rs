@@ -1,11 +1,65 @@
 //! Index operations for MongoDB collections.
 
-use mongodb::Client;
-use mongodb::IndexModel;
-use mongodb::bson::{Document, doc};
+use mongodb::bson::{Bson, Document, doc};
+use mongodb::{Client, Database, IndexModel};
 
 use crate::connection::ConnectionManager;
-use crate::error::Result;
+use crate::connection::ops::ensure_writable;
+use crate::error::{Error, Result};
+
+/// Duplicate keys listed when a unique conversion is refused.
+const MAX_REPORTED_DUPLICATES: usize = 5;
+
+/// A `collMod` change to one existing index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexChange {
+    /// Hide from the query planner while keeping the index maintained.
+    Hide,
+    Unhide,
+    /// New `expireAfterSeconds` of a TTL index.
+    ExpireAfter(u64),
+    /// Set `prepareUnique` so new duplicates are rejected, check existing
+    /// keys with a dry run, then `unique`.
+    ConvertToUnique,
+}
+
+impl IndexChange {
+    /// The `index` options of each `collMod` the change runs, in order.
+    fn collmod_options(self, name: &str) -> Vec<Document> {
+        match self {
+            Self::Hide => vec![doc! { "name": name, "hidden": true }],
+            Self::Unhide => vec![doc! { "name": name, "hidden": false }],
+            Self::ExpireAfter(seconds) => {
+                vec![doc! { "name": name, "expireAfterSeconds": seconds as i64 }]
+            }
+            Self::ConvertToUnique => vec![
+                doc! { "name": name, "prepareUnique": true },
+                doc! { "name": name, "unique": true },
+            ],
+        }
+    }
+}
+
+/// An index build in progress, from `$currentOp`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IndexBuild {
+    pub indexes: Vec<String>,
+    /// Current phase, e.g. `Index Build: scanning collection`.
+    pub message: Option<String>,
+    pub done: Option<u64>,
+    pub total: Option<u64>,
+    pub secs_running: Option<u64>,
+}
+
+impl IndexBuild {
+    /// Share of the current phase completed.
+    pub fn progress(&self) -> Option<f64> {
+        match (self.done, self.total) {
+            (Some(done), Some(total)) if total > 0 => Some(done as f64 / total as f64),
+            _ => None,
+        }
+    }
+}
 
 impl ConnectionManager {
     /// List indexes for a collection (runs in Tokio runtime)
@@ -37,6 +91,7 @@ impl ConnectionManager {
         collection: &str,
         index: Document,
     ) -> Result<()> {
+        ensure_writable(client, "create index")?;
         let client = client.clone();
         let database = database.to_string();
         let collection = collection.to_string();
@@ -56,6 +111,7 @@ impl ConnectionManager {
         collection: &str,
         indexes: Vec<Document>,
     ) -> Result<()> {
+        ensure_writable(client, "create index")?;
         let client = client.clone();
         let database = database.to_string();
         let collection = collection.to_string();
@@ -75,6 +131,7 @@ impl ConnectionManager {
         collection: &str,
         name: &str,
     ) -> Result<()> {
+        ensure_writable(client, "drop index")?;
         let client = client.clone();
         let database = database.to_string();
         let collection = collection.to_string();
@@ -86,4 +143,270 @@ impl ConnectionManager {
             Ok(())
         })
     }
+
+    /// Apply a `collMod` change to an index by name (runs in Tokio runtime)
+    pub fn modify_index(
+        &self,
+        client: &Client,
+        database: &str,
+        collection: &str,
+        name: &str,
+        change: IndexChange,
+    ) -> Result<()> {
+        ensure_writable(client, "modify index")?;
+        let client = client.clone();
+        let database = database.to_string();
+        let collection = collection.to_string();
+        let name = name.to_string();
+        let steps = change.collmod_options(&name);
+
+        self.runtime.block_on(async {
+            let db = client.database(&database);
+            if change == IndexChange::ConvertToUnique {
+                return convert_to_unique(&db, &collection, &name, steps).await;
+            }
+            for index in steps {
+                db.run_command(doc! { "collMod": &collection, "index": index }).await?;
+            }
+            Ok(())
+        })
+    }
+
+    /// List index builds running on a collection (runs in Tokio runtime)
+    pub fn index_builds(
+        &self,
+        client: &Client,
+        database: &str,
+        collection: &str,
+    ) -> Result<Vec<IndexBuild>> {
+        use futures::TryStreamExt;
+
+        let client = client.clone();
+        let namespace = format!("{database}.{collection}");
+
+        self.runtime.block_on(async {
+            let pipeline = vec![
+                doc! { "$currentOp": { "allUsers": true, "idleConnections": false } },
+                doc! { "$match": {
+                    "ns": namespace,
+                    "command.createIndexes": { "$exists": true },
+                } },
+            ];
+            let ops: Vec<Document> =
+                client.database("admin").aggregate(pipeline).await?.try_collect().await?;
+            Ok(merge_index_builds(&ops))
+        })
+    }
+}
+
+/// Run the `prepareUnique` and `unique` steps with a dry run in between.
+///
+/// A refused conversion resets `prepareUnique`, which would otherwise keep
+/// rejecting duplicate writes on an index that is still not unique.
+async fn convert_to_unique(
+    db: &Database,
+    collection: &str,
+    name: &str,
+    steps: Vec<Document>,
+) -> Result<()> {
+    let mut steps = steps.into_iter();
+    let (Some(prepare), Some(enforce)) = (steps.next(), steps.next()) else {
+        return Ok(());
+    };
+    db.run_command(doc! { "collMod": collection, "index": prepare }).await?;
+
+    let dry_run = doc! { "collMod": collection, "index": enforce.clone(), "dryRun": true };
+    let result = match db.run_command(dry_run).await {
+        Err(err) if !unique_violations(&err).is_empty() => Err(err),
+        dry_run => {
+            // Servers before 7.1 reject `dryRun`; the enforcing step still checks.
+            if let Err(err) = dry_run {
+                log::debug!("Unique conversion dry run unavailable: {err}");
+            }
+            db.run_command(doc! { "collMod": collection, "index": enforce }).await.map(|_| ())
+        }
+    };
+    let Err(err) = result else {
+        return Ok(());
+    };
+
+    let violations = unique_violations(&err);
+    let mut message = if violations.is_empty() {
+        format!("Converting index {name} to unique failed: {err}")
+    } else {
+        let keys = duplicate_keys(db, collection, name, &violations).await;
+        format!(
+            "Index {name} cannot be made unique: {} key(s) are duplicated, e.g. {}",
+            violations.len(),
+            keys.join(", ")
+        )
+    };
+    let reset = doc! { "collMod": collection, "index": { "name": name, "prepareUnique": false } };
+    if let Err(reset_err) = db.run_command(reset).await {
+        log::warn!("Failed to reset prepareUnique on {name}: {reset_err}");
+        message.push_str(&format!(
+            ". prepareUnique is still set, so new duplicate writes are rejected \
+             although the index is not unique ({reset_err})"
+        ));
+    }
+    Err(Error::Conflict(message))
+}
+
+/// `_id`s of each group of documents sharing a key, from a refused `collMod`.
+fn unique_violations(err: &mongodb::error::Error) -> Vec<Vec<Bson>> {
+    let Some(response) = err.server_response().and_then(|raw| raw.to_document().ok()) else {
+        return Vec::new();
+    };
+    response
+        .get_array("violations")
+        .map(|violations| {
+            violations
+                .iter()
+                .filter_map(Bson::as_document)
+                .filter_map(|violation| violation.get_array("ids").ok().cloned())
+                .filter(|ids| !ids.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The index key of the first document in each violation group.
+async fn duplicate_keys(
+    db: &Database,
+    collection: &str,
+    name: &str,
+    violations: &[Vec<Bson>],
+) -> Vec<String> {
+    use futures::TryStreamExt;
+
+    let coll = db.collection::<Document>(collection);
+    let keys = match coll.list_indexes().await {
+        Ok(cursor) => cursor
+            .try_collect::<Vec<IndexModel>>()
+            .await
+            .ok()
+            .and_then(|indexes| {
+                indexes.into_iter().find(|index| {
+                    index.options.as_ref().and_then(|options| options.name.as_deref()) == Some(name)
+                })
+            })
+            .map(|index| index.keys),
+        Err(_) => None,
+    };
+
+    let projection: Option<Document> =
+        keys.map(|keys| keys.keys().map(|key| (key.clone(), Bson::Int32(1))).collect());
+
+    let mut out = Vec::new();
+    for ids in violations.iter().take(MAX_REPORTED_DUPLICATES) {
+        let id = &ids[0];
+        let found = match &projection {
+            Some(projection) => coll
+                .find_one(doc! { "_id": id.clone() })
+                .projection(projection.clone())
+                .await
+                .ok()
+                .flatten(),
+            None => None,
+        };
+        out.push(match found {
+            Some(mut key) => {
+                if projection.as_ref().is_some_and(|p| !p.contains_key("_id")) {
+                    key.remove("_id");
+                }
+                key.to_string()
+            }
+            None => format!("_id {id}"),
+        });
+    }
+    out
+}
+
+/// One build per set of index names. The client's `createIndexes` and the
+/// server's build coordinator both show up; the one reporting progress wins.
+pub fn merge_index_builds(ops: &[Document]) -> Vec<IndexBuild> {
+    let mut builds: Vec<IndexBuild> = Vec::new();
+    for op in ops {
+        let build = parse_index_build(op);
+        match builds.iter_mut().find(|item| item.indexes == build.indexes) {
+            Some(existing) if existing.total.is_none() && build.total.is_some() => {
+                *existing = build;
+            }
+            Some(_) => {}
+            None => builds.push(build),
+        }
+    }
+    builds
+}
+
+fn parse_index_build(op: &Document) -> IndexBuild {
+    let indexes = op
+        .get_document("command")
+        .and_then(|command| command.get_array("indexes"))
+        .map(|indexes| {
+            indexes
+                .iter()
+                .filter_map(Bson::as_document)
+                .filter_map(|index| index.get_str("name").ok().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+    let progress = op.get_document("progress").ok();
+    IndexBuild {
+        indexes,
+        message: op.get_str("msg").ok().map(str::to_string),
+        done: progress.and_then(|progress| progress.get("done")).and_then(bson_u64),
+        total: progress.and_then(|progress| progress.get("total")).and_then(bson_u64),
+        secs_running: op.get("secs_running").and_then(bson_u64),
+    }
+}
+
+fn bson_u64(value: &Bson) -> Option<u64> {
+    match value {
+        Bson::Int32(n) => u64::try_from(*n).ok(),
+        Bson::Int64(n) => u64::try_from(*n).ok(),
+        Bson::Double(n) if *n >= 0.0 => Some(*n as u64),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_to_unique_prepares_before_enforcing() {
+        assert_eq!(
+            IndexChange::ConvertToUnique.collmod_options("email_1"),
+            vec![
+                doc! { "name": "email_1", "prepareUnique": true },
+                doc! { "name": "email_1", "unique": true },
+            ]
+        );
+        assert_eq!(
+            IndexChange::ExpireAfter(3600).collmod_options("ts_1"),
+            vec![doc! { "name": "ts_1", "expireAfterSeconds": 3600_i64 }]
+        );
+    }
+
+    #[test]
+    fn merges_client_and_coordinator_ops_for_one_build() {
+        let command =
+            doc! { "createIndexes": "orders", "indexes": [{ "key": { "a": 1 }, "name": "a_1" }] };
+        let ops = vec![
+            doc! { "command": command.clone(), "secs_running": 4_i64 },
+            doc! {
+                "command": command,
+                "msg": "Index Build: scanning collection",
+                "progress": { "done": 250_i64, "total": 1000_i64 },
+                "secs_running": 3_i64,
+            },
+        ];
+        let builds = merge_index_builds(&ops);
+        assert_eq!(builds.len(), 1);
+        assert_eq!(builds[0].indexes, vec!["a_1".to_string()]);
+        assert_eq!(builds[0].message.as_deref(), Some("Index Build: scanning collection"));
+        assert_eq!(builds[0].progress(), Some(0.25));
+        assert_eq!(builds[0].secs_running, Some(3));
+    }
 }
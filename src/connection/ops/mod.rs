@@ -7,6 +7,56 @@ pub mod documents;
 pub mod explain;
 pub mod export;
 pub mod import;
+pub mod index_usage;
 pub mod indexes;
 pub mod schema;
 pub mod stats;
+pub mod stream;
+pub mod transactions;
+pub mod validation;
+
+use mongodb::Client;
+use mongodb::bson::{Bson, Document};
+use mongodb::options::Acknowledgment;
+
+use crate::error::{Error, Result};
+
+/// Write concern tag carried by clients of read-only connections.
+///
+/// The tag marks the client for [`ensure_writable`]; a write that still reached the
+/// server would be rejected because no replica set defines it.
+pub const READ_ONLY_WRITE_CONCERN: &str = "openmangoReadOnly";
+
+/// Refuse `operation` when `client` belongs to a read-only connection.
+pub fn ensure_writable(client: &Client, operation: &str) -> Result<()> {
+    let read_only = client.write_concern().and_then(|concern| concern.w.as_ref()).is_some_and(
+        |w| matches!(w, Acknowledgment::Custom(tag) if tag == READ_ONLY_WRITE_CONCERN),
+    );
+    if read_only { Err(Error::ReadOnly(operation.to_string())) } else { Ok(()) }
+}
+
+/// Same guard for external tools that receive a connection string.
+pub fn ensure_writable_uri(uri: &str, operation: &str) -> Result<()> {
+    let query = uri.split_once('?').map(|(_, query)| query).unwrap_or_default();
+    let read_only = query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .any(|(key, value)| key.eq_ignore_ascii_case("w") && value == READ_ONLY_WRITE_CONCERN);
+    if read_only { Err(Error::ReadOnly(operation.to_string())) } else { Ok(()) }
+}
+
+/// `n` of the single `$count` result in `facets[name]`; empty facets count 0.
+pub(crate) fn facet_count(facets: &Document, name: &str) -> u64 {
+    let Ok(items) = facets.get_array(name) else {
+        return 0;
+    };
+    items
+        .first()
+        .and_then(Bson::as_document)
+        .and_then(|item| match item.get("n") {
+            Some(Bson::Int32(n)) => u64::try_from(*n).ok(),
+            Some(Bson::Int64(n)) => u64::try_from(*n).ok(),
+            _ => None,
+        })
+        .unwrap_or(0)
+}
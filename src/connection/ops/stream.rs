@@ -0,0 +1,98 @@
+//! Open find cursors read batch by batch, for infinite scrolling.
+
+use std::future::IntoFuture as _;
+
+use futures::TryStreamExt as _;
+use mongodb::bson::Document;
+use mongodb::{Client, Cursor};
+
+use crate::connection::ConnectionManager;
+use crate::connection::ops::documents::count_with_strategy;
+use crate::connection::types::{DocumentCount, FindDocumentsOptions};
+use crate::error::Result;
+
+/// A find cursor kept open between batches. Dropping it kills the cursor on
+/// the server.
+pub struct DocumentStream {
+    cursor: Option<Cursor<Document>>,
+    runtime: tokio::runtime::Handle,
+}
+
+impl DocumentStream {
+    /// Whether every matching document has been read.
+    pub fn is_exhausted(&self) -> bool {
+        self.cursor.is_none()
+    }
+}
+
+impl Drop for DocumentStream {
+    fn drop(&mut self) {
+        // The driver kills an unfinished cursor from a task on the runtime.
+        let _guard = self.runtime.enter();
+        self.cursor.take();
+    }
+}
+
+impl ConnectionManager {
+    /// Open a cursor over the query in `opts`, counting alongside it (runs in
+    /// Tokio runtime)
+    ///
+    /// `skip` and the keyset cursor are ignored; `limit` is the batch size.
+    pub fn open_document_stream(
+        &self,
+        client: &Client,
+        database: &str,
+        collection: &str,
+        opts: FindDocumentsOptions,
+    ) -> Result<(DocumentStream, DocumentCount)> {
+        let client = client.clone();
+        let database = database.to_string();
+        let collection = collection.to_string();
+        let filter = opts.filter.unwrap_or_default();
+
+        self.runtime.block_on(async {
+            let coll = client.database(&database).collection::<Document>(&collection);
+            let mut options = mongodb::options::FindOptions::default();
+            opts.options.apply_to_find(&mut options);
+            options.sort = opts.sort;
+            options.projection = opts.projection;
+            options.batch_size = u32::try_from(opts.limit).ok().filter(|size| *size > 0);
+
+            let (cursor, count) = futures::try_join!(
+                coll.find(filter.clone()).with_options(options).into_future(),
+                count_with_strategy(&coll, filter, opts.count, &opts.options)
+            )?;
+            let stream =
+                DocumentStream { cursor: Some(cursor), runtime: self.runtime.handle().clone() };
+            Ok((stream, count))
+        })
+    }
+
+    /// Read up to `max` more documents (runs in Tokio runtime)
+    ///
+    /// The stream is exhausted once a batch comes back short.
+    pub fn next_stream_batch(
+        &self,
+        stream: &mut DocumentStream,
+        max: usize,
+    ) -> Result<Vec<Document>> {
+        let Some(cursor) = stream.cursor.as_mut() else {
+            return Ok(Vec::new());
+        };
+        let result = self.runtime.block_on(async {
+            let mut documents = Vec::with_capacity(max);
+            while documents.len() < max {
+                match cursor.try_next().await? {
+                    Some(document) => documents.push(document),
+                    None => break,
+                }
+            }
+            Ok(documents)
+        });
+        if !matches!(&result, Ok(documents) if documents.len() == max) {
+            let _guard = self.runtime.enter();
+            stream.cursor = None;
+        }
+        result
+    }
+}
@@ -0,0 +1,167 @@
+//! Multi-document writes that either all apply or none do.
+//!
+//! Transactions need a replica set or sharded cluster; callers check
+//! [`ConnectionManager::supports_transactions`] and fall back to plain writes
+//! on a standalone server.
+
+use mongodb::bson::{Bson, Document, doc};
+use mongodb::error::{TRANSIENT_TRANSACTION_ERROR, UNKNOWN_TRANSACTION_COMMIT_RESULT};
+use mongodb::{Client, ClientSession};
+
+use crate::bson::bson_value_for_edit;
+use crate::connection::ConnectionManager;
+use crate::connection::concurrency::ConcurrencyCheck;
+use crate::connection::diagnostics::TopologyKind;
+use crate::connection::ops::ensure_writable;
+use crate::error::{Error, Result};
+
+/// Attempts at committing when the server cannot tell whether a commit
+/// went through; committing again is safe.
+const COMMIT_ATTEMPTS: usize = 3;
+
+/// Attempts at running a whole transaction when the server labels a failure
+/// `TransientTransactionError` (write conflict, primary stepdown); rerunning
+/// it from the start is safe.
+const TRANSACTION_ATTEMPTS: usize = 3;
+
+/// One document of a batch save.
+#[derive(Debug, Clone)]
+pub struct DocumentReplacement {
+    pub id: Bson,
+    pub replacement: Document,
+    /// Must still hold on the server; see [`ConcurrencyCheck`].
+    pub check: Option<ConcurrencyCheck>,
+}
+
+impl ConnectionManager {
+    /// Whether the deployment behind `client` supports multi-document
+    /// transactions (runs in Tokio runtime)
+    pub fn supports_transactions(&self, client: &Client) -> Result<bool> {
+        let client = client.clone();
+        self.runtime.block_on(async {
+            let hello = client.database("admin").run_command(doc! { "hello": 1 }).await?;
+            Ok(matches!(
+                TopologyKind::from_hello(&hello),
+                TopologyKind::ReplicaSet { .. } | TopologyKind::Sharded
+            ))
+        })
+    }
+
+    /// Insert documents in one transaction (runs in Tokio runtime)
+    pub fn insert_documents_transactional(
+        &self,
+        client: &Client,
+        database: &str,
+        collection: &str,
+        documents: Vec<Document>,
+    ) -> Result<usize> {
+        ensure_writable(client, "insert")?;
+        let count = documents.len();
+        let client = client.clone();
+        let database = database.to_string();
+        let collection = collection.to_string();
+
+        self.runtime.block_on(async {
+            let coll = client.database(&database).collection::<Document>(&collection);
+            with_transaction(&client, async |session| {
+                coll.insert_many(&documents).session(session).await?;
+                Ok(())
+            })
+            .await?;
+            Ok(count)
+        })
+    }
+
+    /// Replace documents in one transaction (runs in Tokio runtime)
+    ///
+    /// A document that is gone or fails its check aborts the whole batch with
+    /// [`Error::Conflict`], so nothing is written.
+    pub fn replace_documents_transactional(
+        &self,
+        client: &Client,
+        database: &str,
+        collection: &str,
+        replacements: Vec<DocumentReplacement>,
+    ) -> Result<usize> {
+        ensure_writable(client, "update")?;
+        let count = replacements.len();
+        let client = client.clone();
+        let database = database.to_string();
+        let collection = collection.to_string();
+
+        self.runtime.block_on(async {
+            let coll = client.database(&database).collection::<Document>(&collection);
+            with_transaction(&client, async |session| {
+                for item in &replacements {
+                    let filter = match &item.check {
+                        Some(check) => check.filter(&item.id),
+                        None => doc! { "_id": item.id.clone() },
+                    };
+                    let outcome =
+                        coll.replace_one(filter, &item.replacement).session(&mut *session).await?;
+                    if outcome.matched_count != 1 {
+                        return Err(Error::Conflict(format!(
+                            "Document {} changed on the server since it was loaded; nothing was saved",
+                            bson_value_for_edit(&item.id)
+                        )));
+                    }
+                }
+                Ok(())
+            })
+            .await?;
+            Ok(count)
+        })
+    }
+}
+
+/// Run `body` in a transaction on a fresh session and commit it.
+///
+/// Mirrors the driver's convenient transaction API: a failure labelled
+/// `TransientTransactionError` reruns the whole transaction, and a commit
+/// with an unknown outcome is retried on its own.
+pub(super) async fn with_transaction(
+    client: &Client,
+    mut body: impl AsyncFnMut(&mut ClientSession) -> Result<()>,
+) -> Result<()> {
+    let mut session = client.start_session().await?;
+    let mut attempt = 1;
+    loop {
+        session.start_transaction().await?;
+        let result = match body(&mut session).await {
+            Ok(()) => commit(&mut session).await,
+            Err(err) => {
+                if let Err(abort_err) = session.abort_transaction().await {
+                    log::warn!("Failed to abort transaction: {abort_err}");
+                }
+                Err(err)
+            }
+        };
+        match result {
+            Err(err) if is_transient(&err) && attempt < TRANSACTION_ATTEMPTS => {
+                log::debug!("Retrying transient transaction failure: {err}");
+                attempt += 1;
+            }
+            other => return other,
+        }
+    }
+}
+
+/// Commit, retrying while the server cannot tell whether it went through.
+async fn commit(session: &mut ClientSession) -> Result<()> {
+    let mut attempt = 1;
+    loop {
+        match session.commit_transaction().await {
+            Err(err)
+                if err.contains_label(UNKNOWN_TRANSACTION_COMMIT_RESULT)
+                    && attempt < COMMIT_ATTEMPTS =>
+            {
+                attempt += 1;
+            }
+            other => return other.map_err(Error::from),
+        }
+    }
+}
+
+fn is_transient(err: &Error) -> bool {
+    matches!(err, Error::Mongo(err) if err.contains_label(TRANSIENT_TRANSACTION_ERROR))
+}
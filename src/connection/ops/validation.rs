@@ -0,0 +1,184 @@
+//! Collection validation rules: read from `listCollections`, checked against a
+//! sample with `$nor`, applied with `collMod`.
+
+use futures::TryStreamExt;
+use mongodb::Client;
+use mongodb::bson::{Bson, Document, doc};
+use mongodb::options::{ValidationAction, ValidationLevel};
+use mongodb::results::CollectionSpecification;
+
+use crate::connection::ConnectionManager;
+use crate::connection::ops::{ensure_writable, facet_count};
+use crate::error::{Error, Result};
+
+/// Violating documents returned by a check; the count covers all of them.
+pub const VIOLATION_SAMPLE_LIMIT: i64 = 50;
+
+/// Validator, level and action of a collection. The level and action are
+/// `None` when the server omits them, meaning strict and error.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValidationRules {
+    pub validator: Option<Document>,
+    pub level: Option<ValidationLevel>,
+    pub action: Option<ValidationAction>,
+}
+
+impl ValidationRules {
+    pub fn from_spec(spec: &CollectionSpecification) -> Self {
+        Self {
+            validator: spec.options.validator.clone().filter(|validator| !validator.is_empty()),
+            level: spec.options.validation_level.clone(),
+            action: spec.options.validation_action.clone(),
+        }
+    }
+
+    /// The `collMod` command that replaces the rules of `collection`. An empty
+    /// validator removes validation.
+    pub fn coll_mod_command(&self, collection: &str) -> Document {
+        let mut command = doc! {
+            "collMod": collection,
+            "validator": self.validator.clone().unwrap_or_default(),
+        };
+        if let Some(level) = &self.level {
+            command.insert("validationLevel", validation_level_name(level));
+        }
+        if let Some(action) = &self.action {
+            command.insert("validationAction", validation_action_name(action));
+        }
+        command
+    }
+}
+
+pub fn validation_level_name(level: &ValidationLevel) -> &'static str {
+    match level {
+        ValidationLevel::Off => "off",
+        ValidationLevel::Moderate => "moderate",
+        _ => "strict",
+    }
+}
+
+pub fn validation_action_name(action: &ValidationAction) -> &'static str {
+    match action {
+        ValidationAction::Warn => "warn",
+        _ => "error",
+    }
+}
+
+/// Outcome of running a validator over sampled documents.
+#[derive(Debug, Clone, Default)]
+pub struct ValidationCheck {
+    pub sampled: u64,
+    pub violation_count: u64,
+    /// Up to [`VIOLATION_SAMPLE_LIMIT`] of the violating documents.
+    pub violations: Vec<Document>,
+}
+
+impl ConnectionManager {
+    /// Read the validation rules of a collection (runs in Tokio runtime)
+    pub fn collection_validation(
+        &self,
+        client: &Client,
+        database: &str,
+        collection: &str,
+    ) -> Result<ValidationRules> {
+        self.list_collection_specs(client, database)?
+            .iter()
+            .find(|spec| spec.name == collection)
+            .map(ValidationRules::from_spec)
+            .ok_or_else(|| Error::Parse(format!("Collection {database}.{collection} not found")))
+    }
+
+    /// Sample up to `sample_size` documents and report those that do not
+    /// match `validator` (runs in Tokio runtime)
+    pub fn check_validator(
+        &self,
+        client: &Client,
+        database: &str,
+        collection: &str,
+        validator: &Document,
+        sample_size: u64,
+    ) -> Result<ValidationCheck> {
+        let client = client.clone();
+        let database = database.to_string();
+        let collection = collection.to_string();
+        let violating = doc! { "$match": { "$nor": [validator.clone()] } };
+        let pipeline = vec![
+            doc! { "$sample": { "size": sample_size as i64 } },
+            doc! { "$facet": {
+                "sampled": [{ "$count": "n" }],
+                "violationCount": [violating.clone(), { "$count": "n" }],
+                "violations": [violating, { "$limit": VIOLATION_SAMPLE_LIMIT }],
+            } },
+        ];
+
+        self.runtime.block_on(async {
+            let coll = client.database(&database).collection::<Document>(&collection);
+            let facets: Vec<Document> = coll.aggregate(pipeline).await?.try_collect().await?;
+            let facets = facets.into_iter().next().unwrap_or_default();
+            let violations = match facets.get("violations") {
+                Some(Bson::Array(items)) => {
+                    items.iter().filter_map(|item| item.as_document().cloned()).collect()
+                }
+                _ => Vec::new(),
+            };
+            Ok(ValidationCheck {
+                sampled: facet_count(&facets, "sampled"),
+                violation_count: facet_count(&facets, "violationCount"),
+                violations,
+            })
+        })
+    }
+
+    /// Replace the validation rules of a collection (runs in Tokio runtime)
+    pub fn apply_validation(
+        &self,
+        client: &Client,
+        database: &str,
+        collection: &str,
+        rules: &ValidationRules,
+    ) -> Result<()> {
+        ensure_writable(client, "update validation rules")?;
+        let client = client.clone();
+        let database = database.to_string();
+        let command = rules.coll_mod_command(collection);
+        self.runtime.block_on(async {
+            client.database(&database).run_command(command).await?;
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coll_mod_replaces_or_clears_the_validator() {
+        let rules = ValidationRules {
+            validator: Some(doc! { "$jsonSchema": { "required": ["name"] } }),
+            level: Some(ValidationLevel::Moderate),
+            action: Some(ValidationAction::Warn),
+        };
+        assert_eq!(
+            rules.coll_mod_command("people"),
+            doc! {
+                "collMod": "people",
+                "validator": { "$jsonSchema": { "required": ["name"] } },
+                "validationLevel": "moderate",
+                "validationAction": "warn",
+            }
+        );
+        assert_eq!(
+            ValidationRules::default().coll_mod_command("people"),
+            doc! { "collMod": "people", "validator": {} }
+        );
+    }
+
+    #[test]
+    fn facet_count_reads_missing_facets_as_zero() {
+        let facets = doc! { "sampled": [{ "n": 12 }], "violationCount": [] };
+        assert_eq!(facet_count(&facets, "sampled"), 12);
+        assert_eq!(facet_count(&facets, "violationCount"), 0);
+        assert_eq!(facet_count(&facets, "missing"), 0);
+    }
+}
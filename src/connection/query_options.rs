@@ -0,0 +1,230 @@
+//! Cursor options for the documents query: hint, collation, maxTimeMS,
+//! allowDiskUse, read preference and comment.
+//!
+//! The options are entered as one document using the mongosh option names, e.g.
+//! `{ hint: "status_1", collation: { locale: "fr", strength: 2 }, maxTimeMS: 5000 }`,
+//! and applied to `find`, the count and `explain` alike.
+
+use std::time::Duration;
+
+use mongodb::bson::{Bson, Document, doc};
+use mongodb::options::{
+    Collation, CountOptions, EstimatedDocumentCountOptions, FindOptions, Hint, ReadPreference,
+    SelectionCriteria,
+};
+
+/// Parsed query options; every field is optional and unset fields keep the
+/// driver defaults.
+#[derive(Debug, Clone, Default)]
+pub struct QueryOptions {
+    pub hint: Option<Hint>,
+    pub collation: Option<Collation>,
+    pub max_time: Option<Duration>,
+    pub allow_disk_use: Option<bool>,
+    pub read_preference: Option<ReadPreference>,
+    pub comment: Option<Bson>,
+}
+
+impl QueryOptions {
+    /// Parse an options document. Unknown keys are rejected so typos do not
+    /// silently run a different query.
+    pub fn from_document(options: &Document) -> Result<Self, String> {
+        let mut parsed = Self::default();
+        let mut read_preference = None;
+        let mut tag_sets = None;
+
+        for (key, value) in options {
+            match key.as_str() {
+                "hint" => {
+                    parsed.hint = Some(match value {
+                        Bson::String(name) => Hint::Name(name.clone()),
+                        Bson::Document(keys) => Hint::Keys(keys.clone()),
+                        _ => return Err("hint must be an index name or key pattern".into()),
+                    });
+                }
+                "collation" => {
+                    let Bson::Document(collation) = value else {
+                        return Err("collation must be a document".into());
+                    };
+                    let collation = mongodb::bson::from_document(collation.clone())
+                        .map_err(|err| format!("Invalid collation: {err}"))?;
+                    parsed.collation = Some(collation);
+                }
+                "maxTimeMS" => {
+                    let millis =
+                        non_negative_int(value).ok_or("maxTimeMS must be a non-negative number")?;
+                    parsed.max_time = Some(Duration::from_millis(millis));
+                }
+                "allowDiskUse" => {
+                    let Bson::Boolean(allow) = value else {
+                        return Err("allowDiskUse must be true or false".into());
+                    };
+                    parsed.allow_disk_use = Some(*allow);
+                }
+                "readPreference" => {
+                    let Bson::String(mode) = value else {
+                        return Err("readPreference must be a mode name".into());
+                    };
+                    read_preference = Some(mode.clone());
+                }
+                "readPreferenceTags" => {
+                    let Bson::Array(tags) = value else {
+                        return Err("readPreferenceTags must be an array of documents".into());
+                    };
+                    tag_sets = Some(tags.clone());
+                }
+                "comment" => parsed.comment = Some(value.clone()),
+                other => return Err(format!("Unknown query option: {other}")),
+            }
+        }
+
+        if let Some(mode) = read_preference {
+            let mut spec = doc! { "mode": mode };
+            if let Some(tag_sets) = tag_sets {
+                spec.insert("tagSets", tag_sets);
+            }
+            let read_preference = mongodb::bson::from_document(spec)
+                .map_err(|err| format!("Invalid read preference: {err}"))?;
+            parsed.read_preference = Some(read_preference);
+        } else if tag_sets.is_some() {
+            return Err("readPreferenceTags requires readPreference".into());
+        }
+
+        Ok(parsed)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hint.is_none()
+            && self.collation.is_none()
+            && self.max_time.is_none()
+            && self.allow_disk_use.is_none()
+            && self.read_preference.is_none()
+            && self.comment.is_none()
+    }
+
+    pub fn selection_criteria(&self) -> Option<SelectionCriteria> {
+        self.read_preference.clone().map(SelectionCriteria::ReadPreference)
+    }
+
+    pub fn apply_to_find(&self, options: &mut FindOptions) {
+        options.hint = self.hint.clone();
+        options.collation = self.collation.clone();
+        options.max_time = self.max_time;
+        options.allow_disk_use = self.allow_disk_use;
+        options.selection_criteria = self.selection_criteria();
+        options.comment = self.comment.clone();
+    }
+
+    /// The count uses the same index, collation and read preference as the
+    /// query so it reports the documents the query would return.
+    pub fn apply_to_count(&self, options: &mut CountOptions) {
+        options.hint = self.hint.clone();
+        options.collation = self.collation.clone();
+        options.max_time = self.max_time;
+        options.selection_criteria = self.selection_criteria();
+        options.comment = self.comment.clone();
+    }
+
+    pub fn apply_to_estimated_count(&self, options: &mut EstimatedDocumentCountOptions) {
+        options.max_time = self.max_time;
+        options.selection_criteria = self.selection_criteria();
+        options.comment = self.comment.clone();
+    }
+
+    /// Add the options to a raw `find` command, as sent by `explain`. The read
+    /// preference is not part of the command; see [`Self::selection_criteria`].
+    pub fn apply_to_command(&self, command: &mut Document) {
+        match &self.hint {
+            Some(Hint::Name(name)) => {
+                command.insert("hint", name.clone());
+            }
+            Some(Hint::Keys(keys)) => {
+                command.insert("hint", keys.clone());
+            }
+            _ => {}
+        }
+        if let Some(collation) =
+            self.collation.as_ref().and_then(|collation| mongodb::bson::to_document(collation).ok())
+        {
+            command.insert("collation", collation);
+        }
+        if let Some(max_time) = self.max_time {
+            command.insert("maxTimeMS", max_time.as_millis() as i64);
+        }
+        if let Some(allow_disk_use) = self.allow_disk_use {
+            command.insert("allowDiskUse", allow_disk_use);
+        }
+        if let Some(comment) = &self.comment {
+            command.insert("comment", comment.clone());
+        }
+    }
+}
+
+fn non_negative_int(value: &Bson) -> Option<u64> {
+    match value {
+        Bson::Int32(n) => u64::try_from(*n).ok(),
+        Bson::Int64(n) => u64::try_from(*n).ok(),
+        Bson::Double(n) if *n >= 0.0 && n.fract() == 0.0 => Some(*n as u64),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_every_option() {
+        let options = QueryOptions::from_document(&doc! {
+            "hint": { "status": 1 },
+            "collation": { "locale": "fr", "strength": 2 },
+            "maxTimeMS": 5000,
+            "allowDiskUse": true,
+            "readPreference": "secondaryPreferred",
+            "readPreferenceTags": [{ "dc": "east" }],
+            "comment": "orders page",
+        })
+        .expect("options should parse");
+
+        assert_eq!(options.hint, Some(Hint::Keys(doc! { "status": 1 })));
+        assert_eq!(options.collation.as_ref().map(|c| c.locale.as_str()), Some("fr"));
+        assert_eq!(options.max_time, Some(Duration::from_millis(5000)));
+        assert_eq!(options.allow_disk_use, Some(true));
+        let Some(ReadPreference::SecondaryPreferred { options: Some(read_options) }) =
+            &options.read_preference
+        else {
+            panic!("unexpected read preference: {:?}", options.read_preference);
+        };
+        assert_eq!(read_options.tag_sets.as_ref().map(Vec::len), Some(1));
+        assert_eq!(options.comment, Some(Bson::String("orders page".into())));
+
+        let mut command = doc! { "find": "orders" };
+        options.apply_to_command(&mut command);
+        assert_eq!(
+            command,
+            doc! {
+                "find": "orders",
+                "hint": { "status": 1 },
+                "collation": { "locale": "fr", "strength": 2 },
+                "maxTimeMS": 5000_i64,
+                "allowDiskUse": true,
+                "comment": "orders page",
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_options() {
+        for options in [
+            doc! { "hnit": "status_1" },
+            doc! { "hint": 1 },
+            doc! { "maxTimeMS": -1 },
+            doc! { "readPreference": "fastest" },
+            doc! { "readPreferenceTags": [{ "dc": "east" }] },
+            doc! { "collation": { "strength": 2 } },
+        ] {
+            assert!(QueryOptions::from_document(&options).is_err(), "{options} should fail");
+        }
+        assert!(QueryOptions::from_document(&Document::new()).unwrap().is_empty());
+    }
+}
@@ -3,7 +3,7 @@
 use std::collections::HashMap;
 use std::fs;
 use std::io::{Read, Write};
-use std::net::{TcpListener, TcpStream};
+use std::net::{IpAddr, TcpListener, TcpStream};
 use std::path::{Path, PathBuf};
 use std::sync::mpsc::{self, Receiver, Sender};
 use std::thread;
@@ -15,7 +15,7 @@ use sha2::{Digest, Sha256};
 use ssh2::Session;
 
 use crate::error::{Error, Result};
-use crate::models::{SshAuth, SshConfig};
+use crate::models::{ProxyConfig, SshAuth, SshConfig, SshJumpHost};
 
 #[cfg(debug_assertions)]
 const APP_NAME: &str = "openmango-dev";
@@ -34,6 +34,11 @@ const IO_POLL_TIMEOUT_MS: u32 = 50;
 /// Session timeout (ms) used when opening a new channel_direct_tcpip (needs
 /// a round-trip to the remote SSH server).
 const CHANNEL_OPEN_TIMEOUT_MS: u32 = 10_000;
+/// Interval between SSH keepalives; a failed send means the session is gone.
+const KEEPALIVE_INTERVAL_SECS: u32 = 15;
+/// libssh2 codes for a keepalive that merely could not be sent within the poll timeout.
+const LIBSSH2_ERROR_TIMEOUT: i32 = -9;
+const LIBSSH2_ERROR_EAGAIN: i32 = -37;
 
 #[derive(Default, Serialize, Deserialize)]
 struct HostKeyStore {
@@ -43,6 +48,8 @@ struct HostKeyStore {
 pub struct SshTunnelHandle {
     stop_tx: Sender<()>,
     join_handle: Option<thread::JoinHandle<()>>,
+    /// Relays for intermediate jump hosts, ordered from the first hop outward.
+    hop_relays: Vec<HopRelay>,
     pub local_host: String,
     pub local_port: u16,
 }
@@ -52,11 +59,22 @@ impl SshTunnelHandle {
         format!("{}:{}", self.local_host, self.local_port)
     }
 
+    /// Whether the tunnel thread and every jump relay are still running.
+    pub fn is_alive(&self) -> bool {
+        self.join_handle.as_ref().is_some_and(|handle| !handle.is_finished())
+            && self.hop_relays.iter().all(HopRelay::is_alive)
+    }
+
     pub fn stop(&mut self) {
         let _ = self.stop_tx.send(());
         if let Some(join_handle) = self.join_handle.take() {
             let _ = join_handle.join();
         }
+        // Tear down jump hops innermost-first so each session can still send
+        // its disconnect through the hop that carries it.
+        while let Some(mut relay) = self.hop_relays.pop() {
+            relay.stop();
+        }
     }
 }
 
@@ -66,12 +84,65 @@ impl Drop for SshTunnelHandle {
     }
 }
 
-pub fn start_ssh_tunnel(config: &SshConfig) -> Result<SshTunnelHandle> {
+/// Pumps one `channel_direct_tcpip` on a jump host's session to a loopback
+/// socket that the next hop's SSH session uses as its transport.
+struct HopRelay {
+    stop_tx: Sender<()>,
+    join_handle: Option<thread::JoinHandle<()>>,
+}
+
+impl HopRelay {
+    fn is_alive(&self) -> bool {
+        self.join_handle.as_ref().is_some_and(|handle| !handle.is_finished())
+    }
+
+    fn stop(&mut self) {
+        let _ = self.stop_tx.send(());
+        if let Some(join_handle) = self.join_handle.take() {
+            let _ = join_handle.join();
+        }
+    }
+}
+
+impl Drop for HopRelay {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Start a local SOCKS5 listener backed by an SSH session.
+///
+/// When `proxy` is set, the SSH TCP socket itself is opened through that SOCKS5
+/// proxy (e.g. a corporate proxy in front of the bastion host).
+///
+/// Jump hosts are traversed in order: the first hop gets the (possibly proxied)
+/// TCP socket, and every following hop is reached through a
+/// `channel_direct_tcpip` opened on the previous hop's session.
+pub fn start_ssh_tunnel(
+    config: &SshConfig,
+    proxy: Option<&ProxyConfig>,
+) -> Result<SshTunnelHandle> {
     validate_ssh_config(config)?;
+
+    let target = config.target_hop();
+    let first = config.jump_hosts.first().unwrap_or(&target);
+    let mut tcp = open_first_hop_socket(first, proxy)?;
+    let mut hop_relays = Vec::with_capacity(config.jump_hosts.len());
+    for (index, jump) in config.jump_hosts.iter().enumerate() {
+        let next = config.jump_hosts.get(index + 1).unwrap_or(&target);
+        let label = format!("SSH jump host {}", jump.address());
+        let (session, ssh_socket) = establish_ssh_session(jump, tcp)
+            .map_err(|err| Error::Parse(format!("{label}: {err}")))?;
+        let (relay, next_tcp) = start_hop_relay(session, ssh_socket, next)
+            .map_err(|err| Error::Parse(format!("{label}: {err}")))?;
+        hop_relays.push(relay);
+        tcp = next_tcp;
+    }
+
     // Create the long-lived SSH session — this is reused for all SOCKS5 clients
     // so that each new channel is just a `channel_direct_tcpip` (milliseconds)
     // instead of a full SSH handshake (seconds).
-    let (session, ssh_socket) = establish_ssh_session(config)?;
+    let (session, ssh_socket) = establish_ssh_session(&target, tcp)?;
 
     let listener = TcpListener::bind((config.local_bind_host.as_str(), 0))?;
     listener.set_nonblocking(true)?;
@@ -86,7 +157,131 @@ pub fn start_ssh_tunnel(config: &SshConfig) -> Result<SshTunnelHandle> {
         })
         .map_err(Error::from)?;
 
-    Ok(SshTunnelHandle { stop_tx, join_handle: Some(join_handle), local_host, local_port })
+    Ok(SshTunnelHandle {
+        stop_tx,
+        join_handle: Some(join_handle),
+        hop_relays,
+        local_host,
+        local_port,
+    })
+}
+
+fn open_first_hop_socket(hop: &SshJumpHost, proxy: Option<&ProxyConfig>) -> Result<TcpStream> {
+    let host = hop.host.trim();
+    match proxy {
+        Some(proxy) => connect_via_socks5(proxy, host, hop.port),
+        None => Ok(TcpStream::connect((host, hop.port))?),
+    }
+}
+
+/// Open a channel from `session` to `next` and expose it as a loopback socket.
+///
+/// The returned `TcpStream` is handed to the next hop's SSH session; the relay
+/// thread owns the jump session and shuttles bytes between the two.
+fn start_hop_relay(
+    session: Session,
+    ssh_socket: TcpStream,
+    next: &SshJumpHost,
+) -> Result<(HopRelay, TcpStream)> {
+    let next_host = next.host.trim();
+    session.set_timeout(CHANNEL_OPEN_TIMEOUT_MS);
+    let channel = session.channel_direct_tcpip(next_host, next.port, None).map_err(|err| {
+        Error::Parse(format!("could not open a channel to {next_host}:{}: {err}", next.port))
+    })?;
+
+    let listener = TcpListener::bind(("127.0.0.1", 0))?;
+    let outer = TcpStream::connect(listener.local_addr()?)?;
+    let (inner, peer) = listener.accept()?;
+    if peer != outer.local_addr()? {
+        return Err(Error::Parse("unexpected connection on SSH jump relay socket".to_string()));
+    }
+    inner.set_nonblocking(true)?;
+
+    let (stop_tx, stop_rx) = mpsc::channel();
+    let join_handle = thread::Builder::new()
+        .name("openmango-ssh-jump".to_string())
+        .spawn(move || {
+            run_hop_relay(session, ssh_socket, channel, inner, stop_rx);
+        })
+        .map_err(Error::from)?;
+
+    Ok((HopRelay { stop_tx, join_handle: Some(join_handle) }, outer))
+}
+
+/// Relay loop for a single jump hop.  Like `run_tunnel_loop`, every libssh2
+/// call for this session stays on this thread.
+fn run_hop_relay(
+    session: Session,
+    ssh_socket: TcpStream,
+    channel: ssh2::Channel,
+    local_stream: TcpStream,
+    stop_rx: Receiver<()>,
+) {
+    configure_session_polling(&session, &ssh_socket);
+
+    let mut client = ActiveClient { local_stream, channel, last_activity: Instant::now() };
+    let mut local_to_remote = [0u8; 8192];
+    let mut remote_to_local = [0u8; 8192];
+    let mut keepalive = Keepalive::new(&session);
+
+    while stop_rx.try_recv().is_err() && keepalive.poll(&session) {
+        let outcome = poll_client(&mut client, 0, &mut local_to_remote, &mut remote_to_local);
+        if outcome.close {
+            log::debug!("SSH jump relay closed");
+            break;
+        }
+        if !outcome.progressed {
+            thread::sleep(IO_IDLE_SLEEP);
+        }
+    }
+}
+
+/// Keep the session blocking but with a short timeout so channel reads and
+/// writes return quickly (EAGAIN is mapped to WouldBlock) instead of hanging.
+fn configure_session_polling(session: &Session, ssh_socket: &TcpStream) {
+    session.set_blocking(true);
+    session.set_timeout(IO_POLL_TIMEOUT_MS);
+    ssh_socket.set_nonblocking(false).ok();
+    ssh_socket.set_read_timeout(Some(Duration::from_millis(IO_POLL_TIMEOUT_MS as u64))).ok();
+    ssh_socket.set_write_timeout(Some(Duration::from_millis(IO_POLL_TIMEOUT_MS as u64))).ok();
+}
+
+/// Periodic SSH keepalive.  The tunnel exits when a keepalive cannot be
+/// sent, which lets `SshTunnelHandle::is_alive` report the dropped session.
+struct Keepalive {
+    next_at: Instant,
+}
+
+impl Keepalive {
+    fn new(session: &Session) -> Self {
+        session.set_keepalive(true, KEEPALIVE_INTERVAL_SECS);
+        Self { next_at: Instant::now() + Duration::from_secs(KEEPALIVE_INTERVAL_SECS.into()) }
+    }
+
+    /// Send a keepalive when one is due; returns `false` once the session is dead.
+    fn poll(&mut self, session: &Session) -> bool {
+        if Instant::now() < self.next_at {
+            return true;
+        }
+        match session.keepalive_send() {
+            Ok(next_secs) => {
+                self.next_at = Instant::now() + Duration::from_secs(next_secs.max(1).into());
+                true
+            }
+            Err(err)
+                if matches!(
+                    err.code(),
+                    ssh2::ErrorCode::Session(LIBSSH2_ERROR_TIMEOUT | LIBSSH2_ERROR_EAGAIN)
+                ) =>
+            {
+                true
+            }
+            Err(err) => {
+                log::warn!("SSH session lost: {err}");
+                false
+            }
+        }
+    }
 }
 
 struct ActiveClient {
@@ -125,7 +320,7 @@ const SOCKS5_REPLY_HOST_UNREACHABLE: [u8; 10] = [0x05, 0x04, 0x00, 0x01, 0, 0, 0
 ///
 /// ## Loop structure
 ///
-/// 1. Check stop signal
+/// 1. Check stop signal and keep the session alive (exit if it dropped)
 /// 2. Poll active clients (relay data)
 /// 3. Accept new SOCKS5 clients → push to pending queue (always)
 /// 4. Open SSH channels for pending clients → promote to active (only when idle)
@@ -136,25 +331,22 @@ fn run_tunnel_loop(
     session: Session,
     ssh_socket: TcpStream,
 ) {
-    // The session stays blocking.  We use a short session timeout so that
-    // channel read/write calls return quickly (with EAGAIN mapped to
-    // WouldBlock) instead of hanging forever.
-    session.set_blocking(true);
-    session.set_timeout(IO_POLL_TIMEOUT_MS);
-    ssh_socket.set_nonblocking(false).ok();
-    ssh_socket.set_read_timeout(Some(Duration::from_millis(IO_POLL_TIMEOUT_MS as u64))).ok();
-    ssh_socket.set_write_timeout(Some(Duration::from_millis(IO_POLL_TIMEOUT_MS as u64))).ok();
+    configure_session_polling(&session, &ssh_socket);
 
     let mut clients: Vec<ActiveClient> = Vec::new();
     let mut pending: Vec<PendingClient> = Vec::new();
     let mut local_to_remote = [0u8; 8192];
     let mut remote_to_local = [0u8; 8192];
+    let mut keepalive = Keepalive::new(&session);
 
     loop {
-        // 1. Check stop signal
+        // 1. Check stop signal and session liveness
         if stop_rx.try_recv().is_ok() {
             break;
         }
+        if !keepalive.poll(&session) {
+            break;
+        }
 
         // 2. Poll active clients (relay data)
         let mut any_progress = false;
@@ -424,6 +616,148 @@ fn socks5_read_connect(stream: &mut TcpStream) -> Result<(String, u16)> {
     Ok((host, port))
 }
 
+/// Open a TCP stream to `target_host:target_port` through an upstream SOCKS5
+/// proxy (RFC 1928), authenticating with username/password (RFC 1929) when
+/// the proxy config carries credentials.
+fn connect_via_socks5(
+    proxy: &ProxyConfig,
+    target_host: &str,
+    target_port: u16,
+) -> Result<TcpStream> {
+    let proxy_host = proxy.host.trim();
+    let mut stream = TcpStream::connect((proxy_host, proxy.port)).map_err(|err| {
+        Error::Parse(format!(
+            "Failed to connect to SOCKS5 proxy {proxy_host}:{}: {err}",
+            proxy.port
+        ))
+    })?;
+    stream.set_read_timeout(Some(Duration::from_secs(10)))?;
+    stream.set_write_timeout(Some(Duration::from_secs(10)))?;
+
+    let credentials = proxy
+        .username
+        .as_deref()
+        .filter(|username| !username.is_empty())
+        .map(|username| (username, proxy.password.as_deref().unwrap_or_default()));
+
+    // Greeting: offer username/password only when we actually have credentials.
+    if credentials.is_some() {
+        stream.write_all(&[0x05, 0x02, 0x00, 0x02])?;
+    } else {
+        stream.write_all(&[0x05, 0x01, 0x00])?;
+    }
+    let mut choice = [0u8; 2];
+    stream.read_exact(&mut choice)?;
+    if choice[0] != 0x05 {
+        return Err(Error::Parse(format!(
+            "SOCKS5 proxy replied with unsupported version {:#04x}",
+            choice[0]
+        )));
+    }
+
+    match (choice[1], credentials) {
+        (0x00, _) => {}
+        (0x02, Some((username, password))) => {
+            if username.len() > 255 || password.len() > 255 {
+                return Err(Error::Parse(
+                    "SOCKS5 proxy username and password must be at most 255 bytes".to_string(),
+                ));
+            }
+            let mut request = Vec::with_capacity(3 + username.len() + password.len());
+            request.push(0x01);
+            request.push(username.len() as u8);
+            request.extend_from_slice(username.as_bytes());
+            request.push(password.len() as u8);
+            request.extend_from_slice(password.as_bytes());
+            stream.write_all(&request)?;
+
+            let mut status = [0u8; 2];
+            stream.read_exact(&mut status)?;
+            if status[1] != 0x00 {
+                return Err(Error::Parse("SOCKS5 proxy authentication failed".to_string()));
+            }
+        }
+        (0x02, None) => {
+            return Err(Error::Parse(
+                "SOCKS5 proxy requires username/password authentication".to_string(),
+            ));
+        }
+        _ => {
+            return Err(Error::Parse(
+                "SOCKS5 proxy rejected all offered authentication methods".to_string(),
+            ));
+        }
+    }
+
+    // CONNECT request: VER CMD RSV ATYP DST.ADDR DST.PORT
+    let mut request = vec![0x05, 0x01, 0x00];
+    match target_host.parse::<IpAddr>() {
+        Ok(IpAddr::V4(addr)) => {
+            request.push(0x01);
+            request.extend_from_slice(&addr.octets());
+        }
+        Ok(IpAddr::V6(addr)) => {
+            request.push(0x04);
+            request.extend_from_slice(&addr.octets());
+        }
+        Err(_) => {
+            if target_host.len() > 255 {
+                return Err(Error::Parse(format!(
+                    "SOCKS5: target host name is too long: {target_host}"
+                )));
+            }
+            request.push(0x03);
+            request.push(target_host.len() as u8);
+            request.extend_from_slice(target_host.as_bytes());
+        }
+    }
+    request.extend_from_slice(&target_port.to_be_bytes());
+    stream.write_all(&request)?;
+
+    let mut header = [0u8; 4];
+    stream.read_exact(&mut header)?;
+    if header[1] != 0x00 {
+        return Err(Error::Parse(format!(
+            "SOCKS5 proxy could not reach {target_host}:{target_port}: {}",
+            socks5_reply_message(header[1])
+        )));
+    }
+
+    // Drain BND.ADDR and BND.PORT so the stream is positioned at the SSH banner.
+    let addr_len = match header[3] {
+        0x01 => 4,
+        0x04 => 16,
+        0x03 => {
+            let mut len_buf = [0u8; 1];
+            stream.read_exact(&mut len_buf)?;
+            len_buf[0] as usize
+        }
+        atyp => {
+            return Err(Error::Parse(format!(
+                "SOCKS5 proxy replied with unsupported address type {atyp:#04x}"
+            )));
+        }
+    };
+    let mut bound = vec![0u8; addr_len + 2];
+    stream.read_exact(&mut bound)?;
+
+    Ok(stream)
+}
+
+fn socks5_reply_message(code: u8) -> &'static str {
+    match code {
+        0x01 => "general failure",
+        0x02 => "connection not allowed by ruleset",
+        0x03 => "network unreachable",
+        0x04 => "host unreachable",
+        0x05 => "connection refused",
+        0x06 => "TTL expired",
+        0x07 => "command not supported",
+        0x08 => "address type not supported",
+        _ => "unknown error",
+    }
+}
+
 /// Write all data through a blocking ssh2 channel.  The session timeout
 /// (set_timeout) ensures each individual write returns within IO_POLL_TIMEOUT_MS.
 /// libssh2 properly pumps the SSH transport on each call.
@@ -487,8 +821,7 @@ fn write_all_nonblocking<W: Write>(writer: &mut W, data: &[u8]) -> Result<()> {
 
 /// The returned `TcpStream` is a dup'd handle to the SSH socket — callers can use it
 /// to toggle blocking/non-blocking mode without going through the session.
-fn establish_ssh_session(config: &SshConfig) -> Result<(Session, TcpStream)> {
-    let tcp = TcpStream::connect((config.host.as_str(), config.port))?;
+fn establish_ssh_session(config: &SshJumpHost, tcp: TcpStream) -> Result<(Session, TcpStream)> {
     tcp.set_read_timeout(Some(Duration::from_secs(10)))?;
     tcp.set_write_timeout(Some(Duration::from_secs(10)))?;
     let tcp_ctl = tcp.try_clone()?;
@@ -522,6 +855,11 @@ fn establish_ssh_session(config: &SshConfig) -> Result<(Session, TcpStream)> {
                 passphrase,
             )?;
         }
+        SshAuth::Agent => {
+            session
+                .userauth_agent(config.username.as_str())
+                .map_err(|err| Error::Parse(format!("SSH agent authentication failed: {err}")))?;
+        }
     }
 
     if !session.authenticated() {
@@ -531,7 +869,7 @@ fn establish_ssh_session(config: &SshConfig) -> Result<(Session, TcpStream)> {
     Ok((session, tcp_ctl))
 }
 
-fn verify_or_learn_host_key(session: &Session, config: &SshConfig) -> Result<()> {
+fn verify_or_learn_host_key(session: &Session, config: &SshJumpHost) -> Result<()> {
     if !config.strict_host_key_checking {
         return Ok(());
     }
@@ -565,49 +903,66 @@ fn ssh_host_key_fingerprint(host_key: &[u8]) -> String {
 }
 
 fn validate_ssh_config(config: &SshConfig) -> Result<()> {
-    if config.host.trim().is_empty() {
-        return Err(Error::Parse("SSH host is required".to_string()));
+    validate_ssh_hop(&config.target_hop(), "SSH")?;
+    if config.local_bind_host.trim().is_empty() {
+        return Err(Error::Parse("SSH local bind host is required".to_string()));
     }
-    if config.username.trim().is_empty() {
-        return Err(Error::Parse("SSH username is required".to_string()));
+    for (index, jump) in config.jump_hosts.iter().enumerate() {
+        validate_ssh_hop(jump, &format!("SSH jump host #{}", index + 1))?;
     }
-    if config.port == 0 {
-        return Err(Error::Parse("SSH port must be greater than 0".to_string()));
+    Ok(())
+}
+
+/// Validate one hop; `label` prefixes every error ("SSH", "SSH jump host #1").
+fn validate_ssh_hop(hop: &SshJumpHost, label: &str) -> Result<()> {
+    if hop.host.trim().is_empty() {
+        return Err(Error::Parse(format!("{label} host is required")));
     }
-    if config.local_bind_host.trim().is_empty() {
-        return Err(Error::Parse("SSH local bind host is required".to_string()));
+    if hop.username.trim().is_empty() {
+        return Err(Error::Parse(format!("{label} username is required")));
+    }
+    if hop.port == 0 {
+        return Err(Error::Parse(format!("{label} port must be greater than 0")));
     }
 
-    match config.auth {
+    match hop.auth {
         SshAuth::Password => {
-            let password = config.password.as_deref().unwrap_or_default();
+            let password = hop.password.as_deref().unwrap_or_default();
             if password.trim().is_empty() {
-                return Err(Error::Parse(
-                    "SSH password is required for password authentication".to_string(),
-                ));
+                return Err(Error::Parse(format!(
+                    "{label} password is required for password authentication"
+                )));
             }
         }
         SshAuth::IdentityFile => {
-            let identity_file = config.identity_file.as_deref().ok_or_else(|| {
-                Error::Parse(
-                    "SSH identity file path is required for identity-file authentication"
-                        .to_string(),
-                )
+            let identity_file = hop.identity_file.as_deref().ok_or_else(|| {
+                Error::Parse(format!(
+                    "{label} identity file path is required for identity-file authentication"
+                ))
             })?;
             let identity_path = resolve_identity_file_path(identity_file);
             if !identity_path.exists() {
                 return Err(Error::Parse(format!(
-                    "SSH identity file does not exist: {}",
+                    "{label} identity file does not exist: {}",
                     identity_path.display()
                 )));
             }
             if !identity_path.is_file() {
                 return Err(Error::Parse(format!(
-                    "SSH identity file path is not a file: {}",
+                    "{label} identity file path is not a file: {}",
                     identity_path.display()
                 )));
             }
         }
+        SshAuth::Agent =>
+        {
+            #[cfg(unix)]
+            if std::env::var_os("SSH_AUTH_SOCK").is_none_or(|sock| sock.is_empty()) {
+                return Err(Error::Parse(format!(
+                    "{label} agent authentication requires a running SSH agent (SSH_AUTH_SOCK is not set)"
+                )));
+            }
+        }
     }
 
     Ok(())
@@ -663,3 +1018,135 @@ fn save_host_key_store(store: &HostKeyStore) -> Result<()> {
     fs::write(path, serialized)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    use super::{connect_via_socks5, validate_ssh_config};
+    use crate::models::{ProxyConfig, ProxyKind, SshAuth, SshConfig, SshJumpHost};
+
+    fn proxy_config(port: u16, username: Option<&str>, password: Option<&str>) -> ProxyConfig {
+        ProxyConfig {
+            enabled: true,
+            kind: ProxyKind::Socks5,
+            host: "127.0.0.1".to_string(),
+            port,
+            username: username.map(str::to_string),
+            password: password.map(str::to_string),
+        }
+    }
+
+    /// Minimal SOCKS5 server that requires username/password auth and records
+    /// the CONNECT target it was asked for.
+    fn spawn_auth_proxy(
+        expected_user: &'static str,
+        expected_pass: &'static str,
+    ) -> (u16, thread::JoinHandle<Option<(String, u16)>>) {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut greeting = [0u8; 2];
+            stream.read_exact(&mut greeting).unwrap();
+            let mut methods = vec![0u8; greeting[1] as usize];
+            stream.read_exact(&mut methods).unwrap();
+            if !methods.contains(&0x02) {
+                stream.write_all(&[0x05, 0xFF]).unwrap();
+                return None;
+            }
+            stream.write_all(&[0x05, 0x02]).unwrap();
+
+            let mut head = [0u8; 2];
+            stream.read_exact(&mut head).unwrap();
+            let mut user = vec![0u8; head[1] as usize];
+            stream.read_exact(&mut user).unwrap();
+            let mut plen = [0u8; 1];
+            stream.read_exact(&mut plen).unwrap();
+            let mut pass = vec![0u8; plen[0] as usize];
+            stream.read_exact(&mut pass).unwrap();
+            if user != expected_user.as_bytes() || pass != expected_pass.as_bytes() {
+                stream.write_all(&[0x01, 0x01]).unwrap();
+                return None;
+            }
+            stream.write_all(&[0x01, 0x00]).unwrap();
+
+            let mut request = [0u8; 5];
+            stream.read_exact(&mut request).unwrap();
+            assert_eq!(request[3], 0x03, "domain targets should use ATYP=0x03");
+            let mut host = vec![0u8; request[4] as usize];
+            stream.read_exact(&mut host).unwrap();
+            let mut port_buf = [0u8; 2];
+            stream.read_exact(&mut port_buf).unwrap();
+            stream.write_all(&[0x05, 0x00, 0x00, 0x01, 127, 0, 0, 1, 0, 22]).unwrap();
+            stream.write_all(b"SSH-2.0-test\r\n").unwrap();
+            Some((String::from_utf8(host).unwrap(), u16::from_be_bytes(port_buf)))
+        });
+        (port, handle)
+    }
+
+    #[test]
+    fn socks5_client_authenticates_and_connects() {
+        let (port, handle) = spawn_auth_proxy("alice", "s3cret");
+        let proxy = proxy_config(port, Some("alice"), Some("s3cret"));
+
+        let mut stream = connect_via_socks5(&proxy, "bastion.internal", 2222)
+            .expect("SOCKS5 handshake should succeed");
+        let mut banner = [0u8; 14];
+        stream.read_exact(&mut banner).unwrap();
+
+        assert_eq!(&banner, b"SSH-2.0-test\r\n");
+        assert_eq!(handle.join().unwrap(), Some(("bastion.internal".to_string(), 2222)));
+    }
+
+    #[test]
+    fn socks5_client_reports_auth_failure() {
+        let (port, handle) = spawn_auth_proxy("alice", "s3cret");
+        let proxy = proxy_config(port, Some("alice"), Some("wrong"));
+
+        let err = connect_via_socks5(&proxy, "bastion.internal", 22)
+            .expect_err("wrong proxy password should fail");
+
+        assert!(err.to_string().contains("SOCKS5 proxy authentication failed"));
+        assert_eq!(handle.join().unwrap(), None);
+    }
+
+    #[test]
+    fn socks5_client_requires_credentials_when_proxy_demands_them() {
+        let (port, handle) = spawn_auth_proxy("alice", "s3cret");
+        let proxy = proxy_config(port, None, None);
+
+        let err = connect_via_socks5(&proxy, "bastion.internal", 22)
+            .expect_err("missing credentials should fail");
+
+        assert!(err.to_string().contains("rejected all offered authentication methods"));
+        assert_eq!(handle.join().unwrap(), None);
+    }
+
+    #[test]
+    fn validate_reports_which_jump_host_is_misconfigured() {
+        let config = SshConfig {
+            enabled: true,
+            host: "db-bastion".to_string(),
+            username: "ops".to_string(),
+            auth: SshAuth::Password,
+            password: Some("secret".to_string()),
+            jump_hosts: vec![
+                SshJumpHost {
+                    host: "edge".to_string(),
+                    username: "ops".to_string(),
+                    password: Some("secret".to_string()),
+                    ..SshJumpHost::default()
+                },
+                SshJumpHost { host: "inner".to_string(), ..SshJumpHost::default() },
+            ],
+            ..SshConfig::default()
+        };
+
+        let err = validate_ssh_config(&config).expect_err("second hop has no username");
+
+        assert_eq!(err.to_string(), "Parse error: SSH jump host #2 username is required");
+    }
+}
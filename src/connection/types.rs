@@ -244,6 +244,195 @@ pub struct FindDocumentsOptions {
     pub projection: Option<mongodb::bson::Document>,
     pub skip: u64,
     pub limit: i64,
+    pub count: CountStrategy,
+    /// Page relative to this cursor instead of `skip` (keyset pagination).
+    pub keyset: Option<crate::connection::keyset::KeysetCursor>,
+    /// Hint, collation, read preference etc., also applied to the count.
+    pub options: crate::connection::QueryOptions,
+}
+
+/// State of one document before and after a write, keyed by `_id`.
+/// `None` means the document did not exist (inserted or deleted).
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocumentChange {
+    pub id: mongodb::bson::Bson,
+    pub before: Option<mongodb::bson::Document>,
+    pub after: Option<mongodb::bson::Document>,
+}
+
+impl DocumentChange {
+    /// Pair pre-images with post-images by `_id`. Documents present on only
+    /// one side were inserted or deleted; unchanged documents are dropped.
+    pub fn diff(
+        before: Vec<mongodb::bson::Document>,
+        after: Vec<mongodb::bson::Document>,
+    ) -> Vec<Self> {
+        let mut after: Vec<Option<mongodb::bson::Document>> = after.into_iter().map(Some).collect();
+        let mut changes = Vec::new();
+        for doc in before {
+            let Some(id) = doc.get("_id").cloned() else {
+                continue;
+            };
+            let post = after
+                .iter_mut()
+                .find(|post| post.as_ref().and_then(|post| post.get("_id")) == Some(&id))
+                .and_then(Option::take);
+            if post.as_ref() != Some(&doc) {
+                changes.push(Self { id, before: Some(doc), after: post });
+            }
+        }
+        changes.extend(after.into_iter().flatten().filter_map(|doc| {
+            let id = doc.get("_id").cloned()?;
+            Some(Self { id, before: None, after: Some(doc) })
+        }));
+        changes
+    }
+
+    /// The change that undoes this one.
+    pub fn inverse(&self) -> Self {
+        Self { id: self.id.clone(), before: self.after.clone(), after: self.before.clone() }
+    }
+}
+
+/// Result of a replace guarded by a [`ConcurrencyCheck`](crate::connection::ConcurrencyCheck).
+#[derive(Debug, Clone, PartialEq)]
+pub enum SaveOutcome {
+    Saved,
+    /// The document changed on the server since it was loaded. Holds the
+    /// current server copy, `None` if it was deleted.
+    Conflict(Option<mongodb::bson::Document>),
+}
+
+/// Result of replaying journaled changes with
+/// [`apply_document_changes`](crate::connection::ConnectionManager::apply_document_changes).
+#[derive(Debug)]
+pub enum ReplayOutcome {
+    /// Every change was written.
+    Applied,
+    /// Without a transaction a write failed after others went through: the
+    /// documents in `applied` were written, the rest were not.
+    Partial { applied: Vec<mongodb::bson::Bson>, error: crate::error::Error },
+}
+
+/// One page of documents from `find_documents`.
+#[derive(Debug, Clone)]
+pub struct DocumentPage {
+    pub documents: Vec<mongodb::bson::Document>,
+    pub count: DocumentCount,
+    /// Whether another page follows this one.
+    pub has_more: bool,
+}
+
+/// How the document browser moves between pages.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PaginationMode {
+    /// `skip = page * per_page`; allows jumping but slows down on deep pages.
+    #[default]
+    Offset,
+    /// Continue after the last document's sort key and `_id`; constant cost per page.
+    Keyset,
+    /// One open cursor read further as the view scrolls; see
+    /// [`DocumentStream`](crate::connection::DocumentStream).
+    Stream,
+}
+
+impl PaginationMode {
+    pub fn label(self) -> &'static str {
+        match self {
+            PaginationMode::Offset => "Offset (skip)",
+            PaginationMode::Keyset => "Keyset (cursor)",
+            PaginationMode::Stream => "Stream (infinite scroll)",
+        }
+    }
+}
+
+/// How `find_documents` counts the documents matching the filter.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CountStrategy {
+    /// `count_documents(filter)` on every page load.
+    #[default]
+    Exact,
+    /// `estimated_document_count` from collection metadata. Filtered queries fall
+    /// back to a count capped at [`ESTIMATED_FILTER_COUNT_CAP`].
+    Estimated,
+    /// `count_documents` that stops after the given number of matches.
+    Capped(u64),
+    /// Skip counting until the user asks for it.
+    Lazy,
+}
+
+/// Cap applied when an estimated count is requested for a filtered query.
+pub const ESTIMATED_FILTER_COUNT_CAP: u64 = 10_000;
+
+impl CountStrategy {
+    pub fn label(self) -> String {
+        match self {
+            CountStrategy::Exact => "Exact count".to_string(),
+            CountStrategy::Estimated => "Estimated count".to_string(),
+            CountStrategy::Capped(cap) => {
+                format!("Count up to {}", crate::helpers::format_number(cap))
+            }
+            CountStrategy::Lazy => "Count on demand".to_string(),
+        }
+    }
+}
+
+/// Document total as far as the count strategy determined it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DocumentCount {
+    Exact(u64),
+    Estimated(u64),
+    /// A capped count reached its cap.
+    AtLeast(u64),
+    /// Not counted yet.
+    Unknown,
+}
+
+impl Default for DocumentCount {
+    fn default() -> Self {
+        DocumentCount::Exact(0)
+    }
+}
+
+impl DocumentCount {
+    /// The counted number, when there is one.
+    pub fn value(self) -> Option<u64> {
+        match self {
+            DocumentCount::Exact(n) | DocumentCount::Estimated(n) | DocumentCount::AtLeast(n) => {
+                Some(n)
+            }
+            DocumentCount::Unknown => None,
+        }
+    }
+
+    /// Total usable for page math; `None` when the count is only a lower bound.
+    pub fn page_total(self) -> Option<u64> {
+        match self {
+            DocumentCount::Exact(n) | DocumentCount::Estimated(n) => Some(n),
+            DocumentCount::AtLeast(_) | DocumentCount::Unknown => None,
+        }
+    }
+
+    /// Account for one deleted document.
+    pub fn decrement(self) -> Self {
+        match self {
+            DocumentCount::Exact(n) => DocumentCount::Exact(n.saturating_sub(1)),
+            DocumentCount::Estimated(n) => DocumentCount::Estimated(n.saturating_sub(1)),
+            other => other,
+        }
+    }
+}
+
+impl std::fmt::Display for DocumentCount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use crate::helpers::format_number;
+        match *self {
+            DocumentCount::Exact(n) => write!(f, "{}", format_number(n)),
+            DocumentCount::Estimated(n) => write!(f, "~{}", format_number(n)),
+            DocumentCount::AtLeast(n) => write!(f, "{}+", format_number(n)),
+            DocumentCount::Unknown => write!(f, "?"),
+        }
+    }
 }
 
 /// Progress information from mongodump/mongorestore tools.
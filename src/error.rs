@@ -27,6 +27,12 @@ pub enum Error {
 
     #[error("Tool not found: {0}")]
     ToolNotFound(String),
+
+    #[error("Connection is read-only: {0} is not allowed")]
+    ReadOnly(String),
+
+    #[error("{0}")]
+    Conflict(String),
 }
 
 /// Convenience Result type using our Error
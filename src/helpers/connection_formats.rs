@@ -0,0 +1,592 @@
+//! Connection files written by other MongoDB tools.
+//!
+//! - MongoDB Compass: the JSON produced by "Export saved connections".
+//! - Studio 3T: connection URIs with its `3t.*` options (one per line).
+//! - mongosh: shell command lines such as `mongosh "mongodb://..." -u admin --tls`.
+//!
+//! Each entry is mapped to an [`ExportedConnection`] so the regular import
+//! conflict handling applies. Secrets are carried over only when the file
+//! contains them; Compass keeps passphrase-protected secrets in an encrypted
+//! blob that cannot be read here.
+
+use anyhow::{Result, bail};
+use serde::Deserialize;
+
+use crate::helpers::{extract_host_from_uri, extract_uri_password};
+use crate::models::{AuthConfig, AuthMechanism, ProxyConfig, SshAuth, SshConfig, TlsConfig};
+
+use super::connection_io::ExportedConnection;
+
+const COMPASS_EXPORT_TYPE: &str = "Compass Connections";
+const STUDIO3T_PREFIX: &str = "3t.";
+const FALLBACK_NAME: &str = "Imported connection";
+
+/// Tool that wrote a foreign connections file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForeignFormat {
+    Compass,
+    Studio3t,
+    Mongosh,
+}
+
+impl ForeignFormat {
+    /// Value recorded as the import file's `app`.
+    pub fn app_name(self) -> &'static str {
+        match self {
+            ForeignFormat::Compass => "compass",
+            ForeignFormat::Studio3t => "studio3t",
+            ForeignFormat::Mongosh => "mongosh",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CompassExport {
+    #[serde(default)]
+    connections: Vec<CompassConnection>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CompassConnection {
+    connection_options: CompassConnectionOptions,
+    #[serde(default)]
+    favorite: Option<CompassFavorite>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CompassConnectionOptions {
+    connection_string: String,
+    #[serde(default)]
+    ssh_tunnel: Option<CompassSshTunnel>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CompassSshTunnel {
+    #[serde(default)]
+    host: String,
+    #[serde(default)]
+    port: Option<u16>,
+    #[serde(default)]
+    username: Option<String>,
+    #[serde(default)]
+    password: Option<String>,
+    #[serde(default)]
+    identity_key_file: Option<String>,
+    #[serde(default)]
+    identity_key_passphrase: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CompassFavorite {
+    #[serde(default)]
+    name: Option<String>,
+}
+
+/// Whether a parsed JSON document is a Compass connections export.
+pub fn is_compass_export(value: &serde_json::Value) -> bool {
+    if value.get("type").and_then(|kind| kind.as_str()) == Some(COMPASS_EXPORT_TYPE) {
+        return true;
+    }
+    value
+        .get("connections")
+        .and_then(|connections| connections.as_array())
+        .and_then(|connections| connections.first())
+        .is_some_and(|first| first.get("connectionOptions").is_some())
+}
+
+/// Map a Compass export. Proxy options are moved out of the URI into `ProxyConfig`.
+pub fn parse_compass(value: serde_json::Value) -> Result<Vec<ExportedConnection>> {
+    let export: CompassExport = serde_json::from_value(value)?;
+    let connections = export
+        .connections
+        .into_iter()
+        .map(|entry| {
+            let options = entry.connection_options;
+            let (uri, proxy) = extract_proxy(&options.connection_string);
+            let name = entry
+                .favorite
+                .and_then(|favorite| favorite.name)
+                .filter(|name| !name.trim().is_empty())
+                .unwrap_or_else(|| default_name(&uri));
+            let mut conn = ExportedConnection::new(name, uri);
+            conn.proxy = proxy;
+            conn.ssh = options.ssh_tunnel.filter(|ssh| !ssh.host.trim().is_empty()).map(|ssh| {
+                let identity_file = non_empty(ssh.identity_key_file);
+                SshConfig {
+                    enabled: true,
+                    host: ssh.host.trim().to_string(),
+                    port: ssh.port.unwrap_or(SshConfig::default().port),
+                    username: ssh.username.unwrap_or_default(),
+                    auth: if identity_file.is_some() {
+                        SshAuth::IdentityFile
+                    } else {
+                        SshAuth::Password
+                    },
+                    password: non_empty(ssh.password),
+                    identity_file,
+                    identity_passphrase: non_empty(ssh.identity_key_passphrase),
+                    ..SshConfig::default()
+                }
+            });
+            conn
+        })
+        .collect();
+    Ok(connections)
+}
+
+/// Parse Studio 3T URIs and mongosh command lines, one connection per line.
+///
+/// Blank lines and `#`/`//` comments are ignored, as is any line without a
+/// MongoDB URI or `mongosh` invocation.
+pub fn parse_text(contents: &str) -> Result<(ForeignFormat, Vec<ExportedConnection>)> {
+    let mut format = None;
+    let mut connections = Vec::new();
+
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with("//") {
+            continue;
+        }
+        let (line_format, conn) = if is_shell_command(line) {
+            (ForeignFormat::Mongosh, parse_mongosh_command(line)?)
+        } else if let Some(uri) = find_uri(line) {
+            let studio3t = uri.contains(&format!("{STUDIO3T_PREFIX}uriVersion"))
+                || uri.contains(&format!("{STUDIO3T_PREFIX}connection.name"));
+            let kind = if studio3t { ForeignFormat::Studio3t } else { ForeignFormat::Mongosh };
+            (kind, parse_studio3t_uri(&uri))
+        } else {
+            continue;
+        };
+        // Any Studio 3T line marks the file as a Studio 3T export.
+        if format != Some(ForeignFormat::Studio3t) {
+            format = Some(line_format);
+        }
+        connections.push(conn);
+    }
+
+    match format {
+        Some(format) => Ok((format, connections)),
+        None => bail!("no MongoDB connection strings or mongosh commands found"),
+    }
+}
+
+/// Map a Studio 3T URI: `3t.*` options become the name, SSH and proxy settings.
+fn parse_studio3t_uri(uri: &str) -> ExportedConnection {
+    let (uri, options) = take_query_params(uri, |key| key.starts_with(STUDIO3T_PREFIX));
+    let (uri, proxy) = extract_proxy(&uri);
+    let option = |key: &str| {
+        options
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(&format!("{STUDIO3T_PREFIX}{key}")))
+            // Studio 3T encodes its options form-style, with `+` for spaces.
+            .map(|(_, value)| percent_decode(&value.replace('+', " ")).trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+
+    let name = option("connection.name").unwrap_or_else(|| default_name(&uri));
+    let mut conn = ExportedConnection::new(name, uri);
+    conn.proxy = proxy;
+
+    if let Some(host) = option("sshAddress") {
+        let identity_file = option("sshPKPath");
+        let auth = match option("sshAuthMode").map(|mode| mode.to_ascii_lowercase()) {
+            Some(mode) if mode.contains("agent") => SshAuth::Agent,
+            Some(mode) if mode.contains("key") => SshAuth::IdentityFile,
+            None if identity_file.is_some() => SshAuth::IdentityFile,
+            _ => SshAuth::Password,
+        };
+        conn.ssh = Some(SshConfig {
+            enabled: true,
+            host,
+            port: option("sshPort")
+                .and_then(|port| port.parse().ok())
+                .unwrap_or(SshConfig::default().port),
+            username: option("sshUser").unwrap_or_default(),
+            auth,
+            password: option("sshPassword"),
+            identity_file,
+            identity_passphrase: option("sshPKPassphrase"),
+            ..SshConfig::default()
+        });
+    }
+
+    let proxy_type = option("proxyType").map(|kind| kind.to_ascii_lowercase());
+    if conn.proxy.is_none()
+        && proxy_type.is_some_and(|kind| kind.contains("socks"))
+        && let Some(host) = option("proxyHost")
+    {
+        conn.proxy = Some(ProxyConfig {
+            enabled: true,
+            host,
+            port: option("proxyPort")
+                .and_then(|port| port.parse().ok())
+                .unwrap_or(ProxyConfig::default().port),
+            username: option("proxyUser"),
+            password: option("proxyPassword"),
+            ..ProxyConfig::default()
+        });
+    }
+
+    conn
+}
+
+fn is_shell_command(line: &str) -> bool {
+    let program = line.split_whitespace().next().unwrap_or_default();
+    let program = program.rsplit(['/', '\\']).next().unwrap_or(program);
+    matches!(program, "mongosh" | "mongosh.exe" | "mongo" | "mongo.exe")
+}
+
+/// Map a `mongosh` invocation; credentials and TLS flags become typed settings.
+fn parse_mongosh_command(line: &str) -> Result<ExportedConnection> {
+    let args = shell_words(line);
+    let mut positional = None;
+    let mut host = None;
+    let mut port = None;
+    let mut username = None;
+    let mut password = None;
+    let mut auth_source = None;
+    let mut mechanism = None;
+    let mut tls = TlsConfig::default();
+
+    let mut iter = args.into_iter().skip(1);
+    while let Some(arg) = iter.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if arg.starts_with("--") => {
+                (flag.to_string(), Some(value.to_string()))
+            }
+            _ => (arg.clone(), None),
+        };
+        let mut value = || inline.clone().or_else(|| iter.next());
+        match flag.as_str() {
+            "--host" => host = value(),
+            "--port" => port = value(),
+            "-u" | "--username" => username = value(),
+            "-p" | "--password" => password = value(),
+            "--authenticationDatabase" => auth_source = value(),
+            "--authenticationMechanism" => mechanism = value(),
+            "--tls" | "--ssl" => tls.enabled = true,
+            "--tlsCAFile" | "--sslCAFile" => {
+                tls.enabled = true;
+                tls.ca_file = value();
+            }
+            "--tlsCertificateKeyFile" | "--sslPEMKeyFile" => {
+                tls.enabled = true;
+                tls.cert_key_file = value();
+            }
+            "--tlsCertificateKeyFilePassword" | "--sslPEMKeyPassword" => {
+                tls.cert_key_password = value();
+            }
+            "--tlsAllowInvalidHostnames" | "--sslAllowInvalidHostnames" => {
+                tls.allow_invalid_hostnames = true;
+            }
+            "--tlsAllowInvalidCertificates" | "--sslAllowInvalidCertificates" => {
+                tls.insecure = true;
+            }
+            "--eval" | "-f" | "--file" | "--apiVersion" => {
+                value();
+            }
+            _ if !flag.starts_with('-') && positional.is_none() => positional = Some(arg),
+            _ => {}
+        }
+    }
+
+    let uri = match (positional, host) {
+        (Some(target), _) if target.contains("://") => target,
+        (Some(target), _) => format!("mongodb://{target}"),
+        (None, Some(host)) => mongosh_host_uri(&host, port.as_deref()),
+        (None, None) => mongosh_host_uri("localhost", port.as_deref()),
+    };
+    let (uri, proxy) = extract_proxy(&uri);
+
+    let mut conn = ExportedConnection::new(default_name(&uri), uri);
+    conn.proxy = proxy;
+    let mechanism = match mechanism {
+        Some(name) => AuthMechanism::from_uri_name(&name)
+            .ok_or_else(|| anyhow::anyhow!("unsupported authentication mechanism {name}"))?,
+        None => AuthMechanism::default(),
+    };
+    if username.is_some() || mechanism != AuthMechanism::default() {
+        conn.auth = Some(AuthConfig {
+            mechanism,
+            username: username.unwrap_or_default(),
+            auth_source: non_empty(auth_source),
+            password: non_empty(password),
+        });
+    }
+    if tls != TlsConfig::default() {
+        conn.tls = Some(tls);
+    }
+    Ok(conn)
+}
+
+/// `--host` accepts `host[:port]`, a comma-separated seed list, or `setName/seeds`.
+fn mongosh_host_uri(host: &str, port: Option<&str>) -> String {
+    let (set_name, seeds) = match host.split_once('/') {
+        Some((set_name, seeds)) => (Some(set_name), seeds),
+        None => (None, host),
+    };
+    let seeds = seeds
+        .split(',')
+        .map(|seed| match port {
+            Some(port) if !seed.contains(':') => format!("{seed}:{port}"),
+            _ => seed.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    match set_name {
+        Some(set_name) => format!("mongodb://{seeds}/?replicaSet={set_name}"),
+        None => format!("mongodb://{seeds}/"),
+    }
+}
+
+/// Split a command line, honouring single/double quotes and backslash escapes.
+fn shell_words(line: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut quote = None;
+    let mut chars = line.chars();
+
+    while let Some(ch) = chars.next() {
+        match (quote, ch) {
+            (Some(open), ch) if ch == open => quote = None,
+            (Some('"') | None, '\\') => {
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+                in_word = true;
+            }
+            (Some(_), ch) => current.push(ch),
+            (None, '\'' | '"') => {
+                quote = Some(ch);
+                in_word = true;
+            }
+            (None, ch) if ch.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            (None, ch) => {
+                current.push(ch);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        words.push(current);
+    }
+    words
+}
+
+/// First `mongodb://` / `mongodb+srv://` URI on a line, without surrounding quotes.
+fn find_uri(line: &str) -> Option<String> {
+    let start = line.find("mongodb://").or_else(|| line.find("mongodb+srv://"))?;
+    let uri = &line[start..];
+    let end = uri.find(|ch: char| ch.is_whitespace() || matches!(ch, '"' | '\'' | '`'));
+    Some(uri[..end.unwrap_or(uri.len())].to_string())
+}
+
+/// Move `proxyHost`/`proxyPort`/`proxyUsername`/`proxyPassword` out of the URI.
+fn extract_proxy(uri: &str) -> (String, Option<ProxyConfig>) {
+    let (stripped, params) = take_query_params(uri, |key| {
+        ["proxyHost", "proxyPort", "proxyUsername", "proxyPassword"]
+            .iter()
+            .any(|name| key.eq_ignore_ascii_case(name))
+    });
+    let value = |key: &str| {
+        params
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(key))
+            .map(|(_, value)| percent_decode(value))
+            .filter(|value| !value.is_empty())
+    };
+    let Some(host) = value("proxyHost") else {
+        return (uri.to_string(), None);
+    };
+    let proxy = ProxyConfig {
+        enabled: true,
+        host,
+        port: value("proxyPort")
+            .and_then(|port| port.parse().ok())
+            .unwrap_or(ProxyConfig::default().port),
+        username: value("proxyUsername"),
+        password: value("proxyPassword"),
+        ..ProxyConfig::default()
+    };
+    (stripped, Some(proxy))
+}
+
+/// Remove query options whose key matches, returning the URI and their raw values.
+fn take_query_params(uri: &str, matches: impl Fn(&str) -> bool) -> (String, Vec<(String, String)>) {
+    let Some((base, query)) = uri.split_once('?') else {
+        return (uri.to_string(), Vec::new());
+    };
+    let mut kept = Vec::new();
+    let mut taken = Vec::new();
+    for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        if matches(key) {
+            taken.push((key.to_string(), value.to_string()));
+        } else {
+            kept.push(pair);
+        }
+    }
+    let uri = if kept.is_empty() { base.to_string() } else { format!("{base}?{}", kept.join("&")) };
+    (uri, taken)
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%'
+            && let Some(hex) = value.get(index + 1..index + 3)
+            && let Ok(byte) = u8::from_str_radix(hex, 16)
+        {
+            out.push(byte);
+            index += 3;
+            continue;
+        }
+        out.push(bytes[index]);
+        index += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn default_name(uri: &str) -> String {
+    extract_host_from_uri(uri).unwrap_or_else(|| FALLBACK_NAME.to_string())
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|value| !value.trim().is_empty())
+}
+
+/// Whether any imported entry carries a password or passphrase.
+pub fn has_secrets(connections: &[ExportedConnection]) -> bool {
+    connections.iter().any(|conn| {
+        extract_uri_password(&conn.uri).is_some()
+            || conn
+                .ssh
+                .as_ref()
+                .is_some_and(|ssh| ssh.password.is_some() || ssh.identity_passphrase.is_some())
+            || conn.proxy.as_ref().is_some_and(|proxy| proxy.password.is_some())
+            || conn.auth.as_ref().is_some_and(|auth| auth.password.is_some())
+            || conn.tls.as_ref().is_some_and(|tls| tls.cert_key_password.is_some())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compass_export_maps_ssh_and_proxy() {
+        let json = r#"{
+            "type": "Compass Connections",
+            "version": { "$numberInt": "1" },
+            "connections": [
+                {
+                    "id": "6f1c",
+                    "connectionOptions": {
+                        "connectionString": "mongodb://app:pw@db.internal:27017/?authSource=admin&proxyHost=socks.corp&proxyPort=1081&proxyUsername=bob&proxyPassword=p%40ss",
+                        "sshTunnel": {
+                            "host": "bastion.corp",
+                            "port": 2222,
+                            "username": "ec2-user",
+                            "identityKeyFile": "/home/me/.ssh/id_ed25519"
+                        }
+                    },
+                    "favorite": { "name": "Prod", "color": "color1" },
+                    "savedConnectionType": "favorite"
+                },
+                {
+                    "id": "7a2d",
+                    "connectionOptions": { "connectionString": "mongodb://localhost:27017" },
+                    "savedConnectionType": "recent"
+                }
+            ]
+        }"#;
+        let value: serde_json::Value = serde_json::from_str(json).unwrap();
+        assert!(is_compass_export(&value));
+
+        let conns = parse_compass(value).unwrap();
+        assert_eq!(conns.len(), 2);
+        let prod = &conns[0];
+        assert_eq!(prod.name, "Prod");
+        assert_eq!(prod.uri, "mongodb://app:pw@db.internal:27017/?authSource=admin");
+        let proxy = prod.proxy.as_ref().unwrap();
+        assert!(proxy.enabled);
+        assert_eq!((proxy.host.as_str(), proxy.port), ("socks.corp", 1081));
+        assert_eq!(proxy.username.as_deref(), Some("bob"));
+        assert_eq!(proxy.password.as_deref(), Some("p@ss"));
+        let ssh = prod.ssh.as_ref().unwrap();
+        assert!(ssh.enabled);
+        assert_eq!((ssh.host.as_str(), ssh.port), ("bastion.corp", 2222));
+        assert_eq!(ssh.auth, SshAuth::IdentityFile);
+        assert_eq!(ssh.identity_file.as_deref(), Some("/home/me/.ssh/id_ed25519"));
+
+        assert_eq!(conns[1].name, "localhost");
+        assert!(conns[1].ssh.is_none() && conns[1].proxy.is_none());
+        assert!(has_secrets(&conns));
+    }
+
+    #[test]
+    fn studio3t_uri_maps_name_ssh_and_proxy() {
+        let contents = "\
+# exported from Studio 3T
+mongodb://db1:27017,db2:27017/?replicaSet=rs0&3t.uriVersion=3&3t.connection.name=Staging+RS&3t.sshAddress=jump.corp&3t.sshPort=22&3t.sshUser=ops&3t.sshAuthMode=privateKey&3t.sshPKPath=%2Fkeys%2Fops.pem&3t.proxyType=socks5&3t.proxyHost=proxy.corp&3t.proxyPort=1080
+";
+        let (format, conns) = parse_text(contents).unwrap();
+        assert_eq!(format, ForeignFormat::Studio3t);
+        assert_eq!(conns.len(), 1);
+        let conn = &conns[0];
+        assert_eq!(conn.name, "Staging RS");
+        assert_eq!(conn.uri, "mongodb://db1:27017,db2:27017/?replicaSet=rs0");
+        let ssh = conn.ssh.as_ref().unwrap();
+        assert_eq!((ssh.host.as_str(), ssh.username.as_str()), ("jump.corp", "ops"));
+        assert_eq!(ssh.auth, SshAuth::IdentityFile);
+        assert_eq!(ssh.identity_file.as_deref(), Some("/keys/ops.pem"));
+        let proxy = conn.proxy.as_ref().unwrap();
+        assert_eq!((proxy.host.as_str(), proxy.port), ("proxy.corp", 1080));
+        assert!(!has_secrets(&conns));
+    }
+
+    #[test]
+    fn mongosh_commands_map_auth_and_tls() {
+        let contents = r#"
+mongosh "mongodb+srv://cluster0.example.net/app" --username alice --password 's3cr3t' --authenticationDatabase admin
+mongosh --host rs0/a.corp,b.corp --port 27018 --tls --tlsCAFile=/etc/ca.pem --tlsAllowInvalidHostnames -u bob --authenticationMechanism SCRAM-SHA-256
+"#;
+        let (format, conns) = parse_text(contents).unwrap();
+        assert_eq!(format, ForeignFormat::Mongosh);
+        assert_eq!(conns.len(), 2);
+
+        assert_eq!(conns[0].uri, "mongodb+srv://cluster0.example.net/app");
+        assert_eq!(conns[0].name, "cluster0.example.net");
+        let auth = conns[0].auth.as_ref().unwrap();
+        assert_eq!(auth.username, "alice");
+        assert_eq!(auth.password.as_deref(), Some("s3cr3t"));
+        assert_eq!(auth.auth_source.as_deref(), Some("admin"));
+        assert!(conns[0].tls.is_none());
+
+        assert_eq!(conns[1].uri, "mongodb://a.corp:27018,b.corp:27018/?replicaSet=rs0");
+        let auth = conns[1].auth.as_ref().unwrap();
+        assert_eq!(auth.mechanism, AuthMechanism::ScramSha256);
+        let tls = conns[1].tls.as_ref().unwrap();
+        assert!(tls.enabled);
+        assert_eq!(tls.ca_file.as_deref(), Some("/etc/ca.pem"));
+        assert!(tls.allow_invalid_hostnames);
+        assert!(!tls.insecure);
+    }
+
+    #[test]
+    fn text_without_connections_is_rejected() {
+        assert!(parse_text("# nothing here\n\nfoo bar\n").is_err());
+    }
+}
@@ -5,8 +5,11 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::helpers::{extract_uri_password, inject_uri_password, redact_uri_password};
-use crate::models::{ProxyConfig, SavedConnection, SshConfig};
+use crate::models::{
+    AuthConfig, ConnectionEnvironment, ProxyConfig, SavedConnection, SshConfig, TlsConfig,
+};
 
+use super::connection_formats::{self, ForeignFormat};
 use super::crypto;
 
 const CURRENT_VERSION: u32 = 1;
@@ -33,6 +36,36 @@ pub struct ExportedConnection {
     pub ssh: Option<SshConfig>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub proxy: Option<ProxyConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls: Option<TlsConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth: Option<AuthConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub folder: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<ConnectionEnvironment>,
+}
+
+impl ExportedConnection {
+    /// An entry with only a name and URI set.
+    pub fn new(name: String, uri: String) -> Self {
+        Self {
+            name,
+            uri,
+            read_only: false,
+            encrypted_password: None,
+            encrypted_transport: None,
+            ssh: None,
+            proxy: None,
+            tls: None,
+            auth: None,
+            folder: None,
+            tags: Vec::new(),
+            environment: None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -43,6 +76,21 @@ struct TransportSecrets {
     ssh_identity_passphrase: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     proxy_password: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tls_cert_key_password: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    auth_password: Option<String>,
+    /// Per jump host, in `SshConfig::jump_hosts` order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    ssh_jump_hosts: Vec<JumpHostSecrets>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct JumpHostSecrets {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    password: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    identity_passphrase: Option<String>,
 }
 
 impl TransportSecrets {
@@ -50,6 +98,12 @@ impl TransportSecrets {
         self.ssh_password.as_deref().is_some_and(|v| !v.trim().is_empty())
             || self.ssh_identity_passphrase.as_deref().is_some_and(|v| !v.trim().is_empty())
             || self.proxy_password.as_deref().is_some_and(|v| !v.trim().is_empty())
+            || self.tls_cert_key_password.as_deref().is_some_and(|v| !v.trim().is_empty())
+            || self.auth_password.as_deref().is_some_and(|v| !v.is_empty())
+            || self.ssh_jump_hosts.iter().any(|hop| {
+                hop.password.as_deref().is_some_and(|v| !v.trim().is_empty())
+                    || hop.identity_passphrase.as_deref().is_some_and(|v| !v.trim().is_empty())
+            })
     }
 }
 
@@ -71,18 +125,25 @@ pub fn build_export(
     let mut exported = Vec::with_capacity(connections.len());
 
     for conn in connections {
-        let (sanitized_ssh, sanitized_proxy, transport_secrets) =
-            sanitize_transport(conn.ssh.clone(), conn.proxy.clone());
+        let full = ExportedConnection {
+            name: conn.name.clone(),
+            uri: conn.uri.clone(),
+            read_only: conn.read_only,
+            encrypted_password: None,
+            encrypted_transport: None,
+            ssh: conn.ssh.clone(),
+            proxy: conn.proxy.clone(),
+            tls: conn.tls.clone(),
+            auth: conn.auth.clone(),
+            folder: conn.folder.clone(),
+            tags: conn.tags.clone(),
+            environment: conn.environment,
+        };
+        let mut sanitized =
+            ExportedConnection { uri: redact_uri_password(&conn.uri), ..full.clone() };
+        let transport_secrets = sanitize_transport(&mut sanitized);
         let entry = match mode {
-            ExportMode::Redacted => ExportedConnection {
-                name: conn.name.clone(),
-                uri: redact_uri_password(&conn.uri),
-                read_only: conn.read_only,
-                encrypted_password: None,
-                encrypted_transport: None,
-                ssh: sanitized_ssh,
-                proxy: sanitized_proxy,
-            },
+            ExportMode::Redacted => sanitized,
             ExportMode::Encrypted => {
                 let passphrase = passphrase
                     .ok_or_else(|| anyhow::anyhow!("passphrase required for encrypted export"))?;
@@ -98,24 +159,12 @@ pub fn build_export(
                     None
                 };
                 ExportedConnection {
-                    name: conn.name.clone(),
-                    uri: redact_uri_password(&conn.uri),
-                    read_only: conn.read_only,
                     encrypted_password: encrypted,
                     encrypted_transport,
-                    ssh: sanitized_ssh,
-                    proxy: sanitized_proxy,
+                    ..sanitized
                 }
             }
-            ExportMode::Plaintext => ExportedConnection {
-                name: conn.name.clone(),
-                uri: conn.uri.clone(),
-                read_only: conn.read_only,
-                encrypted_password: None,
-                encrypted_transport: None,
-                ssh: conn.ssh.clone(),
-                proxy: conn.proxy.clone(),
-            },
+            ExportMode::Plaintext => full,
         };
         exported.push(entry);
     }
@@ -129,15 +178,47 @@ pub fn build_export(
     })
 }
 
-/// Parse an import file from JSON.
-pub fn parse_import(json: &str) -> Result<ConnectionExportFile> {
-    let file: ConnectionExportFile = serde_json::from_str(json)?;
+/// Parse an import file: an OpenMango export, a Compass export, or Studio 3T
+/// URIs / mongosh commands (see [`connection_formats`]).
+pub fn parse_import(contents: &str) -> Result<ConnectionExportFile> {
+    let trimmed = contents.trim_start();
+    if !trimmed.starts_with('{') {
+        let (format, connections) = connection_formats::parse_text(contents)?;
+        return Ok(foreign_import(format, connections));
+    }
+
+    let value: serde_json::Value = serde_json::from_str(contents)?;
+    if connection_formats::is_compass_export(&value) {
+        let connections = connection_formats::parse_compass(value)?;
+        return Ok(foreign_import(ForeignFormat::Compass, connections));
+    }
+
+    let file: ConnectionExportFile = serde_json::from_value(value)?;
     if file.version > CURRENT_VERSION {
         bail!("unsupported export version {} (max supported: {})", file.version, CURRENT_VERSION);
     }
     Ok(file)
 }
 
+/// Wrap connections parsed from another tool's file; `Redacted` when it held no secrets.
+fn foreign_import(
+    format: ForeignFormat,
+    connections: Vec<ExportedConnection>,
+) -> ConnectionExportFile {
+    let mode = if connection_formats::has_secrets(&connections) {
+        ExportMode::Plaintext
+    } else {
+        ExportMode::Redacted
+    };
+    ConnectionExportFile {
+        version: CURRENT_VERSION,
+        app: format.app_name().to_string(),
+        exported_at: Utc::now(),
+        mode,
+        connections,
+    }
+}
+
 /// Decrypt all encrypted passwords in an import file and inject them back into URIs.
 pub fn decrypt_import_file(file: &mut ConnectionExportFile, passphrase: &str) -> Result<()> {
     for conn in &mut file.connections {
@@ -177,29 +258,46 @@ pub fn resolve_import(
             conn.read_only = ec.read_only;
             conn.ssh = ec.ssh.clone();
             conn.proxy = ec.proxy.clone();
+            conn.tls = ec.tls.clone();
+            conn.auth = ec.auth.clone();
+            conn.folder = ec.folder.clone();
+            conn.tags = ec.tags.clone();
+            conn.environment = ec.environment;
             conn
         })
         .collect()
 }
 
-fn sanitize_transport(
-    ssh: Option<SshConfig>,
-    proxy: Option<ProxyConfig>,
-) -> (Option<SshConfig>, Option<ProxyConfig>, TransportSecrets) {
+/// Move every secret out of the exported settings.
+fn sanitize_transport(conn: &mut ExportedConnection) -> TransportSecrets {
     let mut secrets = TransportSecrets::default();
 
-    let mut sanitized_ssh = ssh;
-    if let Some(ssh_cfg) = sanitized_ssh.as_mut() {
+    if let Some(ssh_cfg) = conn.ssh.as_mut() {
         secrets.ssh_password = ssh_cfg.password.take();
         secrets.ssh_identity_passphrase = ssh_cfg.identity_passphrase.take();
+        secrets.ssh_jump_hosts = ssh_cfg
+            .jump_hosts
+            .iter_mut()
+            .map(|hop| JumpHostSecrets {
+                password: hop.password.take(),
+                identity_passphrase: hop.identity_passphrase.take(),
+            })
+            .collect();
     }
 
-    let mut sanitized_proxy = proxy;
-    if let Some(proxy_cfg) = sanitized_proxy.as_mut() {
+    if let Some(proxy_cfg) = conn.proxy.as_mut() {
         secrets.proxy_password = proxy_cfg.password.take();
     }
 
-    (sanitized_ssh, sanitized_proxy, secrets)
+    if let Some(tls_cfg) = conn.tls.as_mut() {
+        secrets.tls_cert_key_password = tls_cfg.cert_key_password.take();
+    }
+
+    if let Some(auth_cfg) = conn.auth.as_mut() {
+        secrets.auth_password = auth_cfg.password.take();
+    }
+
+    secrets
 }
 
 fn apply_transport_secrets(conn: &mut ExportedConnection, secrets: TransportSecrets) {
@@ -210,6 +308,14 @@ fn apply_transport_secrets(conn: &mut ExportedConnection, secrets: TransportSecr
         if secrets.ssh_identity_passphrase.as_deref().is_some_and(|v| !v.is_empty()) {
             ssh_cfg.identity_passphrase = secrets.ssh_identity_passphrase;
         }
+        for (hop, hop_secrets) in ssh_cfg.jump_hosts.iter_mut().zip(secrets.ssh_jump_hosts) {
+            if hop_secrets.password.as_deref().is_some_and(|v| !v.is_empty()) {
+                hop.password = hop_secrets.password;
+            }
+            if hop_secrets.identity_passphrase.as_deref().is_some_and(|v| !v.is_empty()) {
+                hop.identity_passphrase = hop_secrets.identity_passphrase;
+            }
+        }
     }
 
     if let Some(proxy_cfg) = conn.proxy.as_mut()
@@ -217,12 +323,24 @@ fn apply_transport_secrets(conn: &mut ExportedConnection, secrets: TransportSecr
     {
         proxy_cfg.password = secrets.proxy_password;
     }
+
+    if let Some(tls_cfg) = conn.tls.as_mut()
+        && secrets.tls_cert_key_password.as_deref().is_some_and(|v| !v.is_empty())
+    {
+        tls_cfg.cert_key_password = secrets.tls_cert_key_password;
+    }
+
+    if let Some(auth_cfg) = conn.auth.as_mut()
+        && secrets.auth_password.as_deref().is_some_and(|v| !v.is_empty())
+    {
+        auth_cfg.password = secrets.auth_password;
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{ProxyKind, SshAuth};
+    use crate::models::{AuthMechanism, ProxyKind, SshAuth, SshJumpHost};
     use uuid::Uuid;
 
     fn make_connections() -> Vec<SavedConnection> {
@@ -244,6 +362,12 @@ mod tests {
                     identity_passphrase: Some("ssh-passphrase".into()),
                     strict_host_key_checking: true,
                     local_bind_host: "127.0.0.1".into(),
+                    jump_hosts: vec![SshJumpHost {
+                        host: "edge".into(),
+                        username: "jump".into(),
+                        password: Some("jump-password".into()),
+                        ..SshJumpHost::default()
+                    }],
                 }),
                 proxy: Some(ProxyConfig {
                     enabled: true,
@@ -253,6 +377,21 @@ mod tests {
                     username: Some("proxy-user".into()),
                     password: Some("proxy-password".into()),
                 }),
+                tls: Some(TlsConfig {
+                    enabled: true,
+                    cert_key_file: Some("/certs/client.pem".into()),
+                    cert_key_password: Some("tls-password".into()),
+                    ..TlsConfig::default()
+                }),
+                auth: Some(AuthConfig {
+                    mechanism: AuthMechanism::LdapPlain,
+                    username: "ldap-user".into(),
+                    auth_source: None,
+                    password: Some("auth-password".into()),
+                }),
+                folder: Some("Clients/Acme".into()),
+                tags: vec!["billing".into()],
+                environment: Some(ConnectionEnvironment::Production),
             },
             SavedConnection {
                 id: Uuid::new_v4(),
@@ -262,6 +401,11 @@ mod tests {
                 read_only: true,
                 ssh: None,
                 proxy: None,
+                tls: None,
+                auth: None,
+                folder: None,
+                tags: Vec::new(),
+                environment: None,
             },
         ]
     }
@@ -280,10 +424,17 @@ mod tests {
             if let Some(ssh) = &ec.ssh {
                 assert!(ssh.password.is_none());
                 assert!(ssh.identity_passphrase.is_none());
+                assert!(ssh.jump_hosts.iter().all(|hop| hop.password.is_none()));
             }
             if let Some(proxy) = &ec.proxy {
                 assert!(proxy.password.is_none());
             }
+            if let Some(tls) = &ec.tls {
+                assert!(tls.cert_key_password.is_none());
+            }
+            if let Some(auth) = &ec.auth {
+                assert!(auth.password.is_none());
+            }
         }
     }
 
@@ -316,10 +467,17 @@ mod tests {
             if let Some(ssh) = &ec.ssh {
                 assert!(ssh.password.is_none());
                 assert!(ssh.identity_passphrase.is_none());
+                assert!(ssh.jump_hosts.iter().all(|hop| hop.password.is_none()));
             }
             if let Some(proxy) = &ec.proxy {
                 assert!(proxy.password.is_none());
             }
+            if let Some(tls) = &ec.tls {
+                assert!(tls.cert_key_password.is_none());
+            }
+            if let Some(auth) = &ec.auth {
+                assert!(auth.password.is_none());
+            }
         }
         assert!(file.connections[0].encrypted_transport.is_some());
         decrypt_import_file(&mut file, passphrase).unwrap();
@@ -333,10 +491,37 @@ mod tests {
             file.connections[0].ssh.as_ref().and_then(|cfg| cfg.identity_passphrase.as_deref()),
             Some("ssh-passphrase")
         );
+        assert_eq!(
+            file.connections[0]
+                .ssh
+                .as_ref()
+                .and_then(|cfg| cfg.jump_hosts.first())
+                .and_then(|hop| hop.password.as_deref()),
+            Some("jump-password")
+        );
         assert_eq!(
             file.connections[0].proxy.as_ref().and_then(|cfg| cfg.password.as_deref()),
             Some("proxy-password")
         );
+        assert_eq!(
+            file.connections[0].tls.as_ref().and_then(|cfg| cfg.cert_key_password.as_deref()),
+            Some("tls-password")
+        );
+        assert_eq!(
+            file.connections[0].auth.as_ref().and_then(|cfg| cfg.password.as_deref()),
+            Some("auth-password")
+        );
+    }
+
+    #[test]
+    fn export_keeps_folder_tags_and_environment() {
+        let conns = make_connections();
+        let file = build_export(&conns, ExportMode::Redacted, None).unwrap();
+        let imported = resolve_import(&file, &[]);
+        assert_eq!(imported[0].folder.as_deref(), Some("Clients/Acme"));
+        assert_eq!(imported[0].tags, vec!["billing".to_string()]);
+        assert_eq!(imported[0].environment, Some(ConnectionEnvironment::Production));
+        assert!(imported[1].environment.is_none());
     }
 
     #[test]
@@ -371,6 +556,11 @@ mod tests {
             read_only: false,
             ssh: None,
             proxy: None,
+            tls: None,
+            auth: None,
+            folder: None,
+            tags: Vec::new(),
+            environment: None,
         }];
 
         let file = ConnectionExportFile {
@@ -387,6 +577,11 @@ mod tests {
                     encrypted_transport: None,
                     ssh: None,
                     proxy: None,
+                    tls: None,
+                    auth: None,
+                    folder: None,
+                    tags: Vec::new(),
+                    environment: None,
                 },
                 ExportedConnection {
                     name: "Atlas".into(),
@@ -396,6 +591,11 @@ mod tests {
                     encrypted_transport: None,
                     ssh: None,
                     proxy: None,
+                    tls: None,
+                    auth: None,
+                    folder: None,
+                    tags: Vec::new(),
+                    environment: None,
                 },
             ],
         };
@@ -408,6 +608,27 @@ mod tests {
         assert_ne!(resolved[0].id, existing[0].id);
     }
 
+    #[test]
+    fn foreign_files_are_detected_and_resolved() {
+        let existing = vec![SavedConnection::new("Prod".into(), "mongodb://old:27017".into())];
+
+        let compass = r#"{"type":"Compass Connections","connections":[
+            {"connectionOptions":{"connectionString":"mongodb://db:27017"},"favorite":{"name":"Prod"}}
+        ]}"#;
+        let file = parse_import(compass).unwrap();
+        assert_eq!(file.app, "compass");
+        assert_eq!(file.mode, ExportMode::Redacted);
+        let resolved = resolve_import(&file, &existing);
+        assert_eq!(resolved[0].name, "Prod (imported)");
+        assert_eq!(resolved[0].uri, "mongodb://db:27017");
+
+        let file = parse_import("mongosh mongodb://localhost:27017 -u root -p secret\n").unwrap();
+        assert_eq!(file.app, "mongosh");
+        assert_eq!(file.mode, ExportMode::Plaintext);
+        let resolved = resolve_import(&file, &existing);
+        assert_eq!(resolved[0].auth.as_ref().unwrap().password.as_deref(), Some("secret"));
+    }
+
     #[test]
     fn no_password_uri_handles_gracefully() {
         let conns = vec![SavedConnection {
@@ -418,6 +639,11 @@ mod tests {
             read_only: false,
             ssh: None,
             proxy: None,
+            tls: None,
+            auth: None,
+            folder: None,
+            tags: Vec::new(),
+            environment: None,
         }];
 
         let file = build_export(&conns, ExportMode::Encrypted, Some("pass")).unwrap();
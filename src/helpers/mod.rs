@@ -1,8 +1,10 @@
 pub mod auto_pair;
+pub mod connection_formats;
 pub mod connection_io;
 pub mod crypto;
 pub mod format;
 pub mod keystore;
+pub mod ssh_config;
 pub mod validate;
 
 pub use format::{format_bytes, format_number};
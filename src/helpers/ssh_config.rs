@@ -0,0 +1,374 @@
+//! OpenSSH client config (`~/.ssh/config`) import for SSH tunnels.
+//!
+//! Only the keywords the tunnel understands are resolved: `HostName`, `Port`,
+//! `User`, `IdentityFile`, `ProxyJump` and `StrictHostKeyChecking`. As in
+//! OpenSSH, the first value found for a keyword wins. `Match` blocks are
+//! skipped.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{Error, Result};
+use crate::models::{SshAuth, SshConfig, SshJumpHost};
+
+/// Nested `Include` depth, mirroring OpenSSH's own limit.
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+/// Settings resolved for one host alias.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct HostSettings {
+    host_name: Option<String>,
+    port: Option<u16>,
+    user: Option<String>,
+    identity_file: Option<String>,
+    proxy_jump: Option<String>,
+    strict_host_key_checking: Option<bool>,
+}
+
+/// One `Host`/`Match` section; `patterns == None` is the leading global section.
+struct Section {
+    patterns: Option<Vec<String>>,
+    entries: Vec<(String, String)>,
+}
+
+pub fn default_ssh_config_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".ssh").join("config"))
+}
+
+/// Resolve `alias` from `~/.ssh/config` into a tunnel configuration.
+pub fn import_host_alias(alias: &str) -> Result<SshConfig> {
+    let path = default_ssh_config_path()
+        .ok_or_else(|| Error::Parse("Could not determine home directory".to_string()))?;
+    let contents = read_with_includes(&path, 0)?;
+    resolve_host_alias(&contents, alias)
+}
+
+/// Resolve `alias` against config text (with `Include`s already inlined).
+///
+/// Auth is `IdentityFile` when the alias names a key and `Agent` otherwise.
+/// Jump hosts that are themselves aliases are resolved one level deep.
+pub fn resolve_host_alias(contents: &str, alias: &str) -> Result<SshConfig> {
+    let alias = alias.trim();
+    if alias.is_empty() {
+        return Err(Error::Parse("SSH host alias is required".to_string()));
+    }
+
+    let sections = parse_sections(contents);
+    // A catch-all `Host *` block carries defaults; it doesn't make an alias known.
+    let is_named = |section: &&Section| {
+        section.patterns.as_ref().is_some_and(|patterns| patterns.iter().any(|p| p != "*"))
+    };
+    if !sections.iter().filter(is_named).any(|section| section_matches(section, alias)) {
+        return Err(Error::Parse(format!("Host \"{alias}\" was not found in SSH config")));
+    }
+
+    let settings = settings_for(&sections, alias);
+    let user = settings.user.clone().unwrap_or_default();
+    let mut jump_hosts = match settings.proxy_jump.as_deref() {
+        Some(spec) if !spec.eq_ignore_ascii_case("none") => {
+            SshJumpHost::parse_proxy_jump(spec).map_err(Error::Parse)?
+        }
+        _ => Vec::new(),
+    };
+    for hop in &mut jump_hosts {
+        let hop_settings = settings_for(&sections, &hop.host);
+        resolve_jump_host(hop, &hop_settings);
+    }
+
+    let host = settings.host_name.clone().unwrap_or_else(|| alias.to_string());
+    let identity_file = settings
+        .identity_file
+        .as_deref()
+        .map(|path| expand_tokens(path, &host, settings.port.unwrap_or(22), &user));
+    Ok(SshConfig {
+        enabled: true,
+        host,
+        port: settings.port.unwrap_or(22),
+        username: user,
+        auth: if identity_file.is_some() { SshAuth::IdentityFile } else { SshAuth::Agent },
+        identity_file,
+        strict_host_key_checking: settings.strict_host_key_checking.unwrap_or(true),
+        jump_hosts,
+        ..SshConfig::default()
+    })
+}
+
+fn resolve_jump_host(hop: &mut SshJumpHost, settings: &HostSettings) {
+    if let Some(host_name) = &settings.host_name {
+        hop.host = host_name.clone();
+    }
+    // `parse_proxy_jump` fills in 22 when the spec has no port, so only an
+    // explicit non-default port in the spec beats the alias' `Port`.
+    if hop.port == 22
+        && let Some(port) = settings.port
+    {
+        hop.port = port;
+    }
+    if hop.username.is_empty()
+        && let Some(user) = &settings.user
+    {
+        hop.username = user.clone();
+    }
+    if let Some(strict) = settings.strict_host_key_checking {
+        hop.strict_host_key_checking = strict;
+    }
+    match &settings.identity_file {
+        Some(path) => {
+            hop.auth = SshAuth::IdentityFile;
+            hop.identity_file = Some(expand_tokens(path, &hop.host, hop.port, &hop.username));
+        }
+        None => hop.auth = SshAuth::Agent,
+    }
+}
+
+fn settings_for(sections: &[Section], alias: &str) -> HostSettings {
+    let mut settings = HostSettings::default();
+    for section in sections.iter().filter(|section| section_matches(section, alias)) {
+        for (key, value) in &section.entries {
+            match key.as_str() {
+                "hostname" if settings.host_name.is_none() => {
+                    settings.host_name = Some(value.replace("%h", alias));
+                }
+                "port" if settings.port.is_none() => settings.port = value.parse().ok(),
+                "user" if settings.user.is_none() => settings.user = Some(value.clone()),
+                "identityfile" if settings.identity_file.is_none() => {
+                    settings.identity_file = Some(value.clone());
+                }
+                "proxyjump" if settings.proxy_jump.is_none() => {
+                    settings.proxy_jump = Some(value.clone());
+                }
+                "stricthostkeychecking" if settings.strict_host_key_checking.is_none() => {
+                    settings.strict_host_key_checking =
+                        Some(!matches!(value.to_ascii_lowercase().as_str(), "no" | "off"));
+                }
+                _ => {}
+            }
+        }
+    }
+    settings
+}
+
+fn parse_sections(contents: &str) -> Vec<Section> {
+    let mut sections = vec![Section { patterns: None, entries: Vec::new() }];
+    for line in contents.lines() {
+        let Some((key, value)) = split_keyword(line) else {
+            continue;
+        };
+        match key.as_str() {
+            "host" => sections.push(Section {
+                patterns: Some(value.split_whitespace().map(str::to_string).collect()),
+                entries: Vec::new(),
+            }),
+            // Match criteria need runtime context we don't have; never apply them.
+            "match" => sections.push(Section { patterns: Some(Vec::new()), entries: Vec::new() }),
+            _ => {
+                if let Some(section) = sections.last_mut() {
+                    section.entries.push((key, value));
+                }
+            }
+        }
+    }
+    sections
+}
+
+/// Split a config line into a lowercased keyword and its unquoted value.
+fn split_keyword(line: &str) -> Option<(String, String)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let split_at = line.find(|c: char| c.is_whitespace() || c == '=')?;
+    let (key, rest) = line.split_at(split_at);
+    let value = rest.trim_start_matches(|c: char| c.is_whitespace() || c == '=').trim();
+    let value = value.strip_prefix('"').and_then(|inner| inner.strip_suffix('"')).unwrap_or(value);
+    Some((key.to_ascii_lowercase(), value.to_string()))
+}
+
+fn section_matches(section: &Section, alias: &str) -> bool {
+    let Some(patterns) = &section.patterns else {
+        return true;
+    };
+    let mut matched = false;
+    for pattern in patterns {
+        if let Some(negated) = pattern.strip_prefix('!') {
+            if wildcard_match(negated, alias) {
+                return false;
+            }
+        } else if wildcard_match(pattern, alias) {
+            matched = true;
+        }
+    }
+    matched
+}
+
+/// OpenSSH-style glob: `*` matches any run, `?` matches one character.
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = backtrack {
+            p = star_p + 1;
+            t = star_t + 1;
+            backtrack = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Expand the `%` tokens OpenSSH allows in `IdentityFile`.
+fn expand_tokens(value: &str, host: &str, port: u16, user: &str) -> String {
+    let home = dirs::home_dir().map(|home| home.to_string_lossy().to_string()).unwrap_or_default();
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('%') => out.push('%'),
+            Some('h') => out.push_str(host),
+            Some('p') => out.push_str(&port.to_string()),
+            Some('r') => out.push_str(user),
+            Some('d') => out.push_str(&home),
+            Some(other) => {
+                out.push('%');
+                out.push(other);
+            }
+            None => out.push('%'),
+        }
+    }
+    out
+}
+
+/// Read a config file, inlining `Include` directives that name literal paths.
+fn read_with_includes(path: &Path, depth: usize) -> Result<String> {
+    let contents = fs::read_to_string(path).map_err(|err| {
+        Error::Parse(format!("Failed to read SSH config {}: {err}", path.display()))
+    })?;
+    if depth >= MAX_INCLUDE_DEPTH {
+        return Ok(contents);
+    }
+
+    let mut out = String::with_capacity(contents.len());
+    for line in contents.lines() {
+        match split_keyword(line) {
+            Some((key, value)) if key == "include" => {
+                for include in value.split_whitespace() {
+                    if include.contains(['*', '?']) {
+                        log::debug!("Skipping wildcard SSH config include: {include}");
+                        continue;
+                    }
+                    let include_path = resolve_include_path(include);
+                    if include_path.is_file() {
+                        out.push_str(&read_with_includes(&include_path, depth + 1)?);
+                        out.push('\n');
+                    }
+                }
+            }
+            _ => {
+                out.push_str(line);
+                out.push('\n');
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Relative includes are resolved against `~/.ssh`, like OpenSSH does for
+/// the user config.
+fn resolve_include_path(raw: &str) -> PathBuf {
+    let home = dirs::home_dir().unwrap_or_default();
+    if let Some(rest) = raw.strip_prefix("~/") {
+        return home.join(rest);
+    }
+    let path = Path::new(raw);
+    if path.is_absolute() { path.to_path_buf() } else { home.join(".ssh").join(path) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+Host bastion
+    HostName bastion.example.com
+    Port 2222
+    User ops
+    IdentityFile ~/.ssh/bastion_%r
+
+Host db-* !db-legacy
+    HostName %h.internal
+    ProxyJump bastion,edge:2200
+
+Host edge
+    HostName edge.example.com
+    StrictHostKeyChecking no
+
+Match host *.example.com
+    User never-applied
+
+# Defaults go last: the first value obtained for a keyword wins.
+Host *
+    User fallback
+    StrictHostKeyChecking yes
+"#;
+
+    #[test]
+    fn resolves_alias_with_identity_file_and_tokens() {
+        let config = resolve_host_alias(CONFIG, "bastion").unwrap();
+
+        assert_eq!(config.host, "bastion.example.com");
+        assert_eq!(config.port, 2222);
+        assert_eq!(config.username, "ops");
+        assert_eq!(config.auth, SshAuth::IdentityFile);
+        assert_eq!(config.identity_file.as_deref(), Some("~/.ssh/bastion_ops"));
+        assert!(config.jump_hosts.is_empty());
+    }
+
+    #[test]
+    fn wildcard_alias_uses_agent_and_resolves_jump_aliases() {
+        let config = resolve_host_alias(CONFIG, "db-orders").unwrap();
+
+        assert_eq!(config.host, "db-orders.internal");
+        assert_eq!(config.username, "fallback");
+        assert_eq!(config.auth, SshAuth::Agent);
+        assert_eq!(config.jump_hosts.len(), 2);
+
+        let bastion = &config.jump_hosts[0];
+        assert_eq!((bastion.host.as_str(), bastion.port), ("bastion.example.com", 2222));
+        assert_eq!(bastion.username, "ops");
+        assert_eq!(bastion.auth, SshAuth::IdentityFile);
+
+        let edge = &config.jump_hosts[1];
+        assert_eq!((edge.host.as_str(), edge.port), ("edge.example.com", 2200));
+        assert_eq!(edge.auth, SshAuth::Agent);
+        assert!(!edge.strict_host_key_checking);
+    }
+
+    #[test]
+    fn negated_pattern_and_unknown_alias_are_rejected() {
+        assert!(resolve_host_alias(CONFIG, "db-legacy").is_err());
+        assert!(resolve_host_alias(CONFIG, "").is_err());
+    }
+
+    #[test]
+    fn keyword_equals_syntax_and_quotes_are_accepted() {
+        let config =
+            resolve_host_alias("Host app\n  HostName=\"10.0.0.5\"\n  Port = 2200\n", "app")
+                .unwrap();
+
+        assert_eq!(config.host, "10.0.0.5");
+        assert_eq!(config.port, 2200);
+    }
+}
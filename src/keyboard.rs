@@ -34,11 +34,14 @@ actions!(
         EditDocumentJson,
         CopyDocumentJson,
         DuplicateDocument,
+        CompareDocuments,
         DeleteDocument,
         DeleteCollection,
         DeleteDatabase,
         DeleteConnection,
         PasteDocuments,
+        UndoDocumentChange,
+        RedoDocumentChange,
         EditValueType,
         RenameField,
         RemoveSelectedField,
@@ -238,6 +241,11 @@ fn default_keybindings() -> Vec<KeyBinding> {
         ),
         KeyBinding::new("cmd-shift-v", PasteDocuments, Some("Documents && !Input")),
         KeyBinding::new("ctrl-shift-v", PasteDocuments, Some("Documents && !Input")),
+        KeyBinding::new("cmd-z", UndoDocumentChange, Some("Documents && !Input")),
+        KeyBinding::new("ctrl-z", UndoDocumentChange, Some("Documents && !Input")),
+        KeyBinding::new("cmd-shift-z", RedoDocumentChange, Some("Documents && !Input")),
+        KeyBinding::new("ctrl-shift-z", RedoDocumentChange, Some("Documents && !Input")),
+        KeyBinding::new("ctrl-y", RedoDocumentChange, Some("Documents && !Input")),
         KeyBinding::new("alt-enter", EditValueType, Some("Documents && !Input")),
         KeyBinding::new("alt-return", EditValueType, Some("Documents && !Input")),
         KeyBinding::new("f2", RenameField, Some("Documents && !Input")),
@@ -12,6 +12,20 @@ pub enum SshAuth {
     #[default]
     Password,
     IdentityFile,
+    /// Keys offered by the running SSH agent (`SSH_AUTH_SOCK` / Pageant).
+    Agent,
+}
+
+impl SshAuth {
+    pub const ALL: [SshAuth; 3] = [SshAuth::Password, SshAuth::IdentityFile, SshAuth::Agent];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SshAuth::Password => "Password",
+            SshAuth::IdentityFile => "Identity file",
+            SshAuth::Agent => "SSH agent",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -36,6 +50,26 @@ pub struct SshConfig {
     pub strict_host_key_checking: bool,
     #[serde(default = "default_local_bind_host")]
     pub local_bind_host: String,
+    /// Ordered jump hosts traversed before `host` (OpenSSH `ProxyJump`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub jump_hosts: Vec<SshJumpHost>,
+}
+
+impl SshConfig {
+    /// The final SSH hop (the bastion that forwards to MongoDB) as a jump-host entry.
+    pub fn target_hop(&self) -> SshJumpHost {
+        SshJumpHost {
+            id: Uuid::nil(),
+            host: self.host.clone(),
+            port: self.port,
+            username: self.username.clone(),
+            auth: self.auth.clone(),
+            password: self.password.clone(),
+            identity_file: self.identity_file.clone(),
+            identity_passphrase: self.identity_passphrase.clone(),
+            strict_host_key_checking: self.strict_host_key_checking,
+        }
+    }
 }
 
 impl Default for SshConfig {
@@ -51,10 +85,121 @@ impl Default for SshConfig {
             identity_passphrase: None,
             strict_host_key_checking: default_strict_host_key_checking(),
             local_bind_host: default_local_bind_host(),
+            jump_hosts: Vec::new(),
         }
     }
 }
 
+/// One intermediate SSH host in a jump chain, with its own auth and host-key policy.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SshJumpHost {
+    /// Stable identity that keys this hop's keychain secrets across edits and reorders.
+    #[serde(default = "Uuid::new_v4")]
+    pub id: Uuid,
+    #[serde(default)]
+    pub host: String,
+    #[serde(default = "default_ssh_port")]
+    pub port: u16,
+    #[serde(default)]
+    pub username: String,
+    #[serde(default)]
+    pub auth: SshAuth,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identity_file: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identity_passphrase: Option<String>,
+    #[serde(default = "default_strict_host_key_checking")]
+    pub strict_host_key_checking: bool,
+}
+
+impl Default for SshJumpHost {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            host: String::new(),
+            port: default_ssh_port(),
+            username: String::new(),
+            auth: SshAuth::default(),
+            password: None,
+            identity_file: None,
+            identity_passphrase: None,
+            strict_host_key_checking: default_strict_host_key_checking(),
+        }
+    }
+}
+
+impl SshJumpHost {
+    /// Parse an OpenSSH `ProxyJump` value (`[user@]host[:port]`, comma separated).
+    ///
+    /// Only addressing is parsed; auth fields keep their defaults.
+    pub fn parse_proxy_jump(spec: &str) -> Result<Vec<SshJumpHost>, String> {
+        let mut hops = Vec::new();
+        for raw in spec.split(',') {
+            let raw = raw.trim();
+            if raw.is_empty() {
+                continue;
+            }
+            let raw = raw.strip_prefix("ssh://").unwrap_or(raw);
+            let (username, address) = match raw.rsplit_once('@') {
+                Some((user, address)) => (user.to_string(), address),
+                None => (String::new(), raw),
+            };
+            let (host, port) = split_host_port(address)
+                .ok_or_else(|| format!("Invalid jump host port in \"{raw}\""))?;
+            if host.is_empty() {
+                return Err(format!("Jump host is missing a host name: \"{raw}\""));
+            }
+            hops.push(SshJumpHost { host, port, username, ..SshJumpHost::default() });
+        }
+        Ok(hops)
+    }
+
+    /// Render a chain back into `ProxyJump` syntax.
+    pub fn format_proxy_jump(hops: &[SshJumpHost]) -> String {
+        hops.iter().map(SshJumpHost::address).collect::<Vec<_>>().join(", ")
+    }
+
+    /// `[user@]host[:port]`, omitting the port when it is the SSH default.
+    pub fn address(&self) -> String {
+        let mut out = String::new();
+        if !self.username.is_empty() {
+            out.push_str(&self.username);
+            out.push('@');
+        }
+        if self.host.contains(':') {
+            out.push('[');
+            out.push_str(&self.host);
+            out.push(']');
+        } else {
+            out.push_str(&self.host);
+        }
+        if self.port != default_ssh_port() {
+            out.push(':');
+            out.push_str(&self.port.to_string());
+        }
+        out
+    }
+}
+
+/// Split `host[:port]` / `[v6]:port`; `None` if the port is not a valid u16.
+fn split_host_port(address: &str) -> Option<(String, u16)> {
+    if let Some(rest) = address.strip_prefix('[') {
+        let (host, tail) = rest.split_once(']')?;
+        let port = match tail.strip_prefix(':') {
+            Some(port) => port.parse().ok()?,
+            None if tail.is_empty() => default_ssh_port(),
+            None => return None,
+        };
+        return Some((host.to_string(), port));
+    }
+    match address.rsplit_once(':') {
+        Some((host, port)) => Some((host.to_string(), port.parse().ok()?)),
+        None => Some((address.to_string(), default_ssh_port())),
+    }
+}
+
 fn default_ssh_port() -> u16 {
     22
 }
@@ -107,6 +252,168 @@ fn default_proxy_port() -> u16 {
     1080
 }
 
+/// TLS/X.509 settings applied on top of the connection URI at connect time.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct TlsConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_file: Option<String>,
+    /// PEM file holding the client certificate and its private key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cert_key_file: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cert_key_password: Option<String>,
+    #[serde(default)]
+    pub allow_invalid_hostnames: bool,
+    /// `tlsInsecure`: skip both certificate and hostname validation.
+    #[serde(default)]
+    pub insecure: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthMechanism {
+    /// SCRAM, with the variant negotiated by the server.
+    #[default]
+    Scram,
+    ScramSha1,
+    ScramSha256,
+    /// `MONGODB-X509`: the TLS client certificate subject is the user.
+    X509,
+    /// `PLAIN` against `$external`, as used by LDAP.
+    LdapPlain,
+    /// `MONGODB-OIDC` with an OAuth device authorization login.
+    OidcDeviceFlow,
+}
+
+impl AuthMechanism {
+    pub const ALL: [AuthMechanism; 6] = [
+        AuthMechanism::Scram,
+        AuthMechanism::ScramSha256,
+        AuthMechanism::ScramSha1,
+        AuthMechanism::X509,
+        AuthMechanism::LdapPlain,
+        AuthMechanism::OidcDeviceFlow,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AuthMechanism::Scram => "SCRAM (negotiated)",
+            AuthMechanism::ScramSha1 => "SCRAM-SHA-1",
+            AuthMechanism::ScramSha256 => "SCRAM-SHA-256",
+            AuthMechanism::X509 => "X.509 certificate",
+            AuthMechanism::LdapPlain => "LDAP (PLAIN)",
+            AuthMechanism::OidcDeviceFlow => "OIDC (device login)",
+        }
+    }
+
+    /// The URI `authMechanism` value, if the mechanism has one.
+    pub fn uri_name(self) -> Option<&'static str> {
+        match self {
+            AuthMechanism::Scram => None,
+            AuthMechanism::ScramSha1 => Some("SCRAM-SHA-1"),
+            AuthMechanism::ScramSha256 => Some("SCRAM-SHA-256"),
+            AuthMechanism::X509 => Some("MONGODB-X509"),
+            AuthMechanism::LdapPlain => Some("PLAIN"),
+            AuthMechanism::OidcDeviceFlow => Some("MONGODB-OIDC"),
+        }
+    }
+
+    /// Parse a URI `authMechanism` value.
+    pub fn from_uri_name(name: &str) -> Option<Self> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Some(AuthMechanism::Scram);
+        }
+        Self::ALL
+            .into_iter()
+            .find(|mechanism| mechanism.uri_name().is_some_and(|n| n.eq_ignore_ascii_case(name)))
+    }
+
+    /// Auth database used when none is configured.
+    pub fn default_source(self) -> &'static str {
+        match self {
+            AuthMechanism::Scram | AuthMechanism::ScramSha1 | AuthMechanism::ScramSha256 => "admin",
+            AuthMechanism::X509 | AuthMechanism::LdapPlain | AuthMechanism::OidcDeviceFlow => {
+                "$external"
+            }
+        }
+    }
+
+    pub fn uses_password(self) -> bool {
+        matches!(
+            self,
+            AuthMechanism::Scram
+                | AuthMechanism::ScramSha1
+                | AuthMechanism::ScramSha256
+                | AuthMechanism::LdapPlain
+        )
+    }
+}
+
+/// Credentials kept outside the URI; the password lives in the keychain.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct AuthConfig {
+    #[serde(default)]
+    pub mechanism: AuthMechanism,
+    #[serde(default)]
+    pub username: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth_source: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+}
+
+impl AuthConfig {
+    /// Configured auth database, or the mechanism default.
+    pub fn source(&self) -> &str {
+        self.auth_source
+            .as_deref()
+            .map(str::trim)
+            .filter(|source| !source.is_empty())
+            .unwrap_or_else(|| self.mechanism.default_source())
+    }
+}
+
+/// Deployment environment a connection targets; tints the UI so production stands out.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionEnvironment {
+    Development,
+    Testing,
+    Staging,
+    Production,
+}
+
+impl ConnectionEnvironment {
+    pub const ALL: [ConnectionEnvironment; 4] = [
+        ConnectionEnvironment::Development,
+        ConnectionEnvironment::Testing,
+        ConnectionEnvironment::Staging,
+        ConnectionEnvironment::Production,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ConnectionEnvironment::Development => "Development",
+            ConnectionEnvironment::Testing => "Testing",
+            ConnectionEnvironment::Staging => "Staging",
+            ConnectionEnvironment::Production => "Production",
+        }
+    }
+
+    /// Compact badge text for lists, tabs and the status bar.
+    pub fn badge(self) -> &'static str {
+        match self {
+            ConnectionEnvironment::Development => "DEV",
+            ConnectionEnvironment::Testing => "TEST",
+            ConnectionEnvironment::Staging => "STAGE",
+            ConnectionEnvironment::Production => "PROD",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct ConnectionRuntimeMeta {
     pub ssh_tunnel_active: bool,
@@ -114,6 +421,17 @@ pub struct ConnectionRuntimeMeta {
     pub proxy_active: bool,
 }
 
+/// Liveness of an active connection as seen by the health monitor.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConnectionHealth {
+    #[default]
+    Healthy,
+    /// Tunnel or server heartbeat failed; a reconnect is about to start.
+    Lost,
+    /// Rebuilding the connection; `attempt` starts at 1.
+    Reconnecting { attempt: u32 },
+}
+
 /// A saved connection configuration (persisted to disk)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SavedConnection {
@@ -127,6 +445,18 @@ pub struct SavedConnection {
     pub ssh: Option<SshConfig>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub proxy: Option<ProxyConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls: Option<TlsConfig>,
+    /// Typed credentials; when set they replace any credentials in `uri`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth: Option<AuthConfig>,
+    /// Folder path in the connection list; `/` separates nested folders.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub folder: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<ConnectionEnvironment>,
 }
 
 impl SavedConnection {
@@ -139,7 +469,30 @@ impl SavedConnection {
             read_only: false,
             ssh: None,
             proxy: None,
+            tls: None,
+            auth: None,
+            folder: None,
+            tags: Vec::new(),
+            environment: None,
+        }
+    }
+
+    /// Parse a comma-separated tag list, dropping blanks and case-insensitive duplicates.
+    pub fn parse_tags(input: &str) -> Vec<String> {
+        let mut tags: Vec<String> = Vec::new();
+        for tag in input.split(',').map(str::trim).filter(|tag| !tag.is_empty()) {
+            if !tags.iter().any(|existing| existing.eq_ignore_ascii_case(tag)) {
+                tags.push(tag.to_string());
+            }
         }
+        tags
+    }
+
+    /// Normalize a folder path: trims each segment and drops empty ones.
+    pub fn normalize_folder(input: &str) -> Option<String> {
+        let segments: Vec<&str> =
+            input.split('/').map(str::trim).filter(|segment| !segment.is_empty()).collect();
+        if segments.is_empty() { None } else { Some(segments.join("/")) }
     }
 
     /// Return a copy with all secrets removed (for disk persistence).
@@ -150,10 +503,20 @@ impl SavedConnection {
         if let Some(ssh) = &mut c.ssh {
             ssh.password = None;
             ssh.identity_passphrase = None;
+            for hop in &mut ssh.jump_hosts {
+                hop.password = None;
+                hop.identity_passphrase = None;
+            }
         }
         if let Some(proxy) = &mut c.proxy {
             proxy.password = None;
         }
+        if let Some(tls) = &mut c.tls {
+            tls.cert_key_password = None;
+        }
+        if let Some(auth) = &mut c.auth {
+            auth.password = None;
+        }
         c
     }
 }
@@ -167,4 +530,152 @@ pub struct ActiveConnection {
     /// Collections per database (db_name -> collection_names)
     pub collections: HashMap<String, Vec<String>>,
     pub runtime_meta: ConnectionRuntimeMeta,
+    pub health: ConnectionHealth,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_proxy_jump_handles_users_ports_and_ipv6() {
+        let hops =
+            SshJumpHost::parse_proxy_jump("ops@edge.example.com:2222, inner, [fd00::1]:2200")
+                .unwrap();
+
+        assert_eq!(hops.len(), 3);
+        assert_eq!((hops[0].username.as_str(), hops[0].host.as_str()), ("ops", "edge.example.com"));
+        assert_eq!(hops[0].port, 2222);
+        assert_eq!((hops[1].username.as_str(), hops[1].port), ("", 22));
+        assert_eq!((hops[2].host.as_str(), hops[2].port), ("fd00::1", 2200));
+        assert_eq!(
+            SshJumpHost::format_proxy_jump(&hops),
+            "ops@edge.example.com:2222, inner, [fd00::1]:2200"
+        );
+    }
+
+    #[test]
+    fn parse_proxy_jump_rejects_bad_ports() {
+        assert!(SshJumpHost::parse_proxy_jump("edge:notaport").is_err());
+        assert!(SshJumpHost::parse_proxy_jump("user@:22").is_err());
+        assert!(SshJumpHost::parse_proxy_jump("  ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn jump_hosts_saved_without_id_get_distinct_ids() {
+        let json = r#"{"host":"bastion","jump_hosts":[{"host":"edge"},{"host":"inner"}]}"#;
+        let ssh: SshConfig = serde_json::from_str(json).unwrap();
+
+        assert_ne!(ssh.jump_hosts[0].id, ssh.jump_hosts[1].id);
+        assert!(ssh.jump_hosts.iter().all(|hop| !hop.id.is_nil()));
+
+        let reloaded: SshConfig =
+            serde_json::from_str(&serde_json::to_string(&ssh).unwrap()).unwrap();
+        assert_eq!(reloaded.jump_hosts[0].id, ssh.jump_hosts[0].id);
+    }
+
+    #[test]
+    fn secrets_stripped_from_jump_hosts() {
+        let mut conn = SavedConnection::new("jump".into(), "mongodb://localhost".into());
+        conn.ssh = Some(SshConfig {
+            jump_hosts: vec![SshJumpHost {
+                host: "edge".into(),
+                password: Some("pw".into()),
+                identity_passphrase: Some("pp".into()),
+                ..SshJumpHost::default()
+            }],
+            ..SshConfig::default()
+        });
+
+        let stripped = conn.with_secrets_stripped();
+        let hop = &stripped.ssh.unwrap().jump_hosts[0];
+
+        assert_eq!(hop.host, "edge");
+        assert!(hop.password.is_none());
+        assert!(hop.identity_passphrase.is_none());
+    }
+
+    #[test]
+    fn secrets_stripped_from_tls_key_password() {
+        let mut conn = SavedConnection::new("tls".into(), "mongodb://localhost".into());
+        conn.tls = Some(TlsConfig {
+            enabled: true,
+            cert_key_file: Some("/certs/client.pem".into()),
+            cert_key_password: Some("key-pass".into()),
+            ..TlsConfig::default()
+        });
+
+        let tls = conn.with_secrets_stripped().tls.unwrap();
+
+        assert_eq!(tls.cert_key_file.as_deref(), Some("/certs/client.pem"));
+        assert!(tls.cert_key_password.is_none());
+    }
+
+    #[test]
+    fn secrets_stripped_from_auth_password() {
+        let mut conn = SavedConnection::new("auth".into(), "mongodb://localhost".into());
+        conn.auth = Some(AuthConfig {
+            username: "app".into(),
+            password: Some("secret".into()),
+            ..AuthConfig::default()
+        });
+
+        let auth = conn.with_secrets_stripped().auth.unwrap();
+
+        assert_eq!(auth.username, "app");
+        assert!(auth.password.is_none());
+    }
+
+    #[test]
+    fn auth_mechanism_round_trips_uri_names() {
+        for mechanism in AuthMechanism::ALL {
+            let name = mechanism.uri_name().unwrap_or_default();
+            assert_eq!(AuthMechanism::from_uri_name(name), Some(mechanism));
+        }
+        assert_eq!(AuthMechanism::from_uri_name("mongodb-x509"), Some(AuthMechanism::X509));
+        assert_eq!(AuthMechanism::from_uri_name("GSSAPI"), None);
+    }
+
+    #[test]
+    fn auth_source_defaults_per_mechanism() {
+        let mut auth = AuthConfig::default();
+        assert_eq!(auth.source(), "admin");
+        auth.mechanism = AuthMechanism::LdapPlain;
+        assert_eq!(auth.source(), "$external");
+        auth.auth_source = Some("ldap".into());
+        assert_eq!(auth.source(), "ldap");
+    }
+
+    #[test]
+    fn parse_tags_trims_and_dedupes() {
+        assert_eq!(
+            SavedConnection::parse_tags(" billing, EU ,,billing, eu, reports "),
+            vec!["billing".to_string(), "EU".to_string(), "reports".to_string()]
+        );
+        assert!(SavedConnection::parse_tags(" , ").is_empty());
+    }
+
+    #[test]
+    fn normalize_folder_drops_empty_segments() {
+        assert_eq!(
+            SavedConnection::normalize_folder(" Clients // Acme / "),
+            Some("Clients/Acme".to_string())
+        );
+        assert_eq!(SavedConnection::normalize_folder(" / "), None);
+    }
+
+    #[test]
+    fn organization_fields_default_when_missing() {
+        let json = r#"{"id":"6f1c1d8e-8d3b-4b61-9a57-3c1f5c0e2a10","name":"a","uri":"mongodb://localhost","last_connected":null}"#;
+        let conn: SavedConnection = serde_json::from_str(json).unwrap();
+        assert!(conn.folder.is_none());
+        assert!(conn.tags.is_empty());
+        assert!(conn.environment.is_none());
+
+        let mut conn = conn;
+        conn.environment = Some(ConnectionEnvironment::Production);
+        let value = serde_json::to_value(&conn).unwrap();
+        assert_eq!(value["environment"], "production");
+        assert!(value.get("tags").is_none());
+    }
 }
@@ -5,7 +5,8 @@ pub mod connection;
 mod tree_node_id;
 
 pub use connection::{
-    ActiveConnection, ConnectionRuntimeMeta, ProxyConfig, ProxyKind, SavedConnection, SshAuth,
-    SshConfig,
+    ActiveConnection, AuthConfig, AuthMechanism, ConnectionEnvironment, ConnectionHealth,
+    ConnectionRuntimeMeta, ProxyConfig, ProxyKind, SavedConnection, SshAuth, SshConfig,
+    SshJumpHost, TlsConfig,
 };
 pub use tree_node_id::TreeNodeId;
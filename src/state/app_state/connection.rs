@@ -6,10 +6,11 @@ use gpui::{App, Context};
 use uuid::Uuid;
 
 use super::AppState;
+use crate::connection::manager::tool_connection_uri;
 use crate::helpers::keystore::KeyStore;
 use crate::helpers::validate::{REDACTED_PASSWORD, extract_uri_password};
 use crate::models::TreeNodeId;
-use crate::models::{ActiveConnection, SavedConnection};
+use crate::models::{ActiveConnection, ConnectionEnvironment, ConnectionHealth, SavedConnection};
 use crate::state::ActiveTab;
 use crate::state::View;
 use crate::state::events::AppEvent;
@@ -35,12 +36,54 @@ pub(crate) fn write_conn_secrets(cx: &App, conn: &SavedConnection) {
     {
         KeyStore::write_conn(cx, conn.id, "proxy", pwd).detach();
     }
+    if let Some(pwd) = conn.tls.as_ref().and_then(|t| t.cert_key_password.as_deref())
+        && !pwd.is_empty()
+    {
+        KeyStore::write_conn(cx, conn.id, "tls", pwd).detach();
+    }
+    if let Some(pwd) = conn.auth.as_ref().and_then(|a| a.password.as_deref())
+        && !pwd.is_empty()
+    {
+        KeyStore::write_conn(cx, conn.id, "auth", pwd).detach();
+    }
+    for hop in conn.ssh.iter().flat_map(|s| s.jump_hosts.iter()) {
+        let (password_key, passphrase_key) = jump_secret_keys(hop.id);
+        if let Some(pwd) = hop.password.as_deref().filter(|pwd| !pwd.is_empty()) {
+            KeyStore::write_conn(cx, conn.id, &password_key, pwd).detach();
+        }
+        if let Some(pp) = hop.identity_passphrase.as_deref().filter(|pp| !pp.is_empty()) {
+            KeyStore::write_conn(cx, conn.id, &passphrase_key, pp).detach();
+        }
+    }
+}
+
+/// Keychain keys for the password and identity passphrase of the jump host `hop_id`.
+pub(crate) fn jump_secret_keys(hop_id: Uuid) -> (String, String) {
+    (format!("ssh-jump-{hop_id}"), format!("ssh-jump-{hop_id}-passphrase"))
+}
+
+/// Position-based keys written before hops had ids; only read to migrate them.
+pub(crate) fn legacy_jump_secret_keys(index: usize) -> (String, String) {
+    (format!("ssh-jump-{index}"), format!("ssh-jump-{index}-passphrase"))
 }
 
-fn delete_conn_secrets(cx: &App, id: Uuid) {
-    for key in &["uri", "ssh", "ssh-passphrase", "proxy"] {
+fn delete_jump_secrets(cx: &App, id: Uuid, hop_ids: impl IntoIterator<Item = Uuid>) {
+    for hop_id in hop_ids {
+        let (password_key, passphrase_key) = jump_secret_keys(hop_id);
+        KeyStore::delete_conn(cx, id, &password_key).detach();
+        KeyStore::delete_conn(cx, id, &passphrase_key).detach();
+    }
+}
+
+fn delete_conn_secrets(cx: &App, id: Uuid, hop_ids: Vec<Uuid>) {
+    for key in &["uri", "ssh", "ssh-passphrase", "proxy", "tls", "auth"] {
         KeyStore::delete_conn(cx, id, key).detach();
     }
+    delete_jump_secrets(cx, id, hop_ids);
+}
+
+fn jump_host_ids(conn: &SavedConnection) -> Vec<Uuid> {
+    conn.ssh.iter().flat_map(|ssh| ssh.jump_hosts.iter().map(|hop| hop.id)).collect()
 }
 
 impl AppState {
@@ -56,10 +99,23 @@ impl AppState {
         self.connection_by_id(connection_id).map(|conn| conn.name.clone())
     }
 
+    pub fn connection_environment(&self, connection_id: Uuid) -> Option<ConnectionEnvironment> {
+        self.connection_by_id(connection_id).and_then(|conn| conn.environment)
+    }
+
     pub fn connection_uri(&self, connection_id: Uuid) -> Option<String> {
         self.connection_by_id(connection_id).map(|conn| conn.uri.clone())
     }
 
+    /// Connection string for external tools (mongosh, mongodump, mongorestore),
+    /// with the credentials, TLS options and read-only tag the driver connects with.
+    pub fn tool_connection_uri(&self, connection_id: Uuid) -> Option<String> {
+        let conn = self.connection_by_id(connection_id)?;
+        tool_connection_uri(conn)
+            .inspect_err(|err| log::warn!("Failed to build tool connection URI: {err}"))
+            .ok()
+    }
+
     pub fn active_connections_snapshot(&self) -> HashMap<Uuid, ActiveConnection> {
         self.conn.active.clone()
     }
@@ -87,9 +143,18 @@ impl AppState {
         &mut self,
         connection_id: Uuid,
     ) -> Option<ActiveConnection> {
+        self.conn.health_monitors.remove(&connection_id);
         self.conn.active.remove(&connection_id)
     }
 
+    pub(crate) fn set_health_monitor(&mut self, connection_id: Uuid, monitor: gpui::Task<()>) {
+        self.conn.health_monitors.insert(connection_id, monitor);
+    }
+
+    pub fn connection_health(&self, connection_id: Uuid) -> Option<ConnectionHealth> {
+        self.conn.active.get(&connection_id).map(|conn| conn.health)
+    }
+
     pub fn active_connection_client(&self, connection_id: Uuid) -> Option<mongodb::Client> {
         self.conn.active.get(&connection_id).map(|conn| conn.client.clone())
     }
@@ -261,6 +326,9 @@ impl AppState {
         for existing in &mut self.connections {
             if existing.id == connection.id {
                 uri_changed = existing.uri != connection.uri;
+                let kept = jump_host_ids(&connection);
+                let removed = jump_host_ids(existing).into_iter().filter(|id| !kept.contains(id));
+                delete_jump_secrets(cx, connection.id, removed);
                 *existing = connection.clone();
                 updated = true;
                 break;
@@ -297,7 +365,8 @@ impl AppState {
     }
 
     pub fn remove_connection(&mut self, connection_id: Uuid, cx: &mut Context<Self>) {
-        delete_conn_secrets(cx, connection_id);
+        let hop_ids = self.connection_by_id(connection_id).map(jump_host_ids).unwrap_or_default();
+        delete_conn_secrets(cx, connection_id, hop_ids);
 
         let was_active = self.conn.active.contains_key(&connection_id);
 
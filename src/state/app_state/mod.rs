@@ -4,6 +4,8 @@ mod aggregation;
 mod connection;
 mod database_sessions;
 mod forge;
+mod query_history;
+mod saved_queries;
 mod selection;
 mod sessions;
 mod status;
@@ -17,8 +19,9 @@ pub(crate) use aggregation::{
     PipelineAnalysis, PipelineStage, PipelineState, StageDocCounts, StageStatsMode,
     default_stage_body,
 };
-pub(crate) use connection::write_conn_secrets;
+pub(crate) use connection::{jump_secret_keys, legacy_jump_secret_keys, write_conn_secrets};
 pub(crate) use database_sessions::DatabaseSessionStore;
+pub use saved_queries::SavedQueryRun;
 pub(crate) use sessions::SessionStore;
 pub use types::{
     ActiveTab, BsonOutputFormat, CardinalityBand, CollectionOverview, CollectionProgress,
@@ -27,10 +30,11 @@ pub use types::{
     DatabaseTransferProgress, DocumentViewMode, Encoding, ExplainBottleneck, ExplainCostBand,
     ExplainDiff, ExplainNode, ExplainOpenMode, ExplainPanelTab, ExplainRejectedPlan, ExplainRun,
     ExplainScope, ExplainSeverity, ExplainStageDelta, ExplainState, ExplainSummary,
-    ExplainViewMode, ExtendedJsonMode, ForgeTabKey, ForgeTabState, InsertMode, SchemaAnalysis,
-    SchemaCardinality, SchemaField, SchemaFieldType, SessionData, SessionDocument, SessionKey,
-    SessionState, SessionViewState, TabKey, TargetWriteMode, TransferFormat, TransferMode,
-    TransferScope, TransferTabKey, TransferTabState, View,
+    ExplainViewMode, ExtendedJsonMode, ForgeTabKey, ForgeTabState, IndexAdvice, InsertMode,
+    PagingSnapshot, SchemaAnalysis, SchemaCardinality, SchemaField, SchemaFieldType, SessionData,
+    SessionDocument, SessionKey, SessionState, SessionViewState, TabKey, TargetWriteMode,
+    TransferFormat, TransferMode, TransferScope, TransferTabKey, TransferTabState, ValidationState,
+    View,
 };
 
 use std::collections::{HashMap, HashSet};
@@ -46,7 +50,7 @@ use crate::state::StatusMessage;
 use crate::state::editor_sessions::EditorSessionStore;
 use crate::state::events::AppEvent;
 use crate::state::settings::{AppSettings, migrate_islands_tab_style_to_islands};
-use crate::state::{ConfigManager, WorkspaceState};
+use crate::state::{ConfigManager, QueryHistory, SavedQueryLibrary, WorkspaceState};
 
 use updater::UpdateStatus;
 
@@ -105,9 +109,12 @@ pub struct AppState {
 
     // Workspace persistence
     pub workspace: WorkspaceState,
+    pub query_history: QueryHistory,
+    pub saved_queries: SavedQueryLibrary,
     pub(crate) workspace_restore_pending: bool,
     pub(crate) changelog_pending: bool,
     aggregation_workspace_save_gen: Arc<AtomicU64>,
+    query_history_save_gen: Arc<AtomicU64>,
 
     // Auto-update
     pub update_status: UpdateStatus,
@@ -147,6 +154,16 @@ impl AppState {
             log::warn!("Failed to load workspace: {}", e);
             WorkspaceState::default()
         });
+        let query_history = config.load_query_history().unwrap_or_else(|e| {
+            log::warn!("Failed to load query history: {}", e);
+            QueryHistory::default()
+        });
+        let saved_queries = config
+            .load_saved_queries(&config.saved_queries_path(&settings.saved_queries_path))
+            .unwrap_or_else(|e| {
+                log::warn!("Failed to load saved queries: {}", e);
+                SavedQueryLibrary::default()
+            });
         let workspace_restore_pending = workspace.last_connection_id.is_some();
         let aggregation_workspace_save_gen = Arc::new(AtomicU64::new(0));
 
@@ -176,9 +193,12 @@ impl AppState {
             copied_tree_item: None,
             config,
             workspace,
+            query_history,
+            saved_queries,
             workspace_restore_pending,
             changelog_pending: false,
             aggregation_workspace_save_gen,
+            query_history_save_gen: Arc::new(AtomicU64::new(0)),
             update_status: UpdateStatus::Idle,
             export_progress: None,
             editor_sessions: EditorSessionStore::default(),
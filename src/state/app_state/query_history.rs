@@ -0,0 +1,171 @@
+//! Recording and restoring entries of the persisted query history.
+
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use gpui::Context;
+use mongodb::bson::Document;
+
+use crate::bson::parse_document_from_json;
+use crate::connection::{DocumentCount, QueryOptions};
+use crate::state::query_history::QueryHistoryEntry;
+use crate::state::{AppState, SessionKey};
+
+impl AppState {
+    /// Record the query currently applied to a session. The duration and
+    /// result count are filled in once its first page loads.
+    pub(in crate::state::app_state) fn record_query(&mut self, session_key: &SessionKey) {
+        let Some(session) = self.session(session_key) else {
+            return;
+        };
+        let mut entry = QueryHistoryEntry::new(
+            session_key.connection_id,
+            session_key.database.clone(),
+            session_key.collection.clone(),
+        );
+        entry.filter_raw = session.data.filter_raw.clone();
+        entry.sort_raw = session.data.sort_raw.clone();
+        entry.projection_raw = session.data.projection_raw.clone();
+        entry.query_options_raw = session.data.query_options_raw.clone();
+        let id = entry.id;
+
+        self.query_history.record(entry);
+        if let Some(session) = self.session_mut(session_key) {
+            session.data.history_entry = Some(id);
+        }
+        self.save_query_history_debounced();
+    }
+
+    /// Attach the timing and count of the first page load to the entry
+    /// recorded for the session's current query.
+    pub(crate) fn finish_query_history(
+        &mut self,
+        session_key: &SessionKey,
+        duration: Duration,
+        count: DocumentCount,
+    ) {
+        let Some(id) = self.session_mut(session_key).and_then(|s| s.data.history_entry.take())
+        else {
+            return;
+        };
+        let Some(entry) = self.query_history.get_mut(id) else {
+            return;
+        };
+        entry.duration_ms = Some(duration.as_millis() as u64);
+        entry.result_count = Some(count);
+        self.save_query_history_debounced();
+    }
+
+    /// Apply a history entry's filter, sort, projection and options to a session.
+    pub fn restore_query(
+        &mut self,
+        session_key: &SessionKey,
+        entry: &QueryHistoryEntry,
+    ) -> Result<(), String> {
+        self.apply_raw_query(
+            session_key,
+            &entry.filter_raw,
+            &entry.sort_raw,
+            &entry.projection_raw,
+            &entry.query_options_raw,
+        )
+    }
+
+    /// Parse and apply a complete query given as raw text, replacing the
+    /// session's filter, sort, projection and options, and record it.
+    pub(crate) fn apply_raw_query(
+        &mut self,
+        session_key: &SessionKey,
+        filter_raw: &str,
+        sort_raw: &str,
+        projection_raw: &str,
+        options_raw: &str,
+    ) -> Result<(), String> {
+        let filter = parse_optional(filter_raw).map_err(|e| format!("filter: {e}"))?;
+        let sort = parse_optional(sort_raw).map_err(|e| format!("sort: {e}"))?;
+        let projection = parse_optional(projection_raw).map_err(|e| format!("projection: {e}"))?;
+        let options = parse_optional(options_raw)
+            .and_then(|doc| doc.as_ref().map(QueryOptions::from_document).transpose())
+            .map_err(|e| format!("options: {e}"))?
+            .unwrap_or_default();
+
+        self.promote_preview_collection_tab(session_key);
+        let Some(session) = self.session_mut(session_key) else {
+            return Err("Collection tab is not open".to_string());
+        };
+        session.data.filter_raw = raw_or_empty(filter_raw, &filter);
+        session.data.filter = filter;
+        session.data.sort_raw = raw_or_empty(sort_raw, &sort);
+        session.data.sort = sort;
+        session.data.projection_raw = raw_or_empty(projection_raw, &projection);
+        session.data.projection = projection;
+        session.data.query_options_raw =
+            if options.is_empty() { String::new() } else { options_raw.trim().to_string() };
+        session.data.query_options = options;
+        session.data.reset_paging();
+        session.data.explain.mark_stale();
+
+        self.update_workspace_session_filters(session_key);
+        self.record_query(session_key);
+        Ok(())
+    }
+
+    /// Open the entry's collection in a tab and apply the query there.
+    pub fn restore_query_in_new_tab(
+        &mut self,
+        entry: &QueryHistoryEntry,
+        cx: &mut Context<Self>,
+    ) -> Result<SessionKey, String> {
+        if !self.is_connected(entry.connection_id) {
+            return Err("Connect to the query's connection first".to_string());
+        }
+        self.select_connection(Some(entry.connection_id), cx);
+        self.select_collection(entry.database.clone(), entry.collection.clone(), cx);
+        let session_key =
+            SessionKey::new(entry.connection_id, entry.database.clone(), entry.collection.clone());
+        self.restore_query(&session_key, entry)?;
+        Ok(session_key)
+    }
+
+    pub fn clear_query_history(&mut self) {
+        self.query_history.clear();
+        self.flush_query_history_now();
+    }
+
+    /// Write the history immediately, superseding any pending debounced save.
+    pub fn flush_query_history_now(&self) {
+        self.query_history_save_gen.fetch_add(1, Ordering::SeqCst);
+        if let Err(err) = self.config.save_query_history(&self.query_history) {
+            log::error!("Failed to save query history: {err}");
+        }
+    }
+
+    /// Debounced history save — a query records its entry and then its timing
+    /// moments later, so both land in one write off the UI thread.
+    fn save_query_history_debounced(&self) {
+        let generation = self.query_history_save_gen.fetch_add(1, Ordering::SeqCst) + 1;
+        let history_snapshot = self.query_history.clone();
+        let config = self.config.clone();
+        let generation_counter = self.query_history_save_gen.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(400));
+            if generation_counter.load(Ordering::SeqCst) != generation {
+                return;
+            }
+            if let Err(err) = config.save_query_history(&history_snapshot) {
+                log::error!("Failed to save query history: {err}");
+            }
+        });
+    }
+}
+
+fn parse_optional(raw: &str) -> Result<Option<Document>, String> {
+    match raw.trim() {
+        "" | "{}" => Ok(None),
+        trimmed => parse_document_from_json(trimmed).map(Some),
+    }
+}
+
+fn raw_or_empty(raw: &str, doc: &Option<Document>) -> String {
+    if doc.is_some() { raw.trim().to_string() } else { String::new() }
+}
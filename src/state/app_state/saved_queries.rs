@@ -0,0 +1,107 @@
+//! Saved query library: persistence and running a query in a tab.
+
+use std::path::PathBuf;
+
+use gpui::Context;
+use uuid::Uuid;
+
+use crate::state::saved_queries::{SavedQuery, SavedQueryBody, SavedQueryScope};
+use crate::state::{AppState, CollectionSubview, SessionKey};
+
+/// What running a saved query opened, so the caller can start the matching load.
+pub enum SavedQueryRun {
+    Documents(SessionKey),
+    Aggregation(SessionKey),
+    Forge,
+}
+
+impl AppState {
+    pub fn saved_queries_path(&self) -> PathBuf {
+        self.config.saved_queries_path(&self.settings.saved_queries_path)
+    }
+
+    /// Re-read the library, picking up changes made outside the app (e.g. a
+    /// `git pull` of the team file).
+    pub fn reload_saved_queries(&mut self) {
+        match self.config.load_saved_queries(&self.saved_queries_path()) {
+            Ok(library) => self.saved_queries = library,
+            Err(e) => log::warn!("Failed to load saved queries: {e}"),
+        }
+    }
+
+    pub fn set_saved_queries_path(&mut self, path: String) {
+        self.settings.saved_queries_path = path;
+        self.save_settings();
+        self.reload_saved_queries();
+    }
+
+    /// Add or replace a query. The file is re-read first so edits made by
+    /// others since it was loaded are kept.
+    pub fn save_query(&mut self, query: SavedQuery) -> Result<(), String> {
+        self.reload_saved_queries();
+        self.saved_queries.upsert(query);
+        self.write_saved_queries()
+    }
+
+    /// Open a saved query with its parameters already filled in. Collection
+    /// queries run on the selected connection; global ones on the selected
+    /// collection (or database, for scripts).
+    pub fn open_saved_query(
+        &mut self,
+        query: &SavedQuery,
+        body: &SavedQueryBody,
+        cx: &mut Context<Self>,
+    ) -> Result<SavedQueryRun, String> {
+        let Some(connection_id) = self.selected_connection_id().filter(|id| self.is_connected(*id))
+        else {
+            return Err("Connect to a server first".to_string());
+        };
+
+        match body {
+            SavedQueryBody::Find { filter, sort, projection } => {
+                let session_key = self.saved_query_session(query, connection_id, cx)?;
+                self.apply_raw_query(&session_key, filter, sort, projection, "")?;
+                self.set_collection_subview(&session_key, CollectionSubview::Documents);
+                Ok(SavedQueryRun::Documents(session_key))
+            }
+            SavedQueryBody::Aggregation { pipeline } => {
+                let session_key = self.saved_query_session(query, connection_id, cx)?;
+                self.replace_pipeline_stages(&session_key, pipeline.clone());
+                self.set_collection_subview(&session_key, CollectionSubview::Aggregation);
+                Ok(SavedQueryRun::Aggregation(session_key))
+            }
+            SavedQueryBody::Forge { script } => {
+                let database = match &query.scope {
+                    SavedQueryScope::Collection { database, .. } => Some(database.clone()),
+                    SavedQueryScope::Global => self.selected_database_name(),
+                }
+                .ok_or("Select a database to run this script")?;
+                self.open_forge_tab_with_content(connection_id, database, script.clone(), cx);
+                Ok(SavedQueryRun::Forge)
+            }
+        }
+    }
+
+    fn saved_query_session(
+        &mut self,
+        query: &SavedQuery,
+        connection_id: Uuid,
+        cx: &mut Context<Self>,
+    ) -> Result<SessionKey, String> {
+        match &query.scope {
+            SavedQueryScope::Collection { database, collection } => {
+                self.select_collection(database.clone(), collection.clone(), cx);
+                Ok(SessionKey::new(connection_id, database.clone(), collection.clone()))
+            }
+            SavedQueryScope::Global => self
+                .current_session_key()
+                .ok_or_else(|| "Open a collection to run this query".into()),
+        }
+    }
+
+    fn write_saved_queries(&self) -> Result<(), String> {
+        self.config
+            .save_saved_queries(&self.saved_queries_path(), &self.saved_queries)
+            .map_err(|e| format!("{e:#}"))
+    }
+}
@@ -0,0 +1,53 @@
+//! Undo/redo journal of committed document writes.
+
+use crate::connection::DocumentChange;
+use crate::state::AppState;
+use crate::state::app_state::types::SessionKey;
+use crate::state::journal::{JournalDirection, JournalEntry};
+
+impl AppState {
+    /// Journal a committed write so it can be undone.
+    pub(crate) fn record_operation(
+        &mut self,
+        session_key: &SessionKey,
+        label: impl Into<String>,
+        changes: Vec<DocumentChange>,
+    ) {
+        if let Some(session) = self.session_mut(session_key) {
+            session.journal.record(JournalEntry::new(label, changes));
+        }
+    }
+
+    /// Label of the operation the next undo or redo would apply.
+    pub fn journal_label(
+        &self,
+        session_key: &SessionKey,
+        direction: JournalDirection,
+    ) -> Option<&str> {
+        let journal = &self.session(session_key)?.journal;
+        match direction {
+            JournalDirection::Undo => journal.undo_label(),
+            JournalDirection::Redo => journal.redo_label(),
+        }
+    }
+
+    pub(crate) fn take_journal_entry(
+        &mut self,
+        session_key: &SessionKey,
+        direction: JournalDirection,
+    ) -> Option<JournalEntry> {
+        self.session_mut(session_key)?.journal.take(direction)
+    }
+
+    pub(crate) fn finish_journal_entry(
+        &mut self,
+        session_key: &SessionKey,
+        direction: JournalDirection,
+        entry: JournalEntry,
+        applied: bool,
+    ) {
+        if let Some(session) = self.session_mut(session_key) {
+            session.journal.finish(direction, entry, applied);
+        }
+    }
+}
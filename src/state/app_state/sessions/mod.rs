@@ -1,10 +1,12 @@
 mod aggregation;
 mod document_ops;
 mod explain;
+mod journal;
 mod model;
 mod pagination;
 mod persistence;
 mod query_ops;
 mod schema;
+mod validation;
 
 pub use model::SessionStore;
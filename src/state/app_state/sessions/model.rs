@@ -9,8 +9,8 @@ use uuid::Uuid;
 use crate::bson::DocumentKey;
 use crate::state::AppState;
 use crate::state::app_state::types::{
-    CollectionSubview, DocumentViewMode, SessionData, SessionKey, SessionSnapshot, SessionState,
-    SessionViewState,
+    CollectionSubview, DocumentViewMode, PagingSnapshot, SessionData, SessionKey, SessionSnapshot,
+    SessionState, SessionViewState,
 };
 
 #[derive(Default)]
@@ -116,6 +116,14 @@ impl AppState {
             total: session.data.total,
             page: session.data.page,
             per_page: session.data.per_page,
+            paging: PagingSnapshot {
+                mode: session.data.pagination,
+                count_strategy: session.data.count_strategy,
+                has_more: session.data.has_more,
+                count_loading: session.data.count_loading,
+                stream_offset: session.data.stream_offset,
+                stream_fetching: session.data.stream_fetching,
+            },
             is_loading: session.data.is_loading,
             selected_doc,
             selected_docs,
@@ -124,6 +132,7 @@ impl AppState {
             filter_raw: session.data.filter_raw.clone(),
             sort_raw: session.data.sort_raw.clone(),
             projection_raw: session.data.projection_raw.clone(),
+            query_options_raw: session.data.query_options_raw.clone(),
             query_options_open: session.view.query_options_open,
             filter_builder_open: session.view.filter_builder_open,
             subview: session.view.subview,
@@ -133,6 +142,7 @@ impl AppState {
             indexes: session.data.indexes.clone(),
             indexes_loading: session.data.indexes_loading,
             indexes_error: session.data.indexes_error.clone(),
+            index_usage: session.data.index_usage.clone(),
             aggregation: session.data.aggregation.clone(),
             explain: session.data.explain.clone(),
             ai_chat: session.data.ai_chat.clone(),
@@ -142,6 +152,7 @@ impl AppState {
             schema_selected_field: session.view.schema_selected_field.clone(),
             schema_expanded_fields: session.view.schema_expanded_fields.clone(),
             schema_filter: session.view.schema_filter.clone(),
+            validation: session.data.validation.clone(),
         })
     }
 
@@ -1,7 +1,12 @@
 //! Pagination operations for sessions.
 
+use mongodb::bson::Document;
+
+use crate::bson::DocumentKey;
+use crate::connection::keyset::{KeysetCursor, keyset_sort};
+use crate::connection::{CountStrategy, PaginationMode};
 use crate::state::AppState;
-use crate::state::app_state::types::SessionKey;
+use crate::state::app_state::types::{SessionData, SessionDocument, SessionKey};
 
 impl AppState {
     pub fn prev_page(&mut self, session_key: &SessionKey) -> bool {
@@ -9,16 +14,21 @@ impl AppState {
             && session.data.page > 0
         {
             session.data.page -= 1;
+            session.data.keyset = match session.data.page {
+                0 => None,
+                _ => keyset_anchor(&session.data, false),
+            };
             return true;
         }
         false
     }
 
-    pub fn next_page(&mut self, session_key: &SessionKey, total_pages: u64) -> bool {
+    pub fn next_page(&mut self, session_key: &SessionKey) -> bool {
         if let Some(session) = self.session_mut(session_key)
-            && session.data.page + 1 < total_pages
+            && session.data.has_more
         {
             session.data.page += 1;
+            session.data.keyset = keyset_anchor(&session.data, true);
             return true;
         }
         false
@@ -27,11 +37,87 @@ impl AppState {
     pub fn set_per_page(&mut self, session_key: &SessionKey, per_page: i64) {
         if let Some(session) = self.session_mut(session_key) {
             session.data.per_page = per_page;
-            session.data.page = 0;
+            session.data.reset_paging();
+        }
+    }
+
+    pub fn set_pagination_mode(&mut self, session_key: &SessionKey, mode: PaginationMode) {
+        if let Some(session) = self.session_mut(session_key) {
+            session.data.pagination = mode;
+            session.data.reset_paging();
+        }
+    }
+
+    /// Append documents read from the session's stream, then drop the oldest
+    /// beyond `window`. A document with unsaved changes is kept, along with
+    /// everything after it. Returns how many were dropped.
+    pub fn append_stream_batch(
+        &mut self,
+        session_key: &SessionKey,
+        documents: Vec<Document>,
+        window: usize,
+    ) -> usize {
+        let Some(session) = self.session_mut(session_key) else {
+            return 0;
+        };
+        let data = &mut session.data;
+        let start = data.stream_offset as usize + data.items.len();
+        data.items.extend(documents.into_iter().enumerate().map(|(idx, doc)| SessionDocument {
+            key: DocumentKey::from_document(&doc, start + idx),
+            doc,
+        }));
+
+        let excess = data.items.len().saturating_sub(window);
+        let view = &mut session.view;
+        let dropped = data.items[..excess]
+            .iter()
+            .take_while(|item| !view.drafts.contains_key(&item.key))
+            .count();
+        for item in data.items.drain(..dropped) {
+            view.selected_docs.remove(&item.key);
+            if view.selected_doc.as_ref() == Some(&item.key) {
+                view.selected_doc = None;
+                view.selected_node_id = None;
+            }
+        }
+        data.stream_offset += dropped as u64;
+        data.index_by_key =
+            data.items.iter().enumerate().map(|(idx, item)| (item.key.clone(), idx)).collect();
+        session.generation = session.generation.wrapping_add(1);
+        dropped
+    }
+
+    pub fn set_count_strategy(&mut self, session_key: &SessionKey, strategy: CountStrategy) {
+        if let Some(session) = self.session_mut(session_key) {
+            session.data.count_strategy = strategy;
         }
     }
 }
 
+impl SessionData {
+    /// Back to the first page, dropping any keyset anchor or open stream.
+    pub(crate) fn reset_paging(&mut self) {
+        self.page = 0;
+        self.keyset = None;
+        self.stream = None;
+        self.stream_offset = 0;
+        self.stream_fetching = false;
+    }
+}
+
+/// Keyset cursor past the last (`forward`) or before the first loaded document.
+fn keyset_anchor(data: &SessionData, forward: bool) -> Option<KeysetCursor> {
+    if data.pagination != PaginationMode::Keyset {
+        return None;
+    }
+    let sort = keyset_sort(data.sort.as_ref());
+    if forward {
+        data.items.last().map(|item| KeysetCursor::after(&sort, &item.doc))
+    } else {
+        data.items.first().map(|item| KeysetCursor::before(&sort, &item.doc))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::state::{AppState, SessionKey};
@@ -43,7 +129,72 @@ mod tests {
         state.ensure_session(session_key.clone());
 
         assert!(!state.prev_page(&session_key));
-        assert!(state.next_page(&session_key, 2));
+        assert!(!state.next_page(&session_key));
+        state.ensure_session(session_key.clone()).data.has_more = true;
+        assert!(state.next_page(&session_key));
+        assert!(state.prev_page(&session_key));
+    }
+
+    #[test]
+    fn keyset_paging_tracks_page_boundaries() {
+        use mongodb::bson::doc;
+
+        use crate::bson::DocumentKey;
+        use crate::connection::PaginationMode;
+        use crate::connection::keyset::KeysetDirection;
+        use crate::state::SessionDocument;
+
+        let mut state = AppState::new();
+        let session_key = SessionKey::new(uuid::Uuid::new_v4(), "db", "col");
+        state.ensure_session(session_key.clone());
+        state.set_pagination_mode(&session_key, PaginationMode::Keyset);
+        {
+            let data = &mut state.ensure_session(session_key.clone()).data;
+            data.items = [1, 2]
+                .into_iter()
+                .enumerate()
+                .map(|(idx, id)| {
+                    let doc = doc! { "_id": id };
+                    SessionDocument { key: DocumentKey::from_document(&doc, idx), doc }
+                })
+                .collect();
+            data.has_more = true;
+        }
+
+        assert!(state.next_page(&session_key));
+        let cursor = state.session_data(&session_key).and_then(|data| data.keyset.clone());
+        assert_eq!(cursor.as_ref().map(|c| c.direction), Some(KeysetDirection::After));
+        assert_eq!(cursor.map(|c| c.values), Some(doc! { "_id": 2 }));
+
+        // Back on the first page there is no anchor.
         assert!(state.prev_page(&session_key));
+        assert!(state.session_data(&session_key).is_some_and(|data| data.keyset.is_none()));
+
+        state.set_per_page(&session_key, 25);
+        assert_eq!(state.session_data(&session_key).map(|data| data.page), Some(0));
+    }
+
+    #[test]
+    fn stream_window_drops_oldest_clean_documents() {
+        use mongodb::bson::doc;
+
+        use crate::bson::DocumentKey;
+
+        let mut state = AppState::new();
+        let session_key = SessionKey::new(uuid::Uuid::new_v4(), "db", "col");
+        state.ensure_session(session_key.clone());
+        let batch = |ids: std::ops::Range<i32>| ids.map(|id| doc! { "_id": id }).collect();
+
+        assert_eq!(state.append_stream_batch(&session_key, batch(0..3), 4), 0);
+        let second = DocumentKey::from_document(&doc! { "_id": 1 }, 0);
+        state.ensure_session(session_key.clone()).view.drafts.insert(second, doc! { "_id": 1 });
+
+        // Two over the window, but the edited second document stops the drop.
+        assert_eq!(state.append_stream_batch(&session_key, batch(3..6), 4), 1);
+        let data = state.session_data(&session_key).unwrap();
+        assert_eq!(data.stream_offset, 1);
+        assert_eq!(data.items.len(), 5);
+        assert_eq!(data.items[0].doc, doc! { "_id": 1 });
+        assert_eq!(data.index_by_key.len(), 5);
     }
 }
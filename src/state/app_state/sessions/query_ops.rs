@@ -1,7 +1,8 @@
-//! Filter, sort, and projection operations for sessions.
+//! Filter, sort, projection and query option operations for sessions.
 
 use mongodb::bson::Document;
 
+use crate::connection::QueryOptions;
 use crate::state::AppState;
 use crate::state::app_state::types::{CollectionSubview, SessionKey};
 
@@ -15,10 +16,11 @@ impl AppState {
         if let Some(session) = self.session_mut(session_key) {
             session.data.filter_raw = raw;
             session.data.filter = filter;
-            session.data.page = 0;
+            session.data.reset_paging();
             session.data.explain.mark_stale();
         }
         self.update_workspace_session_filters(session_key);
+        self.record_query(session_key);
     }
 
     pub fn clear_filter(&mut self, session_key: &SessionKey) {
@@ -26,10 +28,11 @@ impl AppState {
         if let Some(session) = self.session_mut(session_key) {
             session.data.filter_raw.clear();
             session.data.filter = None;
-            session.data.page = 0;
+            session.data.reset_paging();
             session.data.explain.mark_stale();
         }
         self.update_workspace_session_filters(session_key);
+        self.record_query(session_key);
     }
 
     pub fn set_sort_projection(
@@ -46,7 +49,24 @@ impl AppState {
             session.data.sort = sort;
             session.data.projection_raw = projection_raw;
             session.data.projection = projection;
-            session.data.page = 0;
+            session.data.reset_paging();
+            session.data.explain.mark_stale();
+        }
+        self.update_workspace_session_filters(session_key);
+        self.record_query(session_key);
+    }
+
+    pub fn set_query_options(
+        &mut self,
+        session_key: &SessionKey,
+        raw: String,
+        options: QueryOptions,
+    ) {
+        self.promote_preview_collection_tab(session_key);
+        if let Some(session) = self.session_mut(session_key) {
+            session.data.query_options_raw = raw;
+            session.data.query_options = options;
+            session.data.reset_paging();
             session.data.explain.mark_stale();
         }
         self.update_workspace_session_filters(session_key);
@@ -74,6 +94,13 @@ impl AppState {
         }
     }
 
+    pub fn save_query_options_draft(&mut self, session_key: &SessionKey, raw: String) {
+        if let Some(session) = self.session_mut(session_key) {
+            session.data.query_options_raw = raw;
+            session.data.explain.mark_stale();
+        }
+    }
+
     pub fn set_query_options_open(&mut self, session_key: &SessionKey, open: bool) {
         self.promote_preview_collection_tab(session_key);
         let mut changed = false;
@@ -140,6 +167,11 @@ impl AppState {
                     !session.data.schema_loading
                         && (session.data.schema.is_none() || session.data.schema_error.is_some())
                 }
+                CollectionSubview::Validation => {
+                    let validation = &session.data.validation;
+                    !validation.loading
+                        && (validation.current.is_none() || validation.error.is_some())
+                }
                 _ => false,
             };
             changed = true;
@@ -0,0 +1,127 @@
+//! Validation-rule session state operations.
+
+use mongodb::options::{ValidationAction, ValidationLevel};
+
+use crate::bson::{document_to_shell_string, format_relaxed_json_value};
+use crate::connection::ValidationRules;
+use crate::state::AppState;
+use crate::state::app_state::types::SessionKey;
+use crate::state::commands::schema_to_json_schema;
+
+impl AppState {
+    /// Store rules read from the server and reset the draft to them.
+    pub fn set_validation_rules(&mut self, session_key: &SessionKey, rules: ValidationRules) {
+        if let Some(session) = self.session_mut(session_key) {
+            let validation = &mut session.data.validation;
+            validation.loading = false;
+            validation.error = None;
+            validation.current = Some(rules);
+        }
+        self.reset_validation_draft(session_key);
+    }
+
+    /// Replace the draft with the rules on the server.
+    pub fn reset_validation_draft(&mut self, session_key: &SessionKey) {
+        let Some(session) = self.session_mut(session_key) else {
+            return;
+        };
+        let validation = &mut session.data.validation;
+        let current = validation.current.clone().unwrap_or_default();
+        validation.draft_raw =
+            current.validator.as_ref().map(document_to_shell_string).unwrap_or_default();
+        validation.draft_level = current.level.unwrap_or(ValidationLevel::Strict);
+        validation.draft_action = current.action.unwrap_or(ValidationAction::Error);
+        validation.draft_revision += 1;
+        validation.check = None;
+        validation.check_error = None;
+    }
+
+    /// Text typed into the validator editor.
+    pub fn set_validation_draft(&mut self, session_key: &SessionKey, raw: String) {
+        if let Some(session) = self.session_mut(session_key) {
+            session.data.validation.draft_raw = raw;
+        }
+    }
+
+    pub fn set_validation_draft_level(&mut self, session_key: &SessionKey, level: ValidationLevel) {
+        if let Some(session) = self.session_mut(session_key) {
+            session.data.validation.draft_level = level;
+        }
+    }
+
+    pub fn set_validation_draft_action(
+        &mut self,
+        session_key: &SessionKey,
+        action: ValidationAction,
+    ) {
+        if let Some(session) = self.session_mut(session_key) {
+            session.data.validation.draft_action = action;
+        }
+    }
+
+    /// Replace the draft with a `$jsonSchema` validator built from the schema
+    /// analysis. Returns `false` when the collection has not been analyzed.
+    pub fn fill_validation_starter(&mut self, session_key: &SessionKey) -> bool {
+        let Some(session) = self.session_mut(session_key) else {
+            return false;
+        };
+        session.data.validation.starter_pending = false;
+        let Some(schema) = &session.data.schema else {
+            return false;
+        };
+        let json_schema = serde_json::from_str::<serde_json::Value>(&schema_to_json_schema(schema))
+            .unwrap_or_default();
+        let validator = serde_json::json!({ "$jsonSchema": json_schema });
+        let validation = &mut session.data.validation;
+        validation.draft_raw = format_relaxed_json_value(&validator);
+        validation.draft_revision += 1;
+        validation.check = None;
+        validation.check_error = None;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mongodb::bson::doc;
+
+    use super::*;
+    use crate::state::commands::build_schema_analysis;
+
+    #[test]
+    fn draft_follows_loaded_rules_and_schema_starter() {
+        let mut state = AppState::new();
+        let key = SessionKey::new(uuid::Uuid::new_v4(), "db", "col");
+        state.ensure_session(key.clone());
+
+        state.set_validation_rules(
+            &key,
+            ValidationRules {
+                validator: Some(doc! { "age": { "$gte": 0 } }),
+                level: Some(ValidationLevel::Moderate),
+                action: None,
+            },
+        );
+        let validation = &state.session(&key).unwrap().data.validation;
+        assert!(validation.draft_raw.contains("$gte"));
+        assert_eq!(validation.draft_level, ValidationLevel::Moderate);
+        assert_eq!(validation.draft_action, ValidationAction::Error);
+        let revision = validation.draft_revision;
+
+        assert!(!state.fill_validation_starter(&key));
+        state.session_mut(&key).unwrap().data.schema =
+            Some(build_schema_analysis(&[doc! { "name": "a" }, doc! { "name": "b" }], 2));
+        assert!(state.fill_validation_starter(&key));
+        let validation = &state.session(&key).unwrap().data.validation;
+        assert!(validation.draft_raw.contains("$jsonSchema"));
+        assert!(validation.draft_raw.contains("required"));
+        assert_eq!(validation.draft_revision, revision + 1);
+        assert!(!validation.draft_removes_validation());
+
+        for raw in ["", "  {} ", "{\n}"] {
+            state.set_validation_draft(&key, raw.to_string());
+            let validation = &state.session(&key).unwrap().data.validation;
+            assert!(validation.draft_removes_validation(), "{raw:?}");
+        }
+    }
+}
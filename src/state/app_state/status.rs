@@ -1,5 +1,6 @@
 //! Status message updates derived from events.
 
+use crate::connection::{DocumentCount, IndexChange};
 use crate::state::StatusMessage;
 use crate::state::app_state::{
     CollectionProgress, CollectionTransferStatus, DatabaseTransferProgress,
@@ -25,6 +26,19 @@ impl AppState {
                     "Connection failed: {error}"
                 ))));
             }
+            AppEvent::ConnectionLost { error, .. } => {
+                self.set_status_message(Some(StatusMessage::error(format!(
+                    "Connection lost: {error}"
+                ))));
+            }
+            AppEvent::Reconnecting { attempt, .. } => {
+                self.set_status_message(Some(StatusMessage::info(format!(
+                    "Reconnecting (attempt {attempt})..."
+                ))));
+            }
+            AppEvent::Reconnected(_) => {
+                self.set_status_message(Some(StatusMessage::info("Reconnected")));
+            }
             AppEvent::ConnectionUpdated => {
                 self.set_status_message(Some(StatusMessage::info("Connection updated")));
             }
@@ -49,8 +63,15 @@ impl AppState {
                 ))));
             }
             AppEvent::DocumentsLoaded { total, .. } => {
-                self.set_status_message(Some(StatusMessage::info(format!(
-                    "Loaded {total} documents"
+                let message = match total {
+                    DocumentCount::Unknown => "Loaded documents".to_string(),
+                    total => format!("Loaded {total} documents"),
+                };
+                self.set_status_message(Some(StatusMessage::info(message)));
+            }
+            AppEvent::DocumentsStreamFailed { error, .. } => {
+                self.set_status_message(Some(StatusMessage::error(format!(
+                    "Loading more documents failed: {error}"
                 ))));
             }
             AppEvent::DocumentInserted => {
@@ -81,6 +102,21 @@ impl AppState {
                     "Save failed: {error}"
                 ))));
             }
+            AppEvent::DocumentsSaved { count, .. } => {
+                self.set_status_message(Some(StatusMessage::info(format!(
+                    "Saved {count} documents in one transaction"
+                ))));
+            }
+            AppEvent::DocumentsSaveFailed { count, error, .. } => {
+                self.set_status_message(Some(StatusMessage::error(format!(
+                    "Failed to save {count} documents, none were written: {error}"
+                ))));
+            }
+            AppEvent::DocumentSaveConflict { .. } => {
+                self.set_status_message(Some(StatusMessage::error(
+                    "Document changed on the server since it was loaded; review the conflict",
+                )));
+            }
             AppEvent::DocumentDeleted { .. } => {
                 self.set_status_message(Some(StatusMessage::info("Document deleted")));
             }
@@ -121,7 +157,23 @@ impl AppState {
                     "Create index failed: {error}"
                 ))));
             }
-            AppEvent::DocumentsUpdated { matched, modified, .. } => {
+            AppEvent::IndexModified { name, change, .. } => {
+                let message = match change {
+                    IndexChange::Hide => format!("Index {name} hidden"),
+                    IndexChange::Unhide => format!("Index {name} unhidden"),
+                    IndexChange::ExpireAfter(seconds) => {
+                        format!("Index {name} now expires documents after {seconds}s")
+                    }
+                    IndexChange::ConvertToUnique => format!("Index {name} is now unique"),
+                };
+                self.set_status_message(Some(StatusMessage::info(message)));
+            }
+            AppEvent::IndexModifyFailed { error, .. } => {
+                self.set_status_message(Some(StatusMessage::error(format!(
+                    "Modify index failed: {error}"
+                ))));
+            }
+            AppEvent::DocumentsUpdated { matched, modified, skipped, .. } => {
                 let message = if *matched == 0 {
                     "No documents matched the update.".to_string()
                 } else if *modified == 0 {
@@ -129,24 +181,21 @@ impl AppState {
                 } else {
                     format!("Updated {modified} of {matched} documents")
                 };
-                self.set_status_message(Some(StatusMessage::info(message)));
+                self.set_status_message(Some(with_skipped(message, *skipped)));
             }
             AppEvent::DocumentsUpdateFailed { error, .. } => {
                 self.set_status_message(Some(StatusMessage::error(format!(
                     "Update failed: {error}"
                 ))));
             }
-            AppEvent::DocumentsDeleted { session, deleted } => {
+            AppEvent::DocumentsDeleted { session, deleted, skipped } => {
                 let _ = session;
-                if *deleted == 0 {
-                    self.set_status_message(Some(StatusMessage::info(
-                        "No documents matched the delete.".to_string(),
-                    )));
+                let message = if *deleted == 0 {
+                    "No documents matched the delete.".to_string()
                 } else {
-                    self.set_status_message(Some(StatusMessage::info(format!(
-                        "Deleted {deleted} document(s)"
-                    ))));
-                }
+                    format!("Deleted {deleted} document(s)")
+                };
+                self.set_status_message(Some(with_skipped(message, *skipped)));
             }
             AppEvent::DocumentsDeleteFailed { session, error } => {
                 let _ = session;
@@ -252,6 +301,14 @@ impl AppState {
                     "Schema analysis failed: {error}"
                 ))));
             }
+            AppEvent::ValidationLoadFailed { error, .. } => {
+                self.set_status_message(Some(StatusMessage::error(format!(
+                    "Loading validation rules failed: {error}"
+                ))));
+            }
+            AppEvent::ValidationApplied { .. } => {
+                self.set_status_message(Some(StatusMessage::info("Validation rules applied")));
+            }
             AppEvent::UpdateAvailable { version } => {
                 self.set_status_message(Some(StatusMessage::info(format!(
                     "Update available: v{version}"
@@ -261,3 +318,15 @@ impl AppState {
         }
     }
 }
+
+/// Note documents a journaled bulk write left out because they matched only
+/// after its pre-read; running the write again picks them up.
+fn with_skipped(message: String, skipped: u64) -> StatusMessage {
+    if skipped == 0 {
+        return StatusMessage::info(message);
+    }
+    StatusMessage::error(format!(
+        "{message}; {skipped} document(s) started matching during the write and were left \
+         unchanged. Run it again to include them."
+    ))
+}
@@ -1,4 +1,5 @@
 use crate::bson::parse_document_from_json;
+use crate::connection::QueryOptions;
 use crate::state::app_state::StageDocCounts;
 use std::collections::{HashMap, HashSet};
 
@@ -208,6 +209,15 @@ impl AppState {
                 session.data.projection_raw = raw;
                 session.data.projection = doc;
             });
+            restore_doc_option(&tab.query_options_raw, |raw, doc| {
+                match doc.as_ref().map(QueryOptions::from_document).transpose() {
+                    Ok(options) => {
+                        session.data.query_options_raw = raw;
+                        session.data.query_options = options.unwrap_or_default();
+                    }
+                    Err(e) => log::warn!("Invalid query options, resetting to empty: {e}"),
+                }
+            });
             session.view.table_column_widths = tab.table_column_widths.clone();
             session.view.table_column_order = tab.table_column_order.clone();
             session.view.table_pinned_columns = tab.table_pinned_columns.clone();
@@ -238,6 +248,7 @@ impl AppState {
                     filter_raw,
                     sort_raw,
                     projection_raw,
+                    query_options_raw,
                     aggregation_pipeline,
                     subview,
                     stats_open,
@@ -252,6 +263,7 @@ impl AppState {
                             session.data.filter_raw.clone(),
                             session.data.sort_raw.clone(),
                             session.data.projection_raw.clone(),
+                            session.data.query_options_raw.clone(),
                             session.data.aggregation.stages.clone(),
                             session.view.subview,
                             matches!(session.view.subview, CollectionSubview::Stats),
@@ -266,6 +278,7 @@ impl AppState {
                             String::new(),
                             String::new(),
                             String::new(),
+                            String::new(),
                             Vec::new(),
                             CollectionSubview::Documents,
                             false,
@@ -283,6 +296,7 @@ impl AppState {
                     filter_raw,
                     sort_raw,
                     projection_raw,
+                    query_options_raw,
                     aggregation_pipeline,
                     stats_open,
                     subview,
@@ -305,6 +319,7 @@ impl AppState {
                 filter_raw: String::new(),
                 sort_raw: String::new(),
                 projection_raw: String::new(),
+                query_options_raw: String::new(),
                 aggregation_pipeline: Vec::new(),
                 stats_open: false,
                 subview: CollectionSubview::Documents,
@@ -328,6 +343,7 @@ impl AppState {
                     filter_raw: String::new(),
                     sort_raw: String::new(),
                     projection_raw: String::new(),
+                    query_options_raw: String::new(),
                     aggregation_pipeline: Vec::new(),
                     stats_open: false,
                     subview: CollectionSubview::Documents,
@@ -356,6 +372,7 @@ impl AppState {
                     filter_raw: String::new(),
                     sort_raw: String::new(),
                     projection_raw: String::new(),
+                    query_options_raw: String::new(),
                     aggregation_pipeline: Vec::new(),
                     stats_open: false,
                     subview: CollectionSubview::Documents,
@@ -380,6 +397,7 @@ impl AppState {
                     filter_raw: String::new(),
                     sort_raw: String::new(),
                     projection_raw: String::new(),
+                    query_options_raw: String::new(),
                     aggregation_pipeline: Vec::new(),
                     stats_open: false,
                     subview: CollectionSubview::Documents,
@@ -499,6 +517,29 @@ mod tests {
         assert_eq!(state.workspace.open_tabs[1].collection, "preview");
     }
 
+    #[test]
+    fn workspace_roundtrips_query_options() {
+        let mut state = AppState::new();
+        let conn_id = Uuid::new_v4();
+        let session = SessionKey::new(conn_id, "db", "col");
+        let raw = r#"{"hint": "status_1", "maxTimeMS": 500}"#;
+
+        state.conn.selected_connection = Some(conn_id);
+        state.ensure_session(session.clone());
+        state.tabs.open.push(TabKey::Collection(session.clone()));
+        let options = QueryOptions::from_document(&parse_document_from_json(raw).unwrap()).unwrap();
+        state.set_query_options(&session, raw.to_string(), options);
+        state.update_workspace_tabs();
+        assert_eq!(state.workspace.open_tabs[0].query_options_raw, raw);
+
+        let mut restored = AppState::new();
+        restored.workspace = state.workspace.clone();
+        let _active = restored.restore_tabs_from_workspace(conn_id, &["db".to_string()]);
+        let data = restored.session_data(&session).expect("session should be restored");
+        assert_eq!(data.query_options_raw, raw);
+        assert_eq!(data.query_options.max_time, Some(std::time::Duration::from_millis(500)));
+    }
+
     #[test]
     fn workspace_roundtrips_ai_chat_state() {
         let mut state = AppState::new();
@@ -541,6 +582,7 @@ mod tests {
             filter_raw: String::new(),
             sort_raw: String::new(),
             projection_raw: String::new(),
+            query_options_raw: String::new(),
             aggregation_pipeline: Vec::new(),
             stats_open: false,
             subview: CollectionSubview::Documents,
@@ -5,12 +5,21 @@ use std::sync::atomic::AtomicU64;
 use std::sync::{Arc, Mutex};
 
 use crate::ai::AiChatState;
-use crate::bson::DocumentKey;
+use crate::bson::{DocumentKey, parse_document_from_json};
+use crate::connection::keyset::KeysetCursor;
+use crate::connection::ops::explain::SampleSelectivity;
+use crate::connection::{
+    CountStrategy, DocumentCount, DocumentStream, IndexBuild, IndexUsage, PaginationMode,
+    QueryOptions, ValidationCheck, ValidationRules,
+};
 use crate::models::connection::ActiveConnection;
 use crate::state::app_state::PipelineState;
+use crate::state::commands::IndexSuggestion;
+use crate::state::journal::OperationJournal;
 use futures::future::AbortHandle;
 use mongodb::IndexModel;
 use mongodb::bson::{Bson, Document};
+use mongodb::options::{ValidationAction, ValidationLevel};
 use mongodb::results::{CollectionSpecification, CollectionType};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -45,6 +54,7 @@ pub enum CollectionSubview {
     Stats,
     Aggregation,
     Schema,
+    Validation,
 }
 
 impl CollectionSubview {
@@ -54,6 +64,7 @@ impl CollectionSubview {
             2 => Self::Stats,
             3 => Self::Aggregation,
             4 => Self::Schema,
+            5 => Self::Validation,
             _ => Self::Documents,
         }
     }
@@ -65,6 +76,7 @@ impl CollectionSubview {
             Self::Stats => 2,
             Self::Aggregation => 3,
             Self::Schema => 4,
+            Self::Validation => 5,
         }
     }
 }
@@ -508,6 +520,8 @@ pub struct ConnectionState {
     pub selected_collection: Option<String>,
     /// Remembered selection per connection (db, collection)
     pub selection_cache: HashMap<Uuid, (Option<String>, Option<String>)>,
+    /// Health monitor per active connection; dropping the task stops it
+    pub health_monitors: HashMap<Uuid, gpui::Task<()>>,
 }
 
 /// Tab management state
@@ -733,6 +747,16 @@ pub struct ExplainState {
     pub diff: Option<ExplainDiff>,
     pub signature: Option<u64>,
     pub generated_at_unix_ms: Option<u64>,
+    /// Index proposed for the last find explain that scanned or sorted.
+    pub advice: Option<IndexAdvice>,
+}
+
+/// Index suggestion for an explained find, with its sampled selectivity.
+#[derive(Debug, Clone)]
+pub struct IndexAdvice {
+    pub suggestion: IndexSuggestion,
+    pub selectivity: Option<SampleSelectivity>,
+    pub selectivity_loading: bool,
 }
 
 impl ExplainState {
@@ -938,6 +962,7 @@ impl Default for ExplainState {
             diff: None,
             signature: None,
             generated_at_unix_ms: None,
+            advice: None,
         }
     }
 }
@@ -1095,6 +1120,55 @@ pub struct SchemaAnalysis {
     pub cardinality: HashMap<String, SchemaCardinality>,
 }
 
+/// Validation subview: the rules on the server and the draft being edited.
+#[derive(Debug, Clone)]
+pub struct ValidationState {
+    /// Rules last read from the server; `None` until loaded.
+    pub current: Option<ValidationRules>,
+    pub loading: bool,
+    pub error: Option<String>,
+    /// Validator being edited, as relaxed JSON; empty removes validation.
+    pub draft_raw: String,
+    pub draft_level: ValidationLevel,
+    pub draft_action: ValidationAction,
+    /// Bumped when the draft is replaced rather than typed, so the editor
+    /// picks up the new text.
+    pub draft_revision: u64,
+    pub check: Option<ValidationCheck>,
+    pub checking: bool,
+    pub check_error: Option<String>,
+    pub applying: bool,
+    /// Fill the draft from the schema once the running analysis finishes.
+    pub starter_pending: bool,
+}
+
+impl Default for ValidationState {
+    fn default() -> Self {
+        Self {
+            current: None,
+            loading: false,
+            error: None,
+            draft_raw: String::new(),
+            draft_level: ValidationLevel::Strict,
+            draft_action: ValidationAction::Error,
+            draft_revision: 0,
+            check: None,
+            checking: false,
+            check_error: None,
+            applying: false,
+            starter_pending: false,
+        }
+    }
+}
+
+impl ValidationState {
+    /// Whether applying the draft clears the validator (empty or `{}`).
+    pub fn draft_removes_validation(&self) -> bool {
+        let raw = self.draft_raw.trim();
+        raw.is_empty() || parse_document_from_json(raw).is_ok_and(|doc| doc.is_empty())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct SessionDocument {
     pub key: DocumentKey,
@@ -1107,7 +1181,21 @@ pub struct SessionData {
     pub index_by_key: HashMap<DocumentKey, usize>,
     pub page: u64,
     pub per_page: i64,
-    pub total: u64,
+    pub total: DocumentCount,
+    /// Whether a page follows the loaded one.
+    pub has_more: bool,
+    pub pagination: PaginationMode,
+    pub count_strategy: CountStrategy,
+    /// Anchor of the current page in keyset mode; `None` on the first page.
+    pub keyset: Option<KeysetCursor>,
+    /// Open cursor in stream mode. Dropping the session drops it, which
+    /// kills the cursor.
+    pub stream: Option<Arc<Mutex<DocumentStream>>>,
+    /// Documents dropped from the front of the stream to stay within the
+    /// window.
+    pub stream_offset: u64,
+    pub stream_fetching: bool,
+    pub count_loading: bool,
     pub is_loading: bool,
     pub loaded: bool,
     pub request_id: u64,
@@ -1117,18 +1205,30 @@ pub struct SessionData {
     pub sort: Option<Document>,
     pub projection_raw: String,
     pub projection: Option<Document>,
+    pub query_options_raw: String,
+    pub query_options: QueryOptions,
+    /// History entry of the applied query, awaiting its first page load.
+    pub history_entry: Option<Uuid>,
     pub stats: Option<CollectionStats>,
     pub stats_loading: bool,
     pub stats_error: Option<String>,
     pub indexes: Option<Vec<IndexModel>>,
     pub indexes_loading: bool,
     pub indexes_error: Option<String>,
+    /// Usage by index name; `None` when `$indexStats` is unavailable.
+    pub index_usage: Option<HashMap<String, IndexUsage>>,
+    /// Index builds running on the collection while they are watched.
+    pub index_builds: Vec<IndexBuild>,
+    pub index_builds_watching: bool,
+    /// `createIndexes` commands sent from this session that have not returned.
+    pub index_creates_in_flight: usize,
     pub aggregation: PipelineState,
     pub explain: ExplainState,
     pub ai_chat: AiChatState,
     pub schema: Option<SchemaAnalysis>,
     pub schema_loading: bool,
     pub schema_error: Option<String>,
+    pub validation: ValidationState,
 }
 
 impl Default for SessionData {
@@ -1138,7 +1238,15 @@ impl Default for SessionData {
             index_by_key: HashMap::new(),
             page: 0,
             per_page: 50,
-            total: 0,
+            total: DocumentCount::default(),
+            has_more: false,
+            pagination: PaginationMode::default(),
+            count_strategy: CountStrategy::default(),
+            keyset: None,
+            stream: None,
+            stream_offset: 0,
+            stream_fetching: false,
+            count_loading: false,
             is_loading: false,
             loaded: false,
             request_id: 0,
@@ -1148,18 +1256,26 @@ impl Default for SessionData {
             sort: None,
             projection_raw: String::new(),
             projection: None,
+            query_options_raw: String::new(),
+            query_options: QueryOptions::default(),
+            history_entry: None,
             stats: None,
             stats_loading: false,
             stats_error: None,
             indexes: None,
             indexes_loading: false,
             indexes_error: None,
+            index_usage: None,
+            index_builds: Vec::new(),
+            index_builds_watching: false,
+            index_creates_in_flight: 0,
             aggregation: PipelineState::default(),
             explain: ExplainState::default(),
             ai_chat: AiChatState::default(),
             schema: None,
             schema_loading: false,
             schema_error: None,
+            validation: ValidationState::default(),
         }
     }
 }
@@ -1199,14 +1315,29 @@ pub struct SessionState {
     /// Monotonically increasing counter bumped on document load, save, or delete.
     /// Used by the tree cache to detect stale entries.
     pub generation: u64,
+    /// Committed writes that can be undone or redone.
+    pub journal: OperationJournal,
+}
+
+/// Pagination settings and state shown by the pagination bar.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PagingSnapshot {
+    pub mode: PaginationMode,
+    pub count_strategy: CountStrategy,
+    pub has_more: bool,
+    pub count_loading: bool,
+    /// Position of the first loaded document in stream mode.
+    pub stream_offset: u64,
+    pub stream_fetching: bool,
 }
 
 #[derive(Debug, Clone)]
 pub struct SessionSnapshot {
     pub items: Vec<SessionDocument>,
-    pub total: u64,
+    pub total: DocumentCount,
     pub page: u64,
     pub per_page: i64,
+    pub paging: PagingSnapshot,
     pub is_loading: bool,
     pub selected_doc: Option<DocumentKey>,
     pub selected_docs: HashSet<DocumentKey>,
@@ -1215,6 +1346,7 @@ pub struct SessionSnapshot {
     pub filter_raw: String,
     pub sort_raw: String,
     pub projection_raw: String,
+    pub query_options_raw: String,
     pub query_options_open: bool,
     pub filter_builder_open: bool,
     pub subview: CollectionSubview,
@@ -1224,6 +1356,8 @@ pub struct SessionSnapshot {
     pub indexes: Option<Vec<IndexModel>>,
     pub indexes_loading: bool,
     pub indexes_error: Option<String>,
+    /// Usage by index name; `None` when `$indexStats` is unavailable.
+    pub index_usage: Option<HashMap<String, IndexUsage>>,
     pub aggregation: PipelineState,
     pub explain: ExplainState,
     pub ai_chat: AiChatState,
@@ -1233,6 +1367,7 @@ pub struct SessionSnapshot {
     pub schema_selected_field: Option<String>,
     pub schema_expanded_fields: HashSet<String>,
     pub schema_filter: String,
+    pub validation: ValidationState,
 }
 
 #[derive(Debug, Clone)]
@@ -1,6 +1,7 @@
 use gpui::{App, AppContext as _, Entity};
 use uuid::Uuid;
 
+use crate::connection::CreateCollectionOptions;
 use crate::state::{AppEvent, AppState, StatusMessage};
 
 use super::AppCommands;
@@ -11,6 +12,7 @@ impl AppCommands {
         state: Entity<AppState>,
         database: String,
         collection: String,
+        options: CreateCollectionOptions,
         cx: &mut App,
     ) {
         let connection_id = state.read(cx).selected_connection_id();
@@ -28,7 +30,7 @@ impl AppCommands {
         let task = cx.background_spawn({
             let database = database.clone();
             let collection = collection.clone();
-            async move { manager.create_collection(&client, &database, &collection) }
+            async move { manager.create_collection(&client, &database, &collection, &options) }
         });
 
         cx.spawn({
@@ -2,7 +2,7 @@ use chrono::Utc;
 use gpui::{App, AppContext as _, Entity};
 use uuid::Uuid;
 
-use crate::models::ActiveConnection;
+use crate::models::{ActiveConnection, ConnectionHealth};
 use crate::state::{AppEvent, AppState, StatusMessage, View};
 
 use super::AppCommands;
@@ -67,6 +67,7 @@ impl AppCommands {
                                     databases: databases.clone(),
                                     collections: std::collections::HashMap::new(),
                                     runtime_meta,
+                                    health: ConnectionHealth::Healthy,
                                 },
                             );
                             state.update_connection(saved, cx);
@@ -81,6 +82,7 @@ impl AppCommands {
                             cx.emit(loaded);
                             cx.notify();
                         });
+                        Self::monitor_connection(state.clone(), connection_id, cx);
                     }
                     Err(e) => {
                         log::error!("Failed to connect: {}", e);
@@ -1,5 +1,6 @@
 use gpui::{App, AppContext as _, Entity};
 
+use crate::connection::CreateCollectionOptions;
 use crate::state::{
     AppEvent, AppState, CollectionOverview, DatabaseKey, DatabaseStats, StatusMessage, View,
 };
@@ -18,7 +19,13 @@ impl AppCommands {
         if !Self::ensure_writable(&state, connection_id, cx) {
             return;
         }
-        Self::create_collection(state, database, collection, cx);
+        Self::create_collection(
+            state,
+            database,
+            collection,
+            CreateCollectionOptions::default(),
+            cx,
+        );
     }
 
     /// Drop a database.
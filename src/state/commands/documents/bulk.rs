@@ -1,11 +1,19 @@
 use gpui::{App, AppContext as _, Entity};
 use mongodb::bson::Document;
 
+use crate::connection::DocumentChange;
 use crate::state::AppCommands;
+use crate::state::journal::JOURNAL_MAX_DOCUMENTS;
 use crate::state::{AppEvent, AppState, SessionKey};
 
+use super::journal::{
+    capture_before, capture_changes, count_skipped, document_ids, journaled_filter,
+    operation_label, with_object_id,
+};
+
 impl AppCommands {
-    /// Insert multiple documents into a collection.
+    /// Insert multiple documents into a collection, in one transaction when
+    /// enabled in settings and the deployment supports it.
     pub fn insert_documents(
         state: Entity<AppState>,
         session_key: SessionKey,
@@ -22,21 +30,44 @@ impl AppCommands {
         let database = session_key.database.clone();
         let collection = session_key.collection.clone();
         let manager = state.read(cx).connection_manager();
+        let transactional = count > 1 && state.read(cx).settings.transactional_batches;
+        let documents: Vec<Document> = documents.into_iter().map(with_object_id).collect();
 
         let task = cx.background_spawn({
             let database = database.clone();
             let collection = collection.clone();
-            async move { manager.insert_documents(&client, &database, &collection, documents) }
+            async move {
+                let ids = document_ids(&documents);
+                let inserted = if transactional && manager.supports_transactions(&client)? {
+                    manager.insert_documents_transactional(
+                        &client,
+                        &database,
+                        &collection,
+                        documents,
+                    )?
+                } else {
+                    manager.insert_documents(&client, &database, &collection, documents)?
+                };
+                let before = (ids.len() <= JOURNAL_MAX_DOCUMENTS).then(Vec::new);
+                let changes =
+                    capture_changes(&manager, &client, &database, &collection, before, ids);
+                Ok((inserted, changes))
+            }
         });
 
         cx.spawn({
             let state = state.clone();
             let session_key = session_key.clone();
             async move |cx: &mut gpui::AsyncApp| {
-                let result: Result<usize, crate::error::Error> = task.await;
+                let result: Result<(usize, Option<Vec<DocumentChange>>), crate::error::Error> =
+                    task.await;
                 let _ = cx.update(|cx| match result {
-                    Ok(inserted) => {
+                    Ok((inserted, changes)) => {
                         state.update(cx, |state, cx| {
+                            if let Some(changes) = changes {
+                                let label = operation_label("insert", changes.len());
+                                state.record_operation(&session_key, label, changes);
+                            }
                             let event = AppEvent::DocumentsInserted { count: inserted };
                             state.update_status_from_event(&event);
                             cx.emit(event);
@@ -86,23 +117,42 @@ impl AppCommands {
             let database = database.clone();
             let collection = collection.clone();
             let update = update.clone();
-            async move { manager.update_many(&client, &database, &collection, filter, update) }
+            async move {
+                let before =
+                    capture_before(&manager, &client, &database, &collection, filter.clone());
+                let journaled = journaled_filter(filter.clone(), &before);
+                let ids = before.as_deref().map(document_ids).unwrap_or_default();
+                let result =
+                    manager.update_many(&client, &database, &collection, journaled, update)?;
+                let skipped =
+                    count_skipped(&manager, &client, &database, &collection, filter, &before);
+                let changes =
+                    capture_changes(&manager, &client, &database, &collection, before, ids);
+                Ok((result, skipped, changes))
+            }
         });
 
         cx.spawn({
             let state = state.clone();
             let session_key = session_key.clone();
             async move |cx: &mut gpui::AsyncApp| {
-                let result: Result<mongodb::results::UpdateResult, crate::error::Error> =
-                    task.await;
+                let result: Result<
+                    (mongodb::results::UpdateResult, u64, Option<Vec<DocumentChange>>),
+                    crate::error::Error,
+                > = task.await;
                 let _ = cx.update(|cx| match result {
-                    Ok(result) => {
+                    Ok((result, skipped, changes)) => {
                         state.update(cx, |state, cx| {
+                            if let Some(changes) = changes {
+                                let label = operation_label("update", changes.len());
+                                state.record_operation(&session_key, label, changes);
+                            }
                             state.clear_all_drafts(&session_key);
                             let event = AppEvent::DocumentsUpdated {
                                 session: session_key.clone(),
                                 matched: result.matched_count,
                                 modified: result.modified_count,
+                                skipped,
                             };
                             state.update_status_from_event(&event);
                             cx.emit(event);
@@ -152,20 +202,38 @@ impl AppCommands {
         let task = cx.background_spawn({
             let database = database.clone();
             let collection = collection.clone();
-            async move { manager.delete_documents(&client, &database, &collection, filter) }
+            async move {
+                let before =
+                    capture_before(&manager, &client, &database, &collection, filter.clone());
+                let journaled = journaled_filter(filter.clone(), &before);
+                let ids = before.as_deref().map(document_ids).unwrap_or_default();
+                let deleted =
+                    manager.delete_documents(&client, &database, &collection, journaled)?;
+                let skipped =
+                    count_skipped(&manager, &client, &database, &collection, filter, &before);
+                let changes =
+                    capture_changes(&manager, &client, &database, &collection, before, ids);
+                Ok((deleted, skipped, changes))
+            }
         });
 
         cx.spawn({
             let state = state.clone();
             let session_key = session_key.clone();
             async move |cx: &mut gpui::AsyncApp| {
-                let result: Result<u64, crate::error::Error> = task.await;
+                let result: Result<(u64, u64, Option<Vec<DocumentChange>>), crate::error::Error> =
+                    task.await;
                 let _ = cx.update(|cx| match result {
-                    Ok(deleted) => {
+                    Ok((deleted, skipped, changes)) => {
                         state.update(cx, |state, cx| {
+                            if let Some(changes) = changes {
+                                let label = operation_label("delete", changes.len());
+                                state.record_operation(&session_key, label, changes);
+                            }
                             let event = AppEvent::DocumentsDeleted {
                                 session: session_key.clone(),
                                 deleted,
+                                skipped,
                             };
                             state.update_status_from_event(&event);
                             cx.emit(event);
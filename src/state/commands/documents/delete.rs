@@ -1,11 +1,14 @@
 use gpui::{App, AppContext as _, Entity};
-use mongodb::bson::Document;
+use mongodb::bson::{Document, doc};
 
 use crate::bson::DocumentKey;
+use crate::connection::DocumentChange;
 use crate::state::{AppEvent, AppState, SessionKey};
 
 use crate::state::AppCommands;
 
+use super::journal::{capture_before, capture_changes};
+
 impl AppCommands {
     /// Delete a document by _id in MongoDB.
     pub fn delete_document(
@@ -36,7 +39,19 @@ impl AppCommands {
         let task = cx.background_spawn({
             let database = database.clone();
             let collection = collection.clone();
-            async move { manager.delete_document(&client, &database, &collection, &original_id) }
+            async move {
+                let filter = doc! { "_id": original_id.clone() };
+                let before = capture_before(&manager, &client, &database, &collection, filter);
+                manager.delete_document(&client, &database, &collection, &original_id)?;
+                Ok(capture_changes(
+                    &manager,
+                    &client,
+                    &database,
+                    &collection,
+                    before,
+                    vec![original_id],
+                ))
+            }
         });
 
         cx.spawn({
@@ -44,11 +59,14 @@ impl AppCommands {
             let session_key = session_key.clone();
             let doc_key = doc_key.clone();
             async move |cx: &mut gpui::AsyncApp| {
-                let result: Result<(), crate::error::Error> = task.await;
+                let result: Result<Option<Vec<DocumentChange>>, crate::error::Error> = task.await;
 
                 let _ = cx.update(|cx| match result {
-                    Ok(()) => {
+                    Ok(changes) => {
                         state.update(cx, |state, cx| {
+                            if let Some(changes) = changes {
+                                state.record_operation(&session_key, "delete", changes);
+                            }
                             if let Some(session) = state.session_mut(&session_key) {
                                 if let Some(index) =
                                     session.data.index_by_key.get(&doc_key).copied()
@@ -61,7 +79,7 @@ impl AppCommands {
                                         .enumerate()
                                         .map(|(idx, item)| (item.key.clone(), idx))
                                         .collect();
-                                    session.data.total = session.data.total.saturating_sub(1);
+                                    session.data.total = session.data.total.decrement();
                                 }
                                 session.view.drafts.remove(&doc_key);
                                 session.view.dirty.remove(&doc_key);
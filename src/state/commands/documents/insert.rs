@@ -1,10 +1,13 @@
 use gpui::{App, AppContext as _, Entity};
 use mongodb::bson::Document;
 
+use crate::connection::DocumentChange;
 use crate::state::{AppEvent, AppState, SessionKey};
 
 use crate::state::AppCommands;
 
+use super::journal::{capture_changes, document_ids, with_object_id};
+
 impl AppCommands {
     /// Insert a document into a collection.
     pub fn insert_document(
@@ -22,22 +25,36 @@ impl AppCommands {
         let database = session_key.database.clone();
         let collection = session_key.collection.clone();
         let manager = state.read(cx).connection_manager();
+        let document = with_object_id(document);
 
         let task = cx.background_spawn({
             let database = database.clone();
             let collection = collection.clone();
-            let document = document.clone();
-            async move { manager.insert_document(&client, &database, &collection, document) }
+            async move {
+                let ids = document_ids(std::slice::from_ref(&document));
+                manager.insert_document(&client, &database, &collection, document)?;
+                Ok(capture_changes(
+                    &manager,
+                    &client,
+                    &database,
+                    &collection,
+                    Some(Vec::new()),
+                    ids,
+                ))
+            }
         });
 
         cx.spawn({
             let state = state.clone();
             let session_key = session_key.clone();
             async move |cx: &mut gpui::AsyncApp| {
-                let result: Result<(), crate::error::Error> = task.await;
+                let result: Result<Option<Vec<DocumentChange>>, crate::error::Error> = task.await;
                 let _ = cx.update(|cx| match result {
-                    Ok(()) => {
+                    Ok(changes) => {
                         state.update(cx, |state, cx| {
+                            if let Some(changes) = changes {
+                                state.record_operation(&session_key, "insert", changes);
+                            }
                             let event = AppEvent::DocumentInserted;
                             state.update_status_from_event(&event);
                             cx.emit(event);
@@ -0,0 +1,207 @@
+use gpui::{App, AppContext as _, Entity};
+use mongodb::Client;
+use mongodb::bson::oid::ObjectId;
+use mongodb::bson::{Bson, Document, doc};
+
+use crate::bson::bson_value_for_edit;
+use crate::connection::{ConnectionManager, DocumentChange, QueryOptions, ReplayOutcome};
+use crate::state::journal::{JOURNAL_MAX_DOCUMENTS, JournalDirection};
+use crate::state::{AppState, SessionKey, StatusMessage};
+
+use crate::state::AppCommands;
+
+impl AppCommands {
+    /// Undo or redo the session's most recent journaled operation.
+    pub fn replay_operation(
+        state: Entity<AppState>,
+        session_key: SessionKey,
+        direction: JournalDirection,
+        cx: &mut App,
+    ) {
+        if !Self::ensure_writable(&state, Some(session_key.connection_id), cx) {
+            return;
+        }
+        let Some(client) = Self::client_for_session(&state, &session_key, cx) else {
+            return;
+        };
+        let Some(entry) =
+            state.update(cx, |state, _cx| state.take_journal_entry(&session_key, direction))
+        else {
+            state.update(cx, |state, cx| {
+                let message = format!("Nothing to {}", direction.verb().to_lowercase());
+                state.set_status_message(Some(StatusMessage::info(message)));
+                cx.notify();
+            });
+            return;
+        };
+        let database = session_key.database.clone();
+        let collection = session_key.collection.clone();
+        let changes = entry.changes_for(direction);
+        let manager = state.read(cx).connection_manager();
+
+        let task = cx.background_spawn(async move {
+            manager.apply_document_changes(&client, &database, &collection, changes)
+        });
+
+        cx.spawn({
+            let state = state.clone();
+            async move |cx: &mut gpui::AsyncApp| {
+                let result: Result<ReplayOutcome, crate::error::Error> = task.await;
+                let _ = cx.update(|cx| {
+                    let reload = result.is_ok();
+                    state.update(cx, |state, cx| {
+                        let message = match result {
+                            Ok(ReplayOutcome::Applied) => {
+                                let message = StatusMessage::info(format!(
+                                    "{} {}",
+                                    direction.past_tense(),
+                                    entry.label
+                                ));
+                                state.finish_journal_entry(&session_key, direction, entry, true);
+                                message
+                            }
+                            Ok(ReplayOutcome::Partial { applied, error }) => {
+                                log::error!(
+                                    "{} of {} stopped partway: {}",
+                                    direction.verb(),
+                                    entry.label,
+                                    error
+                                );
+                                // The entry no longer matches the server either way, so
+                                // it is dropped rather than handed back.
+                                let ids = applied
+                                    .iter()
+                                    .map(bson_value_for_edit)
+                                    .collect::<Vec<_>>()
+                                    .join(", ");
+                                StatusMessage::error(format!(
+                                    "{} of {} stopped partway: {error}. Already applied to \
+                                     document(s) {ids}; it was removed from the history.",
+                                    direction.verb(),
+                                    entry.label
+                                ))
+                            }
+                            Err(e) => {
+                                log::error!(
+                                    "Failed to {} {}: {}",
+                                    direction.verb(),
+                                    entry.label,
+                                    e
+                                );
+                                let message = StatusMessage::error(format!(
+                                    "{} of {} refused: {e}",
+                                    direction.verb(),
+                                    entry.label
+                                ));
+                                state.finish_journal_entry(&session_key, direction, entry, false);
+                                message
+                            }
+                        };
+                        state.set_status_message(Some(message));
+                        cx.notify();
+                    });
+                    if reload {
+                        AppCommands::load_documents_for_session(state.clone(), session_key, cx);
+                    }
+                });
+            }
+        })
+        .detach();
+    }
+}
+
+/// "edit" for one document, "edit of 3 documents" for several.
+pub(super) fn operation_label(verb: &str, count: usize) -> String {
+    if count == 1 { verb.to_string() } else { format!("{verb} of {count} documents") }
+}
+
+/// Read the documents a write is about to touch. `None` (the read failed or
+/// matched too many documents) leaves the write out of the journal.
+pub(super) fn capture_before(
+    manager: &ConnectionManager,
+    client: &Client,
+    database: &str,
+    collection: &str,
+    filter: Document,
+) -> Option<Vec<Document>> {
+    let limit = JOURNAL_MAX_DOCUMENTS as i64 + 1;
+    match manager.find_documents_limited(client, database, collection, filter, limit) {
+        Ok(docs) if docs.len() <= JOURNAL_MAX_DOCUMENTS => Some(docs),
+        Ok(_) => {
+            log::info!("Write touches over {JOURNAL_MAX_DOCUMENTS} documents; not journaled");
+            None
+        }
+        Err(e) => {
+            log::warn!("Failed to read pre-images, write is not journaled: {e}");
+            None
+        }
+    }
+}
+
+/// Pair the pre-images with the state of `ids` after the write.
+pub(super) fn capture_changes(
+    manager: &ConnectionManager,
+    client: &Client,
+    database: &str,
+    collection: &str,
+    before: Option<Vec<Document>>,
+    ids: Vec<Bson>,
+) -> Option<Vec<DocumentChange>> {
+    let before = before?;
+    match manager.find_documents_by_ids(client, database, collection, ids) {
+        Ok(after) => Some(DocumentChange::diff(before, after)),
+        Err(e) => {
+            log::warn!("Failed to read post-images, write is not journaled: {e}");
+            None
+        }
+    }
+}
+
+pub(super) fn document_ids(docs: &[Document]) -> Vec<Bson> {
+    docs.iter().filter_map(|doc| doc.get("_id").cloned()).collect()
+}
+
+/// Restrict a bulk write to the documents whose pre-images were read, so the
+/// journal covers exactly what it changes. Documents that start matching in
+/// between are left alone; [`count_skipped`] reports them.
+pub(super) fn journaled_filter(filter: Document, before: &Option<Vec<Document>>) -> Document {
+    match before {
+        Some(docs) => doc! { "$and": [filter, { "_id": { "$in": document_ids(docs) } }] },
+        None => filter,
+    }
+}
+
+/// Count documents matching the user's `filter` that a [`journaled_filter`]
+/// write left out because they were not among the pre-images.
+pub(super) fn count_skipped(
+    manager: &ConnectionManager,
+    client: &Client,
+    database: &str,
+    collection: &str,
+    filter: Document,
+    before: &Option<Vec<Document>>,
+) -> u64 {
+    let Some(docs) = before else {
+        return 0;
+    };
+    let filter = doc! { "$and": [filter, { "_id": { "$nin": document_ids(docs) } }] };
+    let options = QueryOptions::default();
+    match manager.count_documents(client, database, collection, Some(filter), &options) {
+        Ok(count) => count,
+        Err(e) => {
+            log::warn!("Failed to count documents left out of a journaled write: {e}");
+            0
+        }
+    }
+}
+
+/// Give a document to insert an `_id` up front, as the driver would, so the
+/// journal knows which document to remove on undo.
+pub(super) fn with_object_id(document: Document) -> Document {
+    if document.contains_key("_id") {
+        return document;
+    }
+    let mut with_id = doc! { "_id": ObjectId::new() };
+    with_id.extend(document);
+    with_id
+}
@@ -3,6 +3,7 @@
 mod bulk;
 mod delete;
 mod insert;
+mod journal;
 mod query;
 pub mod save_as;
 mod update;
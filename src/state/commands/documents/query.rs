@@ -1,8 +1,15 @@
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Instant;
+
 use gpui::{App, AppContext as _, Entity};
-use mongodb::bson::{Document, doc};
+use mongodb::bson::doc;
 
 use crate::bson::DocumentKey;
-use crate::connection::FindDocumentsOptions;
+use crate::connection::keyset::keyset_sort;
+use crate::connection::{
+    DocumentCount, DocumentPage, DocumentStream, FindDocumentsOptions, PaginationMode,
+};
+use crate::state::app_state::SessionData;
 use crate::state::{AppEvent, AppState, SessionDocument, SessionKey};
 
 use crate::state::AppCommands;
@@ -19,60 +26,63 @@ impl AppCommands {
         };
 
         // Get selected db/collection + session data
-        let (database, collection, skip, limit, request_id, filter, sort, projection, sort_raw) = {
+        let (database, collection, request_id, query, streaming) = {
             let state = state.read(cx);
-            let (page, per_page, request_id, filter, sort, projection, sort_raw) =
-                match state.session(&session_key) {
-                    Some(session) => (
-                        session.data.page,
-                        session.data.per_page,
-                        session.data.request_id + 1,
-                        session.data.filter.clone(),
-                        session.data.sort.clone(),
-                        session.data.projection.clone(),
-                        session.data.sort_raw.clone(),
-                    ),
-                    None => (0, 50, 1, None, None, None, String::new()),
-                };
+            let (request_id, query, streaming) = match state.session_data(&session_key) {
+                Some(data) => (
+                    data.request_id + 1,
+                    find_options(data),
+                    data.pagination == PaginationMode::Stream,
+                ),
+                None => (1, find_options(&SessionData::default()), false),
+            };
             (
                 session_key.database.clone(),
                 session_key.collection.clone(),
-                page * per_page as u64,
-                per_page,
                 request_id,
-                filter,
-                sort,
-                projection,
-                sort_raw,
+                query,
+                streaming,
             )
         };
 
-        let effective_sort = if sort.is_none() && sort_raw.trim().is_empty() {
-            Some(doc! { "$natural": 1 })
-        } else {
-            sort
-        };
-
         // Mark session as loading and bump request id
         let manager = state.read(cx).connection_manager();
         state.update(cx, |state, cx| {
             let session = state.ensure_session(session_key.clone());
             session.data.is_loading = true;
             session.data.request_id = request_id;
+            // A reload starts any stream over; dropping it kills the cursor.
+            session.data.stream = None;
+            session.data.stream_offset = 0;
+            session.data.stream_fetching = false;
             cx.notify();
         });
 
         // Run blocking MongoDB operation in background thread
+        let started = Instant::now();
         let task = cx.background_spawn({
             let database_for_task = database.clone();
             let collection_for_task = collection.clone();
             async move {
-                manager.find_documents(
+                if !streaming {
+                    let page = manager.find_documents(
+                        &client,
+                        &database_for_task,
+                        &collection_for_task,
+                        query,
+                    )?;
+                    return Ok((page, None));
+                }
+                let batch = usize::try_from(query.limit.max(1)).unwrap_or(1);
+                let (mut stream, count) = manager.open_document_stream(
                     &client,
                     &database_for_task,
                     &collection_for_task,
-                    FindDocumentsOptions { filter, sort: effective_sort, projection, skip, limit },
-                )
+                    query,
+                )?;
+                let documents = manager.next_stream_batch(&mut stream, batch)?;
+                let has_more = !stream.is_exhausted();
+                Ok((DocumentPage { documents, count, has_more }, Some(stream)))
             }
         });
 
@@ -81,10 +91,11 @@ impl AppCommands {
             let state = state.clone();
             let session_key = session_key.clone();
             async move |cx: &mut gpui::AsyncApp| {
-                let result: Result<(Vec<Document>, u64), crate::error::Error> = task.await;
+                let result: Result<(DocumentPage, Option<DocumentStream>), crate::error::Error> =
+                    task.await;
 
                 let _ = cx.update(|cx| match result {
-                    Ok((documents, total)) => {
+                    Ok((DocumentPage { documents, count: total, has_more }, stream)) => {
                         state.update(cx, |state, cx| {
                             let Some(session) = state.session_mut(&session_key) else {
                                 return;
@@ -107,6 +118,9 @@ impl AppCommands {
                                 .collect();
                             session.data.items = items;
                             session.data.total = total;
+                            session.data.has_more = has_more;
+                            session.data.stream = stream.map(|stream| Arc::new(Mutex::new(stream)));
+                            session.data.count_loading = false;
                             session.data.loaded = true;
                             session.data.is_loading = false;
 
@@ -115,6 +129,7 @@ impl AppCommands {
                             session.view.selected_node_id = None;
 
                             session.generation = session.generation.wrapping_add(1);
+                            state.finish_query_history(&session_key, started.elapsed(), total);
                             let event =
                                 AppEvent::DocumentsLoaded { session: session_key.clone(), total };
                             state.update_status_from_event(&event);
@@ -138,4 +153,171 @@ impl AppCommands {
         })
         .detach();
     }
+
+    /// Read the next batch from a session's stream. Does nothing outside
+    /// stream mode, while a batch is being read or once the stream is done.
+    pub fn load_more_documents(state: Entity<AppState>, session_key: SessionKey, cx: &mut App) {
+        let (stream, request_id, batch, window) = {
+            let state_ref = state.read(cx);
+            let Some(data) = state_ref.session_data(&session_key) else {
+                return;
+            };
+            let Some(stream) = data.stream.clone() else {
+                return;
+            };
+            if data.pagination != PaginationMode::Stream || data.stream_fetching || !data.has_more {
+                return;
+            }
+            let batch = usize::try_from(data.per_page.max(1)).unwrap_or(1);
+            // Never less than two batches, so the view keeps something to
+            // scroll back to.
+            let window = (state_ref.settings.browser.stream_window as usize).max(batch * 2);
+            (stream, data.request_id, batch, window)
+        };
+
+        let manager = state.read(cx).connection_manager();
+        state.update(cx, |state, cx| {
+            if let Some(session) = state.session_mut(&session_key) {
+                session.data.stream_fetching = true;
+            }
+            cx.notify();
+        });
+
+        let task = cx.background_spawn(async move {
+            let mut stream = stream.lock().unwrap_or_else(PoisonError::into_inner);
+            let documents = manager.next_stream_batch(&mut stream, batch)?;
+            Ok::<_, crate::error::Error>((documents, !stream.is_exhausted()))
+        });
+
+        cx.spawn({
+            let state = state.clone();
+            async move |cx: &mut gpui::AsyncApp| {
+                let result = task.await;
+                let _ = cx.update(|cx| {
+                    state.update(cx, |state, cx| {
+                        let Some(session) = state.session_mut(&session_key) else {
+                            return;
+                        };
+                        // A reload since has replaced the stream.
+                        if session.data.request_id != request_id {
+                            return;
+                        }
+                        session.data.stream_fetching = false;
+                        let event = match result {
+                            Ok((documents, has_more)) => {
+                                session.data.has_more = has_more;
+                                let added = documents.len();
+                                let dropped =
+                                    state.append_stream_batch(&session_key, documents, window);
+                                AppEvent::DocumentsStreamed {
+                                    session: session_key.clone(),
+                                    added,
+                                    dropped,
+                                }
+                            }
+                            Err(e) => {
+                                log::error!("Failed to load more documents: {}", e);
+                                session.data.has_more = false;
+                                AppEvent::DocumentsStreamFailed {
+                                    session: session_key.clone(),
+                                    error: e.to_string(),
+                                }
+                            }
+                        };
+                        state.update_status_from_event(&event);
+                        cx.emit(event);
+                        cx.notify();
+                    });
+                });
+            }
+        })
+        .detach();
+    }
+
+    /// Run the exact count for a session whose count strategy skipped it.
+    pub fn load_document_count(state: Entity<AppState>, session_key: SessionKey, cx: &mut App) {
+        let Some(client) = Self::client_for_session(&state, &session_key, cx) else {
+            return;
+        };
+
+        let (filter, options, request_id) = {
+            let Some(data) = state.read(cx).session_data(&session_key) else {
+                return;
+            };
+            if data.count_loading {
+                return;
+            }
+            (data.filter.clone(), data.query_options.clone(), data.request_id)
+        };
+
+        let manager = state.read(cx).connection_manager();
+        state.update(cx, |state, cx| {
+            if let Some(session) = state.session_mut(&session_key) {
+                session.data.count_loading = true;
+            }
+            cx.notify();
+        });
+
+        let task = cx.background_spawn({
+            let database = session_key.database.clone();
+            let collection = session_key.collection.clone();
+            async move {
+                manager.count_documents(&client, &database, &collection, filter, &options)
+            }
+        });
+
+        cx.spawn({
+            let state = state.clone();
+            async move |cx: &mut gpui::AsyncApp| {
+                let result: Result<u64, crate::error::Error> = task.await;
+                let _ = cx.update(|cx| {
+                    state.update(cx, |state, cx| {
+                        let Some(session) = state.session_mut(&session_key) else {
+                            return;
+                        };
+                        session.data.count_loading = false;
+                        match result {
+                            // A reload since the request may have changed the filter.
+                            Ok(total) if session.data.request_id == request_id => {
+                                session.data.total = DocumentCount::Exact(total);
+                            }
+                            Ok(_) => {}
+                            Err(e) => log::error!("Failed to count documents: {}", e),
+                        }
+                        cx.notify();
+                    });
+                });
+            }
+        })
+        .detach();
+    }
+}
+
+/// Build the find request for the session's current page.
+fn find_options(data: &SessionData) -> FindDocumentsOptions {
+    let (sort, skip) = match data.pagination {
+        PaginationMode::Offset => {
+            let sort = if data.sort.is_none() && data.sort_raw.trim().is_empty() {
+                Some(doc! { "$natural": 1 })
+            } else {
+                data.sort.clone()
+            };
+            (sort, data.page * data.per_page as u64)
+        }
+        PaginationMode::Keyset => (Some(keyset_sort(data.sort.as_ref())), 0),
+        PaginationMode::Stream => (data.sort.clone(), 0),
+    };
+    FindDocumentsOptions {
+        filter: data.filter.clone(),
+        sort,
+        projection: data.projection.clone(),
+        skip,
+        limit: data.per_page,
+        count: data.count_strategy,
+        keyset: match data.pagination {
+            PaginationMode::Offset | PaginationMode::Stream => None,
+            PaginationMode::Keyset => data.keyset.clone(),
+        },
+        options: data.query_options.clone(),
+    }
 }
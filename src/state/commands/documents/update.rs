@@ -2,18 +2,37 @@ use gpui::{App, AppContext as _, Entity};
 use mongodb::bson::{Document, doc};
 
 use crate::bson::{DocumentKey, parse_bson_from_relaxed_json};
-use crate::state::{AppEvent, AppState, SessionKey, StatusMessage};
+use crate::connection::{ConcurrencyCheck, DocumentChange, DocumentReplacement, SaveOutcome};
+use crate::state::{AppEvent, AppState, SaveConflict, SessionKey, StatusMessage};
 
 use crate::state::AppCommands;
 
+use super::journal::{capture_before, capture_changes, operation_label};
+
 impl AppCommands {
-    /// Save a document by replacing it in MongoDB.
+    /// Save a document by replacing it in MongoDB. The write only goes through
+    /// while the server copy still matches the document as loaded.
     pub fn save_document(
         state: Entity<AppState>,
         session_key: SessionKey,
         doc_key: DocumentKey,
         updated: Document,
         cx: &mut App,
+    ) {
+        let baseline = state.read(cx).document_for_key(&session_key, &doc_key);
+        Self::save_document_against(state, session_key, doc_key, baseline, updated, cx);
+    }
+
+    /// Save a document, checking for concurrent changes against `baseline`
+    /// (the copy the edit started from). A mismatch emits
+    /// [`AppEvent::DocumentSaveConflict`] and leaves the draft in place.
+    pub fn save_document_against(
+        state: Entity<AppState>,
+        session_key: SessionKey,
+        doc_key: DocumentKey,
+        baseline: Option<Document>,
+        updated: Document,
+        cx: &mut App,
     ) {
         if !Self::ensure_writable(&state, Some(session_key.connection_id), cx) {
             return;
@@ -51,20 +70,52 @@ impl AppCommands {
                 doc_index.is_none(),
             )
         };
-        let manager = state.read(cx).connection_manager();
+        let (manager, check) = {
+            let state_ref = state.read(cx);
+            let projected = state_ref
+                .session(&session_key)
+                .is_some_and(|session| session.data.projection.is_some());
+            let check = baseline.as_ref().map(|loaded| {
+                ConcurrencyCheck::for_loaded(
+                    loaded,
+                    projected,
+                    &state_ref.settings.save_version_field,
+                )
+            });
+            (state_ref.connection_manager(), check)
+        };
+        let mut updated = updated;
+        if let Some(check) = &check {
+            check.advance(&mut updated);
+        }
 
         let updated_for_task = updated.clone();
         let task = cx.background_spawn({
             let database = database.clone();
             let collection = collection.clone();
             async move {
-                manager.replace_document(
+                let filter = doc! { "_id": original_id.clone() };
+                let before = capture_before(&manager, &client, &database, &collection, filter);
+                let outcome = manager.replace_document(
                     &client,
                     &database,
                     &collection,
                     &original_id,
                     updated_for_task,
-                )
+                    check.as_ref(),
+                )?;
+                let changes = match outcome {
+                    SaveOutcome::Saved => capture_changes(
+                        &manager,
+                        &client,
+                        &database,
+                        &collection,
+                        before,
+                        vec![original_id],
+                    ),
+                    SaveOutcome::Conflict(_) => None,
+                };
+                Ok((outcome, changes))
             }
         });
 
@@ -74,11 +125,33 @@ impl AppCommands {
             let doc_key = doc_key.clone();
             let updated = updated.clone();
             async move |cx: &mut gpui::AsyncApp| {
-                let result: Result<(), crate::error::Error> = task.await;
+                let result: Result<
+                    (SaveOutcome, Option<Vec<DocumentChange>>),
+                    crate::error::Error,
+                > = task.await;
 
                 let _ = cx.update(|cx| match result {
-                    Ok(()) => {
+                    Ok((SaveOutcome::Conflict(current), _)) => {
                         state.update(cx, |state, cx| {
+                            let event = AppEvent::DocumentSaveConflict {
+                                session: session_key.clone(),
+                                document: doc_key.clone(),
+                                conflict: Box::new(SaveConflict {
+                                    loaded: baseline.unwrap_or_default(),
+                                    draft: updated,
+                                    current,
+                                }),
+                            };
+                            state.update_status_from_event(&event);
+                            cx.emit(event);
+                            cx.notify();
+                        });
+                    }
+                    Ok((SaveOutcome::Saved, changes)) => {
+                        state.update(cx, |state, cx| {
+                            if let Some(changes) = changes {
+                                state.record_operation(&session_key, "edit", changes);
+                            }
                             if let Some(session) = state.session_mut(&session_key) {
                                 let index = doc_index.or_else(|| {
                                     session.data.items.iter().position(|item| item.key == doc_key)
@@ -126,6 +199,162 @@ impl AppCommands {
         .detach();
     }
 
+    /// Save the drafts of several documents. With transactional batches
+    /// enabled on a replica set or sharded cluster they are written in one
+    /// transaction and reported as one event; otherwise each is saved on its
+    /// own.
+    pub fn save_documents(
+        state: Entity<AppState>,
+        session_key: SessionKey,
+        doc_keys: Vec<DocumentKey>,
+        cx: &mut App,
+    ) {
+        let (drafts, transactional) = {
+            let state_ref = state.read(cx);
+            let drafts: Vec<(DocumentKey, Document)> = doc_keys
+                .into_iter()
+                .filter_map(|doc_key| {
+                    let draft = state_ref.session_draft(&session_key, &doc_key)?;
+                    Some((doc_key, draft))
+                })
+                .collect();
+            (drafts, state_ref.settings.transactional_batches)
+        };
+        if drafts.len() < 2 || !transactional {
+            for (doc_key, draft) in drafts {
+                Self::save_document(state.clone(), session_key.clone(), doc_key, draft, cx);
+            }
+            return;
+        }
+
+        if !Self::ensure_writable(&state, Some(session_key.connection_id), cx) {
+            return;
+        }
+        let Some(client) = Self::client_for_session(&state, &session_key, cx) else {
+            return;
+        };
+        let mut drafts = drafts;
+        let replacements = {
+            let state_ref = state.read(cx);
+            let projected = state_ref
+                .session(&session_key)
+                .is_some_and(|session| session.data.projection.is_some());
+            let version_field = &state_ref.settings.save_version_field;
+            let mut replacements = Vec::with_capacity(drafts.len());
+            for (doc_key, draft) in &mut drafts {
+                let baseline = state_ref.document_for_key(&session_key, doc_key);
+                let Some(id) = baseline
+                    .as_ref()
+                    .and_then(|loaded| loaded.get("_id"))
+                    .or_else(|| draft.get("_id"))
+                    .cloned()
+                else {
+                    state.update(cx, |state, cx| {
+                        state.set_status_message(Some(StatusMessage::error(
+                            "Could not resolve original document ID for save.",
+                        )));
+                        cx.notify();
+                    });
+                    return;
+                };
+                let check = baseline
+                    .as_ref()
+                    .map(|loaded| ConcurrencyCheck::for_loaded(loaded, projected, version_field));
+                if let Some(check) = &check {
+                    check.advance(draft);
+                }
+                replacements.push(DocumentReplacement { id, replacement: draft.clone(), check });
+            }
+            replacements
+        };
+        let count = replacements.len();
+        let database = session_key.database.clone();
+        let collection = session_key.collection.clone();
+        let manager = state.read(cx).connection_manager();
+
+        let task = cx.background_spawn(async move {
+            if !manager.supports_transactions(&client)? {
+                return Ok::<_, crate::error::Error>(None);
+            }
+            let ids: Vec<_> = replacements.iter().map(|item| item.id.clone()).collect();
+            let filter = doc! { "_id": { "$in": ids.clone() } };
+            let before = capture_before(&manager, &client, &database, &collection, filter);
+            let saved = manager.replace_documents_transactional(
+                &client,
+                &database,
+                &collection,
+                replacements,
+            )?;
+            let changes = capture_changes(&manager, &client, &database, &collection, before, ids);
+            Ok(Some((saved, changes)))
+        });
+
+        cx.spawn({
+            let state = state.clone();
+            async move |cx: &mut gpui::AsyncApp| {
+                let result = task.await;
+
+                let _ = cx.update(|cx| match result {
+                    Ok(None) => {
+                        for (doc_key, draft) in drafts {
+                            AppCommands::save_document(
+                                state.clone(),
+                                session_key.clone(),
+                                doc_key,
+                                draft,
+                                cx,
+                            );
+                        }
+                    }
+                    Ok(Some((saved, changes))) => {
+                        state.update(cx, |state, cx| {
+                            if let Some(changes) = changes {
+                                let label = operation_label("edit", changes.len());
+                                state.record_operation(&session_key, label, changes);
+                            }
+                            if let Some(session) = state.session_mut(&session_key) {
+                                for (doc_key, draft) in drafts {
+                                    if let Some(existing) = session
+                                        .data
+                                        .items
+                                        .iter_mut()
+                                        .find(|item| item.key == doc_key)
+                                    {
+                                        existing.doc = draft;
+                                    }
+                                    session.view.drafts.remove(&doc_key);
+                                    session.view.dirty.remove(&doc_key);
+                                }
+                                session.generation = session.generation.wrapping_add(1);
+                            }
+                            let event = AppEvent::DocumentsSaved {
+                                session: session_key.clone(),
+                                count: saved,
+                            };
+                            state.update_status_from_event(&event);
+                            cx.emit(event);
+                            cx.notify();
+                        });
+                    }
+                    Err(e) => {
+                        log::error!("Failed to save documents: {}", e);
+                        state.update(cx, |state, cx| {
+                            let event = AppEvent::DocumentsSaveFailed {
+                                session: session_key.clone(),
+                                count,
+                                error: e.to_string(),
+                            };
+                            state.update_status_from_event(&event);
+                            cx.emit(event);
+                            cx.notify();
+                        });
+                    }
+                });
+            }
+        })
+        .detach();
+    }
+
     /// Update a single document by _id.
     pub fn update_document_by_key(
         state: Entity<AppState>,
@@ -169,7 +398,13 @@ impl AppCommands {
             let collection = collection.clone();
             let update = update.clone();
             async move {
-                manager.update_one(&client, &database, &collection, doc! { "_id": id }, update)
+                let filter = doc! { "_id": id.clone() };
+                let before =
+                    capture_before(&manager, &client, &database, &collection, filter.clone());
+                let result = manager.update_one(&client, &database, &collection, filter, update)?;
+                let changes =
+                    capture_changes(&manager, &client, &database, &collection, before, vec![id]);
+                Ok((result, changes))
             }
         });
 
@@ -178,16 +413,22 @@ impl AppCommands {
             let session_key = session_key.clone();
             let doc_key = doc_key.clone();
             async move |cx: &mut gpui::AsyncApp| {
-                let result: Result<mongodb::results::UpdateResult, crate::error::Error> =
-                    task.await;
+                let result: Result<
+                    (mongodb::results::UpdateResult, Option<Vec<DocumentChange>>),
+                    crate::error::Error,
+                > = task.await;
                 let _ = cx.update(|cx| match result {
-                    Ok(result) => {
+                    Ok((result, changes)) => {
                         state.update(cx, |state, cx| {
+                            if let Some(changes) = changes {
+                                state.record_operation(&session_key, "update", changes);
+                            }
                             state.clear_draft(&session_key, &doc_key);
                             let event = AppEvent::DocumentsUpdated {
                                 session: session_key.clone(),
                                 matched: result.matched_count,
                                 modified: result.modified_count,
+                                skipped: 0,
                             };
                             state.update_status_from_event(&event);
                             cx.emit(event);
@@ -6,9 +6,11 @@ use std::time::{SystemTime, UNIX_EPOCH};
 
 use gpui::{App, AppContext as _, Entity};
 use mongodb::bson::{Bson, Document};
+use mongodb::options::Hint;
 
 use crate::bson::parse_bson_from_relaxed_json;
 use crate::connection::ops::explain::ExplainFindRequest;
+use crate::state::app_state::IndexAdvice;
 use crate::state::app_state::PipelineStage;
 use crate::state::{
     AppCommands, AppEvent, AppState, ExplainBottleneck, ExplainCostBand, ExplainNode,
@@ -16,6 +18,8 @@ use crate::state::{
     ExplainSummary, ExplainViewMode, SessionKey,
 };
 
+use super::index_advisor::{IndexSuggestion, suggest_index};
+
 const EXPLAIN_VERBOSITY: &str = "executionStats";
 const EXPLAIN_HISTORY_LIMIT: usize = 20;
 
@@ -29,11 +33,31 @@ struct ParsedExplain {
 
 impl AppCommands {
     pub fn run_explain_for_session(state: Entity<AppState>, session_key: SessionKey, cx: &mut App) {
+        Self::run_find_explain(state, session_key, None, cx);
+    }
+
+    /// Explain the session's find forced onto `index_name`, keeping the current
+    /// advice so the Diff tab can compare it with the unhinted run.
+    pub fn explain_with_index_hint(
+        state: Entity<AppState>,
+        session_key: SessionKey,
+        index_name: String,
+        cx: &mut App,
+    ) {
+        Self::run_find_explain(state, session_key, Some(index_name), cx);
+    }
+
+    fn run_find_explain(
+        state: Entity<AppState>,
+        session_key: SessionKey,
+        hint: Option<String>,
+        cx: &mut App,
+    ) {
         let Some(client) = Self::client_for_session(&state, &session_key, cx) else {
             return;
         };
 
-        let (database, collection, filter, sort, projection, signature) = {
+        let (database, collection, filter, sort, projection, mut options, signature) = {
             let state_ref = state.read(cx);
             let Some(session) = state_ref.session(&session_key) else {
                 return;
@@ -44,9 +68,16 @@ impl AppCommands {
                 session.data.filter.clone(),
                 session.data.sort.clone(),
                 session.data.projection.clone(),
-                signature_for_find(&session_key, session),
+                session.data.query_options.clone(),
+                signature_for_find(&session_key, session, hint.as_deref()),
             )
         };
+        let hinted = hint.is_some();
+        if let Some(name) = hint {
+            options.hint = Some(Hint::Name(name));
+        }
+        let advice_filter = filter.clone().unwrap_or_default();
+        let advice_sort = sort.clone();
 
         state.update(cx, |state, cx| {
             let Some(session) = state.session_mut(&session_key) else {
@@ -77,6 +108,7 @@ impl AppCommands {
                     filter,
                     sort,
                     projection,
+                    options,
                     verbosity: EXPLAIN_VERBOSITY.to_string(),
                 },
             )
@@ -90,14 +122,30 @@ impl AppCommands {
 
                 let _ = cx.update(|cx| match result {
                     Ok(explain_doc) => {
-                        let parsed = parse_explain_document(&explain_doc);
+                        let mut parsed = parse_explain_document(&explain_doc);
                         let raw_json = explain_to_pretty_json(&explain_doc);
                         let generated_at_unix_ms = now_unix_ms();
+                        let suggestion = (parsed.summary.has_collscan
+                            || parsed.summary.has_sort_stage)
+                            .then(|| suggest_index(&advice_filter, advice_sort.as_ref()))
+                            .flatten();
+                        if let Some(suggestion) = &suggestion {
+                            name_suggested_index(&mut parsed.bottlenecks, suggestion);
+                        }
+                        let sample_selectivity =
+                            !hinted && suggestion.is_some() && !advice_filter.is_empty();
                         state.update(cx, |state, cx| {
                             let Some(session) = state.session_mut(&session_key) else {
                                 return;
                             };
                             let explain = &mut session.data.explain;
+                            if !hinted {
+                                explain.advice = suggestion.map(|suggestion| IndexAdvice {
+                                    suggestion,
+                                    selectivity: None,
+                                    selectivity_loading: sample_selectivity,
+                                });
+                            }
                             explain.loading = false;
                             explain.error = None;
                             explain.scope = ExplainScope::Find;
@@ -125,6 +173,21 @@ impl AppCommands {
                             cx.emit(event);
                             cx.notify();
                         });
+                        // The advice card checks the suggestion against existing indexes.
+                        AppCommands::load_collection_indexes(
+                            state.clone(),
+                            session_key.clone(),
+                            false,
+                            cx,
+                        );
+                        if sample_selectivity {
+                            AppCommands::sample_advice_selectivity(
+                                state.clone(),
+                                session_key.clone(),
+                                advice_filter,
+                                cx,
+                            );
+                        }
                     }
                     Err(error) => {
                         let error_message = error.to_string();
@@ -349,14 +412,20 @@ fn build_explain_pipeline(
     Ok(pipeline)
 }
 
-fn signature_for_find(session_key: &SessionKey, session: &crate::state::SessionState) -> u64 {
+fn signature_for_find(
+    session_key: &SessionKey,
+    session: &crate::state::SessionState,
+    hint: Option<&str>,
+) -> u64 {
     let mut hasher = DefaultHasher::new();
+    hint.hash(&mut hasher);
     session_key.connection_id.hash(&mut hasher);
     session_key.database.hash(&mut hasher);
     session_key.collection.hash(&mut hasher);
     session.data.filter_raw.hash(&mut hasher);
     session.data.sort_raw.hash(&mut hasher);
     session.data.projection_raw.hash(&mut hasher);
+    session.data.query_options_raw.hash(&mut hasher);
     hasher.finish()
 }
 
@@ -824,6 +893,22 @@ fn recommendation_for_node(node: &ExplainNode) -> String {
     "Monitor this stage across runs; optimize if examined counts continue to grow.".to_string()
 }
 
+/// Name the suggested key pattern in COLLSCAN and SORT recommendations.
+fn name_suggested_index(bottlenecks: &mut [ExplainBottleneck], suggestion: &IndexSuggestion) {
+    for bottleneck in bottlenecks {
+        let upper = bottleneck.stage.to_ascii_uppercase();
+        if !upper.contains("COLLSCAN") && !upper.contains("SORT") {
+            continue;
+        }
+        let keys = Bson::Document(suggestion.keys.clone()).into_relaxed_extjson();
+        bottleneck.recommendation = format!(
+            "{} Suggested index: {keys} ({}).",
+            bottleneck.recommendation,
+            suggestion.rationale()
+        );
+    }
+}
+
 fn stage_label(doc: &Document) -> String {
     if let Ok(stage) = doc.get_str("stage") {
         return stage.to_string();
@@ -1163,4 +1248,31 @@ mod tests {
         assert!(!parsed.bottlenecks.is_empty());
         assert_eq!(parsed.bottlenecks[0].rank, 1);
     }
+
+    #[test]
+    fn suggested_index_is_named_in_scan_and_sort_recommendations() {
+        let explain = doc! {
+            "executionStats": {
+                "nReturned": 10,
+                "totalDocsExamined": 5000,
+                "executionStages": {
+                    "stage": "SORT",
+                    "nReturned": 10,
+                    "inputStage": { "stage": "COLLSCAN", "docsExamined": 5000, "nReturned": 40 }
+                }
+            }
+        };
+        let mut parsed = parse_explain_document(&explain);
+        let suggestion =
+            suggest_index(&doc! { "status": "open" }, Some(&doc! { "createdAt": -1 })).unwrap();
+        name_suggested_index(&mut parsed.bottlenecks, &suggestion);
+
+        let collscan =
+            parsed.bottlenecks.iter().find(|item| item.stage == "COLLSCAN").expect("collscan");
+        assert!(collscan.recommendation.starts_with("Add a selective index"));
+        assert!(collscan.recommendation.contains("\"createdAt\":-1"));
+        assert!(collscan.recommendation.ends_with("(equality status · sort createdAt)."));
+        let sort = parsed.bottlenecks.iter().find(|item| item.stage == "SORT").expect("sort");
+        assert!(sort.recommendation.contains("Suggested index"));
+    }
 }
@@ -0,0 +1,179 @@
+//! Connection health monitoring and automatic reconnect.
+
+use std::time::Duration;
+
+use gpui::{App, AppContext as _, AsyncApp, Entity};
+use uuid::Uuid;
+
+use crate::models::ConnectionHealth;
+use crate::state::{AppEvent, AppState};
+
+use super::AppCommands;
+
+/// Interval between heartbeats of an active connection.
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(15);
+const RECONNECT_BASE_DELAY: Duration = Duration::from_secs(1);
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// Backoff after failed reconnect `attempt` (1-based): doubles from 1s, capped at 60s.
+fn reconnect_delay(attempt: u32) -> Duration {
+    let factor = 1u32 << attempt.saturating_sub(1).min(6);
+    RECONNECT_BASE_DELAY.saturating_mul(factor).min(RECONNECT_MAX_DELAY)
+}
+
+impl AppCommands {
+    /// Watch an active connection and rebuild it when its SSH tunnel dies or the
+    /// server stops answering heartbeats.
+    ///
+    /// The monitor task is owned by `AppState` and cancelled on disconnect.
+    pub(super) fn monitor_connection(state: Entity<AppState>, connection_id: Uuid, cx: &mut App) {
+        let monitor = cx.spawn({
+            let state = state.clone();
+            async move |cx: &mut AsyncApp| {
+                loop {
+                    cx.background_executor().timer(HEARTBEAT_INTERVAL).await;
+
+                    let target = cx.update(|cx| {
+                        let state = state.read(cx);
+                        state
+                            .active_connection_client(connection_id)
+                            .map(|client| (client, state.connection_manager()))
+                    });
+                    let Ok(Some((client, manager))) = target else {
+                        return;
+                    };
+
+                    let check = cx
+                        .background_spawn(
+                            async move { manager.check_health(connection_id, &client) },
+                        )
+                        .await;
+                    let Err(error) = check else {
+                        continue;
+                    };
+
+                    log::warn!("Connection {connection_id} lost: {error}");
+                    let lost = AppEvent::ConnectionLost { connection_id, error: error.to_string() };
+                    if !set_health(&state, connection_id, ConnectionHealth::Lost, lost, cx) {
+                        return;
+                    }
+                    if !reconnect_with_backoff(&state, connection_id, cx).await {
+                        return;
+                    }
+                }
+            }
+        });
+
+        state.update(cx, |state, _cx| state.set_health_monitor(connection_id, monitor));
+    }
+}
+
+/// Retry `connect_managed` until it succeeds; returns `false` if the user
+/// disconnected in the meantime.
+async fn reconnect_with_backoff(
+    state: &Entity<AppState>,
+    connection_id: Uuid,
+    cx: &mut AsyncApp,
+) -> bool {
+    let mut attempt = 0u32;
+    loop {
+        attempt += 1;
+        let reconnecting = AppEvent::Reconnecting { connection_id, attempt };
+        if !set_health(
+            state,
+            connection_id,
+            ConnectionHealth::Reconnecting { attempt },
+            reconnecting,
+            cx,
+        ) {
+            return false;
+        }
+
+        let target = cx.update(|cx| {
+            let state = state.read(cx);
+            state
+                .connection_by_id(connection_id)
+                .cloned()
+                .map(|saved| (saved, state.connection_manager()))
+        });
+        let Ok(Some((saved, manager))) = target else {
+            return false;
+        };
+
+        let result = cx
+            .background_spawn({
+                let manager = manager.clone();
+                async move {
+                    let (client, runtime_meta) = manager.connect_managed(connection_id, &saved)?;
+                    manager.check_health(connection_id, &client)?;
+                    Ok::<_, crate::error::Error>((client, runtime_meta))
+                }
+            })
+            .await;
+
+        match result {
+            Ok((client, runtime_meta)) => {
+                let restored = cx.update(|cx| {
+                    state.update(cx, |state, cx| {
+                        let Some(conn) = state.active_connection_mut(connection_id) else {
+                            return false;
+                        };
+                        conn.client = client;
+                        conn.runtime_meta = runtime_meta;
+                        conn.health = ConnectionHealth::Healthy;
+                        let event = AppEvent::Reconnected(connection_id);
+                        state.update_status_from_event(&event);
+                        cx.emit(event);
+                        cx.notify();
+                        true
+                    })
+                });
+                let restored = matches!(restored, Ok(true));
+                if !restored {
+                    // Disconnected while the new tunnel was being built.
+                    manager.disconnect(connection_id);
+                }
+                return restored;
+            }
+            Err(error) => {
+                log::warn!("Reconnect attempt {attempt} for {connection_id} failed: {error}");
+                cx.background_executor().timer(reconnect_delay(attempt)).await;
+            }
+        }
+    }
+}
+
+/// Record `health` on the active connection and emit `event`; returns `false`
+/// when the connection is no longer active.
+fn set_health(
+    state: &Entity<AppState>,
+    connection_id: Uuid,
+    health: ConnectionHealth,
+    event: AppEvent,
+    cx: &mut AsyncApp,
+) -> bool {
+    cx.update(|cx| {
+        state.update(cx, |state, cx| {
+            let Some(conn) = state.active_connection_mut(connection_id) else {
+                return false;
+            };
+            conn.health = health;
+            state.update_status_from_event(&event);
+            cx.emit(event);
+            cx.notify();
+            true
+        })
+    })
+    .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reconnect_delay_doubles_up_to_a_minute() {
+        let delays: Vec<u64> = (1..=8).map(|attempt| reconnect_delay(attempt).as_secs()).collect();
+        assert_eq!(delays, vec![1, 2, 4, 8, 16, 32, 60, 60]);
+    }
+}
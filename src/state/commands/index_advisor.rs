@@ -0,0 +1,349 @@
+//! Index suggestions for find queries following the equality-sort-range rule.
+//!
+//! Equality predicates lead the key pattern, then the sort keys, then range
+//! predicates, so the index both narrows the scan and returns documents in
+//! sort order without an in-memory SORT stage.
+
+use gpui::{App, AppContext as _, Entity};
+use mongodb::IndexModel;
+use mongodb::bson::{Bson, Document};
+
+use crate::bson::parse_document_from_json;
+use crate::connection::ops::explain::SampleSelectivity;
+use crate::connection::ops::index_usage::keys_start_with;
+use crate::state::{AppState, QueryHistoryEntry, SessionKey};
+
+use super::AppCommands;
+
+/// Suggestions listed from the query history of one collection.
+pub(crate) const HISTORY_SUGGESTION_LIMIT: usize = 5;
+/// Documents sampled to estimate how selective an explained filter is.
+pub(crate) const SELECTIVITY_SAMPLE_SIZE: u64 = 1000;
+
+/// Operators that keep a predicate an equality match.
+const EQUALITY_OPERATORS: [&str; 2] = ["$eq", "$in"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexSuggestion {
+    pub keys: Document,
+    pub equality: Vec<String>,
+    pub sort: Vec<String>,
+    pub range: Vec<String>,
+    /// Top-level operators such as `$or` or `$expr` that the pattern ignores.
+    pub unsupported: Vec<String>,
+}
+
+impl IndexSuggestion {
+    /// The server's default name for the key pattern, e.g. `status_1_age_-1`.
+    pub fn index_name(&self) -> String {
+        self.keys
+            .iter()
+            .map(|(key, value)| format!("{key}_{}", direction(value).unwrap_or(1)))
+            .collect::<Vec<_>>()
+            .join("_")
+    }
+
+    /// The `createIndexes` entry for this suggestion.
+    pub fn index_document(&self) -> Document {
+        let mut index = Document::new();
+        index.insert("key", self.keys.clone());
+        index.insert("name", self.index_name());
+        index
+    }
+
+    /// Which fields went where, e.g. `equality status · sort age · range total`.
+    pub fn rationale(&self) -> String {
+        let mut parts = Vec::new();
+        for (label, fields) in
+            [("equality", &self.equality), ("sort", &self.sort), ("range", &self.range)]
+        {
+            if !fields.is_empty() {
+                parts.push(format!("{label} {}", fields.join(", ")));
+            }
+        }
+        if !self.unsupported.is_empty() {
+            parts.push(format!("ignores {}", self.unsupported.join(", ")));
+        }
+        parts.join(" · ")
+    }
+}
+
+impl AppCommands {
+    /// Estimate the selectivity of the advised filter from a sample and store
+    /// it on the session's explain advice.
+    pub(super) fn sample_advice_selectivity(
+        state: Entity<AppState>,
+        session_key: SessionKey,
+        filter: Document,
+        cx: &mut App,
+    ) {
+        let Some(client) = Self::client_for_session(&state, &session_key, cx) else {
+            return;
+        };
+        let manager = state.read(cx).connection_manager();
+
+        let task = cx.background_spawn({
+            let database = session_key.database.clone();
+            let collection = session_key.collection.clone();
+            async move {
+                manager.sample_selectivity(
+                    &client,
+                    &database,
+                    &collection,
+                    &filter,
+                    SELECTIVITY_SAMPLE_SIZE,
+                )
+            }
+        });
+
+        cx.spawn({
+            let state = state.clone();
+            async move |cx: &mut gpui::AsyncApp| {
+                let result: Result<SampleSelectivity, crate::error::Error> = task.await;
+                let _ = cx.update(|cx| {
+                    state.update(cx, |state, cx| {
+                        let Some(advice) = state
+                            .session_mut(&session_key)
+                            .and_then(|session| session.data.explain.advice.as_mut())
+                        else {
+                            return;
+                        };
+                        advice.selectivity_loading = false;
+                        advice.selectivity = result
+                            .inspect_err(|e| log::warn!("Selectivity sample failed: {}", e))
+                            .ok();
+                        cx.notify();
+                    });
+                });
+            }
+        })
+        .detach();
+    }
+}
+
+/// Propose a key pattern for `filter` and `sort`. Returns `None` when nothing
+/// is indexable or the `_id` index already answers the query.
+pub fn suggest_index(filter: &Document, sort: Option<&Document>) -> Option<IndexSuggestion> {
+    let mut equality = Vec::new();
+    let mut range = Vec::new();
+    let mut unsupported = Vec::new();
+    classify_filter(filter, &mut equality, &mut range, &mut unsupported);
+    if equality.iter().any(|field| field == "_id") {
+        return None;
+    }
+    range.retain(|field| !equality.contains(field));
+
+    let mut keys = Document::new();
+    for field in &equality {
+        keys.insert(field.clone(), 1);
+    }
+    let mut sort_fields = Vec::new();
+    for (field, value) in sort.into_iter().flatten() {
+        if equality.contains(field) || keys.contains_key(field) {
+            continue;
+        }
+        let Some(direction) = direction(value) else {
+            unsupported.push(format!("sort {field}"));
+            continue;
+        };
+        keys.insert(field.clone(), direction);
+        sort_fields.push(field.clone());
+    }
+    range.retain(|field| !sort_fields.contains(field));
+    for field in &range {
+        keys.insert(field.clone(), 1);
+    }
+    if keys.is_empty() || keys.keys().eq(["_id"]) {
+        return None;
+    }
+    Some(IndexSuggestion { keys, equality, sort: sort_fields, range, unsupported })
+}
+
+/// Name of an index whose key pattern starts with `keys`, so it already serves
+/// the suggestion.
+pub fn existing_index_for(keys: &Document, indexes: &[IndexModel]) -> Option<String> {
+    indexes
+        .iter()
+        .filter(|index| {
+            let hidden = index.options.as_ref().and_then(|options| options.hidden);
+            let partial = index
+                .options
+                .as_ref()
+                .and_then(|options| options.partial_filter_expression.as_ref());
+            !hidden.unwrap_or(false) && partial.is_none()
+        })
+        .find(|index| keys_start_with(&index.keys, keys))
+        .and_then(|index| index.options.as_ref().and_then(|options| options.name.clone()))
+}
+
+/// An index suggested by past queries on one collection.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistorySuggestion {
+    pub suggestion: IndexSuggestion,
+    pub queries: usize,
+    /// Summed first-page round trips of those queries.
+    pub total_ms: u64,
+}
+
+/// Group the history entries by suggested key pattern, skipping patterns an
+/// existing index serves, slowest first.
+pub fn suggestions_from_history<'a>(
+    entries: impl IntoIterator<Item = &'a QueryHistoryEntry>,
+    indexes: &[IndexModel],
+) -> Vec<HistorySuggestion> {
+    let mut grouped: Vec<HistorySuggestion> = Vec::new();
+    for entry in entries {
+        let Some(filter) = parse_raw(&entry.filter_raw) else {
+            continue;
+        };
+        let sort = parse_raw(&entry.sort_raw);
+        let Some(suggestion) = suggest_index(&filter, sort.as_ref()) else {
+            continue;
+        };
+        if existing_index_for(&suggestion.keys, indexes).is_some() {
+            continue;
+        }
+        let duration = entry.duration_ms.unwrap_or(0);
+        match grouped.iter_mut().find(|item| item.suggestion.keys == suggestion.keys) {
+            Some(item) => {
+                item.queries += 1;
+                item.total_ms += duration;
+            }
+            None => grouped.push(HistorySuggestion { suggestion, queries: 1, total_ms: duration }),
+        }
+    }
+    grouped.sort_by(|left, right| {
+        right.total_ms.cmp(&left.total_ms).then(right.queries.cmp(&left.queries))
+    });
+    grouped.truncate(HISTORY_SUGGESTION_LIMIT);
+    grouped
+}
+
+fn parse_raw(raw: &str) -> Option<Document> {
+    match raw.trim() {
+        "" => Some(Document::new()),
+        raw => parse_document_from_json(raw).ok(),
+    }
+}
+
+fn classify_filter(
+    filter: &Document,
+    equality: &mut Vec<String>,
+    range: &mut Vec<String>,
+    unsupported: &mut Vec<String>,
+) {
+    for (key, value) in filter {
+        if key == "$and" {
+            for clause in value.as_array().into_iter().flatten() {
+                if let Some(clause) = clause.as_document() {
+                    classify_filter(clause, equality, range, unsupported);
+                }
+            }
+            continue;
+        }
+        if key.starts_with('$') {
+            if !unsupported.contains(key) {
+                unsupported.push(key.clone());
+            }
+            continue;
+        }
+        let target = if is_equality(value) { &mut *equality } else { &mut *range };
+        if !target.contains(key) {
+            target.push(key.clone());
+        }
+    }
+}
+
+fn is_equality(value: &Bson) -> bool {
+    match value {
+        Bson::RegularExpression(_) => false,
+        Bson::Document(operators) if operators.keys().any(|key| key.starts_with('$')) => {
+            operators.keys().all(|key| EQUALITY_OPERATORS.contains(&key.as_str()))
+        }
+        _ => true,
+    }
+}
+
+/// `1` or `-1` for a numeric sort or key direction.
+fn direction(value: &Bson) -> Option<i32> {
+    let number = match value {
+        Bson::Int32(n) => f64::from(*n),
+        Bson::Int64(n) => *n as f64,
+        Bson::Double(n) => *n,
+        _ => return None,
+    };
+    Some(if number < 0.0 { -1 } else { 1 })
+}
+
+#[cfg(test)]
+mod tests {
+    use mongodb::bson::doc;
+    use mongodb::options::IndexOptions;
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[test]
+    fn orders_equality_then_sort_then_range() {
+        let filter = doc! {
+            "total": { "$gt": 100 },
+            "status": "shipped",
+            "$and": [{ "region": { "$in": ["eu", "us"] } }],
+            "$or": [{ "a": 1 }, { "b": 2 }],
+        };
+        let sort = doc! { "createdAt": -1, "status": 1 };
+        let suggestion = suggest_index(&filter, Some(&sort)).expect("suggestion");
+        assert_eq!(suggestion.keys, doc! { "status": 1, "region": 1, "createdAt": -1, "total": 1 });
+        assert_eq!(suggestion.index_name(), "status_1_region_1_createdAt_-1_total_1");
+        assert_eq!(
+            suggestion.rationale(),
+            "equality status, region · sort createdAt · range total · ignores $or"
+        );
+
+        let range_sorted =
+            suggest_index(&doc! { "age": { "$gte": 18 } }, Some(&doc! { "age": 1 })).unwrap();
+        assert_eq!(range_sorted.keys, doc! { "age": 1 });
+        assert_eq!(range_sorted.range, Vec::<String>::new());
+
+        assert!(suggest_index(&doc! { "_id": 5, "x": 1 }, None).is_none());
+        assert!(suggest_index(&doc! {}, None).is_none());
+    }
+
+    #[test]
+    fn history_suggestions_skip_served_patterns_and_group_repeats() {
+        let entry = |filter: &str, sort: &str, duration_ms: u64| {
+            let mut entry = QueryHistoryEntry::new(Uuid::nil(), "shop", "orders");
+            entry.filter_raw = filter.to_string();
+            entry.sort_raw = sort.to_string();
+            entry.duration_ms = Some(duration_ms);
+            entry
+        };
+        let entries = [
+            entry("{\"status\": \"a\"}", "", 40),
+            entry("{\"status\": \"b\"}", "", 50),
+            entry("{\"customer\": 7}", "{\"createdAt\": -1}", 300),
+            entry("{\"sku\": \"x\"}", "", 900),
+            entry("not json", "", 1000),
+        ];
+        let indexes = vec![
+            IndexModel::builder()
+                .keys(doc! { "sku": 1, "warehouse": 1 })
+                .options(IndexOptions::builder().name("sku_1_warehouse_1".to_string()).build())
+                .build(),
+        ];
+        assert_eq!(
+            existing_index_for(&doc! { "sku": 1 }, &indexes).as_deref(),
+            Some("sku_1_warehouse_1")
+        );
+
+        let suggestions = suggestions_from_history(&entries, &indexes);
+        let summary: Vec<(String, usize, u64)> = suggestions
+            .iter()
+            .map(|item| (item.suggestion.index_name(), item.queries, item.total_ms))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("customer_1_createdAt_-1".to_string(), 1, 300), ("status_1".to_string(), 2, 90),]
+        );
+    }
+}
@@ -1,10 +1,15 @@
+use std::time::Duration;
+
 use gpui::{App, AppContext as _, Entity};
 use mongodb::{IndexModel, bson::Document};
 
+use crate::connection::{IndexBuild, IndexChange};
 use crate::state::{AppEvent, AppState, SessionKey};
 
 use super::AppCommands;
 
+const INDEX_BUILD_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
 impl AppCommands {
     /// Load indexes for a collection session.
     pub fn load_collection_indexes(
@@ -41,19 +46,28 @@ impl AppCommands {
         let task = cx.background_spawn({
             let database = database.clone();
             let collection = collection.clone();
-            async move { manager.list_indexes(&client, &database, &collection) }
+            async move {
+                let indexes = manager.list_indexes(&client, &database, &collection)?;
+                // Usage needs the indexStats privilege; indexes still show without it.
+                let usage = manager
+                    .index_usage(&client, &database, &collection)
+                    .inspect_err(|e| log::warn!("Index usage unavailable: {}", e))
+                    .ok();
+                Ok::<_, crate::error::Error>((indexes, usage))
+            }
         });
 
         cx.spawn({
             let state = state.clone();
             let session_key = session_key.clone();
             async move |cx: &mut gpui::AsyncApp| {
-                let result: Result<Vec<IndexModel>, crate::error::Error> = task.await;
+                let result = task.await;
                 let _ = cx.update(|cx| match result {
-                    Ok(indexes) => {
+                    Ok((indexes, usage)) => {
                         state.update(cx, |state, cx| {
                             if let Some(session) = state.session_mut(&session_key) {
                                 session.data.indexes = Some(indexes.clone());
+                                session.data.index_usage = usage;
                                 session.data.indexes_loading = false;
                                 session.data.indexes_error = None;
                             }
@@ -62,6 +76,7 @@ impl AppCommands {
                             cx.emit(event);
                             cx.notify();
                         });
+                        AppCommands::watch_index_builds(state.clone(), session_key.clone(), cx);
                     }
                     Err(e) => {
                         log::error!("Failed to load indexes: {}", e);
@@ -160,12 +175,18 @@ impl AppCommands {
         let manager = state.read(cx).connection_manager();
 
         let index_name = index_doc.get_str("name").ok().map(|value| value.to_string());
+        state.update(cx, |state, _cx| {
+            if let Some(session) = state.session_mut(&session_key) {
+                session.data.index_creates_in_flight += 1;
+            }
+        });
         let task = cx.background_spawn({
             let database = database.clone();
             let collection = collection.clone();
             let index_doc = index_doc.clone();
             async move { manager.create_index(&client, &database, &collection, index_doc) }
         });
+        Self::watch_index_builds(state.clone(), session_key.clone(), cx);
 
         cx.spawn({
             let state = state.clone();
@@ -176,6 +197,7 @@ impl AppCommands {
                 let _ = cx.update(|cx| match result {
                     Ok(()) => {
                         state.update(cx, |state, cx| {
+                            finish_index_create(state, &session_key);
                             let event = AppEvent::IndexCreated {
                                 session: session_key.clone(),
                                 name: index_name.clone(),
@@ -194,6 +216,7 @@ impl AppCommands {
                     Err(e) => {
                         log::error!("Failed to create index: {}", e);
                         state.update(cx, |state, cx| {
+                            finish_index_create(state, &session_key);
                             let event = AppEvent::IndexCreateFailed {
                                 session: session_key.clone(),
                                 error: e.to_string(),
@@ -228,6 +251,11 @@ impl AppCommands {
         let manager = state.read(cx).connection_manager();
 
         let new_name = index_doc.get_str("name").ok().map(|value| value.to_string());
+        state.update(cx, |state, _cx| {
+            if let Some(session) = state.session_mut(&session_key) {
+                session.data.index_creates_in_flight += 1;
+            }
+        });
         let task = cx.background_spawn({
             let database = database.clone();
             let collection = collection.clone();
@@ -245,6 +273,7 @@ impl AppCommands {
                 Ok::<(), crate::error::Error>(())
             }
         });
+        Self::watch_index_builds(state.clone(), session_key.clone(), cx);
 
         cx.spawn({
             let state = state.clone();
@@ -255,6 +284,7 @@ impl AppCommands {
                 let _ = cx.update(|cx| match result {
                     Ok(()) => {
                         state.update(cx, |state, cx| {
+                            finish_index_create(state, &session_key);
                             let event = AppEvent::IndexCreated {
                                 session: session_key.clone(),
                                 name: new_name.clone(),
@@ -273,6 +303,7 @@ impl AppCommands {
                     Err(e) => {
                         log::error!("Failed to replace index: {}", e);
                         state.update(cx, |state, cx| {
+                            finish_index_create(state, &session_key);
                             let event = AppEvent::IndexCreateFailed {
                                 session: session_key.clone(),
                                 error: e.to_string(),
@@ -287,4 +318,155 @@ impl AppCommands {
         })
         .detach();
     }
+    /// Hide, unhide, change the TTL of, or make unique an index via `collMod`.
+    pub fn modify_collection_index(
+        state: Entity<AppState>,
+        session_key: SessionKey,
+        index_name: String,
+        change: IndexChange,
+        cx: &mut App,
+    ) {
+        if !Self::ensure_writable(&state, Some(session_key.connection_id), cx) {
+            return;
+        }
+        let Some(client) = Self::client_for_session(&state, &session_key, cx) else {
+            return;
+        };
+        let manager = state.read(cx).connection_manager();
+
+        let task = cx.background_spawn({
+            let database = session_key.database.clone();
+            let collection = session_key.collection.clone();
+            let index_name = index_name.clone();
+            async move {
+                manager.modify_index(&client, &database, &collection, &index_name, change)
+            }
+        });
+
+        cx.spawn({
+            let state = state.clone();
+            async move |cx: &mut gpui::AsyncApp| {
+                let result: Result<(), crate::error::Error> = task.await;
+                let _ = cx.update(|cx| {
+                    let event = match &result {
+                        Ok(()) => AppEvent::IndexModified {
+                            session: session_key.clone(),
+                            name: index_name.clone(),
+                            change,
+                        },
+                        Err(e) => {
+                            log::error!("Failed to modify index: {}", e);
+                            AppEvent::IndexModifyFailed {
+                                session: session_key.clone(),
+                                error: e.to_string(),
+                            }
+                        }
+                    };
+                    state.update(cx, |state, cx| {
+                        state.update_status_from_event(&event);
+                        cx.emit(event);
+                        cx.notify();
+                    });
+                    if result.is_ok() {
+                        AppCommands::load_collection_indexes(
+                            state.clone(),
+                            session_key.clone(),
+                            true,
+                            cx,
+                        );
+                    }
+                });
+            }
+        })
+        .detach();
+    }
+
+    /// Poll `$currentOp` for index builds on the collection while any run or a
+    /// `createIndexes` from this session is pending, then reload the indexes.
+    pub fn watch_index_builds(state: Entity<AppState>, session_key: SessionKey, cx: &mut App) {
+        let started = state.update(cx, |state, _cx| {
+            let Some(session) = state.session_mut(&session_key) else {
+                return false;
+            };
+            !std::mem::replace(&mut session.data.index_builds_watching, true)
+        });
+        if !started {
+            return;
+        }
+
+        cx.spawn({
+            let state = state.clone();
+            async move |cx: &mut gpui::AsyncApp| {
+                let mut saw_builds = false;
+                loop {
+                    let target = cx.update(|cx| {
+                        let client = Self::client_for_session(&state, &session_key, cx)?;
+                        Some((client, state.read(cx).connection_manager()))
+                    });
+                    let Ok(Some((client, manager))) = target else {
+                        let _ = cx.update(|cx| stop_watching(&state, &session_key, cx));
+                        return;
+                    };
+                    let database = session_key.database.clone();
+                    let collection = session_key.collection.clone();
+                    let result = cx
+                        .background_spawn(async move {
+                            manager.index_builds(&client, &database, &collection)
+                        })
+                        .await;
+                    let builds: Vec<IndexBuild> = match result {
+                        Ok(builds) => builds,
+                        Err(e) => {
+                            // $currentOp needs the inprog privilege; builds just go unseen.
+                            log::warn!("Index builds unavailable: {}", e);
+                            let _ = cx.update(|cx| stop_watching(&state, &session_key, cx));
+                            return;
+                        }
+                    };
+                    saw_builds |= !builds.is_empty();
+
+                    let keep_watching = cx.update(|cx| {
+                        state.update(cx, |state, cx| {
+                            let Some(session) = state.session_mut(&session_key) else {
+                                return false;
+                            };
+                            let keep =
+                                !builds.is_empty() || session.data.index_creates_in_flight > 0;
+                            session.data.index_builds = builds;
+                            session.data.index_builds_watching = keep;
+                            cx.notify();
+                            keep
+                        })
+                    });
+                    if !keep_watching.unwrap_or(false) {
+                        break;
+                    }
+                    cx.background_executor().timer(INDEX_BUILD_POLL_INTERVAL).await;
+                }
+                if saw_builds {
+                    let _ = cx.update(|cx| {
+                        AppCommands::load_collection_indexes(state.clone(), session_key, true, cx);
+                    });
+                }
+            }
+        })
+        .detach();
+    }
+}
+
+fn finish_index_create(state: &mut AppState, session_key: &SessionKey) {
+    if let Some(session) = state.session_mut(session_key) {
+        session.data.index_creates_in_flight =
+            session.data.index_creates_in_flight.saturating_sub(1);
+    }
+}
+
+fn stop_watching(state: &Entity<AppState>, session_key: &SessionKey, cx: &mut App) {
+    state.update(cx, |state, cx| {
+        if let Some(session) = state.session_mut(session_key) {
+            session.data.index_builds_watching = false;
+            session.data.index_builds.clear();
+        }
+        cx.notify();
+    });
 }
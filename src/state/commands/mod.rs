@@ -51,11 +51,17 @@ mod connections;
 mod databases;
 mod documents;
 mod explain;
+mod health;
+mod index_advisor;
 mod indexes;
 mod schema;
 pub use documents::save_as::ExportProgress;
+pub use index_advisor::{
+    HistorySuggestion, IndexSuggestion, existing_index_for, suggest_index, suggestions_from_history,
+};
 pub(crate) use schema::{SCHEMA_SAMPLE_SIZE, build_schema_analysis};
 pub use schema::{schema_to_compass, schema_to_json_schema, schema_to_summary};
 mod stats;
 mod transfer;
 mod updater;
+mod validation;
@@ -57,6 +57,12 @@ impl AppCommands {
                                 session.data.schema = Some(analysis);
                                 session.data.schema_loading = false;
                             }
+                            if state
+                                .session_data(&session_key)
+                                .is_some_and(|data| data.validation.starter_pending)
+                            {
+                                state.fill_validation_starter(&session_key);
+                            }
                             cx.emit(AppEvent::SchemaAnalyzed { session: session_key.clone() });
                             state.update_status_from_event(&AppEvent::SchemaAnalyzed {
                                 session: session_key.clone(),
@@ -71,6 +77,7 @@ impl AppCommands {
                             if let Some(session) = state.session_mut(&session_key) {
                                 session.data.schema_loading = false;
                                 session.data.schema_error = Some(error.clone());
+                                session.data.validation.starter_pending = false;
                             }
                             cx.emit(AppEvent::SchemaFailed {
                                 session: session_key.clone(),
@@ -39,7 +39,7 @@ impl AppCommands {
 
         // For BSON export, we need the connection string instead of client
         let connection_uri = if matches!(config.format, TransferFormat::Bson) {
-            state.read(cx).connection_uri(connection_id)
+            state.read(cx).tool_connection_uri(connection_id)
         } else {
             None
         };
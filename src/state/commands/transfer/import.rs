@@ -38,7 +38,7 @@ impl AppCommands {
 
         // For BSON import, we need the connection string instead of client
         let connection_uri = if matches!(config.format, TransferFormat::Bson) {
-            state.read(cx).connection_uri(connection_id)
+            state.read(cx).tool_connection_uri(connection_id)
         } else {
             None
         };
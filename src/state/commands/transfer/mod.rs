@@ -11,7 +11,7 @@ use gpui::{App, AppContext as _, Entity};
 use uuid::Uuid;
 
 use crate::connection::csv_utils::detect_problematic_fields;
-use crate::connection::{JsonTransferFormat, generate_export_preview};
+use crate::connection::{DocumentCount, JsonTransferFormat, generate_export_preview};
 use crate::state::app_state::CollectionTransferStatus;
 use crate::state::{
     AppCommands, AppEvent, AppState, SessionKey, StatusMessage, TransferFormat, TransferMode,
@@ -375,7 +375,7 @@ impl AppCommands {
                             state.set_status_message(Some(StatusMessage::info(message)));
                             cx.emit(AppEvent::DocumentsLoaded {
                                 session: session_key.clone(),
-                                total: count,
+                                total: DocumentCount::Exact(count),
                             });
                             cx.notify();
                         });
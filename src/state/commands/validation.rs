@@ -0,0 +1,275 @@
+//! Validation rule commands: load, check a draft against a sample, apply.
+
+use gpui::{App, AppContext as _, Entity};
+use mongodb::bson::Document;
+
+use crate::bson::parse_document_from_json;
+use crate::connection::{ValidationCheck, ValidationRules};
+use crate::state::events::AppEvent;
+use crate::state::{AppState, SessionKey, StatusMessage};
+
+use super::AppCommands;
+
+/// Documents sampled when checking a draft validator.
+pub(crate) const VALIDATION_SAMPLE_SIZE: u64 = 1000;
+
+impl AppCommands {
+    /// Load the validator, level and action of a session's collection.
+    pub fn load_collection_validation(
+        state: Entity<AppState>,
+        session_key: SessionKey,
+        cx: &mut App,
+    ) {
+        let Some(client) = Self::client_for_session(&state, &session_key, cx) else {
+            return;
+        };
+        let manager = state.read(cx).connection_manager();
+
+        state.update(cx, |state, cx| {
+            let session = state.ensure_session(session_key.clone());
+            session.data.validation.loading = true;
+            session.data.validation.error = None;
+            cx.notify();
+        });
+
+        let task = cx.background_spawn({
+            let database = session_key.database.clone();
+            let collection = session_key.collection.clone();
+            async move { manager.collection_validation(&client, &database, &collection) }
+        });
+
+        cx.spawn({
+            let state = state.clone();
+            async move |cx: &mut gpui::AsyncApp| {
+                let result: Result<ValidationRules, crate::error::Error> = task.await;
+                let _ = cx.update(|cx| {
+                    state.update(cx, |state, cx| {
+                        let event = match result {
+                            Ok(rules) => {
+                                state.set_validation_rules(&session_key, rules);
+                                AppEvent::ValidationLoaded { session: session_key.clone() }
+                            }
+                            Err(e) => {
+                                log::error!("Failed to load validation rules: {}", e);
+                                if let Some(session) = state.session_mut(&session_key) {
+                                    session.data.validation.loading = false;
+                                    session.data.validation.error = Some(e.to_string());
+                                }
+                                AppEvent::ValidationLoadFailed {
+                                    session: session_key.clone(),
+                                    error: e.to_string(),
+                                }
+                            }
+                        };
+                        state.update_status_from_event(&event);
+                        cx.emit(event);
+                        cx.notify();
+                    });
+                });
+            }
+        })
+        .detach();
+    }
+
+    /// Fill the draft with a `$jsonSchema` starter from the schema analysis,
+    /// analyzing the collection first when needed.
+    pub fn generate_validation_starter(
+        state: Entity<AppState>,
+        session_key: SessionKey,
+        cx: &mut App,
+    ) {
+        let filled = state.update(cx, |state, cx| {
+            let filled = state.fill_validation_starter(&session_key);
+            if !filled && let Some(session) = state.session_mut(&session_key) {
+                session.data.validation.starter_pending = true;
+            }
+            cx.notify();
+            filled
+        });
+        let analyzing =
+            state.read(cx).session_data(&session_key).is_some_and(|data| data.schema_loading);
+        if !filled && !analyzing {
+            Self::analyze_collection_schema(state, session_key, cx);
+        }
+    }
+
+    /// Run the draft validator over a sample and list the documents it would
+    /// reject.
+    pub fn check_validation_draft(state: Entity<AppState>, session_key: SessionKey, cx: &mut App) {
+        let Some(client) = Self::client_for_session(&state, &session_key, cx) else {
+            return;
+        };
+        let validator = match draft_validator(&state, &session_key, cx) {
+            Ok(Some(validator)) => validator,
+            Ok(None) => {
+                set_check_error(&state, &session_key, "Enter a validator to check.".into(), cx);
+                return;
+            }
+            Err(error) => {
+                set_check_error(&state, &session_key, error, cx);
+                return;
+            }
+        };
+        let manager = state.read(cx).connection_manager();
+
+        state.update(cx, |state, cx| {
+            if let Some(session) = state.session_mut(&session_key) {
+                session.data.validation.checking = true;
+                session.data.validation.check_error = None;
+            }
+            cx.notify();
+        });
+
+        let task = cx.background_spawn({
+            let database = session_key.database.clone();
+            let collection = session_key.collection.clone();
+            async move {
+                manager.check_validator(
+                    &client,
+                    &database,
+                    &collection,
+                    &validator,
+                    VALIDATION_SAMPLE_SIZE,
+                )
+            }
+        });
+
+        cx.spawn({
+            let state = state.clone();
+            async move |cx: &mut gpui::AsyncApp| {
+                let result: Result<ValidationCheck, crate::error::Error> = task.await;
+                let _ = cx.update(|cx| {
+                    state.update(cx, |state, cx| {
+                        let Some(session) = state.session_mut(&session_key) else {
+                            return;
+                        };
+                        let validation = &mut session.data.validation;
+                        validation.checking = false;
+                        match result {
+                            Ok(check) => {
+                                let message = format!(
+                                    "{} of {} sampled documents violate the draft",
+                                    check.violation_count, check.sampled
+                                );
+                                validation.check = Some(check);
+                                state.set_status_message(Some(StatusMessage::info(message)));
+                            }
+                            Err(e) => {
+                                log::error!("Failed to check validator: {}", e);
+                                validation.check = None;
+                                validation.check_error = Some(e.to_string());
+                            }
+                        }
+                        cx.notify();
+                    });
+                });
+            }
+        })
+        .detach();
+    }
+
+    /// Replace the collection's rules with the draft via `collMod`.
+    pub fn apply_validation_draft(state: Entity<AppState>, session_key: SessionKey, cx: &mut App) {
+        if !Self::ensure_writable(&state, Some(session_key.connection_id), cx) {
+            return;
+        }
+        let Some(client) = Self::client_for_session(&state, &session_key, cx) else {
+            return;
+        };
+        let validator = match draft_validator(&state, &session_key, cx) {
+            Ok(validator) => validator,
+            Err(error) => {
+                set_check_error(&state, &session_key, error, cx);
+                return;
+            }
+        };
+        let Some(validation) = state.read(cx).session_data(&session_key).map(|d| &d.validation)
+        else {
+            return;
+        };
+        let rules = ValidationRules {
+            validator,
+            level: Some(validation.draft_level.clone()),
+            action: Some(validation.draft_action.clone()),
+        };
+        let manager = state.read(cx).connection_manager();
+
+        state.update(cx, |state, cx| {
+            if let Some(session) = state.session_mut(&session_key) {
+                session.data.validation.applying = true;
+            }
+            cx.notify();
+        });
+
+        let task = cx.background_spawn({
+            let database = session_key.database.clone();
+            let collection = session_key.collection.clone();
+            async move { manager.apply_validation(&client, &database, &collection, &rules) }
+        });
+
+        cx.spawn({
+            let state = state.clone();
+            async move |cx: &mut gpui::AsyncApp| {
+                let result: Result<(), crate::error::Error> = task.await;
+                let _ = cx.update(|cx| {
+                    state.update(cx, |state, cx| {
+                        if let Some(session) = state.session_mut(&session_key) {
+                            session.data.validation.applying = false;
+                        }
+                        match &result {
+                            Ok(()) => {
+                                let event =
+                                    AppEvent::ValidationApplied { session: session_key.clone() };
+                                state.update_status_from_event(&event);
+                                cx.emit(event);
+                            }
+                            Err(e) => {
+                                log::error!("Failed to apply validation rules: {}", e);
+                                state.set_status_message(Some(StatusMessage::error(format!(
+                                    "Applying validation rules failed: {e}"
+                                ))));
+                            }
+                        }
+                        cx.notify();
+                    });
+                    if result.is_ok() {
+                        Self::load_collection_validation(state.clone(), session_key.clone(), cx);
+                    }
+                });
+            }
+        })
+        .detach();
+    }
+}
+
+/// The parsed draft validator; `None` when the draft is empty.
+fn draft_validator(
+    state: &Entity<AppState>,
+    session_key: &SessionKey,
+    cx: &App,
+) -> Result<Option<Document>, String> {
+    let raw = state
+        .read(cx)
+        .session_data(session_key)
+        .map(|data| data.validation.draft_raw.trim().to_string())
+        .unwrap_or_default();
+    if raw.is_empty() {
+        return Ok(None);
+    }
+    parse_document_from_json(&raw).map(Some).map_err(|err| format!("Invalid validator: {err}"))
+}
+
+fn set_check_error(
+    state: &Entity<AppState>,
+    session_key: &SessionKey,
+    error: String,
+    cx: &mut App,
+) {
+    state.update(cx, |state, cx| {
+        if let Some(session) = state.session_mut(session_key) {
+            session.data.validation.check = None;
+            session.data.validation.check_error = Some(error);
+        }
+        cx.notify();
+    });
+}
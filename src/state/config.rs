@@ -3,9 +3,11 @@
 use anyhow::{Context, Result};
 use serde::{Serialize, de::DeserializeOwned};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::models::connection::SavedConnection;
+use crate::state::query_history::QueryHistory;
+use crate::state::saved_queries::SavedQueryLibrary;
 use crate::state::settings::AppSettings;
 use crate::state::workspace::WorkspaceState;
 
@@ -46,30 +48,34 @@ impl ConfigManager {
 
     /// Load data from a JSON file
     fn load_json<T: DeserializeOwned>(&self, filename: &str) -> Result<Option<T>> {
-        let path = self.file_path(filename);
+        Self::load_json_at(&self.file_path(filename))
+    }
+
+    /// Save data to a JSON file (atomic via temp + rename).
+    fn save_json<T: Serialize + ?Sized>(&self, filename: &str, data: &T) -> Result<()> {
+        Self::save_json_at(&self.file_path(filename), data)
+    }
 
+    fn load_json_at<T: DeserializeOwned>(path: &Path) -> Result<Option<T>> {
         if !path.exists() {
             return Ok(None);
         }
 
-        let data =
-            fs::read_to_string(&path).with_context(|| format!("Failed to read {}", filename))?;
+        let data = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
 
         let value: T = serde_json::from_str(&data)
-            .with_context(|| format!("Failed to deserialize {}", filename))?;
+            .with_context(|| format!("Failed to deserialize {}", path.display()))?;
 
         Ok(Some(value))
     }
 
-    /// Save data to a JSON file (atomic via temp + rename).
-    fn save_json<T: Serialize + ?Sized>(&self, filename: &str, data: &T) -> Result<()> {
-        let path = self.file_path(filename);
-
+    fn save_json_at<T: Serialize + ?Sized>(path: &Path, data: &T) -> Result<()> {
         let json = serde_json::to_string_pretty(data)
-            .with_context(|| format!("Failed to serialize {}", filename))?;
+            .with_context(|| format!("Failed to serialize {}", path.display()))?;
 
-        atomic_write(&path, json.as_bytes())
-            .with_context(|| format!("Failed to write {}", filename))?;
+        atomic_write(path, json.as_bytes())
+            .with_context(|| format!("Failed to write {}", path.display()))?;
 
         Ok(())
     }
@@ -134,6 +140,50 @@ impl ConfigManager {
         to_save.ai.api_key.clear();
         self.save_json(Self::SETTINGS_FILE, &to_save)
     }
+
+    // =========================================================================
+    // Query history
+    // =========================================================================
+
+    const QUERY_HISTORY_FILE: &'static str = "query_history.json";
+
+    /// Load the query history from disk
+    pub fn load_query_history(&self) -> Result<QueryHistory> {
+        if let Some(history) = self.load_json(Self::QUERY_HISTORY_FILE)? {
+            return Ok(history);
+        }
+        Ok(QueryHistory::default())
+    }
+
+    /// Save the query history to disk
+    pub fn save_query_history(&self, history: &QueryHistory) -> Result<()> {
+        self.save_json(Self::QUERY_HISTORY_FILE, history)
+    }
+
+    // =========================================================================
+    // Saved queries
+    // =========================================================================
+
+    const SAVED_QUERIES_FILE: &'static str = "saved_queries.json";
+
+    /// Location of the saved query library: `custom_path` when set (e.g. a
+    /// file in a team repository), otherwise the config directory.
+    pub fn saved_queries_path(&self, custom_path: &str) -> PathBuf {
+        match custom_path.trim() {
+            "" => self.file_path(Self::SAVED_QUERIES_FILE),
+            path => PathBuf::from(path),
+        }
+    }
+
+    /// Load the saved query library; a missing file is an empty library
+    pub fn load_saved_queries(&self, path: &Path) -> Result<SavedQueryLibrary> {
+        Ok(Self::load_json_at(path)?.unwrap_or_default())
+    }
+
+    /// Save the saved query library
+    pub fn save_saved_queries(&self, path: &Path, library: &SavedQueryLibrary) -> Result<()> {
+        Self::save_json_at(path, library)
+    }
 }
 
 impl Default for ConfigManager {
@@ -185,4 +235,50 @@ mod tests {
         assert_eq!(loaded.len(), 1);
         assert_eq!(loaded[0].name, connection.name);
     }
+
+    #[test]
+    fn query_history_roundtrips() {
+        let temp_dir = TempDir::new().expect("failed to create temp dir");
+        let manager = ConfigManager::with_config_dir(temp_dir.path().to_path_buf());
+        assert!(manager.load_query_history().expect("missing file is empty").entries.is_empty());
+
+        let mut history = QueryHistory::default();
+        let mut entry =
+            crate::state::QueryHistoryEntry::new(uuid::Uuid::new_v4(), "shop", "orders");
+        entry.filter_raw = "{status: \"a\"}".to_string();
+        entry.result_count = Some(crate::connection::DocumentCount::AtLeast(1000));
+        history.record(entry.clone());
+        manager.save_query_history(&history).expect("failed to save history");
+
+        let loaded = manager.load_query_history().expect("failed to load history");
+        assert_eq!(loaded.entries, vec![entry]);
+    }
+
+    #[test]
+    fn saved_queries_use_custom_path() {
+        let temp_dir = TempDir::new().expect("failed to create temp dir");
+        let manager = ConfigManager::with_config_dir(temp_dir.path().join("config"));
+        assert_eq!(
+            manager.saved_queries_path(""),
+            temp_dir.path().join("config").join("saved_queries.json")
+        );
+
+        let team_file = temp_dir.path().join("team-queries.json");
+        let path = manager.saved_queries_path(&team_file.display().to_string());
+        assert_eq!(path, team_file);
+        assert!(
+            manager.load_saved_queries(&path).expect("missing file is empty").queries.is_empty()
+        );
+
+        let mut library = SavedQueryLibrary::default();
+        library.upsert(crate::state::saved_queries::SavedQuery::new(
+            "All",
+            Default::default(),
+            crate::state::saved_queries::SavedQueryBody::Forge { script: "db.stats()".into() },
+        ));
+        manager.save_saved_queries(&path, &library).expect("failed to save library");
+        let loaded = manager.load_saved_queries(&path).expect("failed to load library");
+        assert_eq!(loaded.queries.len(), 1);
+        assert_eq!(loaded.queries[0].name, "All");
+    }
 }
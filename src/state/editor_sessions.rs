@@ -31,12 +31,24 @@ pub enum EditorSessionTarget {
     Insert,
 }
 
+/// A save refused because the server copy changed since the edit started.
+#[derive(Debug, Clone)]
+pub struct SaveConflict {
+    /// The document as it was when editing started.
+    pub loaded: Document,
+    /// The edit that was being saved.
+    pub draft: Document,
+    /// The current server copy; `None` when it has been deleted.
+    pub current: Option<Document>,
+}
+
 #[derive(Debug, Clone)]
 pub struct EditorSession {
     pub id: EditorSessionId,
     pub session_key: SessionKey,
     pub target: EditorSessionTarget,
     pub content: String,
+    pub conflict: Option<SaveConflict>,
 }
 
 impl EditorSession {
@@ -96,6 +108,7 @@ impl EditorSessionStore {
                 baseline_document: Box::new(baseline_document),
             },
             content,
+            conflict: None,
         };
         self.with_inner_mut(|inner| {
             inner.sessions.insert(id, session);
@@ -112,8 +125,13 @@ impl EditorSessionStore {
     ) -> EditorSessionId {
         let id = EditorSessionId::new();
         let key = EditorSessionKey::Insert { session_key: session_key.clone() };
-        let session =
-            EditorSession { id, session_key, target: EditorSessionTarget::Insert, content };
+        let session = EditorSession {
+            id,
+            session_key,
+            target: EditorSessionTarget::Insert,
+            content,
+            conflict: None,
+        };
         self.with_inner_mut(|inner| {
             inner.sessions.insert(id, session);
             inner.keys.insert(key.clone(), id);
@@ -157,6 +175,17 @@ impl EditorSessionStore {
         .unwrap_or(false)
     }
 
+    pub fn set_conflict(&self, id: EditorSessionId, conflict: Option<SaveConflict>) -> bool {
+        self.with_inner_mut(|inner| {
+            let Some(session) = inner.sessions.get_mut(&id) else {
+                return false;
+            };
+            session.conflict = conflict;
+            true
+        })
+        .unwrap_or(false)
+    }
+
     pub fn close(&self, id: EditorSessionId) -> bool {
         self.with_inner_mut(|inner| {
             let removed = inner.sessions.remove(&id).is_some();
@@ -3,8 +3,10 @@
 use uuid::Uuid;
 
 use crate::bson::DocumentKey;
+use crate::connection::{DocumentCount, IndexChange};
 use crate::state::SessionKey;
 use crate::state::app_state::CollectionTransferStatus;
+use crate::state::editor_sessions::SaveConflict;
 
 /// Events emitted by AppState for UI reactivity
 #[derive(Debug, Clone)]
@@ -20,6 +22,16 @@ pub enum AppEvent {
     Connected(Uuid),
     Disconnected(Uuid),
     ConnectionFailed(String),
+    /// The health monitor saw the tunnel or server heartbeat fail.
+    ConnectionLost {
+        connection_id: Uuid,
+        error: String,
+    },
+    Reconnecting {
+        connection_id: Uuid,
+        attempt: u32,
+    },
+    Reconnected(Uuid),
 
     // Data loaded
     DatabasesLoaded(Vec<String>),
@@ -27,7 +39,18 @@ pub enum AppEvent {
     CollectionsFailed(String),
     DocumentsLoaded {
         session: SessionKey,
-        total: u64,
+        total: DocumentCount,
+    },
+    /// More documents were read from a stream; `dropped` fell out of the
+    /// window at the front.
+    DocumentsStreamed {
+        session: SessionKey,
+        added: usize,
+        dropped: usize,
+    },
+    DocumentsStreamFailed {
+        session: SessionKey,
+        error: String,
     },
     DocumentInserted,
     DocumentInsertFailed {
@@ -48,6 +71,20 @@ pub enum AppEvent {
         session: SessionKey,
         error: String,
     },
+    DocumentsSaved {
+        session: SessionKey,
+        count: usize,
+    },
+    DocumentsSaveFailed {
+        session: SessionKey,
+        count: usize,
+        error: String,
+    },
+    DocumentSaveConflict {
+        session: SessionKey,
+        document: DocumentKey,
+        conflict: Box<SaveConflict>,
+    },
     DocumentDeleted {
         session: SessionKey,
         document: DocumentKey,
@@ -76,10 +113,21 @@ pub enum AppEvent {
         session: SessionKey,
         error: String,
     },
+    IndexModified {
+        session: SessionKey,
+        name: String,
+        change: IndexChange,
+    },
+    IndexModifyFailed {
+        session: SessionKey,
+        error: String,
+    },
     DocumentsUpdated {
         session: SessionKey,
         matched: u64,
         modified: u64,
+        /// Matched the filter only after the journal's pre-read, so left unchanged.
+        skipped: u64,
     },
     DocumentsUpdateFailed {
         session: SessionKey,
@@ -88,6 +136,8 @@ pub enum AppEvent {
     DocumentsDeleted {
         session: SessionKey,
         deleted: u64,
+        /// Matched the filter only after the journal's pre-read, so left in place.
+        skipped: u64,
     },
     DocumentsDeleteFailed {
         session: SessionKey,
@@ -160,6 +210,18 @@ pub enum AppEvent {
         error: String,
     },
 
+    // Validation rules
+    ValidationLoaded {
+        session: SessionKey,
+    },
+    ValidationLoadFailed {
+        session: SessionKey,
+        error: String,
+    },
+    ValidationApplied {
+        session: SessionKey,
+    },
+
     // View navigation
     ViewChanged,
 
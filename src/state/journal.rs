@@ -0,0 +1,192 @@
+//! Per-session journal of committed document writes, for undo and redo.
+
+use std::collections::VecDeque;
+
+use crate::connection::DocumentChange;
+
+/// Operations kept per session; the oldest are dropped first.
+pub const JOURNAL_LIMIT: usize = 50;
+
+/// Writes touching more documents than this are not journaled, so a bulk
+/// update does not hold a copy of a whole collection in memory.
+pub const JOURNAL_MAX_DOCUMENTS: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JournalDirection {
+    Undo,
+    Redo,
+}
+
+impl JournalDirection {
+    pub fn verb(self) -> &'static str {
+        match self {
+            Self::Undo => "Undo",
+            Self::Redo => "Redo",
+        }
+    }
+
+    pub fn past_tense(self) -> &'static str {
+        match self {
+            Self::Undo => "Undid",
+            Self::Redo => "Redid",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct JournalEntry {
+    /// What the operation did, e.g. "edit" or "update of 3 documents".
+    pub label: String,
+    pub changes: Vec<DocumentChange>,
+}
+
+impl JournalEntry {
+    pub fn new(label: impl Into<String>, changes: Vec<DocumentChange>) -> Self {
+        Self { label: label.into(), changes }
+    }
+
+    /// Changes to apply to undo or redo the operation. Undo restores the
+    /// pre-images in reverse order.
+    pub fn changes_for(&self, direction: JournalDirection) -> Vec<DocumentChange> {
+        match direction {
+            JournalDirection::Undo => {
+                self.changes.iter().rev().map(DocumentChange::inverse).collect()
+            }
+            JournalDirection::Redo => self.changes.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct OperationJournal {
+    undo: VecDeque<JournalEntry>,
+    redo: Vec<JournalEntry>,
+}
+
+impl OperationJournal {
+    /// Add a newly committed operation. Anything undone before it can no
+    /// longer be redone.
+    pub fn record(&mut self, entry: JournalEntry) {
+        if entry.changes.is_empty() {
+            return;
+        }
+        self.redo.clear();
+        self.push_undo(entry);
+    }
+
+    /// Take the next entry to undo or redo. It stays off both stacks until
+    /// handed back with [`finish`](Self::finish).
+    pub fn take(&mut self, direction: JournalDirection) -> Option<JournalEntry> {
+        match direction {
+            JournalDirection::Undo => self.undo.pop_back(),
+            JournalDirection::Redo => self.redo.pop(),
+        }
+    }
+
+    /// Hand back a taken entry: onto the opposite stack once applied, or back
+    /// where it came from when the server refused it.
+    pub fn finish(&mut self, direction: JournalDirection, entry: JournalEntry, applied: bool) {
+        match (direction, applied) {
+            (JournalDirection::Undo, true) | (JournalDirection::Redo, false) => {
+                self.redo.push(entry)
+            }
+            (JournalDirection::Undo, false) | (JournalDirection::Redo, true) => {
+                self.push_undo(entry)
+            }
+        }
+    }
+
+    fn push_undo(&mut self, entry: JournalEntry) {
+        self.undo.push_back(entry);
+        if self.undo.len() > JOURNAL_LIMIT {
+            self.undo.pop_front();
+        }
+    }
+
+    pub fn undo_label(&self) -> Option<&str> {
+        self.undo.back().map(|entry| entry.label.as_str())
+    }
+
+    pub fn redo_label(&self) -> Option<&str> {
+        self.redo.last().map(|entry| entry.label.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mongodb::bson::{Bson, doc};
+
+    use super::*;
+
+    fn edit(n: i32) -> JournalEntry {
+        JournalEntry::new(
+            format!("edit {n}"),
+            vec![DocumentChange {
+                id: Bson::Int32(n),
+                before: Some(doc! { "_id": n, "v": 0 }),
+                after: Some(doc! { "_id": n, "v": 1 }),
+            }],
+        )
+    }
+
+    #[test]
+    fn record_clears_redo_and_is_bounded() {
+        let mut journal = OperationJournal::default();
+        journal.record(edit(1));
+        journal.record(JournalEntry::new("noop", Vec::new()));
+        assert_eq!(journal.undo_label(), Some("edit 1"));
+
+        let entry = journal.take(JournalDirection::Undo).unwrap();
+        assert_eq!(journal.undo_label(), None);
+        journal.finish(JournalDirection::Undo, entry, false);
+        assert_eq!(journal.undo_label(), Some("edit 1"));
+
+        let entry = journal.take(JournalDirection::Undo).unwrap();
+        journal.finish(JournalDirection::Undo, entry, true);
+        assert_eq!(journal.undo_label(), None);
+        assert_eq!(journal.redo_label(), Some("edit 1"));
+
+        journal.record(edit(2));
+        assert_eq!(journal.redo_label(), None);
+
+        for n in 0..JOURNAL_LIMIT as i32 + 5 {
+            journal.record(edit(n));
+        }
+        let mut count = 0;
+        while journal.take(JournalDirection::Undo).is_some() {
+            count += 1;
+        }
+        assert_eq!(count, JOURNAL_LIMIT);
+    }
+
+    #[test]
+    fn undo_changes_invert_in_reverse_order() {
+        let entry = JournalEntry::new(
+            "paste",
+            vec![
+                DocumentChange { id: Bson::Int32(1), before: None, after: Some(doc! { "_id": 1 }) },
+                DocumentChange { id: Bson::Int32(2), before: Some(doc! { "_id": 2 }), after: None },
+            ],
+        );
+        assert_eq!(entry.changes_for(JournalDirection::Redo), entry.changes);
+        let undo = entry.changes_for(JournalDirection::Undo);
+        assert_eq!(undo[0].id, Bson::Int32(2));
+        assert_eq!(undo[0].after, Some(doc! { "_id": 2 }));
+        assert_eq!(undo[1].before, Some(doc! { "_id": 1 }));
+        assert_eq!(undo[1].after, None);
+    }
+
+    #[test]
+    fn diff_pairs_images_by_id() {
+        let before = vec![doc! { "_id": 1, "v": 0 }, doc! { "_id": 2, "v": 0 }];
+        let after = vec![doc! { "v": 0, "_id": 2 }, doc! { "_id": 1, "v": 1 }, doc! { "_id": 3 }];
+        let changes = DocumentChange::diff(before, after);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].id, Bson::Int32(1));
+        assert_eq!(changes[0].after, Some(doc! { "_id": 1, "v": 1 }));
+        assert_eq!(changes[1].before, None);
+
+        let deleted = DocumentChange::diff(vec![doc! { "_id": 1 }], Vec::new());
+        assert_eq!(deleted[0].after, None);
+    }
+}
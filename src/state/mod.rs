@@ -6,6 +6,9 @@ pub mod commands;
 pub mod config;
 pub mod editor_sessions;
 pub mod events;
+pub mod journal;
+pub mod query_history;
+pub mod saved_queries;
 pub mod settings;
 pub mod status;
 pub mod transfer_rules;
@@ -18,17 +21,21 @@ pub use app_state::{
     DatabaseSessionState, DatabaseStats, DocumentViewMode, Encoding, ExplainBottleneck,
     ExplainCostBand, ExplainDiff, ExplainNode, ExplainOpenMode, ExplainPanelTab,
     ExplainRejectedPlan, ExplainRun, ExplainScope, ExplainSeverity, ExplainStageDelta,
-    ExplainState, ExplainSummary, ExplainViewMode, ExtendedJsonMode, InsertMode, SchemaAnalysis,
-    SchemaCardinality, SchemaField, SchemaFieldType, SessionData, SessionDocument, SessionKey,
-    SessionState, SessionViewState, TabKey, TargetWriteMode, TransferFormat, TransferMode,
-    TransferScope, TransferTabKey, TransferTabState, View,
+    ExplainState, ExplainSummary, ExplainViewMode, ExtendedJsonMode, IndexAdvice, InsertMode,
+    PagingSnapshot, SavedQueryRun, SchemaAnalysis, SchemaCardinality, SchemaField, SchemaFieldType,
+    SessionData, SessionDocument, SessionKey, SessionState, SessionViewState, TabKey,
+    TargetWriteMode, TransferFormat, TransferMode, TransferScope, TransferTabKey, TransferTabState,
+    ValidationState, View,
 };
 pub use commands::AppCommands;
 pub use config::ConfigManager;
 pub use editor_sessions::{
-    EditorSession, EditorSessionId, EditorSessionStore, EditorSessionTarget,
+    EditorSession, EditorSessionId, EditorSessionStore, EditorSessionTarget, SaveConflict,
 };
 pub use events::AppEvent;
+pub use journal::{JournalDirection, JournalEntry, OperationJournal};
+pub use query_history::{QueryHistory, QueryHistoryEntry};
+pub use saved_queries::{SavedQuery, SavedQueryBody, SavedQueryLibrary, SavedQueryScope};
 pub use settings::{
     AppSettings, AppTheme, AppearanceSettings, DATABASE_SCOPE_FILENAME_TEMPLATE,
     DEFAULT_FILENAME_TEMPLATE, FILENAME_PLACEHOLDERS, IslandsAppearanceSettings,
@@ -0,0 +1,193 @@
+//! Persisted history of queries run from collection tabs.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::connection::DocumentCount;
+
+/// Entries kept on disk; the oldest are dropped first.
+pub const QUERY_HISTORY_LIMIT: usize = 500;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueryHistoryEntry {
+    pub id: Uuid,
+    pub connection_id: Uuid,
+    pub database: String,
+    pub collection: String,
+    #[serde(default)]
+    pub filter_raw: String,
+    #[serde(default)]
+    pub sort_raw: String,
+    #[serde(default)]
+    pub projection_raw: String,
+    #[serde(default)]
+    pub query_options_raw: String,
+    pub executed_at: DateTime<Utc>,
+    /// Round trip of the first page load; `None` until it completes.
+    #[serde(default)]
+    pub duration_ms: Option<u64>,
+    #[serde(default)]
+    pub result_count: Option<DocumentCount>,
+}
+
+impl QueryHistoryEntry {
+    pub fn new(
+        connection_id: Uuid,
+        database: impl Into<String>,
+        collection: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            connection_id,
+            database: database.into(),
+            collection: collection.into(),
+            filter_raw: String::new(),
+            sort_raw: String::new(),
+            projection_raw: String::new(),
+            query_options_raw: String::new(),
+            executed_at: Utc::now(),
+            duration_ms: None,
+            result_count: None,
+        }
+    }
+
+    /// One-line summary of the query, e.g. `{status: "a"} sort {age: -1}`.
+    pub fn summary(&self) -> String {
+        let mut summary = match self.filter_raw.trim() {
+            "" => "{}".to_string(),
+            filter => filter.to_string(),
+        };
+        for (label, raw) in [
+            ("sort", &self.sort_raw),
+            ("project", &self.projection_raw),
+            ("options", &self.query_options_raw),
+        ] {
+            let raw = raw.trim();
+            if !raw.is_empty() && raw != "{}" {
+                summary.push_str(&format!(" {label} {raw}"));
+            }
+        }
+        summary
+    }
+
+    /// Namespace, local run time, duration and result count.
+    pub fn details(&self) -> String {
+        let mut details = format!(
+            "{}.{} · {}",
+            self.database,
+            self.collection,
+            self.executed_at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")
+        );
+        if let Some(duration_ms) = self.duration_ms {
+            details.push_str(&format!(" · {duration_ms} ms"));
+        }
+        if let Some(count) = self.result_count {
+            details.push_str(&format!(" · {count} docs"));
+        }
+        details
+    }
+
+    fn same_query(&self, other: &Self) -> bool {
+        self.connection_id == other.connection_id
+            && self.database == other.database
+            && self.collection == other.collection
+            && self.filter_raw.trim() == other.filter_raw.trim()
+            && self.sort_raw.trim() == other.sort_raw.trim()
+            && self.projection_raw.trim() == other.projection_raw.trim()
+            && self.query_options_raw.trim() == other.query_options_raw.trim()
+    }
+
+    fn matches(&self, needle: &str) -> bool {
+        [
+            &self.database,
+            &self.collection,
+            &self.filter_raw,
+            &self.sort_raw,
+            &self.projection_raw,
+            &self.query_options_raw,
+        ]
+        .iter()
+        .any(|field| field.to_lowercase().contains(needle))
+    }
+}
+
+/// Most recent first.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct QueryHistory {
+    #[serde(default)]
+    pub entries: Vec<QueryHistoryEntry>,
+}
+
+impl QueryHistory {
+    /// Add `entry` at the front. Re-running a query already in the history moves
+    /// it to the front instead of adding a duplicate.
+    pub fn record(&mut self, entry: QueryHistoryEntry) {
+        self.entries.retain(|existing| !existing.same_query(&entry));
+        self.entries.insert(0, entry);
+        self.entries.truncate(QUERY_HISTORY_LIMIT);
+    }
+
+    pub fn get(&self, id: Uuid) -> Option<&QueryHistoryEntry> {
+        self.entries.iter().find(|entry| entry.id == id)
+    }
+
+    pub fn get_mut(&mut self, id: Uuid) -> Option<&mut QueryHistoryEntry> {
+        self.entries.iter_mut().find(|entry| entry.id == id)
+    }
+
+    /// Entries whose namespace or query text contains `query` (case-insensitive).
+    pub fn search(&self, query: &str) -> impl Iterator<Item = &QueryHistoryEntry> {
+        let needle = query.trim().to_lowercase();
+        self.entries.iter().filter(move |entry| needle.is_empty() || entry.matches(&needle))
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(filter: &str) -> QueryHistoryEntry {
+        let mut entry = QueryHistoryEntry::new(Uuid::nil(), "shop", "orders");
+        entry.filter_raw = filter.to_string();
+        entry
+    }
+
+    #[test]
+    fn record_moves_repeated_queries_to_front_and_is_bounded() {
+        let mut history = QueryHistory::default();
+        history.record(entry("{a: 1}"));
+        history.record(entry("{b: 1}"));
+        history.record(entry(" {a: 1} "));
+        let filters: Vec<&str> =
+            history.entries.iter().map(|entry| entry.filter_raw.as_str()).collect();
+        assert_eq!(filters, vec![" {a: 1} ", "{b: 1}"]);
+
+        for i in 0..QUERY_HISTORY_LIMIT + 10 {
+            history.record(entry(&format!("{{n: {i}}}")));
+        }
+        assert_eq!(history.entries.len(), QUERY_HISTORY_LIMIT);
+        assert_eq!(history.entries[0].filter_raw, format!("{{n: {}}}", QUERY_HISTORY_LIMIT + 9));
+    }
+
+    #[test]
+    fn search_and_summary() {
+        let mut history = QueryHistory::default();
+        let mut sorted = entry("{status: \"Shipped\"}");
+        sorted.sort_raw = "{total: -1}".to_string();
+        sorted.projection_raw = "{}".to_string();
+        history.record(sorted);
+        history.record(entry(""));
+
+        assert_eq!(history.search("").count(), 2);
+        assert_eq!(history.search("shipped").count(), 1);
+        assert_eq!(history.search("ORDERS").count(), 2);
+        assert_eq!(history.search("missing").count(), 0);
+        assert_eq!(history.entries[0].summary(), "{}");
+        assert_eq!(history.entries[1].summary(), "{status: \"Shipped\"} sort {total: -1}");
+    }
+}
@@ -0,0 +1,273 @@
+//! Library of named queries with `{{param}}` placeholders.
+//!
+//! The library is a plain JSON file. By default it lives in the config
+//! directory, but it can point anywhere, e.g. into a git repository so a team
+//! shares one set of queries.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::state::app_state::PipelineStage;
+
+/// Where a saved query is offered.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SavedQueryScope {
+    /// Runs against whichever collection (or database, for scripts) is selected.
+    #[default]
+    Global,
+    /// Tied to a collection by name, on any connection.
+    Collection { database: String, collection: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SavedQueryBody {
+    Find {
+        #[serde(default)]
+        filter: String,
+        #[serde(default)]
+        sort: String,
+        #[serde(default)]
+        projection: String,
+    },
+    Aggregation {
+        #[serde(default)]
+        pipeline: Vec<PipelineStage>,
+    },
+    Forge {
+        #[serde(default)]
+        script: String,
+    },
+}
+
+impl SavedQueryBody {
+    pub fn kind_label(&self) -> &'static str {
+        match self {
+            Self::Find { .. } => "Find",
+            Self::Aggregation { .. } => "Aggregation",
+            Self::Forge { .. } => "Forge script",
+        }
+    }
+
+    fn texts(&self) -> Vec<&str> {
+        match self {
+            Self::Find { filter, sort, projection } => vec![filter, sort, projection],
+            Self::Aggregation { pipeline } => {
+                pipeline.iter().map(|stage| stage.body.as_str()).collect()
+            }
+            Self::Forge { script } => vec![script],
+        }
+    }
+
+    /// Placeholder names in order of first appearance.
+    pub fn parameters(&self) -> Vec<String> {
+        let mut names = Vec::new();
+        for text in self.texts() {
+            for name in placeholders(text) {
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
+        names
+    }
+
+    /// Copy of the body with every placeholder replaced by its value.
+    pub fn with_parameters(&self, values: &HashMap<String, String>) -> Self {
+        match self {
+            Self::Find { filter, sort, projection } => Self::Find {
+                filter: substitute(filter, values),
+                sort: substitute(sort, values),
+                projection: substitute(projection, values),
+            },
+            Self::Aggregation { pipeline } => Self::Aggregation {
+                pipeline: pipeline
+                    .iter()
+                    .map(|stage| PipelineStage {
+                        body: substitute(&stage.body, values),
+                        ..stage.clone()
+                    })
+                    .collect(),
+            },
+            Self::Forge { script } => Self::Forge { script: substitute(script, values) },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedQuery {
+    pub id: Uuid,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub scope: SavedQueryScope,
+    pub query: SavedQueryBody,
+}
+
+impl SavedQuery {
+    pub fn new(name: impl Into<String>, scope: SavedQueryScope, query: SavedQueryBody) -> Self {
+        Self { id: Uuid::new_v4(), name: name.into(), description: String::new(), scope, query }
+    }
+
+    /// Whether the query is offered while `database.collection` is selected.
+    pub fn applies_to(&self, database: Option<&str>, collection: Option<&str>) -> bool {
+        match &self.scope {
+            SavedQueryScope::Global => true,
+            SavedQueryScope::Collection { database: db, collection: coll } => {
+                database == Some(db.as_str()) && collection == Some(coll.as_str())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SavedQueryLibrary {
+    #[serde(default)]
+    pub queries: Vec<SavedQuery>,
+}
+
+impl SavedQueryLibrary {
+    pub fn get(&self, id: Uuid) -> Option<&SavedQuery> {
+        self.queries.iter().find(|query| query.id == id)
+    }
+
+    /// Insert a query, replacing the one with the same id.
+    pub fn upsert(&mut self, query: SavedQuery) {
+        match self.queries.iter_mut().find(|existing| existing.id == query.id) {
+            Some(existing) => *existing = query,
+            None => self.queries.push(query),
+        }
+        self.queries.sort_by_key(|query| query.name.to_lowercase());
+    }
+}
+
+/// Names of the `{{name}}` placeholders in `text`, in order, with repeats.
+pub fn placeholders(text: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        let name = after[..end].trim();
+        if is_parameter_name(name) {
+            names.push(name.to_string());
+            rest = &after[end + 2..];
+        } else {
+            rest = &rest[start + 1..];
+        }
+    }
+    names
+}
+
+/// Replace each `{{name}}` with its value verbatim; values are inserted as
+/// written, so string parameters need their own quotes. Unknown placeholders
+/// are left in place.
+pub fn substitute(text: &str, values: &HashMap<String, String>) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        let name = after[..end].trim();
+        match values.get(name).filter(|_| is_parameter_name(name)) {
+            Some(value) => {
+                out.push_str(&rest[..start]);
+                out.push_str(value);
+                rest = &after[end + 2..];
+            }
+            None => {
+                out.push_str(&rest[..start + 1]);
+                rest = &rest[start + 1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn is_parameter_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn placeholders_are_found_and_substituted() {
+        let text = r#"{status: {{status}}, total: {$gt: {{ min_total }}}, tags: {{status}}}"#;
+        assert_eq!(placeholders(text), vec!["status", "min_total", "status"]);
+        assert!(placeholders("{a: {b: 1}}").is_empty());
+
+        let values = HashMap::from([
+            ("status".to_string(), "\"shipped\"".to_string()),
+            ("min_total".to_string(), "100".to_string()),
+        ]);
+        assert_eq!(
+            substitute(text, &values),
+            r#"{status: "shipped", total: {$gt: 100}, tags: "shipped"}"#
+        );
+        assert_eq!(substitute("{a: {{other}}}", &values), "{a: {{other}}}");
+    }
+
+    #[test]
+    fn body_parameters_span_every_text() {
+        let body = SavedQueryBody::Aggregation {
+            pipeline: vec![
+                PipelineStage { body: "{ city: {{city}} }".into(), ..PipelineStage::new("$match") },
+                PipelineStage { body: "{{limit}}".into(), ..PipelineStage::new("$limit") },
+            ],
+        };
+        assert_eq!(body.parameters(), vec!["city", "limit"]);
+        let values = HashMap::from([
+            ("city".to_string(), "\"Oslo\"".to_string()),
+            ("limit".to_string(), "5".to_string()),
+        ]);
+        let SavedQueryBody::Aggregation { pipeline } = body.with_parameters(&values) else {
+            panic!("kind changed");
+        };
+        assert_eq!(pipeline[0].body, "{ city: \"Oslo\" }");
+        assert_eq!(pipeline[1].body, "5");
+        assert_eq!(pipeline[1].operator, "$limit");
+    }
+
+    #[test]
+    fn library_json_roundtrip_and_scope() {
+        let mut library = SavedQueryLibrary::default();
+        let scoped = SavedQuery::new(
+            "Late orders",
+            SavedQueryScope::Collection { database: "shop".into(), collection: "orders".into() },
+            SavedQueryBody::Find {
+                filter: "{late: true}".into(),
+                sort: String::new(),
+                projection: String::new(),
+            },
+        );
+        let global = SavedQuery::new(
+            "count",
+            SavedQueryScope::Global,
+            SavedQueryBody::Forge { script: "db.stats()".into() },
+        );
+        library.upsert(scoped.clone());
+        library.upsert(global);
+
+        assert_eq!(library.queries[0].name, "count");
+        assert!(scoped.applies_to(Some("shop"), Some("orders")));
+        assert!(!scoped.applies_to(Some("shop"), Some("users")));
+        assert!(library.queries[0].applies_to(None, None));
+
+        let json = serde_json::to_string_pretty(&library).unwrap();
+        assert!(json.contains("\"kind\": \"find\""));
+        let parsed: SavedQueryLibrary = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.queries.len(), 2);
+        assert_eq!(parsed.get(scoped.id).map(|q| q.scope.clone()), Some(scoped.scope));
+    }
+}
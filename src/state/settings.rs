@@ -13,11 +13,24 @@ pub struct AppSettings {
     #[serde(default)]
     pub transfer: TransferSettings,
     #[serde(default)]
+    pub browser: BrowserSettings,
+    #[serde(default)]
     pub ai: AiSettings,
     #[serde(default = "default_current_version")]
     pub last_seen_version: String,
     #[serde(default = "default_true")]
     pub auto_update: bool,
+    /// Saved query library file; empty uses the config directory.
+    #[serde(default)]
+    pub saved_queries_path: String,
+    /// Field (dotted path) compared instead of the whole document when saving
+    /// edits, e.g. `version` or `updatedAt`. Empty compares the document.
+    #[serde(default)]
+    pub save_version_field: String,
+    /// Save several edited documents, or paste several, in one transaction
+    /// on replica sets and sharded clusters.
+    #[serde(default)]
+    pub transactional_batches: bool,
 }
 
 fn default_current_version() -> String {
@@ -226,6 +239,25 @@ fn default_batch_size() -> u32 {
     1000
 }
 
+/// Document browser settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrowserSettings {
+    /// Documents kept in memory in stream mode; older ones are dropped as
+    /// more are loaded.
+    #[serde(default = "default_stream_window")]
+    pub stream_window: u32,
+}
+
+impl Default for BrowserSettings {
+    fn default() -> Self {
+        Self { stream_window: default_stream_window() }
+    }
+}
+
+fn default_stream_window() -> u32 {
+    5000
+}
+
 fn default_true() -> bool {
     true
 }
@@ -295,6 +327,7 @@ mod tests {
         assert!(settings.appearance.islands.tab_style_migrated_to_islands);
         assert_eq!(settings.transfer.default_batch_size, 1000);
         assert_eq!(settings.transfer.export_filename_template, DEFAULT_FILENAME_TEMPLATE);
+        assert_eq!(settings.browser.stream_window, 5000);
         assert!(!settings.ai.enabled);
         assert_eq!(settings.ai.model, "gemini-3-flash-preview");
     }
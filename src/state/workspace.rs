@@ -55,6 +55,9 @@ pub struct WorkspaceTab {
     pub sort_raw: String,
     #[serde(default)]
     pub projection_raw: String,
+    /// Hint, collation, maxTimeMS etc. as entered in the query options panel.
+    #[serde(default)]
+    pub query_options_raw: String,
     #[serde(default)]
     pub aggregation_pipeline: Vec<PipelineStage>,
     #[serde(default)]
@@ -209,6 +212,7 @@ mod tests {
             filter_raw: String::new(),
             sort_raw: String::new(),
             projection_raw: String::new(),
+            query_options_raw: String::new(),
             aggregation_pipeline: Vec::new(),
             stats_open: false,
             subview: CollectionSubview::Documents,
@@ -87,6 +87,8 @@ pub mod colors {
     use gpui::{App, Hsla};
     use gpui_component::ActiveTheme as _;
 
+    use crate::models::ConnectionEnvironment;
+
     // BSON Syntax Highlighting — reads from active theme's base colors
     pub fn syntax_key(cx: &App) -> Hsla {
         cx.theme().blue
@@ -159,6 +161,16 @@ pub mod colors {
         c.a = 0.3;
         c
     }
+
+    // Connection environment accent (production = red)
+    pub fn environment(env: ConnectionEnvironment, cx: &App) -> Hsla {
+        match env {
+            ConnectionEnvironment::Development => cx.theme().success,
+            ConnectionEnvironment::Testing => cx.theme().info,
+            ConnectionEnvironment::Staging => cx.theme().warning,
+            ConnectionEnvironment::Production => cx.theme().danger,
+        }
+    }
 }
 
 // =============================================================================
@@ -7,21 +7,24 @@ use crate::bson::{
 };
 use crate::components::open_confirm_dialog;
 use crate::keyboard::{
-    AddElement, AddField, ClearAggregationStage, CloseSearch, CopyAs, CopyAsCsv, CopyAsJson,
-    CopyAsJsonLines, CopyAsMarkdown, CopyAsTsv, CopyDocumentJson, CopyKey, CopyValue, CreateIndex,
-    DeleteAggregationStage, DeleteCollection, DeleteDocument, DiscardDocumentChanges,
+    AddElement, AddField, ClearAggregationStage, CloseSearch, CompareDocuments, CopyAs, CopyAsCsv,
+    CopyAsJson, CopyAsJsonLines, CopyAsMarkdown, CopyAsTsv, CopyDocumentJson, CopyKey, CopyValue,
+    CreateIndex, DeleteAggregationStage, DeleteCollection, DeleteDocument, DiscardDocumentChanges,
     DuplicateAggregationStage, DuplicateDocument, EditDocumentJson, EditValueType, FindInResults,
     FormatAggregationStage, InsertDocument, MoveAggregationStageDown, MoveAggregationStageUp,
-    NextSearchMatch, PasteDocuments, PrevSearchMatch, RemoveMatchingValues, RemoveSelectedField,
-    RenameField, RunAggregation, SaveDocument, SelectNextAggregationStage,
+    NextSearchMatch, PasteDocuments, PrevSearchMatch, RedoDocumentChange, RemoveMatchingValues,
+    RemoveSelectedField, RenameField, RunAggregation, SaveDocument, SelectNextAggregationStage,
     SelectPrevAggregationStage, ShowAggregationSubview, ShowDocumentsSubview, ShowIndexesSubview,
-    ShowStatsSubview, ToggleAggregationStageEnabled,
+    ShowStatsSubview, ToggleAggregationStageEnabled, UndoDocumentChange,
+};
+use crate::state::{
+    AppCommands, CollectionSubview, DocumentViewMode, JournalDirection, StatusMessage,
 };
-use crate::state::{AppCommands, CollectionSubview, DocumentViewMode, StatusMessage};
 
 use super::export::{CopyFormat, ExportScope, ViewExportSnapshot, render_to_clipboard};
 
 use super::CollectionView;
+use super::dialogs::compare::CompareDocumentsDialog;
 use super::dialogs::index_create::IndexCreateDialog;
 use super::dialogs::property_dialog::PropertyActionDialog;
 use super::node_meta::NodeMeta;
@@ -128,6 +131,18 @@ impl CollectionView {
             new_doc.insert("_id", ObjectId::new());
             AppCommands::insert_document(this.state.clone(), session_key, new_doc, cx);
         }))
+        .on_action(cx.listener(|this, _: &CompareDocuments, window, cx| {
+            let Some((session_key, doc_key)) = this.selected_doc_key_for_current_session(cx) else {
+                return;
+            };
+            CompareDocumentsDialog::open_for_selection(
+                this.state.clone(),
+                session_key,
+                doc_key,
+                window,
+                cx,
+            );
+        }))
         .on_action(cx.listener(|this, _: &DeleteDocument, window, cx| {
             let Some(session_key) = this.view_model.current_session() else {
                 return;
@@ -214,6 +229,28 @@ impl CollectionView {
             };
             paste_documents_from_clipboard(this.state.clone(), session_key, cx);
         }))
+        .on_action(cx.listener(|this, _: &UndoDocumentChange, _window, cx| {
+            let Some(session_key) = this.view_model.current_session() else {
+                return;
+            };
+            AppCommands::replay_operation(
+                this.state.clone(),
+                session_key,
+                JournalDirection::Undo,
+                cx,
+            );
+        }))
+        .on_action(cx.listener(|this, _: &RedoDocumentChange, _window, cx| {
+            let Some(session_key) = this.view_model.current_session() else {
+                return;
+            };
+            AppCommands::replay_operation(
+                this.state.clone(),
+                session_key,
+                JournalDirection::Redo,
+                cx,
+            );
+        }))
         .on_action(cx.listener(|this, _: &CopyDocumentJson, _window, cx| {
             let Some(session_key) = this.view_model.current_session() else {
                 return;
@@ -266,18 +303,7 @@ impl CollectionView {
                     .cloned()
                     .collect()
             };
-            for doc_key in dirty_selected {
-                let doc = this.state.read(cx).session_draft(&session_key, &doc_key);
-                if let Some(doc) = doc {
-                    AppCommands::save_document(
-                        this.state.clone(),
-                        session_key.clone(),
-                        doc_key,
-                        doc,
-                        cx,
-                    );
-                }
-            }
+            AppCommands::save_documents(this.state.clone(), session_key, dirty_selected, cx);
         }))
         .on_action(cx.listener(|this, _: &EditValueType, window, cx| {
             let Some((session_key, meta)) = this.selected_property_context(cx) else {
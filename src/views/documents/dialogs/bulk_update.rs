@@ -1,15 +1,17 @@
 //! Bulk update/replace dialog for documents.
 
+use gpui::prelude::FluentBuilder as _;
 use gpui::*;
 use gpui_component::ActiveTheme as _;
 use gpui_component::WindowExt as _;
 use gpui_component::dialog::Dialog;
-use gpui_component::input::{Input, InputState};
+use gpui_component::input::{Input, InputEvent, InputState};
 use gpui_component::menu::{DropdownMenu as _, PopupMenu, PopupMenuItem};
 use mongodb::bson::{Bson, Document, doc};
 
 use crate::bson::{DocumentKey, document_to_shell_string, parse_document_from_json};
-use crate::components::{Button, cancel_button, open_confirm_dialog};
+use crate::components::{Button, cancel_button};
+use crate::connection::WritePreview;
 use crate::state::{AppCommands, AppEvent, AppState, SessionKey};
 use crate::theme::spacing;
 
@@ -17,6 +19,7 @@ use super::bulk_update_support::{
     BulkUpdateMode, BulkUpdateScope, parse_update_doc, validate_update_doc,
 };
 use super::shared::{escape_key_subscription, status_text, styled_dropdown_button};
+use super::write_preview::{preview_task, render_write_preview};
 
 pub struct BulkUpdateDialog {
     state: Entity<AppState>,
@@ -28,6 +31,9 @@ pub struct BulkUpdateDialog {
     update_state: Entity<InputState>,
     error_message: Option<String>,
     updating: bool,
+    /// Dry run of the current inputs; the write is only offered once shown.
+    preview: Option<(Document, Document, WritePreview)>,
+    previewing: bool,
     _subscriptions: Vec<Subscription>,
 }
 
@@ -92,6 +98,8 @@ impl BulkUpdateDialog {
             update_state,
             error_message: None,
             updating: false,
+            preview: None,
+            previewing: false,
             _subscriptions: Vec::new(),
         };
 
@@ -124,12 +132,23 @@ impl BulkUpdateDialog {
 
         dialog._subscriptions.push(escape_key_subscription(cx));
 
+        for input in [dialog.filter_state.clone(), dialog.update_state.clone()] {
+            let subscription =
+                cx.subscribe_in(&input, window, |view, _input, event, _window, cx| {
+                    if matches!(event, InputEvent::Change) && view.preview.take().is_some() {
+                        cx.notify();
+                    }
+                });
+            dialog._subscriptions.push(subscription);
+        }
+
         dialog
     }
 
     fn set_scope(&mut self, scope: BulkUpdateScope, cx: &mut Context<Self>) {
         self.scope = scope;
         self.error_message = None;
+        self.preview = None;
         cx.notify();
     }
 
@@ -141,6 +160,7 @@ impl BulkUpdateDialog {
 
         self.mode = mode;
         self.error_message = None;
+        self.preview = None;
 
         if current_trimmed.is_empty() || current_trimmed == previous_template.trim() {
             self.update_state.update(cx, |state, cx| {
@@ -197,8 +217,10 @@ impl BulkUpdateDialog {
         self.state.read(cx).active_connection().map(|conn| conn.config.read_only).unwrap_or(false)
     }
 
-    fn submit(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        if self.updating {
+    /// Validate the inputs and run a dry run; once its preview is shown, the
+    /// next submit applies the write.
+    fn submit(&mut self, cx: &mut Context<Self>) {
+        if self.updating || self.previewing {
             return;
         }
         if self.is_read_only(cx) {
@@ -234,37 +256,51 @@ impl BulkUpdateDialog {
             }
         };
 
-        let mode = self.mode;
-        let scope = self.scope;
-        let title = format!("{} documents", mode.label());
-        let target = match scope {
-            BulkUpdateScope::SelectedDocument => "the selected document".to_string(),
-            BulkUpdateScope::FilteredQuery => "documents matching the current filter".to_string(),
-            BulkUpdateScope::AllDocuments => "all documents in this collection".to_string(),
-            BulkUpdateScope::CustomFilter => "documents matching the custom filter".to_string(),
-        };
-        let message = format!("{} {}? This cannot be undone.", mode.label(), target);
-        let confirm_label = mode.label();
+        if let Some((previewed_filter, previewed_update, _)) = &self.preview
+            && *previewed_filter == filter
+            && *previewed_update == update_doc
+        {
+            self.updating = true;
+            AppCommands::update_documents_by_filter(
+                self.state.clone(),
+                self.session_key.clone(),
+                filter,
+                update_doc,
+                cx,
+            );
+            cx.notify();
+            return;
+        }
 
-        let state = self.state.clone();
-        let session_key = self.session_key.clone();
-        let view = cx.entity();
-        open_confirm_dialog(window, cx, title, message, confirm_label, true, move |_window, cx| {
-            let filter = filter.clone();
-            let update_doc = update_doc.clone();
-            view.update(cx, |this, cx| {
-                this.updating = true;
-                this.error_message = None;
-                AppCommands::update_documents_by_filter(
-                    state.clone(),
-                    session_key.clone(),
-                    filter,
-                    update_doc,
-                    cx,
-                );
+        let task = match preview_task(
+            &self.state,
+            &self.session_key,
+            filter.clone(),
+            Some(update_doc.clone()),
+            cx,
+        ) {
+            Ok(task) => task,
+            Err(err) => {
+                self.error_message = Some(err);
+                cx.notify();
+                return;
+            }
+        };
+        self.preview = None;
+        self.previewing = true;
+        cx.notify();
+        cx.spawn(async move |view: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let result = task.await;
+            let _ = view.update(cx, |this, cx| {
+                this.previewing = false;
+                match result {
+                    Ok(preview) => this.preview = Some((filter, update_doc, preview)),
+                    Err(err) => this.error_message = Some(format!("Preview failed: {err}")),
+                }
                 cx.notify();
             });
-        });
+        })
+        .detach();
     }
 
     fn scope_button(
@@ -362,8 +398,23 @@ impl Render for BulkUpdateDialog {
         let has_selected = self.selected_doc.is_some();
         let has_filter = !self.current_filter(cx).is_empty();
 
-        let status =
-            status_text(self.error_message.as_ref(), self.updating, "Applying update...", "", cx);
+        let busy_label = if self.previewing { "Previewing..." } else { "Applying update..." };
+        let status = status_text(
+            self.error_message.as_ref(),
+            self.updating || self.previewing,
+            busy_label,
+            "",
+            cx,
+        );
+        let matched = self.preview.as_ref().map(|(_, _, preview)| preview.matched);
+        let apply_label = match matched {
+            Some(count) => format!("{} {count} document(s)", self.mode.label()),
+            None => "Preview".to_string(),
+        };
+        let preview_verb = match self.mode {
+            BulkUpdateMode::Update => "updated",
+            BulkUpdateMode::Replace => "replaced",
+        };
 
         let scope_row = div()
             .flex()
@@ -445,6 +496,9 @@ impl Render for BulkUpdateDialog {
                             .disabled(self.updating),
                     ),
             )
+            .when_some(self.preview.as_ref(), |this, (_, _, preview)| {
+                this.child(render_write_preview(preview, preview_verb, cx))
+            })
             .child(
                 div()
                     .flex()
@@ -461,13 +515,15 @@ impl Render for BulkUpdateDialog {
                             .child(
                                 Button::new("apply-bulk-update")
                                     .primary()
-                                    .label(self.mode.label())
-                                    .disabled(self.updating)
+                                    .label(apply_label)
+                                    .disabled(
+                                        self.updating || self.previewing || matched == Some(0),
+                                    )
                                     .on_click({
                                         let view = view.clone();
-                                        move |_: &ClickEvent, window: &mut Window, cx: &mut App| {
+                                        move |_: &ClickEvent, _window: &mut Window, cx: &mut App| {
                                             view.update(cx, |this, cx| {
-                                                this.submit(window, cx);
+                                                this.submit(cx);
                                             });
                                         }
                                     }),
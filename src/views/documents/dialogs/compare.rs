@@ -0,0 +1,196 @@
+//! Side-by-side structural diff of two documents, or a document and its
+//! unsaved draft.
+
+use gpui::prelude::FluentBuilder as _;
+use gpui::*;
+use gpui_component::ActiveTheme as _;
+use gpui_component::WindowExt as _;
+use gpui_component::dialog::Dialog;
+use gpui_component::scroll::ScrollableElement as _;
+use mongodb::bson::{Bson, Document};
+
+use crate::bson::{
+    DiffKind, DocumentKey, FieldDiff, bson_type_label, bson_value_preview, diff_documents,
+};
+use crate::state::{AppState, SessionKey, StatusMessage};
+use crate::theme::{fonts, spacing};
+
+use super::shared::escape_key_subscription;
+
+const COMPARE_VALUE_LEN: usize = 64;
+
+pub struct CompareDocumentsDialog {
+    left_title: SharedString,
+    right_title: SharedString,
+    diffs: Vec<FieldDiff>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl CompareDocumentsDialog {
+    pub fn open(
+        left: (impl Into<SharedString>, &Document),
+        right: (impl Into<SharedString>, &Document),
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let diffs = diff_documents(left.1, right.1);
+        let left_title = left.0.into();
+        let right_title = right.0.into();
+        let dialog_view = cx.new(|cx| Self {
+            left_title,
+            right_title,
+            diffs,
+            _subscriptions: vec![escape_key_subscription(cx)],
+        });
+        window.open_dialog(cx, move |dialog: Dialog, _window: &mut Window, _cx: &mut App| {
+            dialog.title("Compare Documents").w(px(760.0)).child(dialog_view.clone())
+        });
+    }
+
+    /// Compare the two selected documents in result order, or `doc_key` with
+    /// its draft when a single document has unsaved changes.
+    pub fn open_for_selection(
+        state: Entity<AppState>,
+        session_key: SessionKey,
+        doc_key: DocumentKey,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let pair = {
+            let state_ref = state.read(cx);
+            let mut selected: Vec<DocumentKey> = state_ref
+                .session_view(&session_key)
+                .map(|view| view.selected_docs.iter().cloned().collect())
+                .unwrap_or_default();
+            if selected.len() == 2 {
+                selected.sort_by_key(|key| state_ref.document_index(&session_key, key));
+                let mut documents = selected
+                    .iter()
+                    .filter_map(|key| state_ref.document_for_key(&session_key, key))
+                    .map(|document| (document_title(&document), document));
+                documents.next().zip(documents.next())
+            } else if selected.len() <= 1 {
+                let doc_key = selected.pop().unwrap_or(doc_key);
+                let loaded = state_ref.document_for_key(&session_key, &doc_key);
+                let draft = state_ref.session_draft(&session_key, &doc_key);
+                loaded.zip(draft).map(|(loaded, draft)| {
+                    let title = document_title(&loaded);
+                    ((format!("{title} (saved)"), loaded), (format!("{title} (draft)"), draft))
+                })
+            } else {
+                None
+            }
+        };
+        if let Some(((left_title, left), (right_title, right))) = pair {
+            Self::open((left_title, &left), (right_title, &right), window, cx);
+            return;
+        }
+        state.update(cx, |state, cx| {
+            state.set_status_message(Some(StatusMessage::info(
+                "Select two documents, or one with unsaved changes, to compare.",
+            )));
+            cx.notify();
+        });
+    }
+}
+
+fn document_title(document: &Document) -> String {
+    match document.get("_id") {
+        Some(id) => format!("_id: {}", bson_value_preview(id, COMPARE_VALUE_LEN)),
+        None => "(no _id)".to_string(),
+    }
+}
+
+impl Render for CompareDocumentsDialog {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let count = |kind: DiffKind| self.diffs.iter().filter(|diff| diff.kind == kind).count();
+        let summary = if self.diffs.is_empty() {
+            "The documents are identical.".to_string()
+        } else {
+            format!(
+                "{} added, {} removed, {} changed, {} type change(s)",
+                count(DiffKind::Added),
+                count(DiffKind::Removed),
+                count(DiffKind::Changed),
+                count(DiffKind::TypeChanged)
+            )
+        };
+
+        let header = div()
+            .flex()
+            .gap(spacing::sm())
+            .pb(px(4.0))
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .text_xs()
+            .font_weight(FontWeight::MEDIUM)
+            .text_color(cx.theme().muted_foreground)
+            .child(div().w(px(180.0)).child("Field"))
+            .child(div().flex_1().min_w(px(0.0)).truncate().child(self.left_title.clone()))
+            .child(div().flex_1().min_w(px(0.0)).truncate().child(self.right_title.clone()));
+
+        let rows = self.diffs.iter().enumerate().map(|(index, diff)| {
+            let (kind_label, color) = match diff.kind {
+                DiffKind::Added => ("added", cx.theme().primary),
+                DiffKind::Removed => ("removed", cx.theme().danger),
+                DiffKind::Changed => ("changed", cx.theme().foreground),
+                DiffKind::TypeChanged => ("type", cx.theme().warning),
+            };
+            let left_color =
+                if diff.kind == DiffKind::Removed { color } else { cx.theme().foreground };
+            let show_type = diff.kind == DiffKind::TypeChanged;
+            div()
+                .id(("compare-row", index))
+                .flex()
+                .gap(spacing::sm())
+                .py(px(2.0))
+                .text_xs()
+                .child(
+                    div()
+                        .w(px(180.0))
+                        .flex()
+                        .gap(spacing::xs())
+                        .child(div().flex_1().min_w(px(0.0)).truncate().child(diff.path_label()))
+                        .child(div().text_color(color).child(kind_label)),
+                )
+                .child(compare_cell(diff.left.as_ref(), show_type, left_color, cx))
+                .child(compare_cell(diff.right.as_ref(), show_type, color, cx))
+        });
+
+        div()
+            .flex()
+            .flex_col()
+            .gap(spacing::xs())
+            .p(spacing::md())
+            .child(div().text_sm().child(summary))
+            .when(!self.diffs.is_empty(), |this| {
+                this.child(header).child(
+                    div()
+                        .id("compare-rows")
+                        .flex()
+                        .flex_col()
+                        .max_h(px(420.0))
+                        .overflow_y_scrollbar()
+                        .children(rows),
+                )
+            })
+    }
+}
+
+/// Type changes prefix the value with its BSON type, e.g. `Int64 1`.
+fn compare_cell(value: Option<&Bson>, show_type: bool, color: Hsla, cx: &App) -> Div {
+    let label = match value {
+        None => "(missing)".to_string(),
+        Some(value) if show_type => {
+            format!("{} {}", bson_type_label(value), bson_value_preview(value, COMPARE_VALUE_LEN))
+        }
+        Some(value) => bson_value_preview(value, COMPARE_VALUE_LEN),
+    };
+    div()
+        .flex_1()
+        .min_w(px(0.0))
+        .truncate()
+        .font_family(fonts::mono())
+        .text_color(if value.is_some() { color } else { cx.theme().muted_foreground })
+        .child(label)
+}
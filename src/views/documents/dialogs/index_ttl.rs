@@ -0,0 +1,91 @@
+//! Dialog for changing `expireAfterSeconds` on a TTL index.
+
+use gpui::*;
+use gpui_component::ActiveTheme as _;
+use gpui_component::WindowExt as _;
+use gpui_component::dialog::Dialog;
+use gpui_component::input::InputState;
+
+use crate::components::{FormField, cancel_button, primary_button};
+use crate::connection::IndexChange;
+use crate::state::{AppCommands, AppState, SessionKey};
+use crate::theme::spacing;
+
+struct IndexTtlDialogState {
+    index_name: String,
+    seconds_state: Entity<InputState>,
+    error: Option<String>,
+}
+
+impl Render for IndexTtlDialogState {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .flex()
+            .flex_col()
+            .gap(spacing::md())
+            .p(spacing::md())
+            .child(FormField::new("Expire after (seconds)", &self.seconds_state).render(cx))
+            .child(div().text_xs().text_color(cx.theme().muted_foreground).child(format!(
+                "Documents indexed by {} are removed once their date is older than this.",
+                self.index_name
+            )))
+            .children(
+                self.error.clone().map(|error| {
+                    div().text_xs().text_color(cx.theme().danger_foreground).child(error)
+                }),
+            )
+    }
+}
+
+/// Ask for a new `expireAfterSeconds` and apply it to `index_name` with `collMod`.
+pub fn open_index_ttl_dialog(
+    state: Entity<AppState>,
+    session_key: SessionKey,
+    index_name: String,
+    current_seconds: u64,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let dialog_state = cx.new(|cx| IndexTtlDialogState {
+        index_name: index_name.clone(),
+        seconds_state: cx
+            .new(|cx| InputState::new(window, cx).default_value(current_seconds.to_string())),
+        error: None,
+    });
+
+    window.open_dialog(cx, move |dialog: Dialog, _window: &mut Window, _cx: &mut App| {
+        dialog.title("Change TTL").w(px(420.0)).child(dialog_state.clone()).footer({
+            let state = state.clone();
+            let session_key = session_key.clone();
+            let index_name = index_name.clone();
+            let dialog_state = dialog_state.clone();
+            move |_ok, _cancel, _window, _cx| {
+                let state = state.clone();
+                let session_key = session_key.clone();
+                let index_name = index_name.clone();
+                let dialog_state = dialog_state.clone();
+                vec![
+                    cancel_button("cancel-index-ttl"),
+                    primary_button("apply-index-ttl", "Apply", move |window, cx| {
+                        let raw = dialog_state.read(cx).seconds_state.read(cx).value().to_string();
+                        let Ok(seconds) = raw.trim().parse::<u64>() else {
+                            dialog_state.update(cx, |dialog, cx| {
+                                dialog.error = Some("Enter a whole number of seconds.".into());
+                                cx.notify();
+                            });
+                            return;
+                        };
+                        AppCommands::modify_collection_index(
+                            state.clone(),
+                            session_key.clone(),
+                            index_name.clone(),
+                            IndexChange::ExpireAfter(seconds),
+                            cx,
+                        );
+                        window.close_dialog(cx);
+                    }),
+                ]
+            }
+        })
+    });
+}
@@ -1,7 +1,10 @@
 pub mod bulk_update;
 pub mod bulk_update_support;
+pub mod compare;
 pub mod index_create;
+pub mod index_ttl;
 pub mod json_dialogs;
 pub mod property_dialog;
 pub mod property_dialog_support;
 mod shared;
+pub mod write_preview;
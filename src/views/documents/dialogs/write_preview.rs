@@ -0,0 +1,343 @@
+//! Dry-run impact previews shown before bulk updates and deletes.
+
+use gpui::prelude::FluentBuilder as _;
+use gpui::*;
+use gpui_component::ActiveTheme as _;
+use gpui_component::WindowExt as _;
+use gpui_component::dialog::Dialog;
+use gpui_component::scroll::ScrollableElement as _;
+use mongodb::bson::{Bson, Document};
+
+use crate::bson::{bson_value_preview, truncate_for_preview};
+use crate::components::{Button, cancel_button};
+use crate::connection::{PREVIEW_SAMPLE_SIZE, WritePreview};
+use crate::state::{AppCommands, AppEvent, AppState, SessionKey};
+use crate::theme::{fonts, spacing};
+
+use super::shared::{escape_key_subscription, status_text};
+
+const PREVIEW_VALUE_LEN: usize = 48;
+const PREVIEW_DOCUMENT_LEN: usize = 120;
+
+/// Read the matched count and a sample of affected documents in the
+/// background. `update` is `None` for deletes.
+pub(super) fn preview_task(
+    state: &Entity<AppState>,
+    session_key: &SessionKey,
+    filter: Document,
+    update: Option<Document>,
+    cx: &mut App,
+) -> Result<Task<crate::error::Result<WritePreview>>, String> {
+    let (client, manager) = {
+        let state_ref = state.read(cx);
+        (
+            state_ref.active_connection_client(session_key.connection_id),
+            state_ref.connection_manager(),
+        )
+    };
+    let Some(client) = client else {
+        return Err("Connection is no longer active.".to_string());
+    };
+    let database = session_key.database.clone();
+    let collection = session_key.collection.clone();
+    Ok(cx.background_spawn(async move {
+        manager.preview_write(
+            &client,
+            &database,
+            &collection,
+            filter,
+            update.as_ref(),
+            PREVIEW_SAMPLE_SIZE,
+        )
+    }))
+}
+
+/// Matched count plus the sampled documents; for updates each sample lists
+/// its changed fields as before → after.
+pub(super) fn render_write_preview(preview: &WritePreview, verb: &str, cx: &App) -> AnyElement {
+    let summary = if preview.matched == 0 {
+        format!("No documents match; nothing would be {verb}.")
+    } else if preview.samples.len() as u64 == preview.matched {
+        format!("{} document(s) would be {verb}.", preview.matched)
+    } else {
+        format!(
+            "{} document(s) would be {verb}. Showing the first {}.",
+            preview.matched,
+            preview.samples.len()
+        )
+    };
+
+    let samples = preview.samples.iter().enumerate().map(|(index, sample)| {
+        let id = sample
+            .before
+            .get("_id")
+            .map(|id| bson_value_preview(id, PREVIEW_VALUE_LEN))
+            .unwrap_or_else(|| "(no _id)".to_string());
+        let header = div()
+            .text_xs()
+            .font_weight(FontWeight::MEDIUM)
+            .text_color(cx.theme().secondary_foreground)
+            .child(format!("_id: {id}"));
+
+        let body = if sample.after.is_none() {
+            div()
+                .text_xs()
+                .font_family(fonts::mono())
+                .text_color(cx.theme().muted_foreground)
+                .truncate()
+                .child(truncate_for_preview(
+                    &Bson::Document(sample.before.clone()).into_relaxed_extjson().to_string(),
+                    PREVIEW_DOCUMENT_LEN,
+                ))
+        } else {
+            let changes = sample.changed_fields();
+            let unchanged = changes.is_empty();
+            div()
+                .flex()
+                .flex_col()
+                .gap(px(2.0))
+                .when(unchanged, |this| {
+                    this.child(
+                        div().text_xs().text_color(cx.theme().muted_foreground).child("No changes"),
+                    )
+                })
+                .children(changes.into_iter().map(|change| {
+                    div()
+                        .flex()
+                        .gap(spacing::sm())
+                        .text_xs()
+                        .font_family(fonts::mono())
+                        .child(div().w(px(160.0)).truncate().child(change.path_label()))
+                        .child(
+                            div()
+                                .flex_1()
+                                .min_w(px(0.0))
+                                .truncate()
+                                .text_color(cx.theme().danger)
+                                .child(preview_value(change.left.as_ref())),
+                        )
+                        .child(div().text_color(cx.theme().muted_foreground).child("→"))
+                        .child(
+                            div()
+                                .flex_1()
+                                .min_w(px(0.0))
+                                .truncate()
+                                .text_color(cx.theme().primary)
+                                .child(preview_value(change.right.as_ref())),
+                        )
+                }))
+        };
+
+        div()
+            .id(("write-preview-sample", index))
+            .flex()
+            .flex_col()
+            .gap(px(2.0))
+            .py(px(4.0))
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .child(header)
+            .child(body)
+    });
+
+    div()
+        .flex()
+        .flex_col()
+        .gap(spacing::xs())
+        .child(div().text_sm().child(summary))
+        .when_some(preview.unsupported.clone(), |this, reason| {
+            this.child(
+                div()
+                    .text_xs()
+                    .text_color(cx.theme().warning)
+                    .child(format!("{reason}; field changes are not shown.")),
+            )
+        })
+        .child(
+            div()
+                .id("write-preview-samples")
+                .flex()
+                .flex_col()
+                .max_h(px(220.0))
+                .overflow_y_scrollbar()
+                .children(samples),
+        )
+        .into_any_element()
+}
+
+fn preview_value(value: Option<&Bson>) -> String {
+    match value {
+        None => "(missing)".to_string(),
+        Some(value @ (Bson::Document(_) | Bson::Array(_))) => truncate_for_preview(
+            &value.clone().into_relaxed_extjson().to_string(),
+            PREVIEW_VALUE_LEN,
+        ),
+        Some(value) => bson_value_preview(value, PREVIEW_VALUE_LEN),
+    }
+}
+
+/// Delete-by-filter with a preview of what would be removed; the delete only
+/// runs once confirmed.
+pub struct DeletePreviewDialog {
+    state: Entity<AppState>,
+    session_key: SessionKey,
+    filter: Document,
+    preview: Option<WritePreview>,
+    error_message: Option<String>,
+    loading: bool,
+    deleting: bool,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl DeletePreviewDialog {
+    pub fn open(
+        state: Entity<AppState>,
+        session_key: SessionKey,
+        filter: Document,
+        title: impl Into<SharedString>,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let title: SharedString = title.into();
+        let dialog_view = cx.new(|cx| Self::new(state, session_key, filter, window, cx));
+        window.open_dialog(cx, move |dialog: Dialog, _window: &mut Window, _cx: &mut App| {
+            dialog.title(title.clone()).w(px(640.0)).child(dialog_view.clone())
+        });
+    }
+
+    fn new(
+        state: Entity<AppState>,
+        session_key: SessionKey,
+        filter: Document,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let mut dialog = Self {
+            state,
+            session_key,
+            filter,
+            preview: None,
+            error_message: None,
+            loading: false,
+            deleting: false,
+            _subscriptions: Vec::new(),
+        };
+
+        let subscription =
+            cx.subscribe_in(&dialog.state, window, move |view, _state, event, window, cx| {
+                match event {
+                    AppEvent::DocumentsDeleted { session, .. }
+                        if session == &view.session_key && view.deleting =>
+                    {
+                        view.deleting = false;
+                        window.close_dialog(cx);
+                    }
+                    AppEvent::DocumentsDeleteFailed { session, error }
+                        if session == &view.session_key =>
+                    {
+                        view.deleting = false;
+                        view.error_message = Some(error.clone());
+                        cx.notify();
+                    }
+                    _ => {}
+                }
+            });
+        dialog._subscriptions.push(subscription);
+        dialog._subscriptions.push(escape_key_subscription(cx));
+
+        dialog.load_preview(cx);
+        dialog
+    }
+
+    fn load_preview(&mut self, cx: &mut Context<Self>) {
+        let task = match preview_task(&self.state, &self.session_key, self.filter.clone(), None, cx)
+        {
+            Ok(task) => task,
+            Err(err) => {
+                self.error_message = Some(err);
+                return;
+            }
+        };
+        self.loading = true;
+        cx.spawn(async move |view: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let result = task.await;
+            let _ = view.update(cx, |this, cx| {
+                this.loading = false;
+                match result {
+                    Ok(preview) => this.preview = Some(preview),
+                    Err(err) => this.error_message = Some(format!("Preview failed: {err}")),
+                }
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    fn confirm(&mut self, cx: &mut Context<Self>) {
+        if self.deleting || self.preview.is_none() {
+            return;
+        }
+        self.deleting = true;
+        self.error_message = None;
+        AppCommands::delete_documents_by_filter(
+            self.state.clone(),
+            self.session_key.clone(),
+            self.filter.clone(),
+            cx,
+        );
+        cx.notify();
+    }
+}
+
+impl Render for DeletePreviewDialog {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let view = cx.entity();
+        let status = status_text(
+            self.error_message.as_ref(),
+            self.loading || self.deleting,
+            if self.deleting { "Deleting..." } else { "Counting affected documents..." },
+            "",
+            cx,
+        );
+        let matched = self.preview.as_ref().map(|preview| preview.matched);
+        let confirm_label = match matched {
+            Some(count) => format!("Delete {count} document(s)"),
+            None => "Delete".to_string(),
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap(spacing::sm())
+            .p(spacing::md())
+            .when_some(self.preview.as_ref(), |this, preview| {
+                this.child(render_write_preview(preview, "deleted", cx))
+            })
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .pt(spacing::xs())
+                    .child(div().min_h(px(18.0)).text_sm().text_color(status.1).child(status.0))
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap(spacing::sm())
+                            .child(cancel_button("cancel-delete-preview"))
+                            .child(
+                                Button::new("confirm-delete-preview")
+                                    .danger()
+                                    .label(confirm_label)
+                                    .disabled(self.deleting || !matches!(matched, Some(1..)))
+                                    .on_click(
+                                        move |_: &ClickEvent, _window: &mut Window, cx: &mut App| {
+                                            view.update(cx, |this, cx| this.confirm(cx));
+                                        },
+                                    ),
+                            ),
+                    ),
+            )
+    }
+}
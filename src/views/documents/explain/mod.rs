@@ -2,14 +2,16 @@ use gpui::*;
 use gpui_component::ActiveTheme as _;
 use gpui_component::resizable::{h_resizable, resizable_panel};
 use gpui_component::scroll::ScrollableElement;
+use mongodb::bson::Bson;
 
 use crate::components::Button;
 use crate::helpers::format_number;
+use crate::state::commands::existing_index_for;
 use crate::state::{
     AppCommands, CollectionSubview, ExplainCostBand, ExplainNode, ExplainOpenMode, ExplainPanelTab,
-    ExplainSeverity, ExplainState, ExplainViewMode, SessionKey,
+    ExplainScope, ExplainSeverity, ExplainState, ExplainViewMode, IndexAdvice, SessionKey,
 };
-use crate::theme::spacing;
+use crate::theme::{fonts, spacing};
 use crate::views::CollectionView;
 
 impl CollectionView {
@@ -570,6 +572,7 @@ impl CollectionView {
                 .gap(spacing::sm())
                 .child(render_explain_summary(explain, cx))
                 .child(render_explain_bottlenecks(explain, cx))
+                .children(self.render_explain_index_advice(explain, session_key.clone(), cx))
                 .child(render_explain_inspector(explain, selected_visual, cx))
                 .into_any_element(),
             ExplainPanelTab::RejectedPlans => render_explain_rejected_plans(explain, cx),
@@ -605,6 +608,111 @@ impl CollectionView {
             )
             .into_any_element()
     }
+
+    /// Suggested key pattern for a scanning or sorting find, with a create or
+    /// hinted-explain action depending on whether an index already serves it.
+    fn render_explain_index_advice(
+        &self,
+        explain: &ExplainState,
+        session_key: Option<SessionKey>,
+        cx: &App,
+    ) -> Option<AnyElement> {
+        let advice = explain.advice.as_ref().filter(|_| explain.scope == ExplainScope::Find)?;
+        let existing = session_key.as_ref().and_then(|key| {
+            let indexes = self.state.read(cx).session_data(key)?.indexes.as_ref()?;
+            existing_index_for(&advice.suggestion.keys, indexes)
+        });
+        let keys = Bson::Document(advice.suggestion.keys.clone()).into_relaxed_extjson();
+
+        let action = match existing.clone() {
+            Some(index_name) => Button::new("explain-advice-hint")
+                .compact()
+                .primary()
+                .label("Explain with hint")
+                .disabled(session_key.is_none() || explain.loading)
+                .on_click({
+                    let state = self.state.clone();
+                    let session_key = session_key.clone();
+                    move |_, _, cx| {
+                        let Some(session_key) = session_key.clone() else {
+                            return;
+                        };
+                        AppCommands::explain_with_index_hint(
+                            state.clone(),
+                            session_key,
+                            index_name.clone(),
+                            cx,
+                        );
+                    }
+                }),
+            None => Button::new("explain-advice-create")
+                .compact()
+                .primary()
+                .label("Create index")
+                .disabled(session_key.is_none())
+                .on_click({
+                    let state = self.state.clone();
+                    let session_key = session_key.clone();
+                    let index = advice.suggestion.index_document();
+                    move |_, _, cx| {
+                        let Some(session_key) = session_key.clone() else {
+                            return;
+                        };
+                        AppCommands::create_collection_index(
+                            state.clone(),
+                            session_key,
+                            index.clone(),
+                            cx,
+                        );
+                    }
+                }),
+        };
+
+        let status = match &existing {
+            Some(name) => format!("Served by {name}. Explain with it hinted and compare in Diff."),
+            None => "No existing index serves this pattern.".to_string(),
+        };
+        let body = div()
+            .flex()
+            .flex_col()
+            .gap(spacing::xs())
+            .child(div().text_xs().font_family(fonts::mono()).child(keys.to_string()))
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(advice.suggestion.rationale()),
+            )
+            .child(render_advice_selectivity(advice, cx))
+            .child(div().text_xs().text_color(cx.theme().muted_foreground).child(status))
+            .child(div().flex().pt(spacing::xs()).child(action));
+
+        Some(explain_section_card(
+            "Index Advice",
+            Some("Equality fields first, then sort keys, then ranges."),
+            None,
+            body.into_any_element(),
+            cx,
+        ))
+    }
+}
+
+fn render_advice_selectivity(advice: &IndexAdvice, cx: &App) -> AnyElement {
+    let muted = cx.theme().muted_foreground;
+    if advice.selectivity_loading {
+        return explain_metric_line("Selectivity", "Sampling...", cx).into_any_element();
+    }
+    let Some(selectivity) = advice.selectivity else {
+        return div().into_any_element();
+    };
+    let Some(fraction) = selectivity.fraction() else {
+        return div().text_xs().text_color(muted).child("Collection is empty.").into_any_element();
+    };
+    let value =
+        format!("~{:.1}% of {} sampled", fraction * 100.0, format_number(selectivity.sampled));
+    // A filter matching most documents gains little from an index over a scan.
+    let accent = if fraction > 0.5 { cx.theme().warning } else { cx.theme().primary };
+    explain_metric_line_accent("Selectivity", &value, accent, cx).into_any_element()
 }
 
 fn render_explain_summary(explain: &ExplainState, cx: &App) -> AnyElement {
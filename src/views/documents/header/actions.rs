@@ -14,14 +14,17 @@ use gpui_component::{Disableable as _, Icon, IconName, Sizable as _, Size};
 use mongodb::bson::Document;
 
 use crate::bson::DocumentKey;
+use crate::components::saved_query_dialog::open_save_query_dialog;
 use crate::components::{Button, open_confirm_dialog};
 use crate::keyboard::RunAggregation;
 use crate::state::{
-    AppCommands, AppState, DocumentViewMode, SessionKey, TransferMode, TransferScope,
+    AppCommands, AppState, DocumentViewMode, JournalDirection, SavedQueryBody, SessionKey,
+    TransferMode, TransferScope,
 };
 use crate::theme::{borders, spacing};
 use crate::views::documents::CollectionView;
 use crate::views::documents::dialogs::bulk_update::BulkUpdateDialog;
+use crate::views::documents::dialogs::write_preview::DeletePreviewDialog;
 use crate::views::documents::export::CopyFormat;
 
 /// Render action buttons for the Documents subview.
@@ -205,27 +208,14 @@ fn render_delete_menu(
                             if filter.is_empty() {
                                 return;
                             }
-                            open_confirm_dialog(
-                        window,
-                        cx,
-                        "Delete filtered documents",
-                        "Delete all documents matching the current filter? This cannot be undone."
-                            .to_string(),
-                        "Delete",
-                        true,
-                        {
-                            let state_for_delete = state_for_delete.clone();
-                            let session_key = session_key.clone();
-                            move |_window, cx| {
-                                AppCommands::delete_documents_by_filter(
-                                    state_for_delete.clone(),
-                                    session_key.clone(),
-                                    filter.clone(),
-                                    cx,
-                                );
-                            }
-                        },
-                    );
+                            DeletePreviewDialog::open(
+                                state_for_delete.clone(),
+                                session_key,
+                                filter,
+                                "Delete filtered documents",
+                                window,
+                                cx,
+                            );
                         }
                     }),
             )
@@ -237,26 +227,13 @@ fn render_delete_menu(
                         let Some(session_key) = session_key.clone() else {
                             return;
                         };
-                        open_confirm_dialog(
+                        DeletePreviewDialog::open(
+                            state_for_delete.clone(),
+                            session_key,
+                            Document::new(),
+                            "Delete all documents",
                             window,
                             cx,
-                            "Delete all documents",
-                            "Delete all documents in this collection? This cannot be undone."
-                                .to_string(),
-                            "Delete",
-                            true,
-                            {
-                                let state_for_delete = state_for_delete.clone();
-                                let session_key = session_key.clone();
-                                move |_window, cx| {
-                                    AppCommands::delete_documents_by_filter(
-                                        state_for_delete.clone(),
-                                        session_key.clone(),
-                                        Document::new(),
-                                        cx,
-                                    );
-                                }
-                            },
                         );
                     }
                 }),
@@ -491,18 +468,7 @@ fn render_documents_actions_clean(
                         .cloned()
                         .collect()
                 };
-                for doc_key in dirty_docs {
-                    let doc = state_for_apply.read(cx).session_draft(&session_key, &doc_key);
-                    if let Some(doc) = doc {
-                        AppCommands::save_document(
-                            state_for_apply.clone(),
-                            session_key.clone(),
-                            doc_key,
-                            doc,
-                            cx,
-                        );
-                    }
-                }
+                AppCommands::save_documents(state_for_apply.clone(), session_key, dirty_docs, cx);
             }
         }),
         IconName::Check,
@@ -512,6 +478,21 @@ fn render_documents_actions_clean(
         apply_button = apply_button.active_style(cx.theme().secondary.opacity(0.55));
     }
 
+    let (can_undo, can_redo) = session_key
+        .as_ref()
+        .map(|sk| {
+            let state_ref = state.read(cx);
+            (
+                state_ref.journal_label(sk, JournalDirection::Undo).is_some(),
+                state_ref.journal_label(sk, JournalDirection::Redo).is_some(),
+            )
+        })
+        .unwrap_or_default();
+    let undo_button =
+        render_journal_button(state.clone(), session_key.clone(), JournalDirection::Undo, can_undo);
+    let redo_button =
+        render_journal_button(state.clone(), session_key.clone(), JournalDirection::Redo, can_redo);
+
     let delete_menu = render_delete_menu(
         state_for_delete.clone(),
         session_key.clone(),
@@ -836,6 +817,8 @@ fn render_documents_actions_clean(
         .child(discard_button)
         .child(apply_button)
         .child(delete_menu)
+        .child(undo_button)
+        .child(redo_button)
         .child(toolbar_separator(cx))
         .child(refresh_button)
         .child(toolbar_separator(cx))
@@ -846,6 +829,30 @@ fn render_documents_actions_clean(
         .child(secondary_actions_menu)
 }
 
+fn render_journal_button(
+    state: Entity<AppState>,
+    session_key: Option<SessionKey>,
+    direction: JournalDirection,
+    enabled: bool,
+) -> Button {
+    let (id, icon, tooltip) = match direction {
+        JournalDirection::Undo => ("undo-document-change", IconName::Undo2, "Undo last write"),
+        JournalDirection::Redo => ("redo-document-change", IconName::Redo2, "Redo write"),
+    };
+    clean_toolbar_icon_button(
+        Button::new(id).compact().disabled(!enabled).on_click(
+            move |_: &ClickEvent, _window: &mut Window, cx: &mut App| {
+                let Some(session_key) = session_key.clone() else {
+                    return;
+                };
+                AppCommands::replay_operation(state.clone(), session_key, direction, cx);
+            },
+        ),
+        icon,
+        tooltip,
+    )
+}
+
 fn render_documents_secondary_menu(
     state_for_dialog: Entity<AppState>,
     state_for_transfer: Entity<AppState>,
@@ -1231,6 +1238,113 @@ pub fn render_schema_actions(
         )
 }
 
+/// Render action buttons for the Validation subview.
+pub fn render_validation_actions(
+    state: Entity<AppState>,
+    session_key: Option<SessionKey>,
+    busy: bool,
+) -> Div {
+    let action = |id: &'static str, command: fn(Entity<AppState>, SessionKey, &mut App)| {
+        let state = state.clone();
+        let session_key = session_key.clone();
+        Button::new(id).compact().disabled(session_key.is_none() || busy).on_click(
+            move |_: &ClickEvent, _window: &mut Window, cx: &mut App| {
+                let Some(session_key) = session_key.clone() else {
+                    return;
+                };
+                command(state.clone(), session_key, cx);
+            },
+        )
+    };
+    let state_for_reset = state.clone();
+
+    div()
+        .flex()
+        .items_center()
+        .gap(spacing::sm())
+        .child(
+            action("validation-starter", AppCommands::generate_validation_starter)
+                .ghost()
+                .label("Generate from Schema"),
+        )
+        .child(
+            action("validation-check", AppCommands::check_validation_draft)
+                .ghost()
+                .label("Check Sample"),
+        )
+        .child(
+            Button::new("validation-reset")
+                .ghost()
+                .compact()
+                .label("Reset")
+                .disabled(session_key.is_none() || busy)
+                .on_click({
+                    let session_key = session_key.clone();
+                    move |_: &ClickEvent, _window: &mut Window, cx: &mut App| {
+                        let Some(session_key) = session_key.clone() else {
+                            return;
+                        };
+                        state_for_reset.update(cx, |state, cx| {
+                            state.reset_validation_draft(&session_key);
+                            cx.notify();
+                        });
+                    }
+                }),
+        )
+        .child(
+            Button::new("validation-apply")
+                .primary()
+                .compact()
+                .label("Apply")
+                .disabled(session_key.is_none() || busy)
+                .on_click({
+                    let state = state.clone();
+                    let session_key = session_key.clone();
+                    move |_: &ClickEvent, window: &mut Window, cx: &mut App| {
+                        let Some(session_key) = session_key.clone() else {
+                            return;
+                        };
+                        let removes = state
+                            .read(cx)
+                            .session_data(&session_key)
+                            .is_some_and(|data| data.validation.draft_removes_validation());
+                        let (title, message, label) = if removes {
+                            (
+                                "Remove validation",
+                                format!(
+                                    "Remove all validation rules from {}? Documents will no \
+                                     longer be checked on insert or update.",
+                                    session_key.collection
+                                ),
+                                "Remove validation",
+                            )
+                        } else {
+                            (
+                                "Apply validation",
+                                format!(
+                                    "Replace the validation rules of {} with the draft? \
+                                     Writes are checked against them from now on.",
+                                    session_key.collection
+                                ),
+                                "Apply",
+                            )
+                        };
+                        open_confirm_dialog(window, cx, title, message, label, removes, {
+                            let state = state.clone();
+                            move |_window, cx| {
+                                AppCommands::apply_validation_draft(state, session_key, cx);
+                            }
+                        });
+                    }
+                }),
+        )
+        .child(
+            action("refresh-validation", AppCommands::load_collection_validation)
+                .ghost()
+                .icon(Icon::new(IconName::Redo).xsmall()),
+        )
+}
+
 /// Render action buttons for the Aggregation subview.
 pub fn render_aggregation_actions(
     state: Entity<AppState>,
@@ -1280,4 +1394,29 @@ pub fn render_aggregation_actions(
                     }
                 }),
         )
+        .child(
+            Button::new("agg-save")
+                .compact()
+                .icon(Icon::new(IconName::Star).xsmall())
+                .label("Save")
+                .tooltip("Save pipeline to the query library")
+                .disabled(session_key.is_none())
+                .on_click({
+                    let session_key = session_key.clone();
+                    let state = state.clone();
+                    move |_: &ClickEvent, window: &mut Window, cx: &mut App| {
+                        let Some(session_key) = session_key.clone() else {
+                            return;
+                        };
+                        let Some(data) = state.read(cx).session_data(&session_key) else {
+                            return;
+                        };
+                        let body = SavedQueryBody::Aggregation {
+                            pipeline: data.aggregation.stages.clone(),
+                        };
+                        let collection = Some((session_key.database, session_key.collection));
+                        open_save_query_dialog(state.clone(), body, collection, window, cx);
+                    }
+                }),
+        )
 }
@@ -4,11 +4,17 @@ use gpui::prelude::FluentBuilder as _;
 use gpui::*;
 use gpui_component::ActiveTheme as _;
 use gpui_component::RopeExt as _;
+use gpui_component::button::{Button as MenuButton, ButtonVariants as _};
 use gpui_component::input::{Input, InputState};
-use gpui_component::{Icon, IconName, Sizable as _};
+use gpui_component::popover::Popover;
+use gpui_component::scroll::ScrollableElement as _;
+use gpui_component::{Disableable as _, Icon, IconName, Sizable as _, Size};
 
 use crate::components::Button;
-use crate::state::{AppCommands, AppState, SessionKey};
+use crate::components::saved_query_dialog::open_save_query_dialog;
+use crate::state::{
+    AppCommands, AppState, QueryHistoryEntry, SavedQueryBody, SessionKey, StatusMessage,
+};
 use crate::theme::{borders, spacing};
 use crate::views::documents::CollectionView;
 
@@ -33,12 +39,13 @@ pub fn render_filter_row(
     sort_valid: bool,
     filter_active: bool,
     sort_active: bool,
-    projection_active: bool,
+    options_panel_active: bool,
     query_options_open: bool,
     filter_builder_open: bool,
     explain_loading: bool,
     filter_error_message: Option<&str>,
     filter_dirty: bool,
+    history_search: Entity<InputState>,
     cx: &App,
 ) -> Div {
     let state_for_filter = state.clone();
@@ -182,7 +189,7 @@ pub fn render_filter_row(
                     .ghost()
                     .compact()
                     .icon(Icon::new(IconName::Settings).xsmall())
-                    .tooltip("Projection options")
+                    .tooltip("Query options")
                     .disabled(session_key.is_none())
                     .on_click({
                         let session_key = session_key.clone();
@@ -197,7 +204,7 @@ pub fn render_filter_row(
                             });
                         }
                     });
-                if query_options_open || sort_active || projection_active {
+                if query_options_open || sort_active || options_panel_active {
                     options_button = options_button.active_style(cx.theme().secondary);
                 }
                 options_button
@@ -226,7 +233,35 @@ pub fn render_filter_row(
                     builder_button = builder_button.active_style(cx.theme().primary.opacity(0.55));
                 }
                 builder_button
-            }),
+            })
+            .child(render_history_popover(state.clone(), session_key.clone(), history_search))
+            .child(
+                Button::new("save-query")
+                    .ghost()
+                    .compact()
+                    .icon(Icon::new(IconName::Star).xsmall())
+                    .tooltip("Save query")
+                    .disabled(session_key.is_none())
+                    .on_click({
+                        let session_key = session_key.clone();
+                        let state = state.clone();
+                        move |_: &ClickEvent, window: &mut Window, cx: &mut App| {
+                            let Some(session_key) = session_key.clone() else {
+                                return;
+                            };
+                            let Some(data) = state.read(cx).session_data(&session_key) else {
+                                return;
+                            };
+                            let body = SavedQueryBody::Find {
+                                filter: data.filter_raw.clone(),
+                                sort: data.sort_raw.clone(),
+                                projection: data.projection_raw.clone(),
+                            };
+                            let collection = Some((session_key.database, session_key.collection));
+                            open_save_query_dialog(state.clone(), body, collection, window, cx);
+                        }
+                    }),
+            ),
     );
 
     if let Some(err) = filter_error_message {
@@ -246,6 +281,189 @@ pub fn render_filter_row(
     col
 }
 
+/// Entries shown in the history popover.
+const HISTORY_POPOVER_LIMIT: usize = 50;
+
+/// History button with a searchable list of recent queries. Clicking an entry
+/// runs it in the current tab; the arrow opens it in its own collection's tab.
+fn render_history_popover(
+    state: Entity<AppState>,
+    session_key: Option<SessionKey>,
+    search: Entity<InputState>,
+) -> impl IntoElement {
+    let trigger = MenuButton::new("query-history")
+        .ghost()
+        .compact()
+        .with_size(Size::Small)
+        .icon(Icon::new(IconName::Undo).path("icons/history.svg").xsmall())
+        .tooltip("Query history")
+        .disabled(session_key.is_none());
+
+    Popover::new("query-history-popover").anchor(Corner::TopRight).trigger(trigger).content(
+        move |_popover, _window, cx| {
+            let popover = cx.entity();
+            let query = search.read(cx).value().to_string();
+            let entries: Vec<QueryHistoryEntry> = state
+                .read(cx)
+                .query_history
+                .search(&query)
+                .take(HISTORY_POPOVER_LIMIT)
+                .cloned()
+                .collect();
+            let muted = cx.theme().muted_foreground;
+            let hover_bg = cx.theme().secondary.opacity(0.5);
+
+            let rows: Vec<_> = entries
+                .into_iter()
+                .map(|entry| {
+                    let id = entry.id;
+                    let summary = entry.summary();
+                    let details = entry.details();
+                    let entry_for_open = entry.clone();
+                    let state_for_restore = state.clone();
+                    let state_for_open = state.clone();
+                    let session_for_restore = session_key.clone();
+                    let popover_for_restore = popover.clone();
+                    let popover_for_open = popover.clone();
+                    div()
+                        .id(SharedString::from(format!("history-row-{id}")))
+                        .flex()
+                        .items_center()
+                        .gap(spacing::xs())
+                        .px(px(6.0))
+                        .py(px(3.0))
+                        .rounded(borders::radius_sm())
+                        .cursor_pointer()
+                        .hover(move |s| s.bg(hover_bg))
+                        .on_click(move |_, window, cx| {
+                            let Some(session_key) = session_for_restore.clone() else {
+                                return;
+                            };
+                            let restored = state_for_restore.update(cx, |state, cx| {
+                                let result = state.restore_query(&session_key, &entry);
+                                report_history_restore(state, &result);
+                                cx.notify();
+                                result.is_ok()
+                            });
+                            if restored {
+                                AppCommands::load_documents_for_session(
+                                    state_for_restore.clone(),
+                                    session_key,
+                                    cx,
+                                );
+                            }
+                            popover_for_restore
+                                .update(cx, |popover, cx| popover.dismiss(window, cx));
+                        })
+                        .child(
+                            div()
+                                .flex()
+                                .flex_col()
+                                .flex_1()
+                                .min_w(px(0.0))
+                                .child(
+                                    div()
+                                        .text_sm()
+                                        .font_family(crate::theme::fonts::mono())
+                                        .truncate()
+                                        .child(summary),
+                                )
+                                .child(div().text_xs().text_color(muted).truncate().child(details)),
+                        )
+                        .child(
+                            Button::new(SharedString::from(format!("history-open-{id}")))
+                                .ghost()
+                                .compact()
+                                .icon(Icon::new(IconName::ExternalLink).xsmall())
+                                .tooltip("Open in collection tab")
+                                .on_click(
+                                    move |_: &ClickEvent, window: &mut Window, cx: &mut App| {
+                                        cx.stop_propagation();
+                                        let result = state_for_open.update(cx, |state, cx| {
+                                            let result =
+                                                state.restore_query_in_new_tab(&entry_for_open, cx);
+                                            report_history_restore(state, &result);
+                                            cx.notify();
+                                            result
+                                        });
+                                        if let Ok(session_key) = result {
+                                            AppCommands::load_documents_for_session(
+                                                state_for_open.clone(),
+                                                session_key,
+                                                cx,
+                                            );
+                                        }
+                                        popover_for_open
+                                            .update(cx, |popover, cx| popover.dismiss(window, cx));
+                                    },
+                                ),
+                        )
+                })
+                .collect();
+
+            let list = if rows.is_empty() {
+                div()
+                    .px(px(6.0))
+                    .py(px(4.0))
+                    .text_xs()
+                    .text_color(muted)
+                    .child(if query.trim().is_empty() {
+                        "No queries run yet"
+                    } else {
+                        "No matching queries"
+                    })
+                    .into_any_element()
+            } else {
+                div()
+                    .flex()
+                    .flex_col()
+                    .max_h(rems(20.))
+                    .overflow_y_scrollbar()
+                    .children(rows)
+                    .into_any_element()
+            };
+
+            let state_for_clear = state.clone();
+            div()
+                .flex()
+                .flex_col()
+                .w(px(420.0))
+                .gap(px(4.0))
+                .p(px(6.0))
+                .child(
+                    div()
+                        .flex()
+                        .items_center()
+                        .gap(spacing::xs())
+                        .child(div().flex_1().child(Input::new(&search).small()))
+                        .child(
+                            Button::new("query-history-clear")
+                                .ghost()
+                                .compact()
+                                .label("Clear")
+                                .on_click(
+                                    move |_: &ClickEvent, _window: &mut Window, cx: &mut App| {
+                                        state_for_clear.update(cx, |state, cx| {
+                                            state.clear_query_history();
+                                            cx.notify();
+                                        });
+                                    },
+                                ),
+                        ),
+                )
+                .child(list)
+        },
+    )
+}
+
+fn report_history_restore<T>(state: &mut AppState, result: &Result<T, String>) {
+    let message = match result {
+        Ok(_) => StatusMessage::info("Query restored from history"),
+        Err(err) => StatusMessage::error(format!("Could not restore query: {err}")),
+    };
+    state.set_status_message(Some(message));
+}
+
 #[allow(clippy::too_many_arguments)]
 fn render_query_segment(
     id: impl Into<ElementId>,
@@ -297,23 +515,27 @@ fn render_query_segment(
     row
 }
 
-/// Render the query options panel (sort/projection).
+/// Render the query options panel (projection and cursor options).
 #[allow(clippy::too_many_arguments)]
 pub fn render_query_options(
     state: Entity<AppState>,
     session_key: Option<SessionKey>,
     sort_state: Option<Entity<InputState>>,
     projection_state: Option<Entity<InputState>>,
+    options_state: Option<Entity<InputState>>,
     sort_valid: bool,
     projection_valid: bool,
+    options_valid: bool,
     sort_active: bool,
     projection_active: bool,
+    options_active: bool,
     cx: &App,
 ) -> Div {
     let state_for_query = state.clone();
     let state_for_clear = state.clone();
 
-    let apply_disabled = session_key.is_none() || !sort_valid || !projection_valid;
+    let apply_disabled =
+        session_key.is_none() || !sort_valid || !projection_valid || !options_valid;
     let disabled = session_key.is_none();
     let segmented_border = cx.theme().sidebar_border.opacity(0.5);
 
@@ -342,6 +564,27 @@ pub fn render_query_options(
                     cx,
                 )),
         )
+        .child(
+            div()
+                .flex()
+                .items_center()
+                .flex_1()
+                .min_w(px(240.0))
+                .rounded(borders::radius_md())
+                .border_1()
+                .border_color(segmented_border)
+                .bg(cx.theme().secondary.opacity(0.14))
+                .child(render_query_segment(
+                    "query-segment-options",
+                    IconName::Settings,
+                    options_state.clone(),
+                    "options {}",
+                    options_valid,
+                    options_active,
+                    disabled,
+                    cx,
+                )),
+        )
         .child(
             filter_action_button(
                 Button::new("apply-query").compact(),
@@ -353,15 +596,15 @@ pub fn render_query_options(
                 let session_key = session_key.clone();
                 let sort_state = sort_state.clone();
                 let projection_state = projection_state.clone();
+                let options_state = options_state.clone();
                 let state_for_query = state_for_query.clone();
                 move |_: &ClickEvent, window: &mut Window, cx: &mut App| {
                     let Some(session_key) = session_key.clone() else {
                         return;
                     };
-                    let Some(sort_state) = sort_state.clone() else {
-                        return;
-                    };
-                    let Some(projection_state) = projection_state.clone() else {
+                    let (Some(sort_state), Some(projection_state), Some(options_state)) =
+                        (sort_state.clone(), projection_state.clone(), options_state.clone())
+                    else {
                         return;
                     };
                     CollectionView::apply_query_options(
@@ -369,6 +612,7 @@ pub fn render_query_options(
                         session_key,
                         sort_state,
                         projection_state,
+                        options_state,
                         window,
                         cx,
                     );
@@ -381,33 +625,35 @@ pub fn render_query_options(
                 IconName::Close,
                 "Clear options",
             )
-            .disabled(session_key.is_none() || (!sort_active && !projection_active))
+            .disabled(
+                session_key.is_none() || (!sort_active && !projection_active && !options_active),
+            )
             .on_click({
                 let session_key = session_key.clone();
                 let sort_state = sort_state.clone();
                 let projection_state = projection_state.clone();
+                let options_state = options_state.clone();
                 let state_for_clear = state_for_clear.clone();
                 move |_: &ClickEvent, window: &mut Window, cx: &mut App| {
                     let Some(session_key) = session_key.clone() else {
                         return;
                     };
-                    let Some(sort_state) = sort_state.clone() else {
-                        return;
-                    };
-                    let Some(projection_state) = projection_state.clone() else {
+                    let (Some(sort_state), Some(projection_state), Some(options_state)) =
+                        (sort_state.clone(), projection_state.clone(), options_state.clone())
+                    else {
                         return;
                     };
-                    sort_state.update(cx, |state, cx| {
-                        set_query_object_default(state, window, cx);
-                    });
-                    projection_state.update(cx, |state, cx| {
-                        set_query_object_default(state, window, cx);
-                    });
+                    for input in [&sort_state, &projection_state, &options_state] {
+                        input.update(cx, |state, cx| {
+                            set_query_object_default(state, window, cx);
+                        });
+                    }
                     CollectionView::apply_query_options(
                         state_for_clear.clone(),
                         session_key,
                         sort_state,
                         projection_state,
+                        options_state,
                         window,
                         cx,
                     );
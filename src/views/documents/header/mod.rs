@@ -3,7 +3,7 @@
 //! This module provides the header UI for collection views, including:
 //! - Collection title and breadcrumb
 //! - Action buttons (varies by subview)
-//! - Subview tabs (Documents/Indexes/Stats/Aggregation/Schema)
+//! - Subview tabs (Documents/Indexes/Stats/Aggregation/Schema/Validation)
 //! - Filter bar and query options (Documents subview only)
 
 mod actions;
@@ -13,7 +13,7 @@ mod tabs_row;
 
 pub use actions::{
     clean_toolbar_icon_button, render_aggregation_actions, render_documents_actions,
-    render_indexes_actions, render_schema_actions, render_stats_actions,
+    render_indexes_actions, render_schema_actions, render_stats_actions, render_validation_actions,
 };
 pub use filter_bar::{render_filter_row, render_query_options};
 pub use stats_panel::render_stats_row;
@@ -26,7 +26,7 @@ use gpui_component::input::InputState;
 use gpui_component::{Icon, IconName, Sizable as _};
 
 use crate::bson::DocumentKey;
-use crate::helpers::format_number;
+use crate::connection::DocumentCount;
 use crate::state::{CollectionSubview, SessionKey};
 use crate::theme::{islands, spacing};
 
@@ -39,7 +39,7 @@ impl CollectionView {
         &self,
         collection_name: &str,
         db_name: &str,
-        total: u64,
+        total: DocumentCount,
         session_key: Option<SessionKey>,
         selected_doc: Option<DocumentKey>,
         selected_count: usize,
@@ -50,10 +50,13 @@ impl CollectionView {
         filter_active: bool,
         sort_state: Option<Entity<InputState>>,
         projection_state: Option<Entity<InputState>>,
+        options_state: Option<Entity<InputState>>,
         sort_valid: bool,
         projection_valid: bool,
+        options_valid: bool,
         sort_active: bool,
         projection_active: bool,
+        options_active: bool,
         query_options_open: bool,
         filter_builder_open: bool,
         active_subview: CollectionSubview,
@@ -62,6 +65,7 @@ impl CollectionView {
         explain_loading: bool,
         schema_loading: bool,
         col_visibility_search: Entity<InputState>,
+        history_search: Entity<InputState>,
         _window: &mut Window,
         cx: &mut Context<Self>,
     ) -> impl IntoElement {
@@ -82,6 +86,7 @@ impl CollectionView {
         let is_stats = active_subview == CollectionSubview::Stats;
         let is_aggregation = active_subview == CollectionSubview::Aggregation;
         let is_schema = active_subview == CollectionSubview::Schema;
+        let is_validation = active_subview == CollectionSubview::Validation;
         let breadcrumb = format!("{connection_name} / {db_name} / {collection_name}");
 
         // Build action row based on active subview
@@ -120,6 +125,15 @@ impl CollectionView {
             )
         } else if is_schema {
             render_schema_actions(self.state.clone(), session_key.clone(), schema_loading)
+        } else if is_validation {
+            let busy = session_key
+                .as_ref()
+                .and_then(|key| self.state.read(cx).session_data(key))
+                .is_some_and(|data| {
+                    let validation = &data.validation;
+                    validation.loading || validation.checking || validation.applying
+                });
+            render_validation_actions(self.state.clone(), session_key.clone(), busy)
         } else {
             div().flex().items_center().gap(spacing::sm())
         };
@@ -165,12 +179,13 @@ impl CollectionView {
                 sort_valid,
                 filter_active,
                 sort_active,
-                projection_active,
+                projection_active || options_active,
                 query_options_open,
                 filter_builder_open,
                 explain_loading,
                 self.filter_error_message.as_deref(),
                 self.filter_dirty,
+                history_search,
                 cx,
             ));
 
@@ -181,10 +196,13 @@ impl CollectionView {
                     session_key.clone(),
                     sort_state,
                     projection_state,
+                    options_state,
                     sort_valid,
                     projection_valid,
+                    options_valid,
                     sort_active,
                     projection_active,
+                    options_active,
                     cx,
                 ));
             }
@@ -197,7 +215,7 @@ impl CollectionView {
 /// Render the title row with collection name, doc count, breadcrumb, and actions.
 fn render_title_row(
     collection_name: &str,
-    total: u64,
+    total: DocumentCount,
     breadcrumb: &str,
     action_row: Div,
     cx: &mut Context<CollectionView>,
@@ -231,7 +249,7 @@ fn render_title_row(
                             div()
                                 .text_sm()
                                 .text_color(cx.theme().muted_foreground)
-                                .child(format!("({} docs)", format_number(total))),
+                                .child(format!("({total} docs)")),
                         ),
                 )
                 .child(
@@ -7,22 +7,32 @@ use gpui_component::menu::{DropdownMenu as _, PopupMenu, PopupMenuItem};
 use gpui_component::{Disableable as _, Icon, IconName, Sizable as _};
 
 use crate::components::Button;
-use crate::state::{AppCommands, AppState, SessionKey};
+use crate::connection::{CountStrategy, DocumentCount, PaginationMode};
+use crate::state::{AppCommands, AppState, PagingSnapshot, SessionKey};
 use crate::theme::spacing;
 
 use super::CollectionView;
 
 const PER_PAGE_OPTIONS: &[i64] = &[10, 25, 50, 100];
+const PAGINATION_MODES: &[PaginationMode] =
+    &[PaginationMode::Offset, PaginationMode::Keyset, PaginationMode::Stream];
+const COUNT_STRATEGIES: &[CountStrategy] = &[
+    CountStrategy::Exact,
+    CountStrategy::Estimated,
+    CountStrategy::Capped(10_000),
+    CountStrategy::Lazy,
+];
 
 impl CollectionView {
     #[allow(clippy::too_many_arguments)]
     pub(super) fn render_pagination(
         page: u64,
-        total_pages: u64,
+        total_pages: Option<u64>,
         per_page: i64,
         range_start: u64,
         range_end: u64,
-        total: u64,
+        total: DocumentCount,
+        paging: PagingSnapshot,
         is_loading: bool,
         session_key: Option<SessionKey>,
         state: Entity<AppState>,
@@ -34,8 +44,91 @@ impl CollectionView {
         let session_key_prev = session_key.clone();
         let session_key_next = session_key.clone();
 
+        let state_for_count = state.clone();
+        let session_key_count = session_key.clone();
+
+        let paging_selector = {
+            let btn = MenuButton::new("paging-selector")
+                .ghost()
+                .compact()
+                .icon(Icon::new(IconName::Settings).xsmall())
+                .dropdown_caret(true)
+                .with_size(gpui_component::Size::XSmall)
+                .disabled(is_loading || session_key.is_none());
+
+            let state = state.clone();
+            let view = view.clone();
+            let sk = session_key.clone();
+            btn.dropdown_menu_with_anchor(Corner::TopLeft, move |mut menu: PopupMenu, _, _| {
+                menu = menu.label("Pagination");
+                for &mode in PAGINATION_MODES {
+                    let state = state.clone();
+                    let view = view.clone();
+                    let sk = sk.clone();
+                    menu = menu.item(
+                        PopupMenuItem::new(mode.label()).checked(mode == paging.mode).on_click(
+                            move |_, _, cx| {
+                                let Some(sk) = sk.clone() else {
+                                    return;
+                                };
+                                state.update(cx, |state, cx| {
+                                    state.set_pagination_mode(&sk, mode);
+                                    cx.notify();
+                                });
+                                view.update(cx, |this, cx| {
+                                    this.view_model.invalidate_table();
+                                    cx.notify();
+                                });
+                                AppCommands::load_documents_for_session(state.clone(), sk, cx);
+                            },
+                        ),
+                    );
+                }
+                menu = menu.separator().label("Total");
+                for &strategy in COUNT_STRATEGIES {
+                    let state = state.clone();
+                    let sk = sk.clone();
+                    menu = menu.item(
+                        PopupMenuItem::new(strategy.label())
+                            .checked(strategy == paging.count_strategy)
+                            .on_click(move |_, _, cx| {
+                                let Some(sk) = sk.clone() else {
+                                    return;
+                                };
+                                state.update(cx, |state, cx| {
+                                    state.set_count_strategy(&sk, strategy);
+                                    cx.notify();
+                                });
+                                AppCommands::load_documents_for_session(state.clone(), sk, cx);
+                            }),
+                    );
+                }
+                menu
+            })
+        };
+
+        let count_button = matches!(total, DocumentCount::AtLeast(_) | DocumentCount::Unknown)
+            .then(|| {
+                Button::new("load-count")
+                    .ghost()
+                    .compact()
+                    .label(if paging.count_loading { "Counting..." } else { "Count" })
+                    .disabled(paging.count_loading || is_loading || session_key.is_none())
+                    .on_click(move |_, _, cx| {
+                        let Some(session_key) = session_key_count.clone() else {
+                            return;
+                        };
+                        AppCommands::load_document_count(state_for_count.clone(), session_key, cx);
+                    })
+            });
+        let page_label = match total_pages {
+            Some(total_pages) => format!("Page {} of {}", page + 1, total_pages),
+            None => format!("Page {}", page + 1),
+        };
+
+        let streaming = paging.mode == PaginationMode::Stream;
         let per_page_selector = {
-            let label = format!("{} / page", per_page);
+            let label = format!("{} / {}", per_page, if streaming { "batch" } else { "page" });
             let btn = MenuButton::new("per-page-selector")
                 .ghost()
                 .compact()
@@ -90,9 +183,24 @@ impl CollectionView {
                             .text_color(cx.theme().muted_foreground)
                             .child(format!("Showing {}-{} of {}", range_start, range_end, total)),
                     )
-                    .child(per_page_selector),
+                    .children(count_button)
+                    .child(per_page_selector)
+                    .child(paging_selector),
             )
-            .child(
+            .child(if streaming {
+                let label = if paging.stream_fetching {
+                    "Loading more..."
+                } else if paging.has_more {
+                    "Scroll for more"
+                } else {
+                    "End of results"
+                };
+                div()
+                    .text_sm()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(label)
+                    .into_any_element()
+            } else {
                 div()
                     .flex()
                     .items_center()
@@ -117,24 +225,18 @@ impl CollectionView {
                                 );
                             }),
                     )
-                    .child(div().text_sm().text_color(cx.theme().foreground).child(format!(
-                        "Page {} of {}",
-                        page + 1,
-                        total_pages
-                    )))
+                    .child(div().text_sm().text_color(cx.theme().foreground).child(page_label))
                     .child(
                         Button::new("next")
                             .ghost()
-                            .disabled(
-                                page + 1 >= total_pages || is_loading || session_key.is_none(),
-                            )
+                            .disabled(!paging.has_more || is_loading || session_key.is_none())
                             .icon(Icon::new(IconName::ChevronRight).xsmall())
                             .on_click(move |_, _, cx| {
                                 let Some(session_key) = session_key_next.clone() else {
                                     return;
                                 };
                                 state_for_next.update(cx, |state, cx| {
-                                    state.next_page(&session_key, total_pages);
+                                    state.next_page(&session_key);
                                     cx.notify();
                                 });
                                 AppCommands::load_documents_for_session(
@@ -143,7 +245,8 @@ impl CollectionView {
                                     cx,
                                 );
                             }),
-                    ),
-            )
+                    )
+                    .into_any_element()
+            })
     }
 }
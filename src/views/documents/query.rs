@@ -4,6 +4,7 @@ use mongodb::bson::Bson;
 use mongodb::bson::Document;
 
 use crate::bson::{format_relaxed_json_compact, parse_document_from_json};
+use crate::connection::QueryOptions;
 use crate::state::{AppCommands, AppState, SessionKey, StatusMessage};
 
 use super::CollectionView;
@@ -54,11 +55,13 @@ impl CollectionView {
         session_key: SessionKey,
         sort_state: Entity<InputState>,
         projection_state: Entity<InputState>,
+        options_state: Entity<InputState>,
         _window: &mut Window,
         cx: &mut App,
     ) {
         let sort_raw = sort_state.read(cx).value().to_string();
         let projection_raw = projection_state.read(cx).value().to_string();
+        let options_raw = options_state.read(cx).value().to_string();
 
         let (sort_raw_store, sort_doc) = match parse_optional_doc(&sort_raw) {
             Ok(result) => result,
@@ -86,13 +89,34 @@ impl CollectionView {
             }
         };
 
-        let message = if sort_doc.is_none() && projection_doc.is_none() {
-            "Sort/projection cleared"
+        let parsed_options = parse_optional_doc(&options_raw).and_then(|(raw, doc)| {
+            let options = doc.as_ref().map(QueryOptions::from_document).transpose()?;
+            Ok((raw, options.unwrap_or_default()))
+        });
+        let (options_raw_store, query_options) = match parsed_options {
+            Ok(result) => result,
+            Err(err) => {
+                state.update(cx, |state, cx| {
+                    state.set_status_message(Some(StatusMessage::error(format!(
+                        "Invalid query options: {err}"
+                    ))));
+                    cx.notify();
+                });
+                return;
+            }
+        };
+
+        let message = if sort_doc.is_none() && projection_doc.is_none() && query_options.is_empty()
+        {
+            "Query options cleared"
         } else {
-            "Sort/projection applied"
+            "Query options applied"
         };
 
         state.update(cx, |state, cx| {
+            // Options first so the history entry recorded by the sort/projection
+            // update includes them.
+            state.set_query_options(&session_key, options_raw_store, query_options);
             state.set_sort_projection(
                 &session_key,
                 sort_raw_store,
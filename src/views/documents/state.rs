@@ -13,6 +13,7 @@ use crate::components::filter_builder::FilterBuilderPanel;
 use crate::helpers::auto_pair::AutoPairState;
 use crate::perf::log_tabs_duration;
 use crate::state::{AppCommands, AppEvent, AppState, CollectionSubview, SessionKey, StatusMessage};
+use crate::views::json_editor_detached::open_save_conflict_window;
 
 use super::node_meta::NodeMeta;
 use super::view_model::DocumentViewModel;
@@ -27,10 +28,12 @@ pub struct CollectionView {
     pub(crate) filter_state: Option<Entity<InputState>>,
     pub(crate) sort_state: Option<Entity<InputState>>,
     pub(crate) projection_state: Option<Entity<InputState>>,
+    pub(crate) options_state: Option<Entity<InputState>>,
     pub(crate) schema_filter_state: Option<Entity<InputState>>,
     pub(crate) filter_auto_pair: AutoPairState,
     pub(crate) sort_auto_pair: AutoPairState,
     pub(crate) projection_auto_pair: AutoPairState,
+    pub(crate) options_auto_pair: AutoPairState,
     pub(crate) filter_error_message: Option<String>,
     pub(crate) filter_dirty: bool,
     pub(crate) calendar_state: Option<Entity<CalendarState>>,
@@ -41,6 +44,7 @@ pub struct CollectionView {
     pub(crate) calendar_second: Option<Entity<InputState>>,
     pub(crate) sort_error: bool,
     pub(crate) projection_error: bool,
+    pub(crate) options_error: bool,
     pub(crate) search_state: Option<Entity<InputState>>,
     pub(crate) search_visible: bool,
     pub(crate) search_matches: Vec<String>,
@@ -59,6 +63,7 @@ pub struct CollectionView {
     pub(crate) filter_subscription: Option<Subscription>,
     pub(crate) sort_subscription: Option<Subscription>,
     pub(crate) projection_subscription: Option<Subscription>,
+    pub(crate) options_subscription: Option<Subscription>,
     pub(crate) schema_filter_subscription: Option<Subscription>,
     pub(crate) search_subscription: Option<Subscription>,
     pub(crate) aggregation_stage_body_state: Option<Entity<InputState>>,
@@ -71,6 +76,11 @@ pub struct CollectionView {
     pub(crate) aggregation_ignore_body_change: bool,
     pub(crate) aggregation_stage_body_subscription: Option<Subscription>,
     pub(crate) aggregation_limit_subscription: Option<Subscription>,
+    pub(crate) validation_editor_state: Option<Entity<InputState>>,
+    pub(crate) validation_editor_subscription: Option<Subscription>,
+    pub(crate) validation_input_session: Option<SessionKey>,
+    pub(crate) validation_draft_revision: u64,
+    pub(crate) validation_ignore_change: bool,
     pub(crate) filter_builder_panel: Option<Entity<FilterBuilderPanel>>,
     pub(crate) filter_builder_session: Option<SessionKey>,
     pub(crate) _subscriptions: Vec<Subscription>,
@@ -415,6 +425,16 @@ impl CollectionView {
                 this.input_session = None;
                 cx.notify();
             }
+            AppEvent::DocumentsStreamed { session, .. } => {
+                if !this.view_model.is_current_session(session) {
+                    return;
+                }
+                this.view_model.rebuild_tree(&state, cx);
+                this.view_model.invalidate_table();
+                this.view_model.sync_dirty_state(&state, cx);
+                this.update_search_results(cx);
+                cx.notify();
+            }
             AppEvent::DocumentSaved { session, document } => {
                 if !this.view_model.is_current_session(session) {
                     return;
@@ -428,6 +448,17 @@ impl CollectionView {
                 this.update_search_results(cx);
                 cx.notify();
             }
+            AppEvent::DocumentsSaved { session, .. } => {
+                if !this.view_model.is_current_session(session) {
+                    return;
+                }
+                this.view_model.clear_inline_edit();
+                this.view_model.rebuild_tree(&state, cx);
+                this.view_model.invalidate_table();
+                this.view_model.sync_dirty_state(&state, cx);
+                this.update_search_results(cx);
+                cx.notify();
+            }
             AppEvent::DocumentDeleted { session, document } => {
                 if !this.view_model.is_current_session(session) {
                     return;
@@ -441,11 +472,29 @@ impl CollectionView {
                 this.update_search_results(cx);
                 cx.notify();
             }
-            AppEvent::DocumentSaveFailed { session, .. } => {
+            AppEvent::DocumentSaveFailed { session, .. }
+            | AppEvent::DocumentsSaveFailed { session, .. } => {
                 if this.view_model.is_current_session(session) {
                     cx.notify();
                 }
             }
+            AppEvent::DocumentSaveConflict { session, document, conflict } => {
+                // Inline saves have no editor to review in; open one unless a
+                // detached editor for this document already handles it.
+                let sessions = state.read(cx).editor_sessions();
+                if this.view_model.is_current_session(session)
+                    && sessions.find_document_session(session, document).is_none()
+                {
+                    let state = state.clone();
+                    let session = session.clone();
+                    let document = document.clone();
+                    let conflict = (**conflict).clone();
+                    cx.defer(move |cx| {
+                        open_save_conflict_window(state, session, document, conflict, cx);
+                    });
+                }
+                cx.notify();
+            }
             AppEvent::DocumentDeleteFailed { session, .. } => {
                 if this.view_model.is_current_session(session) {
                     cx.notify();
@@ -463,10 +512,12 @@ impl CollectionView {
             filter_state: None,
             sort_state: None,
             projection_state: None,
+            options_state: None,
             schema_filter_state: None,
             filter_auto_pair: AutoPairState::new("{}"),
             sort_auto_pair: AutoPairState::new(""),
             projection_auto_pair: AutoPairState::new(""),
+            options_auto_pair: AutoPairState::new(""),
             filter_error_message: None,
             filter_dirty: false,
             calendar_state: None,
@@ -477,6 +528,7 @@ impl CollectionView {
             calendar_second: None,
             sort_error: false,
             projection_error: false,
+            options_error: false,
             search_state: None,
             search_visible: false,
             search_matches: Vec::new(),
@@ -495,6 +547,7 @@ impl CollectionView {
             filter_subscription: None,
             sort_subscription: None,
             projection_subscription: None,
+            options_subscription: None,
             schema_filter_subscription: None,
             search_subscription: None,
             aggregation_stage_body_state: None,
@@ -507,6 +560,11 @@ impl CollectionView {
             aggregation_ignore_body_change: false,
             aggregation_stage_body_subscription: None,
             aggregation_limit_subscription: None,
+            validation_editor_state: None,
+            validation_editor_subscription: None,
+            validation_input_session: None,
+            validation_draft_revision: 0,
+            validation_ignore_change: false,
             filter_builder_panel: None,
             filter_builder_session: None,
             _subscriptions: subscriptions,
@@ -560,6 +618,15 @@ impl CollectionView {
         {
             AppCommands::analyze_collection_schema(state.clone(), session_key.clone(), cx);
         }
+
+        let validation = snapshot.validation;
+        if subview == CollectionSubview::Validation
+            && validation.current.is_none()
+            && !validation.loading
+            && validation.error.is_none()
+        {
+            AppCommands::load_collection_validation(state.clone(), session_key.clone(), cx);
+        }
     }
 
     fn persist_query_input_drafts(&mut self, cx: &mut Context<Self>) {
@@ -571,8 +638,10 @@ impl CollectionView {
         let sort_raw = self.sort_state.as_ref().map(|input| input.read(cx).value().to_string());
         let projection_raw =
             self.projection_state.as_ref().map(|input| input.read(cx).value().to_string());
+        let options_raw =
+            self.options_state.as_ref().map(|input| input.read(cx).value().to_string());
 
-        let (stored_filter, stored_sort, stored_projection) = {
+        let (stored_filter, stored_sort, stored_projection, stored_options) = {
             let state_ref = self.state.read(cx);
             let Some(session_data) = state_ref.session_data(&session_key) else {
                 return;
@@ -581,6 +650,7 @@ impl CollectionView {
                 session_data.filter_raw.clone(),
                 session_data.sort_raw.clone(),
                 session_data.projection_raw.clone(),
+                session_data.query_options_raw.clone(),
             )
         };
 
@@ -593,15 +663,19 @@ impl CollectionView {
         let next_sort = normalize_query_draft(sort_raw.unwrap_or_else(|| stored_sort.clone()));
         let next_projection =
             normalize_query_draft(projection_raw.unwrap_or_else(|| stored_projection.clone()));
+        let next_options =
+            normalize_query_draft(options_raw.unwrap_or_else(|| stored_options.clone()));
 
         let stored_filter = normalize_query_draft(stored_filter);
         let stored_sort = normalize_query_draft(stored_sort);
         let stored_projection = normalize_query_draft(stored_projection);
+        let stored_options = normalize_query_draft(stored_options);
 
         let filter_changed = next_filter != stored_filter;
         let sort_projection_changed =
             next_sort != stored_sort || next_projection != stored_projection;
-        if !filter_changed && !sort_projection_changed {
+        let options_changed = next_options != stored_options;
+        if !filter_changed && !sort_projection_changed && !options_changed {
             return;
         }
 
@@ -616,6 +690,9 @@ impl CollectionView {
                     next_projection.clone(),
                 );
             }
+            if options_changed {
+                state.save_query_options_draft(&session_key, next_options.clone());
+            }
         });
     }
 
@@ -7,6 +7,7 @@ use gpui_component::{ActiveTheme as _, Icon, IconName, Sizable as _};
 use mongodb::bson::{Bson, Document};
 
 use crate::bson::DocumentKey;
+use crate::connection::PaginationMode;
 use crate::state::{AppCommands, AppState, SessionDocument, SessionKey};
 use crate::theme::colors;
 use crate::views::documents::CollectionView;
@@ -151,6 +152,22 @@ impl TableDelegate for DocumentTableDelegate {
         self.documents.len()
     }
 
+    // Despite the name, `true` lets the table call `load_more` near the end.
+    fn is_eof(&self, cx: &App) -> bool {
+        self.session_key
+            .as_ref()
+            .and_then(|session_key| self.state.read(cx).session_data(session_key))
+            .is_some_and(|data| {
+                data.pagination == PaginationMode::Stream && data.has_more && !data.stream_fetching
+            })
+    }
+
+    fn load_more(&mut self, _window: &mut Window, cx: &mut Context<TableState<Self>>) {
+        if let Some(session_key) = self.session_key.clone() {
+            AppCommands::load_more_documents(self.state.clone(), session_key, cx);
+        }
+    }
+
     fn column(&self, col_ix: usize, _cx: &App) -> &Column {
         self.table_cols.column_def(col_ix)
     }
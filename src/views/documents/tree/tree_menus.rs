@@ -9,12 +9,13 @@ use crate::bson::{
     parse_documents_from_json,
 };
 use crate::keyboard::{
-    AddElement, AddField, CopyAsCsv, CopyAsJson, CopyAsJsonLines, CopyAsMarkdown, CopyAsTsv,
-    CopyDocumentJson, CopyKey, CopyValue, DeleteDocument, DiscardDocumentChanges,
+    AddElement, AddField, CompareDocuments, CopyAsCsv, CopyAsJson, CopyAsJsonLines, CopyAsMarkdown,
+    CopyAsTsv, CopyDocumentJson, CopyKey, CopyValue, DeleteDocument, DiscardDocumentChanges,
     DuplicateDocument, EditDocumentJson, EditValueType, PasteDocuments, RemoveMatchingValues,
     RemoveSelectedField, RenameField,
 };
 use crate::state::{AppCommands, AppState, DocumentViewMode, SessionKey, StatusMessage};
+use crate::views::documents::dialogs::compare::CompareDocumentsDialog;
 use crate::views::documents::dialogs::property_dialog::PropertyActionDialog;
 use crate::views::documents::export::CopyFormat;
 use crate::views::documents::node_meta::NodeMeta;
@@ -123,6 +124,30 @@ pub(in crate::views::documents) fn build_document_menu(
                     }
                 }),
         )
+        .item(
+            PopupMenuItem::new(if selected_count == 2 {
+                "Compare Documents"
+            } else {
+                "Compare with Draft"
+            })
+            .icon(Icon::new(IconName::Replace))
+            .disabled(!(selected_count == 2 || (selected_count <= 1 && is_dirty)))
+            .action(Box::new(CompareDocuments))
+            .on_click({
+                let state = state.clone();
+                let session_key = session_key.clone();
+                let doc_key = doc_key.clone();
+                move |_, window, cx| {
+                    CompareDocumentsDialog::open_for_selection(
+                        state.clone(),
+                        session_key.clone(),
+                        doc_key.clone(),
+                        window,
+                        cx,
+                    );
+                }
+            }),
+        )
         .item(PopupMenuItem::new("Paste Document(s)").action(Box::new(PasteDocuments)).on_click({
             let state = state.clone();
             let session_key = session_key.clone();
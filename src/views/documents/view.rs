@@ -1,6 +1,10 @@
 use std::rc::Rc;
 
-use crate::state::{AppCommands, CollectionStats, CollectionSubview, SchemaAnalysis, SessionKey};
+use crate::connection::{DocumentCount, PaginationMode};
+use crate::state::{
+    AppCommands, CollectionStats, CollectionSubview, PagingSnapshot, SchemaAnalysis, SessionKey,
+    ValidationState,
+};
 use crate::theme::spacing;
 use gpui::prelude::FluentBuilder as _;
 use gpui::*;
@@ -128,6 +132,7 @@ impl Render for CollectionView {
         let (
             documents,
             total,
+            paging,
             page,
             per_page,
             is_loading,
@@ -138,6 +143,7 @@ impl Render for CollectionView {
             filter_raw,
             sort_raw,
             projection_raw,
+            query_options_raw,
             query_options_open,
             filter_builder_open,
             subview,
@@ -147,6 +153,7 @@ impl Render for CollectionView {
             indexes,
             indexes_loading,
             indexes_error,
+            index_usage,
             aggregation,
             explain,
             schema,
@@ -155,10 +162,12 @@ impl Render for CollectionView {
             schema_selected_field,
             schema_expanded_fields,
             schema_filter,
+            validation,
         ) = if let Some(snapshot) = snapshot {
             (
                 snapshot.items,
                 snapshot.total,
+                snapshot.paging,
                 snapshot.page,
                 snapshot.per_page,
                 snapshot.is_loading,
@@ -169,6 +178,7 @@ impl Render for CollectionView {
                 snapshot.filter_raw,
                 snapshot.sort_raw,
                 snapshot.projection_raw,
+                snapshot.query_options_raw,
                 snapshot.query_options_open,
                 snapshot.filter_builder_open,
                 snapshot.subview,
@@ -178,6 +188,7 @@ impl Render for CollectionView {
                 snapshot.indexes,
                 snapshot.indexes_loading,
                 snapshot.indexes_error,
+                snapshot.index_usage,
                 snapshot.aggregation,
                 snapshot.explain,
                 snapshot.schema,
@@ -186,11 +197,13 @@ impl Render for CollectionView {
                 snapshot.schema_selected_field,
                 snapshot.schema_expanded_fields,
                 snapshot.schema_filter,
+                snapshot.validation,
             )
         } else {
             (
                 Vec::new(),
-                0,
+                DocumentCount::default(),
+                PagingSnapshot::default(),
                 0,
                 50,
                 false,
@@ -201,6 +214,7 @@ impl Render for CollectionView {
                 String::new(),
                 String::new(),
                 String::new(),
+                String::new(),
                 false,
                 false,
                 CollectionSubview::Documents,
@@ -210,6 +224,7 @@ impl Render for CollectionView {
                 None,
                 false,
                 None,
+                None,
                 Default::default(),
                 Default::default(),
                 None::<SchemaAnalysis>,
@@ -218,11 +233,13 @@ impl Render for CollectionView {
                 None::<String>,
                 std::collections::HashSet::new(),
                 String::new(),
+                ValidationState::default(),
             )
         };
         let filter_active = !matches!(filter_raw.trim(), "" | "{}");
         let sort_active = !matches!(sort_raw.trim(), "" | "{}");
         let projection_active = !matches!(projection_raw.trim(), "" | "{}");
+        let options_active = !matches!(query_options_raw.trim(), "" | "{}");
         let filter_valid = self.filter_error_message.is_none();
 
         let filter_dirty = if let Some(ref fs) = self.filter_state {
@@ -235,11 +252,17 @@ impl Render for CollectionView {
         self.filter_dirty = filter_dirty;
         let sort_valid = !self.sort_error;
         let projection_valid = !self.projection_error;
+        let options_valid = !self.options_error;
         let per_page_u64 = per_page.max(1) as u64;
-        let total_pages = total.div_ceil(per_page_u64).max(1);
-        let display_page = page.min(total_pages.saturating_sub(1));
-        let range_start = if total == 0 { 0 } else { display_page * per_page_u64 + 1 };
-        let range_end = if total == 0 { 0 } else { ((display_page + 1) * per_page_u64).min(total) };
+        let total_pages = total.page_total().map(|total| total.div_ceil(per_page_u64).max(1));
+        let display_page = total_pages.map_or(page, |pages| page.min(pages - 1));
+        let page_offset = match paging.mode {
+            PaginationMode::Stream => paging.stream_offset,
+            _ => display_page * per_page_u64,
+        };
+        let range_start = if documents.is_empty() { 0 } else { page_offset + 1 };
+        let range_end = page_offset + documents.len() as u64;
+        let range_end = if documents.is_empty() { 0 } else { range_end };
 
         if self.filter_state.is_none() {
             let filter_state = cx.new(|cx| {
@@ -508,14 +531,22 @@ impl Render for CollectionView {
                             }
                             view.sort_error = false;
                             if let Some(session_key) = view.view_model.current_session()
-                                && let (Some(sort_state), Some(projection_state)) =
-                                    (view.sort_state.clone(), view.projection_state.clone())
+                                && let (
+                                    Some(sort_state),
+                                    Some(projection_state),
+                                    Some(options_state),
+                                ) = (
+                                    view.sort_state.clone(),
+                                    view.projection_state.clone(),
+                                    view.options_state.clone(),
+                                )
                             {
                                 CollectionView::apply_query_options(
                                     view.state.clone(),
                                     session_key,
                                     sort_state,
                                     projection_state,
+                                    options_state,
                                     window,
                                     cx,
                                 );
@@ -552,75 +583,162 @@ impl Render for CollectionView {
                 )));
                 state
             });
-            let subscription = cx.subscribe_in(
-                &projection_state,
-                window,
-                move |view, state, event, window, cx| match event {
-                    InputEvent::Change => {
-                        if view.syncing_query_inputs {
-                            return;
+            let subscription =
+                cx.subscribe_in(
+                    &projection_state,
+                    window,
+                    move |view, state, event, window, cx| match event {
+                        InputEvent::Change => {
+                            if view.syncing_query_inputs {
+                                return;
+                            }
+                            let (current_text, cursor) = {
+                                let input = state.read(cx);
+                                (input.value().to_string(), input.cursor())
+                            };
+                            let in_string_or_comment =
+                                is_query_input_in_string_or_comment(&current_text, cursor);
+                            if view.projection_auto_pair.try_auto_pair(
+                                state,
+                                in_string_or_comment,
+                                window,
+                                cx,
+                            ) {
+                                return;
+                            }
+                            let raw = state.read(cx).value().to_string();
+                            view.projection_auto_pair.sync(&raw);
+                            let next_error = query_validation_error(&raw).is_some();
+                            if view.projection_error != next_error {
+                                view.projection_error = next_error;
+                                cx.notify();
+                            } else {
+                                view.projection_error = next_error;
+                            }
                         }
-                        let (current_text, cursor) = {
-                            let input = state.read(cx);
-                            (input.value().to_string(), input.cursor())
-                        };
-                        let in_string_or_comment =
-                            is_query_input_in_string_or_comment(&current_text, cursor);
-                        if view.projection_auto_pair.try_auto_pair(
-                            state,
-                            in_string_or_comment,
-                            window,
-                            cx,
-                        ) {
-                            return;
+                        InputEvent::PressEnter { .. } => {
+                            let raw = state.read(cx).value().to_string();
+                            if !is_valid_query(&raw) {
+                                view.projection_error = true;
+                                cx.notify();
+                                return;
+                            }
+                            view.projection_error = false;
+                            if let Some(session_key) = view.view_model.current_session()
+                                && let (
+                                    Some(sort_state),
+                                    Some(projection_state),
+                                    Some(options_state),
+                                ) = (
+                                    view.sort_state.clone(),
+                                    view.projection_state.clone(),
+                                    view.options_state.clone(),
+                                )
+                            {
+                                CollectionView::apply_query_options(
+                                    view.state.clone(),
+                                    session_key,
+                                    sort_state,
+                                    projection_state,
+                                    options_state,
+                                    window,
+                                    cx,
+                                );
+                            }
                         }
-                        let raw = state.read(cx).value().to_string();
-                        view.projection_auto_pair.sync(&raw);
-                        let next_error = query_validation_error(&raw).is_some();
-                        if view.projection_error != next_error {
-                            view.projection_error = next_error;
-                            cx.notify();
-                        } else {
-                            view.projection_error = next_error;
+                        InputEvent::Blur => {
+                            let current = state.read(cx).value().to_string();
+                            if current.trim().is_empty() {
+                                view.projection_auto_pair.sync("");
+                            }
                         }
-                    }
-                    InputEvent::PressEnter { .. } => {
-                        let raw = state.read(cx).value().to_string();
-                        if !is_valid_query(&raw) {
-                            view.projection_error = true;
-                            cx.notify();
-                            return;
+                        InputEvent::Focus => {
+                            state.update(cx, |input, cx| {
+                                move_cursor_inside_query_object(input, window, cx)
+                            });
                         }
-                        view.projection_error = false;
-                        if let Some(session_key) = view.view_model.current_session()
-                            && let (Some(sort_state), Some(projection_state)) =
-                                (view.sort_state.clone(), view.projection_state.clone())
-                        {
-                            CollectionView::apply_query_options(
-                                view.state.clone(),
-                                session_key,
-                                sort_state,
-                                projection_state,
+                    },
+                );
+            self.projection_state = Some(projection_state);
+            self.projection_subscription = Some(subscription);
+        }
+
+        if self.options_state.is_none() {
+            let options_state = cx.new(|cx| {
+                InputState::new(window, cx)
+                    .code_editor("javascript")
+                    .multi_line(false)
+                    .submit_on_enter(true)
+                    .placeholder("options { hint, collation, maxTimeMS, readPreference, comment }")
+                    .clean_on_escape()
+            });
+            let subscription =
+                cx.subscribe_in(&options_state, window, move |view, state, event, window, cx| {
+                    match event {
+                        InputEvent::Change => {
+                            if view.syncing_query_inputs {
+                                return;
+                            }
+                            let (current_text, cursor) = {
+                                let input = state.read(cx);
+                                (input.value().to_string(), input.cursor())
+                            };
+                            let in_string_or_comment =
+                                is_query_input_in_string_or_comment(&current_text, cursor);
+                            if view.options_auto_pair.try_auto_pair(
+                                state,
+                                in_string_or_comment,
                                 window,
                                 cx,
-                            );
+                            ) {
+                                return;
+                            }
+                            let raw = state.read(cx).value().to_string();
+                            view.options_auto_pair.sync(&raw);
+                            let next_error = query_validation_error(&raw).is_some();
+                            if view.options_error != next_error {
+                                view.options_error = next_error;
+                                cx.notify();
+                            }
                         }
-                    }
-                    InputEvent::Blur => {
-                        let current = state.read(cx).value().to_string();
-                        if current.trim().is_empty() {
-                            view.projection_auto_pair.sync("");
+                        InputEvent::PressEnter { .. } => {
+                            let raw = state.read(cx).value().to_string();
+                            if !is_valid_query(&raw) {
+                                view.options_error = true;
+                                cx.notify();
+                                return;
+                            }
+                            view.options_error = false;
+                            if let Some(session_key) = view.view_model.current_session()
+                                && let (Some(sort_state), Some(projection_state)) =
+                                    (view.sort_state.clone(), view.projection_state.clone())
+                            {
+                                CollectionView::apply_query_options(
+                                    view.state.clone(),
+                                    session_key,
+                                    sort_state,
+                                    projection_state,
+                                    state.clone(),
+                                    window,
+                                    cx,
+                                );
+                            }
+                        }
+                        InputEvent::Blur => {
+                            let current = state.read(cx).value().to_string();
+                            if current.trim().is_empty() {
+                                view.options_auto_pair.sync("");
+                            }
+                        }
+                        InputEvent::Focus => {
+                            state.update(cx, |input, cx| {
+                                move_cursor_inside_query_object(input, window, cx)
+                            });
                         }
                     }
-                    InputEvent::Focus => {
-                        state.update(cx, |input, cx| {
-                            move_cursor_inside_query_object(input, window, cx)
-                        });
-                    }
-                },
-            );
-            self.projection_state = Some(projection_state);
-            self.projection_subscription = Some(subscription);
+                });
+            self.options_state = Some(options_state);
+            self.options_subscription = Some(subscription);
         }
 
         if self.schema_filter_state.is_none() {
@@ -695,6 +813,14 @@ impl Render for CollectionView {
                 self.projection_auto_pair.sync(&val);
                 self.projection_error = query_validation_error(&val).is_some();
             }
+            if let Some(options_state) = self.options_state.clone() {
+                let val = query_options_raw.clone();
+                options_state.update(cx, |state, cx| {
+                    state.set_value(val.clone(), window, cx);
+                });
+                self.options_auto_pair.sync(&val);
+                self.options_error = query_validation_error(&val).is_some();
+            }
             self.syncing_query_inputs = false;
         } else if let Some(filter_state) = self.filter_state.clone() {
             // Sync filter input when filter_raw was changed externally (e.g. AI "Open Collection").
@@ -738,6 +864,7 @@ impl Render for CollectionView {
         let filter_state = self.filter_state.clone();
         let sort_state = self.sort_state.clone();
         let projection_state = self.projection_state.clone();
+        let options_state = self.options_state.clone();
         let schema_filter_state = self.schema_filter_state.clone();
 
         let per_page_i64 = per_page;
@@ -748,10 +875,12 @@ impl Render for CollectionView {
             CollectionSubview::Stats => key_context.push_str(" Stats"),
             CollectionSubview::Aggregation => key_context.push_str(" Aggregation"),
             CollectionSubview::Schema => key_context.push_str(" Schema"),
+            CollectionSubview::Validation => key_context.push_str(" Validation"),
             CollectionSubview::Documents => {}
         }
 
         let col_visibility_search = self.view_model.ensure_col_visibility_search(window, cx);
+        let history_search = self.view_model.ensure_history_search(window, cx);
 
         let mut root = div().key_context(key_context.as_str());
         root = self.bind_root_actions(root, cx);
@@ -773,10 +902,13 @@ impl Render for CollectionView {
                     filter_active,
                     sort_state,
                     projection_state,
+                    options_state,
                     sort_valid,
                     projection_valid,
+                    options_valid,
                     sort_active,
                     projection_active,
+                    options_active,
                     query_options_open,
                     filter_builder_open,
                     subview,
@@ -785,6 +917,7 @@ impl Render for CollectionView {
                     explain.loading,
                     schema_loading,
                     col_visibility_search,
+                    history_search,
                     window,
                     cx,
                 ))
@@ -859,6 +992,7 @@ impl Render for CollectionView {
             CollectionSubview::Documents => self.render_documents_subview(
                 &documents,
                 total,
+                paging,
                 display_page,
                 total_pages,
                 per_page_i64,
@@ -875,6 +1009,7 @@ impl Render for CollectionView {
                 indexes,
                 indexes_loading,
                 indexes_error,
+                index_usage,
                 session_key.clone(),
                 cx,
             ),
@@ -895,6 +1030,9 @@ impl Render for CollectionView {
                 session_key.clone(),
                 cx,
             ),
+            CollectionSubview::Validation => {
+                self.render_validation_view(validation, session_key.clone(), window, cx)
+            }
         };
 
         let explain_layer =
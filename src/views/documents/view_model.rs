@@ -49,6 +49,7 @@ pub struct DocumentViewModel {
     table_state: Option<Entity<TableState<DocumentTableDelegate>>>,
     table_generation: Option<u64>,
     col_visibility_search: Option<Entity<InputState>>,
+    history_search: Option<Entity<InputState>>,
     agg_table_state: Option<Entity<TableState<AggregationTableDelegate>>>,
     agg_table_generation: Option<u64>,
 }
@@ -73,6 +74,7 @@ impl DocumentViewModel {
             table_state: None,
             table_generation: None,
             col_visibility_search: None,
+            history_search: None,
             agg_table_state: None,
             agg_table_generation: None,
         }
@@ -778,4 +780,17 @@ impl DocumentViewModel {
         self.col_visibility_search = Some(state.clone());
         state
     }
+
+    pub fn ensure_history_search(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<CollectionView>,
+    ) -> Entity<InputState> {
+        if let Some(ref state) = self.history_search {
+            return state.clone();
+        }
+        let state = cx.new(|cx| InputState::new(window, cx).placeholder("Search history..."));
+        self.history_search = Some(state.clone());
+        state
+    }
 }
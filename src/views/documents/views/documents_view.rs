@@ -8,21 +8,26 @@ use gpui_component::{Icon, IconName, Sizable as _};
 
 use crate::bson::DocumentKey;
 use crate::components::Button;
-use crate::state::{DocumentViewMode, SessionDocument, SessionKey};
+use crate::connection::{DocumentCount, PaginationMode};
+use crate::state::{AppCommands, DocumentViewMode, PagingSnapshot, SessionDocument, SessionKey};
 use crate::theme::spacing;
 
 use super::super::CollectionView;
 use super::super::tree::lazy_tree::collect_all_expandable_nodes;
 use super::super::tree::tree_content::{SearchOptions, render_tree_row};
 
+/// Rows from the end of the tree at which stream mode reads the next batch.
+const STREAM_PREFETCH_ROWS: usize = 20;
+
 impl CollectionView {
     #[allow(clippy::too_many_arguments)]
     pub(in crate::views::documents) fn render_documents_subview(
         &mut self,
         documents: &[SessionDocument],
-        total: u64,
+        total: DocumentCount,
+        paging: PagingSnapshot,
         display_page: u64,
-        total_pages: u64,
+        total_pages: Option<u64>,
         per_page: i64,
         range_start: u64,
         range_end: u64,
@@ -41,6 +46,7 @@ impl CollectionView {
             return self.render_table_subview(
                 documents,
                 total,
+                paging,
                 display_page,
                 total_pages,
                 per_page,
@@ -450,8 +456,26 @@ impl CollectionView {
                                 };
                                 let current_match_id = current_match_id.clone();
                                 let documents_focus = self.documents_focus.clone();
+                                let can_stream = paging.mode == PaginationMode::Stream
+                                    && paging.has_more
+                                    && !paging.stream_fetching;
 
                                 move |ix, entry, selected, _window, cx| {
+                                    // Only visible rows render, so a row near the
+                                    // end means the viewport is close to it.
+                                    if can_stream
+                                        && ix + STREAM_PREFETCH_ROWS >= tree_order.len()
+                                        && let Some(session_key) = session_key.clone()
+                                    {
+                                        let state = state_clone.clone();
+                                        cx.defer(move |cx| {
+                                            AppCommands::load_more_documents(
+                                                state,
+                                                session_key,
+                                                cx,
+                                            );
+                                        });
+                                    }
                                     render_tree_row(
                                         ix,
                                         entry,
@@ -488,6 +512,7 @@ impl CollectionView {
                     range_start,
                     range_end,
                     total,
+                    paging,
                     is_loading,
                     session_key.clone(),
                     self.state.clone(),
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use gpui::*;
 use gpui_component::Sizable as _;
 use gpui_component::scroll::ScrollableElement;
@@ -9,11 +11,18 @@ use gpui_component::ActiveTheme as _;
 
 use crate::bson::bson_value_preview;
 use crate::components::{Button, open_confirm_dialog};
+use crate::connection::ops::index_usage::flag_indexes;
+use crate::connection::{IndexBuild, IndexChange, IndexFlag, IndexUsage};
+use crate::helpers::{format_bytes, format_number};
+use crate::state::commands::suggestions_from_history;
 use crate::state::{AppCommands, SessionKey};
 use crate::theme::spacing;
 
 use super::super::CollectionView;
 use super::super::dialogs::index_create::IndexCreateDialog;
+use super::super::dialogs::index_ttl::open_index_ttl_dialog;
+
+const ACTIONS_WIDTH: f32 = 300.0;
 
 impl CollectionView {
     pub(in crate::views::documents) fn render_indexes_view(
@@ -21,6 +30,7 @@ impl CollectionView {
         indexes: Option<Vec<IndexModel>>,
         indexes_loading: bool,
         indexes_error: Option<String>,
+        index_usage: Option<HashMap<String, IndexUsage>>,
         session_key: Option<SessionKey>,
         cx: &App,
     ) -> AnyElement {
@@ -134,14 +144,29 @@ impl CollectionView {
             .child(
                 div().w(px(200.0)).text_xs().text_color(cx.theme().muted_foreground).child("Flags"),
             )
+            .child(
+                div().w(px(160.0)).text_xs().text_color(cx.theme().muted_foreground).child("Usage"),
+            )
+            .child(
+                div().w(px(90.0)).text_xs().text_color(cx.theme().muted_foreground).child("Size"),
+            )
             .child(
                 div()
-                    .w(px(140.0))
+                    .w(px(ACTIONS_WIDTH))
                     .text_xs()
                     .text_color(cx.theme().muted_foreground)
                     .child("Actions"),
             );
 
+        let findings = flag_indexes(&indexes, index_usage.as_ref());
+        let summary = render_findings_summary(&findings, index_usage.is_some(), cx);
+        let suggestions = self.render_history_suggestions(&indexes, session_key.clone(), cx);
+        let builds = session_key
+            .as_ref()
+            .and_then(|key| self.state.read(cx).session_data(key))
+            .map(|data| data.index_builds.clone())
+            .unwrap_or_default();
+
         let rows = indexes
             .into_iter()
             .enumerate()
@@ -150,8 +175,20 @@ impl CollectionView {
                 let name_label = name.clone().unwrap_or_else(|| "Unnamed".to_string());
                 let keys_label = index_keys_preview(&model.keys);
                 let flags_label = index_flags(&model, &name_label);
+                let usage = index_usage.as_ref().and_then(|usage| usage.get(&name_label));
+                let usage_label = usage_preview(usage);
+                let size_label =
+                    usage.and_then(|usage| usage.size_bytes).map_or("—".to_string(), format_bytes);
+                let finding_labels = findings
+                    .get(&name_label)
+                    .map(|flags| flags.iter().map(|flag| flag.label()).collect::<Vec<_>>())
+                    .unwrap_or_default();
                 let can_drop = name.as_ref().is_some_and(|n| n != "_id_");
                 let can_edit = can_drop && name.is_some();
+                let options = model.options.clone().unwrap_or_default();
+                let hidden = options.hidden.unwrap_or(false);
+                let ttl_seconds = options.expire_after.map(|expire| expire.as_secs());
+                let can_make_unique = can_drop && !options.unique.unwrap_or(false);
 
                 let state = self.state.clone();
                 let session_key = session_key.clone();
@@ -185,17 +222,37 @@ impl CollectionView {
                     )
                     .child(
                         div()
+                            .flex()
+                            .flex_col()
                             .w(px(200.0))
                             .text_sm()
                             .text_color(cx.theme().muted_foreground)
-                            .child(flags_label),
+                            .child(flags_label)
+                            .children(finding_labels.into_iter().map(|label| {
+                                div().text_xs().text_color(cx.theme().warning).child(label)
+                            })),
                     )
                     .child(
                         div()
-                            .w(px(140.0))
+                            .w(px(160.0))
+                            .text_sm()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(usage_label),
+                    )
+                    .child(
+                        div()
+                            .w(px(90.0))
+                            .text_sm()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(size_label),
+                    )
+                    .child(
+                        div()
+                            .w(px(ACTIONS_WIDTH))
                             .child(
                                 div()
                                     .flex()
+                                    .flex_wrap()
                                     .items_center()
                                     .gap(spacing::xs())
                                     .child(
@@ -222,6 +279,107 @@ impl CollectionView {
                                                 }
                                             }),
                                     )
+                                    .child(
+                                        Button::new(("hide-index", index))
+                                            .ghost()
+                                            .compact()
+                                            .label(if hidden { "Unhide" } else { "Hide" })
+                                            .disabled(!can_drop || session_key.is_none())
+                                            .on_click({
+                                                let state = state.clone();
+                                                let session_key = session_key.clone();
+                                                let name = drop_name.clone();
+                                                move |_: &ClickEvent, _window: &mut Window, cx: &mut App| {
+                                                    let (Some(session_key), Some(name)) =
+                                                        (session_key.clone(), name.clone())
+                                                    else {
+                                                        return;
+                                                    };
+                                                    let change = if hidden {
+                                                        IndexChange::Unhide
+                                                    } else {
+                                                        IndexChange::Hide
+                                                    };
+                                                    AppCommands::modify_collection_index(
+                                                        state.clone(),
+                                                        session_key,
+                                                        name,
+                                                        change,
+                                                        cx,
+                                                    );
+                                                }
+                                            }),
+                                    )
+                                    .children(ttl_seconds.map(|current| {
+                                        Button::new(("ttl-index", index))
+                                            .ghost()
+                                            .compact()
+                                            .label("TTL")
+                                            .disabled(session_key.is_none())
+                                            .on_click({
+                                                let state = state.clone();
+                                                let session_key = session_key.clone();
+                                                let name = drop_name.clone();
+                                                move |_: &ClickEvent, window: &mut Window, cx: &mut App| {
+                                                    let (Some(session_key), Some(name)) =
+                                                        (session_key.clone(), name.clone())
+                                                    else {
+                                                        return;
+                                                    };
+                                                    open_index_ttl_dialog(
+                                                        state.clone(),
+                                                        session_key,
+                                                        name,
+                                                        current,
+                                                        window,
+                                                        cx,
+                                                    );
+                                                }
+                                            })
+                                    }))
+                                    .children(can_make_unique.then(|| {
+                                        Button::new(("unique-index", index))
+                                            .ghost()
+                                            .compact()
+                                            .label("Make unique")
+                                            .disabled(session_key.is_none())
+                                            .on_click({
+                                                let state = state.clone();
+                                                let session_key = session_key.clone();
+                                                let name = drop_name.clone();
+                                                move |_: &ClickEvent, window: &mut Window, cx: &mut App| {
+                                                    let (Some(session_key), Some(name)) =
+                                                        (session_key.clone(), name.clone())
+                                                    else {
+                                                        return;
+                                                    };
+                                                    let message = format!(
+                                                        "Make {name} unique? New duplicate keys are rejected from now on; \
+                                                         the conversion fails if duplicates already exist."
+                                                    );
+                                                    open_confirm_dialog(
+                                                        window,
+                                                        cx,
+                                                        "Make index unique",
+                                                        message,
+                                                        "Make unique",
+                                                        false,
+                                                        {
+                                                            let state = state.clone();
+                                                            move |_window, cx| {
+                                                                AppCommands::modify_collection_index(
+                                                                    state.clone(),
+                                                                    session_key,
+                                                                    name,
+                                                                    IndexChange::ConvertToUnique,
+                                                                    cx,
+                                                                );
+                                                            }
+                                                        },
+                                                    );
+                                                }
+                                            })
+                                    }))
                                     .child(
                                         Button::new(("drop-index", index))
                                             .danger()
@@ -276,11 +434,107 @@ impl CollectionView {
             .collect::<Vec<_>>();
 
         content = content
+            .children(summary)
+            .children(render_index_builds(&builds, cx))
+            .children(suggestions)
             .child(header_row)
             .child(div().flex().flex_1().min_w(px(0.0)).overflow_y_scrollbar().children(rows));
 
         content.into_any_element()
     }
+
+    /// Indexes proposed from this collection's query history that no existing
+    /// index serves, slowest first.
+    fn render_history_suggestions(
+        &self,
+        indexes: &[IndexModel],
+        session_key: Option<SessionKey>,
+        cx: &App,
+    ) -> Option<Div> {
+        let key = session_key.as_ref()?;
+        let entries = self.state.read(cx).query_history.entries.iter().filter(|entry| {
+            entry.connection_id == key.connection_id
+                && entry.database == key.database
+                && entry.collection == key.collection
+        });
+        let suggestions = suggestions_from_history(entries, indexes);
+        if suggestions.is_empty() {
+            return None;
+        }
+
+        let rows = suggestions.into_iter().enumerate().map(|(index, item)| {
+            let stats = format!(
+                "{} {} · {} ms total",
+                item.queries,
+                if item.queries == 1 { "query" } else { "queries" },
+                format_number(item.total_ms)
+            );
+            div()
+                .flex()
+                .items_center()
+                .gap(spacing::sm())
+                .child(
+                    div()
+                        .flex()
+                        .flex_1()
+                        .min_w(px(0.0))
+                        .text_sm()
+                        .text_color(cx.theme().secondary_foreground)
+                        .child(index_keys_preview(&item.suggestion.keys)),
+                )
+                .child(
+                    div()
+                        .w(px(280.0))
+                        .text_xs()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(item.suggestion.rationale()),
+                )
+                .child(
+                    div()
+                        .w(px(160.0))
+                        .text_xs()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(stats),
+                )
+                .child(
+                    Button::new(("create-suggested-index", index))
+                        .ghost()
+                        .compact()
+                        .label("Create")
+                        .on_click({
+                            let state = self.state.clone();
+                            let session_key = key.clone();
+                            let index = item.suggestion.index_document();
+                            move |_: &ClickEvent, _window: &mut Window, cx: &mut App| {
+                                AppCommands::create_collection_index(
+                                    state.clone(),
+                                    session_key.clone(),
+                                    index.clone(),
+                                    cx,
+                                );
+                            }
+                        }),
+                )
+        });
+
+        Some(
+            div()
+                .flex()
+                .flex_col()
+                .gap(spacing::xs())
+                .px(spacing::lg())
+                .py(spacing::sm())
+                .border_b_1()
+                .border_color(cx.theme().border)
+                .child(
+                    div()
+                        .text_xs()
+                        .text_color(cx.theme().muted_foreground)
+                        .child("Suggested from recent queries"),
+                )
+                .children(rows),
+        )
+    }
 }
 
 fn index_name(model: &IndexModel) -> Option<String> {
@@ -322,3 +576,101 @@ fn index_flags(model: &IndexModel, name: &str) -> String {
 
     if flags.is_empty() { "—".to_string() } else { flags.join(", ") }
 }
+
+fn usage_preview(usage: Option<&IndexUsage>) -> String {
+    let Some(ops) = usage.and_then(|usage| usage.ops) else {
+        return "—".to_string();
+    };
+    let since = usage
+        .and_then(|usage| usage.since)
+        .and_then(|since| since.try_to_rfc3339_string().ok())
+        .and_then(|since| since.get(..10).map(str::to_string));
+    match since {
+        Some(since) => format!("{} ops since {since}", format_number(ops)),
+        None => format!("{} ops", format_number(ops)),
+    }
+}
+
+/// Progress of index builds running on the collection.
+fn render_index_builds(builds: &[IndexBuild], cx: &App) -> Option<Div> {
+    if builds.is_empty() {
+        return None;
+    }
+    let lines = builds.iter().map(|build| {
+        let mut parts = vec![format!("Building {}", build.indexes.join(", "))];
+        if let (Some(progress), Some(done), Some(total)) =
+            (build.progress(), build.done, build.total)
+        {
+            parts.push(format!(
+                "{:.0}% ({} / {})",
+                progress * 100.0,
+                format_number(done),
+                format_number(total)
+            ));
+        }
+        parts.extend(build.message.clone());
+        if let Some(secs) = build.secs_running {
+            parts.push(format!("{secs}s"));
+        }
+        div()
+            .flex()
+            .items_center()
+            .gap(spacing::sm())
+            .child(Spinner::new().small())
+            .child(div().text_xs().text_color(cx.theme().foreground).child(parts.join(" · ")))
+    });
+    Some(
+        div()
+            .flex()
+            .flex_col()
+            .gap(spacing::xs())
+            .px(spacing::lg())
+            .py(spacing::xs())
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .children(lines),
+    )
+}
+
+/// One line above the table counting flagged indexes, or noting that usage
+/// statistics could not be read.
+fn render_findings_summary(
+    findings: &HashMap<String, Vec<IndexFlag>>,
+    usage_available: bool,
+    cx: &App,
+) -> Option<Div> {
+    let count = |matches: fn(&IndexFlag) -> bool| {
+        findings.values().filter(|flags| flags.iter().any(matches)).count()
+    };
+    let mut parts = Vec::new();
+    for (label, n) in [
+        ("unused", count(|flag| matches!(flag, IndexFlag::Unused))),
+        ("redundant", count(|flag| matches!(flag, IndexFlag::Redundant { .. }))),
+        ("duplicate", count(|flag| matches!(flag, IndexFlag::Duplicate { .. }))),
+    ] {
+        if n > 0 {
+            parts.push(format!("{n} {label}"));
+        }
+    }
+    let mut lines = Vec::new();
+    if !parts.is_empty() {
+        lines.push(format!("Review: {}.", parts.join(", ")));
+    }
+    if !usage_available {
+        lines.push("Usage statistics unavailable.".to_string());
+    }
+    if lines.is_empty() {
+        return None;
+    }
+    let color = if findings.is_empty() { cx.theme().muted_foreground } else { cx.theme().warning };
+    Some(
+        div()
+            .px(spacing::lg())
+            .py(spacing::xs())
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .text_xs()
+            .text_color(color)
+            .child(lines.join(" ")),
+    )
+}
@@ -3,3 +3,4 @@ pub mod documents_view;
 pub mod indexes_view;
 pub mod schema_view;
 pub mod table_view;
+pub mod validation_view;
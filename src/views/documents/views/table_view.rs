@@ -2,7 +2,8 @@ use gpui::*;
 use gpui_component::table::Table;
 
 use crate::bson::DocumentKey;
-use crate::state::{SessionDocument, SessionKey};
+use crate::connection::DocumentCount;
+use crate::state::{PagingSnapshot, SessionDocument, SessionKey};
 
 use super::super::CollectionView;
 
@@ -11,9 +12,10 @@ impl CollectionView {
     pub(in crate::views::documents) fn render_table_subview(
         &mut self,
         _documents: &[SessionDocument],
-        total: u64,
+        total: DocumentCount,
+        paging: PagingSnapshot,
         display_page: u64,
-        total_pages: u64,
+        total_pages: Option<u64>,
         per_page: i64,
         range_start: u64,
         range_end: u64,
@@ -49,6 +51,7 @@ impl CollectionView {
                     range_start,
                     range_end,
                     total,
+                    paging,
                     is_loading,
                     session_key.clone(),
                     self.state.clone(),
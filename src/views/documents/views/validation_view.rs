@@ -0,0 +1,353 @@
+//! Validation rules view — validator editor, level/action and sample check.
+
+use gpui::*;
+use gpui_component::ActiveTheme as _;
+use gpui_component::Sizable as _;
+use gpui_component::input::{Input, InputEvent, InputState};
+use gpui_component::scroll::ScrollableElement as _;
+use gpui_component::spinner::Spinner;
+use mongodb::bson::{Bson, Document};
+use mongodb::options::{ValidationAction, ValidationLevel};
+
+use crate::bson::{bson_value_preview, truncate_for_preview};
+use crate::components::Button;
+use crate::connection::ops::validation::{
+    VIOLATION_SAMPLE_LIMIT, validation_action_name, validation_level_name,
+};
+use crate::connection::{ValidationCheck, ValidationRules};
+use crate::state::{AppCommands, AppState, SessionKey, ValidationState};
+use crate::theme::{fonts, spacing};
+use crate::views::documents::CollectionView;
+
+const LEVEL_CHOICES: [(ValidationLevel, &str); 3] = [
+    (ValidationLevel::Strict, "Strict"),
+    (ValidationLevel::Moderate, "Moderate"),
+    (ValidationLevel::Off, "Off"),
+];
+const ACTION_CHOICES: [(ValidationAction, &str); 2] =
+    [(ValidationAction::Error, "Error"), (ValidationAction::Warn, "Warn")];
+
+impl CollectionView {
+    pub(in crate::views::documents) fn render_validation_view(
+        &mut self,
+        validation: ValidationState,
+        session_key: Option<SessionKey>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        self.ensure_validation_editor(window, cx);
+        self.sync_validation_editor(&validation, session_key.clone(), window, cx);
+
+        let content = div()
+            .flex()
+            .flex_1()
+            .min_w(px(0.0))
+            .min_h(px(0.0))
+            .overflow_hidden()
+            .bg(cx.theme().background);
+
+        if validation.loading && validation.current.is_none() {
+            return content
+                .items_center()
+                .justify_center()
+                .gap(spacing::sm())
+                .child(Spinner::new().small())
+                .child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground)
+                        .child("Loading validation rules..."),
+                )
+                .into_any_element();
+        }
+
+        if let Some(error) = validation.error.clone() {
+            return content
+                .flex_col()
+                .items_center()
+                .justify_center()
+                .gap(spacing::sm())
+                .child(div().text_sm().text_color(cx.theme().danger_foreground).child(error))
+                .child(
+                    Button::new("retry-validation")
+                        .ghost()
+                        .compact()
+                        .label("Retry")
+                        .disabled(session_key.is_none())
+                        .on_click({
+                            let state = self.state.clone();
+                            let session_key = session_key.clone();
+                            move |_: &ClickEvent, _window: &mut Window, cx: &mut App| {
+                                let Some(session_key) = session_key.clone() else {
+                                    return;
+                                };
+                                AppCommands::load_collection_validation(
+                                    state.clone(),
+                                    session_key,
+                                    cx,
+                                );
+                            }
+                        }),
+                )
+                .into_any_element();
+        }
+
+        let state = self.state.clone();
+        let editor = self.validation_editor_state.clone().map(|editor| {
+            Input::new(&editor).font_family(fonts::mono()).h_full().w_full().into_any_element()
+        });
+
+        let draft_panel = div()
+            .flex()
+            .flex_col()
+            .flex_1()
+            .min_w(px(0.0))
+            .gap(spacing::sm())
+            .child(
+                div()
+                    .flex()
+                    .flex_wrap()
+                    .items_center()
+                    .gap(spacing::md())
+                    .child(labeled_row(
+                        "Level",
+                        choice_row(
+                            "validation-level",
+                            &LEVEL_CHOICES,
+                            &validation.draft_level,
+                            &state,
+                            session_key.clone(),
+                            AppState::set_validation_draft_level,
+                        ),
+                        cx,
+                    ))
+                    .child(labeled_row(
+                        "Action",
+                        choice_row(
+                            "validation-action",
+                            &ACTION_CHOICES,
+                            &validation.draft_action,
+                            &state,
+                            session_key.clone(),
+                            AppState::set_validation_draft_action,
+                        ),
+                        cx,
+                    )),
+            )
+            .child(div().flex().flex_1().min_h(px(0.0)).children(editor))
+            .child(
+                div()
+                    .text_xs()
+                    .text_color(cx.theme().muted_foreground)
+                    .child("An empty validator removes validation from the collection."),
+            );
+
+        let side_panel = div()
+            .flex()
+            .flex_col()
+            .w(px(360.0))
+            .min_h(px(0.0))
+            .gap(spacing::md())
+            .overflow_y_scrollbar()
+            .child(render_current_rules(validation.current.as_ref(), cx))
+            .child(render_check_results(&validation, cx));
+
+        content
+            .p(spacing::lg())
+            .gap(spacing::lg())
+            .child(draft_panel)
+            .child(side_panel)
+            .into_any_element()
+    }
+
+    fn ensure_validation_editor(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.validation_editor_state.is_some() {
+            return;
+        }
+        let editor = cx.new(|cx| {
+            InputState::new(window, cx)
+                .code_editor("javascript")
+                .line_number(true)
+                .searchable(true)
+                .soft_wrap(true)
+                .placeholder("{ \"$jsonSchema\": { ... } }")
+        });
+        let subscription =
+            cx.subscribe_in(&editor, window, move |view, state, event, _window, cx| match event {
+                InputEvent::Change => {
+                    if view.validation_ignore_change {
+                        view.validation_ignore_change = false;
+                        return;
+                    }
+                    let Some(session_key) = view.view_model.current_session() else {
+                        return;
+                    };
+                    let raw = state.read(cx).value().to_string();
+                    view.state.update(cx, |state, _cx| {
+                        state.set_validation_draft(&session_key, raw);
+                    });
+                }
+                InputEvent::PressEnter { secondary: true } => {
+                    let Some(session_key) = view.view_model.current_session() else {
+                        return;
+                    };
+                    AppCommands::check_validation_draft(view.state.clone(), session_key, cx);
+                }
+                _ => {}
+            });
+        self.validation_editor_state = Some(editor);
+        self.validation_editor_subscription = Some(subscription);
+    }
+
+    /// Push the draft into the editor when the session changes or the draft
+    /// is replaced from state (load, reset, schema starter).
+    fn sync_validation_editor(
+        &mut self,
+        validation: &ValidationState,
+        session_key: Option<SessionKey>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.validation_input_session == session_key
+            && self.validation_draft_revision == validation.draft_revision
+        {
+            return;
+        }
+        self.validation_input_session = session_key;
+        self.validation_draft_revision = validation.draft_revision;
+        let Some(editor) = self.validation_editor_state.clone() else {
+            return;
+        };
+        if editor.read(cx).value().as_ref() != validation.draft_raw {
+            self.validation_ignore_change = true;
+            let raw = validation.draft_raw.clone();
+            editor.update(cx, |state, cx| state.set_value(raw, window, cx));
+        }
+    }
+}
+
+fn labeled_row(label: &'static str, child: impl IntoElement, cx: &App) -> Div {
+    div()
+        .flex()
+        .items_center()
+        .gap(spacing::sm())
+        .child(div().text_sm().text_color(cx.theme().muted_foreground).child(label))
+        .child(child)
+}
+
+/// A row of compact buttons with the selected one highlighted.
+fn choice_row<T: Clone + PartialEq + 'static>(
+    id: &'static str,
+    choices: &[(T, &'static str)],
+    selected: &T,
+    state: &Entity<AppState>,
+    session_key: Option<SessionKey>,
+    apply: fn(&mut AppState, &SessionKey, T),
+) -> Div {
+    div().flex().gap(spacing::xs()).children(choices.iter().enumerate().map(
+        |(index, (value, label))| {
+            let base = Button::new((id, index))
+                .compact()
+                .label(*label)
+                .disabled(session_key.is_none())
+                .on_click({
+                    let state = state.clone();
+                    let session_key = session_key.clone();
+                    let value = value.clone();
+                    move |_: &ClickEvent, _window: &mut Window, cx: &mut App| {
+                        let Some(session_key) = session_key.clone() else {
+                            return;
+                        };
+                        state.update(cx, |state, cx| {
+                            apply(state, &session_key, value.clone());
+                            cx.notify();
+                        });
+                    }
+                });
+            if value == selected { base.primary() } else { base.ghost() }
+        },
+    ))
+}
+
+fn section(title: &'static str, cx: &App) -> Div {
+    div()
+        .flex()
+        .flex_col()
+        .gap(spacing::xs())
+        .p(spacing::md())
+        .rounded(px(6.0))
+        .border_1()
+        .border_color(cx.theme().border)
+        .child(div().text_sm().font_weight(FontWeight::SEMIBOLD).child(title))
+}
+
+fn muted_line(text: impl Into<SharedString>, cx: &App) -> Div {
+    div().text_xs().text_color(cx.theme().muted_foreground).child(text.into())
+}
+
+fn render_current_rules(current: Option<&ValidationRules>, cx: &App) -> Div {
+    let current = current.cloned().unwrap_or_default();
+    let validator = match &current.validator {
+        Some(validator) => {
+            let keys: Vec<&str> = validator.keys().map(String::as_str).collect();
+            format!("Validator: {}", keys.join(", "))
+        }
+        None => "No validator".to_string(),
+    };
+    let level = current.level.as_ref().map_or("strict", validation_level_name);
+    let action = current.action.as_ref().map_or("error", validation_action_name);
+    section("On the server", cx)
+        .child(muted_line(validator, cx))
+        .child(muted_line(format!("Level: {level}"), cx))
+        .child(muted_line(format!("Action: {action}"), cx))
+}
+
+fn render_check_results(validation: &ValidationState, cx: &App) -> Div {
+    let section = section("Sample check", cx);
+    if validation.checking {
+        return section.child(
+            div()
+                .flex()
+                .items_center()
+                .gap(spacing::sm())
+                .child(Spinner::new().small())
+                .child(muted_line("Checking sampled documents...", cx)),
+        );
+    }
+    if let Some(error) = &validation.check_error {
+        return section
+            .child(div().text_xs().text_color(cx.theme().danger_foreground).child(error.clone()));
+    }
+    let Some(check) = &validation.check else {
+        return section
+            .child(muted_line("Run Check Sample to list documents the draft would reject.", cx));
+    };
+    render_violations(section, check, cx)
+}
+
+fn render_violations(section: Div, check: &ValidationCheck, cx: &App) -> Div {
+    let summary = format!(
+        "{} of {} sampled documents violate the draft",
+        check.violation_count, check.sampled
+    );
+    let mut section = section.child(muted_line(summary, cx));
+    if check.violation_count > VIOLATION_SAMPLE_LIMIT as u64 {
+        section =
+            section.child(muted_line(format!("Showing the first {VIOLATION_SAMPLE_LIMIT}."), cx));
+    }
+    section.children(check.violations.iter().map(|document| violation_row(document, cx)))
+}
+
+fn violation_row(document: &Document, cx: &App) -> Div {
+    let id =
+        document.get("_id").map_or_else(|| "(no _id)".to_string(), |id| bson_value_preview(id, 40));
+    let body = Bson::Document(document.clone()).into_relaxed_extjson().to_string();
+    div()
+        .flex()
+        .flex_col()
+        .py(spacing::xs())
+        .border_t_1()
+        .border_color(cx.theme().border)
+        .child(div().text_xs().font_family(fonts::mono()).child(id))
+        .child(muted_line(truncate_for_preview(&body, 120), cx).font_family(fonts::mono()))
+}
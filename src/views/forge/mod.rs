@@ -26,7 +26,8 @@ use gpui_component::tab::{Tab, TabBar};
 use gpui_component::{Icon, IconName, Sizable};
 
 use crate::components::Button;
-use crate::state::{AppEvent, AppState, View};
+use crate::components::saved_query_dialog::open_save_query_dialog;
+use crate::state::{AppEvent, AppState, SavedQueryBody, View};
 use crate::theme::{fonts, islands, spacing};
 use controller::ForgeController;
 use output::format_result_tab_label;
@@ -83,20 +84,47 @@ impl ForgeView {
             (db, conn_name)
         };
 
-        div().flex().items_center().justify_between().px(spacing::md()).py(spacing::sm()).child(
-            div()
-                .flex()
-                .items_center()
-                .gap(spacing::xs())
-                .child(
-                    div()
-                        .text_sm()
-                        .font_weight(FontWeight::MEDIUM)
-                        .text_color(cx.theme().foreground)
-                        .child("Forge"),
-                )
-                .child(div().text_xs().text_color(cx.theme().muted_foreground).child(database)),
-        )
+        let app_state = self.app_state.clone();
+        let save_button = Button::new("forge-save-query")
+            .compact()
+            .ghost()
+            .icon(Icon::new(IconName::Star).xsmall())
+            .label("Save")
+            .tooltip("Save script to the query library")
+            .on_click(move |_, window, cx| {
+                let script = {
+                    let state = app_state.read(cx);
+                    state
+                        .active_forge_tab_id()
+                        .and_then(|id| state.forge_tab_content(id))
+                        .unwrap_or_default()
+                        .to_string()
+                };
+                let body = SavedQueryBody::Forge { script };
+                open_save_query_dialog(app_state.clone(), body, None, window, cx);
+            });
+
+        div()
+            .flex()
+            .items_center()
+            .justify_between()
+            .px(spacing::md())
+            .py(spacing::sm())
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap(spacing::xs())
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_weight(FontWeight::MEDIUM)
+                            .text_color(cx.theme().foreground)
+                            .child("Forge"),
+                    )
+                    .child(div().text_xs().text_color(cx.theme().muted_foreground).child(database)),
+            )
+            .child(save_button)
     }
 
     fn render_output(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
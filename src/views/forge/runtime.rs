@@ -40,7 +40,7 @@ impl ForgeRuntime {
 
 pub fn active_forge_session_info(state: &AppState) -> Option<(Uuid, String, String)> {
     let key = state.active_forge_tab_key()?.clone();
-    let uri = state.connection_uri(key.connection_id)?;
+    let uri = state.tool_connection_uri(key.connection_id)?;
     Some((key.id, uri, key.database))
 }
 
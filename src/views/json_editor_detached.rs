@@ -5,22 +5,25 @@ use gpui::*;
 use gpui_component::ActiveTheme as _;
 use gpui_component::Root;
 use gpui_component::input::{Input, InputEvent, InputState};
+use gpui_component::scroll::ScrollableElement as _;
 use mongodb::bson::{Bson, Document, oid::ObjectId};
 
 use crate::bson::{
-    DocumentKey, document_to_shell_string, format_relaxed_json_value, parse_bson_from_relaxed_json,
-    parse_document_from_json, parse_value_from_relaxed_json,
+    DocumentKey, FieldChange, bson_value_preview, document_to_shell_string,
+    format_relaxed_json_value, parse_bson_from_relaxed_json, parse_document_from_json,
+    parse_value_from_relaxed_json, three_way_merge, three_way_rows, truncate_for_preview,
 };
 use crate::components::Button;
 use crate::keyboard::CloseEditorWindow;
 use crate::state::{
     AppCommands, AppEvent, AppState, EditorSessionId, EditorSessionStore, EditorSessionTarget,
-    SessionKey,
+    SaveConflict, SessionKey,
 };
 use crate::theme::{fonts, islands, spacing};
 
 const DETACHED_WINDOW_WIDTH: f32 = 980.0;
 const DETACHED_WINDOW_HEIGHT: f32 = 760.0;
+const CONFLICT_VALUE_PREVIEW_LEN: usize = 60;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum SyncIssue {
@@ -87,6 +90,22 @@ impl DetachedJsonEditorView {
                         cx.notify();
                     }
                 }
+                AppEvent::DocumentSaveConflict {
+                    session: conflict_session,
+                    document,
+                    conflict,
+                } if session.session_key == *conflict_session
+                    && matches!(
+                        session.target,
+                        EditorSessionTarget::Document {
+                            doc_key: ref tab_doc_key,
+                            ..
+                        } if tab_doc_key == document
+                    ) =>
+                {
+                    this.show_conflict((**conflict).clone());
+                    cx.notify();
+                }
                 AppEvent::DocumentInserted => {
                     if matches!(session.target, EditorSessionTarget::Insert)
                         || this.awaiting_create_as_new
@@ -113,7 +132,7 @@ impl DetachedJsonEditorView {
                 _ => {}
             }
         }));
-        Self {
+        let mut view = Self {
             state,
             sessions,
             session_id,
@@ -124,7 +143,13 @@ impl DetachedJsonEditorView {
             pending_editor_content: None,
             awaiting_create_as_new: false,
             _subscriptions: subscriptions,
+        };
+        if let Some(conflict) =
+            view.sessions.snapshot(session_id).and_then(|session| session.conflict)
+        {
+            view.show_conflict(conflict);
         }
+        view
     }
 
     fn ensure_editor_state(&mut self, window: &mut Window, cx: &mut Context<Self>) {
@@ -195,6 +220,23 @@ impl DetachedJsonEditorView {
         self.sync_issue = None;
     }
 
+    fn show_conflict(&mut self, conflict: SaveConflict) {
+        self.awaiting_create_as_new = false;
+        if conflict.current.is_none() {
+            self.sessions.set_conflict(self.session_id, None);
+            self.set_sync_issue(
+                SyncIssue::MissingOriginal,
+                "Original document was deleted. Reload or create current JSON as new.",
+            );
+            return;
+        }
+        self.sessions.set_conflict(self.session_id, Some(conflict));
+        self.set_sync_issue(
+            SyncIssue::ConflictChanged,
+            "Document changed on the server since it was loaded. Keep your version, use the server's, or merge.",
+        );
+    }
+
     fn format_json(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let Some(editor_state) = self.editor_state.clone() else {
             return;
@@ -256,10 +298,9 @@ impl DetachedJsonEditorView {
                     return;
                 }
 
-                self.check_latest_and_save(
+                self.save_against_baseline(
                     session.session_key,
                     doc_key,
-                    original_id,
                     baseline_document,
                     document,
                     cx,
@@ -268,11 +309,12 @@ impl DetachedJsonEditorView {
         }
     }
 
-    fn check_latest_and_save(
+    /// Save through the concurrency check: the server refuses the write if
+    /// the document no longer matches `baseline_document`.
+    fn save_against_baseline(
         &mut self,
         session_key: SessionKey,
         doc_key: DocumentKey,
-        original_id: Bson,
         baseline_document: Document,
         updated_document: Document,
         cx: &mut Context<Self>,
@@ -288,72 +330,114 @@ impl DetachedJsonEditorView {
             return;
         }
 
-        let (Some(client), manager) = ({
-            let state_ref = self.state.read(cx);
-            (
-                state_ref.active_connection_client(session_key.connection_id),
-                state_ref.connection_manager(),
-            )
-        }) else {
-            self.set_error("Connection is no longer active.");
-            return;
-        };
-
         self.awaiting_create_as_new = false;
-        self.set_notice(false, "Checking latest document...");
+        self.clear_sync_issue();
+        self.set_notice(false, "Saving...");
+        AppCommands::save_document_against(
+            self.state.clone(),
+            session_key,
+            doc_key,
+            Some(baseline_document),
+            updated_document,
+            cx,
+        );
+    }
 
-        let database = session_key.database.clone();
-        let collection = session_key.collection.clone();
-        let id_for_task = original_id.clone();
-        let task = cx.background_spawn(async move {
-            manager.find_document_by_id(&client, &database, &collection, &id_for_task)
+    /// Take the server copy as the new baseline and put `content` in the
+    /// editor. The inline draft the conflict came from is dropped, since the
+    /// editor now holds the edit.
+    fn resolve_conflict(
+        &mut self,
+        conflict: SaveConflict,
+        content: &Document,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        let Some(session) = self.sessions.snapshot(self.session_id) else {
+            self.set_error("Editor session is no longer available.");
+            return false;
+        };
+        let (EditorSessionTarget::Document { doc_key, .. }, Some(current)) =
+            (session.target, conflict.current)
+        else {
+            return false;
+        };
+
+        self.state.update(cx, |state, cx| {
+            if state.session_draft(&session.session_key, &doc_key).as_ref() == Some(&conflict.draft)
+            {
+                state.clear_draft(&session.session_key, &doc_key);
+                cx.notify();
+            }
         });
 
-        cx.spawn({
-            let session_key = session_key.clone();
-            let doc_key = doc_key.clone();
-            let updated_document = updated_document.clone();
-            async move |view: WeakEntity<Self>, cx: &mut AsyncApp| {
-                let result: Result<Option<Document>, crate::error::Error> = task.await;
-                let _ = cx.update(|cx| {
-                    let _ = view.update(cx, |this, cx| match result {
-                        Ok(Some(current)) => {
-                            if current != baseline_document {
-                                this.set_sync_issue(
-                                    SyncIssue::ConflictChanged,
-                                    "Document changed on server. Reload to review latest version before saving.",
-                                );
-                                cx.notify();
-                                return;
-                            }
+        let text = document_to_shell_string(content);
+        self.sessions.refresh_document_baseline(self.session_id, current);
+        self.sessions.set_conflict(self.session_id, None);
+        self.sessions.update_content(self.session_id, text.clone());
+        self.pending_editor_content = Some(text);
+        self.clear_sync_issue();
+        true
+    }
 
-                            this.clear_sync_issue();
-                            this.set_notice(false, "Saving...");
-                            AppCommands::save_document(
-                                this.state.clone(),
-                                session_key.clone(),
-                                doc_key.clone(),
-                                updated_document.clone(),
-                                cx,
-                            );
-                        }
-                        Ok(None) => {
-                            this.set_sync_issue(
-                                SyncIssue::MissingOriginal,
-                                "Original document was deleted. Reload or create current JSON as new.",
-                            );
-                        }
-                        Err(err) => {
-                            this.set_sync_issue(
-                                SyncIssue::ReloadFailed,
-                                format!("Failed to reload latest document: {err}"),
-                            );
-                        }
-                    });
-                });
+    fn keep_mine(&mut self, cx: &mut Context<Self>) {
+        let Some(editor_state) = self.editor_state.clone() else {
+            return;
+        };
+        let Some(conflict) = self.sessions.snapshot(self.session_id).and_then(|s| s.conflict)
+        else {
+            return;
+        };
+        let raw = editor_state.read(cx).value().to_string();
+        let document = match parse_document_from_json(&raw) {
+            Ok(document) => document,
+            Err(err) => {
+                self.set_error(format!("Invalid JSON: {err}"));
+                return;
             }
-        })
-        .detach();
+        };
+        if self.resolve_conflict(conflict, &document, cx) {
+            self.save_or_insert(cx);
+        }
+        cx.notify();
+    }
+
+    fn use_server(&mut self, cx: &mut Context<Self>) {
+        let Some(conflict) = self.sessions.snapshot(self.session_id).and_then(|s| s.conflict)
+        else {
+            return;
+        };
+        let Some(current) = conflict.current.clone() else {
+            return;
+        };
+        if self.resolve_conflict(conflict, &current, cx) {
+            self.set_notice(false, "Loaded the server version; your changes were discarded.");
+        }
+        cx.notify();
+    }
+
+    fn merge_conflict(&mut self, cx: &mut Context<Self>) {
+        let Some(conflict) = self.sessions.snapshot(self.session_id).and_then(|s| s.conflict)
+        else {
+            return;
+        };
+        let Some(current) = conflict.current.as_ref() else {
+            return;
+        };
+        let (merged, conflicting) = three_way_merge(&conflict.loaded, &conflict.draft, current);
+        if self.resolve_conflict(conflict, &merged, cx) {
+            if conflicting.is_empty() {
+                self.set_notice(false, "Merged server changes into your edit. Review and save.");
+            } else {
+                self.set_notice(
+                    false,
+                    format!(
+                        "Merged; kept your value for fields changed on both sides: {}. Review and save.",
+                        conflicting.join(", ")
+                    ),
+                );
+            }
+        }
+        cx.notify();
     }
 
     fn load_inline_draft(&mut self, cx: &mut Context<Self>) {
@@ -436,6 +520,7 @@ impl DetachedJsonEditorView {
                         let content = document_to_shell_string(&current);
                         this.sessions
                             .refresh_document_baseline(this.session_id, current.clone());
+                        this.sessions.set_conflict(this.session_id, None);
                         this.sessions.update_content(this.session_id, content.clone());
                         this.pending_editor_content = Some(content);
                         this.clear_sync_issue();
@@ -546,6 +631,7 @@ impl Render for DetachedJsonEditorView {
         };
         let notice = self.inline_notice.clone();
         let sync_issue = self.sync_issue;
+        let conflict = session.conflict.clone();
         let state_ref = self.state.read(cx);
         let appearance = state_ref.settings.appearance.clone();
         let vibrancy = state_ref.startup_vibrancy;
@@ -740,6 +826,9 @@ impl Render for DetachedJsonEditorView {
 
                 this.child(row)
             })
+            .when_some(conflict, |this, conflict| {
+                this.child(render_conflict_panel(view.clone(), &conflict, cx))
+            })
             .child(
                 div()
                     .flex()
@@ -752,6 +841,188 @@ impl Render for DetachedJsonEditorView {
     }
 }
 
+/// Field-by-field comparison of the loaded, edited and server copies, with
+/// the ways to resolve the conflict.
+fn render_conflict_panel(
+    view: Entity<DetachedJsonEditorView>,
+    conflict: &SaveConflict,
+    cx: &App,
+) -> AnyElement {
+    let Some(current) = conflict.current.as_ref() else {
+        return div().into_any_element();
+    };
+    let rows: Vec<_> = three_way_rows(&conflict.loaded, &conflict.draft, current)
+        .into_iter()
+        .filter(|row| row.change != FieldChange::Unchanged)
+        .collect();
+
+    let cell = |value: Option<&Bson>, highlight: Option<Hsla>| {
+        div()
+            .flex_1()
+            .min_w(px(0.0))
+            .truncate()
+            .font_family(fonts::mono())
+            .when_some(highlight, |this, color| this.text_color(color))
+            .child(conflict_value_label(value))
+    };
+
+    let header = div()
+        .flex()
+        .gap(spacing::sm())
+        .text_xs()
+        .font_weight(FontWeight::MEDIUM)
+        .text_color(cx.theme().muted_foreground)
+        .child(div().w(px(140.0)).child("Field"))
+        .child(div().flex_1().child("Loaded"))
+        .child(div().flex_1().child("Yours"))
+        .child(div().flex_1().child("Server"));
+
+    let body = rows.iter().map(|row| {
+        let (draft_color, server_color) = match row.change {
+            FieldChange::Draft => (Some(cx.theme().primary), None),
+            FieldChange::Server => (None, Some(cx.theme().primary)),
+            FieldChange::Both => (Some(cx.theme().primary), Some(cx.theme().primary)),
+            FieldChange::Conflict => (Some(cx.theme().danger), Some(cx.theme().danger)),
+            FieldChange::Unchanged => (None, None),
+        };
+        div()
+            .flex()
+            .gap(spacing::sm())
+            .text_xs()
+            .child(div().w(px(140.0)).truncate().child(row.key.clone()))
+            .child(cell(row.loaded.as_ref(), Some(cx.theme().muted_foreground)))
+            .child(cell(row.draft.as_ref(), draft_color))
+            .child(cell(row.current.as_ref(), server_color))
+    });
+
+    div()
+        .mx(spacing::md())
+        .mt(px(6.0))
+        .p(spacing::sm())
+        .flex()
+        .flex_col()
+        .gap(spacing::xs())
+        .rounded(px(6.0))
+        .border_1()
+        .border_color(cx.theme().warning.opacity(0.45))
+        .bg(cx.theme().warning.opacity(0.09))
+        .child(
+            div()
+                .flex()
+                .items_center()
+                .justify_between()
+                .child(
+                    div()
+                        .text_xs()
+                        .font_weight(FontWeight::MEDIUM)
+                        .child(format!("{} field(s) differ", rows.len())),
+                )
+                .child(
+                    div()
+                        .flex()
+                        .gap(spacing::xs())
+                        .child(
+                            Button::new("json-editor-window-keep-mine")
+                                .compact()
+                                .label("Keep Mine")
+                                .on_click({
+                                    let view = view.clone();
+                                    move |_: &ClickEvent, _window: &mut Window, cx: &mut App| {
+                                        view.update(cx, |this, cx| this.keep_mine(cx));
+                                    }
+                                }),
+                        )
+                        .child(
+                            Button::new("json-editor-window-use-server")
+                                .compact()
+                                .label("Use Server")
+                                .on_click({
+                                    let view = view.clone();
+                                    move |_: &ClickEvent, _window: &mut Window, cx: &mut App| {
+                                        view.update(cx, |this, cx| this.use_server(cx));
+                                    }
+                                }),
+                        )
+                        .child(
+                            Button::new("json-editor-window-merge")
+                                .compact()
+                                .label("Merge")
+                                .on_click({
+                                    move |_: &ClickEvent, _window: &mut Window, cx: &mut App| {
+                                        view.update(cx, |this, cx| this.merge_conflict(cx));
+                                    }
+                                }),
+                        ),
+                ),
+        )
+        .child(header)
+        .child(
+            div()
+                .id("json-editor-window-conflict-rows")
+                .flex()
+                .flex_col()
+                .gap(px(2.0))
+                .max_h(px(180.0))
+                .overflow_y_scrollbar()
+                .children(body),
+        )
+        .into_any_element()
+}
+
+fn conflict_value_label(value: Option<&Bson>) -> String {
+    match value {
+        None => "(missing)".to_string(),
+        Some(value @ (Bson::Document(_) | Bson::Array(_))) => truncate_for_preview(
+            &value.clone().into_relaxed_extjson().to_string(),
+            CONFLICT_VALUE_PREVIEW_LEN,
+        ),
+        Some(value) => bson_value_preview(value, CONFLICT_VALUE_PREVIEW_LEN),
+    }
+}
+
+/// Open the editor on a draft whose save was refused, with the conflict
+/// ready for review.
+pub fn open_save_conflict_window(
+    state: Entity<AppState>,
+    session_key: SessionKey,
+    doc_key: DocumentKey,
+    conflict: SaveConflict,
+    cx: &mut App,
+) {
+    // An editor already open on this document shows the conflict itself; one
+    // on another document is left alone.
+    let sessions = state.read(cx).editor_sessions();
+    if let Some(existing_id) = sessions.find_document_session(&session_key, &doc_key) {
+        if focus_existing_window(&sessions, existing_id, cx) {
+            return;
+        }
+        sessions.close(existing_id);
+    }
+
+    let Some(original_id) = conflict.draft.get("_id").cloned() else {
+        log::warn!("Could not open conflict review: draft has no _id");
+        return;
+    };
+    let session_id = sessions.create_document_session(
+        session_key,
+        doc_key,
+        original_id,
+        conflict.loaded.clone(),
+        document_to_shell_string(&conflict.draft),
+    );
+    sessions.set_conflict(session_id, Some(conflict));
+    match open_detached_json_editor_window(state, sessions.clone(), session_id, cx) {
+        Ok(window) => {
+            focus_window_handle(&window, cx);
+            sessions.register_window(session_id, window.into());
+        }
+        Err(err) => {
+            sessions.close(session_id);
+            log::error!("Failed to open JSON editor window: {err}");
+        }
+    }
+}
+
 pub fn open_document_json_editor_window(
     state: Entity<AppState>,
     session_key: SessionKey,
@@ -769,7 +1040,7 @@ pub fn open_document_json_editor_window(
     });
 
     let sessions = state.read(cx).editor_sessions();
-    if let Some(existing_id) = sessions.find_any_document_session() {
+    if let Some(existing_id) = sessions.find_document_session(&session_key, &doc_key) {
         if focus_existing_window(&sessions, existing_id, cx) {
             return;
         }
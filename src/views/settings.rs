@@ -60,6 +60,8 @@ pub struct SettingsView {
     batch_size_input_state: Option<Entity<InputState>>,
     ai_api_key_input_state: Option<Entity<InputState>>,
     ai_ollama_base_url_input_state: Option<Entity<InputState>>,
+    version_field_input_state: Option<Entity<InputState>>,
+    stream_window_input_state: Option<Entity<InputState>>,
     ai_test_in_flight: bool,
     ai_test_result: Option<AiTestResult>,
     last_seen_provider: AiProvider,
@@ -86,6 +88,8 @@ impl SettingsView {
             batch_size_input_state: None,
             ai_api_key_input_state: None,
             ai_ollama_base_url_input_state: None,
+            version_field_input_state: None,
+            stream_window_input_state: None,
             ai_test_in_flight: false,
             ai_test_result: None,
             last_seen_provider,
@@ -98,6 +102,8 @@ impl SettingsView {
             && self.batch_size_input_state.is_some()
             && self.ai_api_key_input_state.is_some()
             && self.ai_ollama_base_url_input_state.is_some()
+            && self.version_field_input_state.is_some()
+            && self.stream_window_input_state.is_some()
         {
             return; // Already initialized
         }
@@ -106,6 +112,8 @@ impl SettingsView {
         let batch_size = self.state.read(cx).settings.transfer.default_batch_size;
         let ai_api_key = self.state.read(cx).settings.ai.api_key.clone();
         let ai_ollama_base_url = self.state.read(cx).settings.ai.ollama_base_url.clone();
+        let version_field = self.state.read(cx).settings.save_version_field.clone();
+        let stream_window = self.state.read(cx).settings.browser.stream_window;
 
         let template_input_state = cx.new(|cx| {
             let mut state = InputState::new(window, cx)
@@ -204,10 +212,58 @@ impl SettingsView {
         );
         self._subscriptions.push(ai_ollama_sub);
 
+        let version_field_input_state = cx.new(|cx| {
+            let mut state = InputState::new(window, cx).placeholder("Whole document");
+            state.set_value(version_field, window, cx);
+            state
+        });
+        let state_for_version_sub = self.state.clone();
+        let version_sub = cx.subscribe_in(
+            &version_field_input_state,
+            window,
+            move |_view, state, event, _window, cx| {
+                if let InputEvent::Change = event {
+                    let value = state.read(cx).value().trim().to_string();
+                    state_for_version_sub.update(cx, |app_state, cx| {
+                        app_state.settings.save_version_field = value;
+                        app_state.save_settings();
+                        cx.notify();
+                    });
+                }
+            },
+        );
+        self._subscriptions.push(version_sub);
+
+        let stream_window_input_state = cx.new(|cx| {
+            let mut state = InputState::new(window, cx).placeholder("5000").clean_on_escape();
+            state.set_value(stream_window.to_string(), window, cx);
+            state
+        });
+        let state_for_window_sub = self.state.clone();
+        let window_sub = cx.subscribe_in(
+            &stream_window_input_state,
+            window,
+            move |_view, state, event, _window, cx| {
+                if let InputEvent::Change = event {
+                    let new_text = state.read(cx).value().to_string();
+                    if let Ok(value) = new_text.parse::<u32>() {
+                        state_for_window_sub.update(cx, |app_state, cx| {
+                            app_state.settings.browser.stream_window = value.clamp(100, 1_000_000);
+                            app_state.save_settings();
+                            cx.notify();
+                        });
+                    }
+                }
+            },
+        );
+        self._subscriptions.push(window_sub);
+
         self.template_input_state = Some(template_input_state);
         self.batch_size_input_state = Some(batch_size_input_state);
         self.ai_api_key_input_state = Some(ai_api_key_input_state);
         self.ai_ollama_base_url_input_state = Some(ai_ollama_base_url_input_state);
+        self.version_field_input_state = Some(version_field_input_state);
+        self.stream_window_input_state = Some(stream_window_input_state);
     }
 
     fn sync_ai_inputs_from_settings(&self, window: &mut Window, cx: &mut App) {
@@ -315,6 +371,14 @@ impl Render for SettingsView {
                 .gap(spacing::lg())
                 .child(render_appearance_section(state.clone(), &settings, cx))
                 .child(render_updates_section(state.clone(), &settings, cx))
+                .child(render_saved_queries_section(state.clone(), &settings, cx))
+                .child(render_editing_section(
+                    state.clone(),
+                    &settings,
+                    self.version_field_input_state.clone().unwrap(),
+                    cx,
+                ))
+                .child(render_browser_section(self.stream_window_input_state.clone().unwrap(), cx))
                 .into_any_element(),
             SettingsSubtab::Transfer => div()
                 .flex()
@@ -561,6 +625,150 @@ fn render_updates_section(
     )
 }
 
+fn render_saved_queries_section(
+    state: Entity<AppState>,
+    settings: &AppSettings,
+    cx: &App,
+) -> impl IntoElement {
+    let is_default = settings.saved_queries_path.is_empty();
+    let path_display = if is_default {
+        "Default (config folder)".to_string()
+    } else {
+        settings.saved_queries_path.clone()
+    };
+
+    let state_for_browse = state.clone();
+    let browse_button = crate::components::Button::new("browse-saved-queries")
+        .compact()
+        .label("Browse...")
+        .on_click(move |_, _, cx| {
+            let state = state_for_browse.clone();
+            cx.spawn(async move |cx| {
+                if let Some(path) = crate::components::file_picker::open_file_dialog_async(
+                    crate::components::file_picker::FilePickerMode::Save,
+                    vec![crate::components::file_picker::FileFilter::json_array()],
+                    Some("saved_queries.json".to_string()),
+                )
+                .await
+                {
+                    cx.update(|cx| {
+                        state.update(cx, |state, cx| {
+                            state.set_saved_queries_path(path.display().to_string());
+                            cx.notify();
+                        });
+                    })
+                    .ok();
+                }
+            })
+            .detach();
+        });
+
+    let clear_button = (!is_default).then(|| {
+        crate::components::Button::new("clear-saved-queries")
+            .ghost()
+            .compact()
+            .label("Clear")
+            .on_click(move |_, _, cx| {
+                state.update(cx, |state, cx| {
+                    state.set_saved_queries_path(String::new());
+                    cx.notify();
+                });
+            })
+    });
+
+    let path_control = div()
+        .flex()
+        .items_center()
+        .gap(spacing::sm())
+        .child(
+            div()
+                .px(spacing::sm())
+                .py(px(6.0))
+                .bg(cx.theme().sidebar)
+                .border_1()
+                .border_color(cx.theme().sidebar_border)
+                .rounded(borders::radius_sm())
+                .text_sm()
+                .text_color(if is_default {
+                    cx.theme().muted_foreground
+                } else {
+                    cx.theme().foreground
+                })
+                .min_w(px(150.0))
+                .max_w(px(320.0))
+                .truncate()
+                .child(path_display),
+        )
+        .child(browse_button)
+        .children(clear_button);
+
+    section(
+        "Saved Queries",
+        div().flex().flex_col().gap(spacing::md()).child(setting_row_with_description(
+            "Library file",
+            "Point this at a JSON file in a shared git repository to use a team library",
+            path_control,
+            cx,
+        )),
+        cx,
+    )
+}
+
+fn render_editing_section(
+    state: Entity<AppState>,
+    settings: &AppSettings,
+    version_field_input_state: Entity<InputState>,
+    cx: &App,
+) -> impl IntoElement {
+    let transactional = settings.transactional_batches;
+    let transactions_checkbox = gpui_component::checkbox::Checkbox::new("transactional-batches")
+        .checked(transactional)
+        .on_click(move |_, _, cx| {
+            state.update(cx, |state, cx| {
+                state.settings.transactional_batches = !transactional;
+                state.save_settings();
+                cx.notify();
+            });
+        });
+
+    section(
+        "Editing",
+        div()
+            .flex()
+            .flex_col()
+            .gap(spacing::md())
+            .child(setting_row_with_description(
+                "Version field",
+                "Saves are refused when this field (or the whole document) changed on the server",
+                Input::new(&version_field_input_state).small().w(px(200.0)),
+                cx,
+            ))
+            .child(setting_row_with_description(
+                "Use transactions",
+                "Save several edited documents, or paste several, all or nothing on replica sets",
+                transactions_checkbox,
+                cx,
+            )),
+        cx,
+    )
+}
+
+fn render_browser_section(
+    stream_window_input_state: Entity<InputState>,
+    cx: &App,
+) -> impl IntoElement {
+    section(
+        "Documents",
+        div().flex().flex_col().gap(spacing::md()).child(setting_row_with_description(
+            "Stream window",
+            "Documents kept in memory with Stream pagination; older ones are dropped while scrolling",
+            NumberInput::new(&stream_window_input_state).small().w(px(100.0)),
+            cx,
+        )),
+        cx,
+    )
+}
+
 fn render_transfer_section(
     state: Entity<AppState>,
     settings: &AppSettings,
@@ -393,6 +393,147 @@ async fn test_replace_document() {
     assert!(doc.get("field2").is_none());
 }
 
+/// Test that of two saves from the same loaded copy, the second is a conflict.
+#[tokio::test]
+async fn test_replace_with_concurrency_check() {
+    use openmango::connection::{ConcurrencyCheck, ConnectionManager, SaveOutcome};
+
+    let mongo = MongoTestContainer::start().await;
+    let collection = mongo.collection::<Document>("test_db", "replace_concurrency");
+    let loaded = doc! { "_id": 1, "name": "original", "rev": 1, "a.b": "$x" };
+    let checks = [
+        ConcurrencyCheck::Document(loaded.clone()),
+        ConcurrencyCheck::Fields(doc! { "_id": 1, "a.b": "$x" }),
+        ConcurrencyCheck::for_loaded(&loaded, false, "rev"),
+    ];
+
+    for check in checks {
+        collection.delete_many(doc! {}).await.expect("Failed to reset");
+        collection.insert_one(loaded.clone()).await.expect("Failed to insert");
+
+        let db = mongo.db_name("test_db");
+        let client = mongo.client.clone();
+        let (first, second) = tokio::task::spawn_blocking({
+            let check = check.clone();
+            let loaded = loaded.clone();
+            move || {
+                let manager = ConnectionManager::new();
+                let mut save = |name: &str| {
+                    // Each editor edits its own copy of the same loaded document.
+                    let mut draft = loaded.clone();
+                    draft.insert("name", name);
+                    draft.insert("a.b", format!("${name}"));
+                    check.advance(&mut draft);
+                    manager
+                        .replace_document(
+                            &client,
+                            &db,
+                            "replace_concurrency",
+                            &1.into(),
+                            draft,
+                            Some(&check),
+                        )
+                        .expect("Failed to replace")
+                };
+                (save("first"), save("second"))
+            }
+        })
+        .await
+        .expect("blocking task panicked");
+
+        let current = collection.find_one(doc! { "_id": 1 }).await.expect("Failed to find");
+        assert_eq!(first, SaveOutcome::Saved, "{check:?}");
+        assert_eq!(second, SaveOutcome::Conflict(current.clone()), "{check:?}");
+        let current = current.expect("document should exist");
+        assert_eq!(current.get_str("name"), Ok("first"), "{check:?}");
+        if matches!(check, ConcurrencyCheck::Version { .. }) {
+            assert_eq!(current.get_i32("rev"), Ok(2));
+        }
+    }
+}
+
+/// Test that a dry-run pipeline predicts what update_many writes.
+#[tokio::test]
+async fn test_update_preview_matches_update_many() {
+    use openmango::connection::dry_run::update_pipeline;
+
+    let mongo = MongoTestContainer::start().await;
+    let collection = mongo.collection::<Document>("test_db", "update_preview");
+    let docs = vec![
+        doc! { "_id": 1, "qty": 2, "tags": ["a"], "stats": { "views": 1 }, "old": "x" },
+        doc! { "_id": 2, "qty": 7, "low": 3, "tags": ["a", "b"], "price": "$5" },
+    ];
+    collection.insert_many(docs).await.expect("Failed to insert");
+
+    let update = doc! {
+        "$set": { "price": "$9", "stats.clicks": 0 },
+        "$inc": { "stats.views": 2 },
+        "$min": { "low": 1 },
+        "$addToSet": { "tags": { "$each": ["b", "c"] } },
+        "$rename": { "old": "renamed" },
+        "$unset": { "qty": "" },
+    };
+    let mut pipeline = vec![doc! { "$sort": { "_id": 1 } }];
+    pipeline.extend(update_pipeline(&update).expect("Failed to build preview"));
+    let predicted: Vec<Document> = collection
+        .aggregate(pipeline)
+        .await
+        .expect("Failed to preview")
+        .try_collect()
+        .await
+        .expect("Failed to collect");
+
+    collection.update_many(doc! {}, update).await.expect("Failed to update");
+    let written: Vec<Document> = collection
+        .find(doc! {})
+        .sort(doc! { "_id": 1 })
+        .await
+        .expect("Failed to find")
+        .try_collect()
+        .await
+        .expect("Failed to collect");
+
+    assert_eq!(predicted, written);
+}
+
+/// Test that $pull and $pop previews leave a missing array field absent.
+#[tokio::test]
+async fn test_update_preview_pull_pop_missing_field() {
+    use openmango::connection::dry_run::update_pipeline;
+
+    let mongo = MongoTestContainer::start().await;
+    let collection = mongo.collection::<Document>("test_db", "update_preview_missing");
+    let docs = vec![
+        doc! { "_id": 1, "tags": ["a", "b"], "queue": [1, 2, 3] },
+        doc! { "_id": 2, "name": "no arrays" },
+    ];
+    collection.insert_many(docs).await.expect("Failed to insert");
+
+    let update = doc! { "$pull": { "tags": "a" }, "$pop": { "queue": -1 } };
+    let mut pipeline = vec![doc! { "$sort": { "_id": 1 } }];
+    pipeline.extend(update_pipeline(&update).expect("Failed to build preview"));
+    let predicted: Vec<Document> = collection
+        .aggregate(pipeline)
+        .await
+        .expect("Failed to preview")
+        .try_collect()
+        .await
+        .expect("Failed to collect");
+
+    collection.update_many(doc! {}, update).await.expect("Failed to update");
+    let written: Vec<Document> = collection
+        .find(doc! {})
+        .sort(doc! { "_id": 1 })
+        .await
+        .expect("Failed to find")
+        .try_collect()
+        .await
+        .expect("Failed to collect");
+
+    assert_eq!(predicted, written);
+    assert_eq!(written[1], doc! { "_id": 2, "name": "no arrays" });
+}
+
 /// Test that replace preserves _id.
 #[tokio::test]
 async fn test_replace_preserves_id() {
@@ -0,0 +1,223 @@
+//! Integration tests for collection options and validation rules using Testcontainers.
+
+mod common;
+
+use std::time::Duration;
+
+use common::MongoTestContainer;
+use mongodb::bson::{Document, doc};
+use mongodb::options::{
+    Collation, CollationStrength, TimeseriesGranularity, ValidationAction, ValidationLevel,
+};
+use mongodb::results::{CollectionSpecification, CollectionType};
+use openmango::connection::{
+    CollectionKind, ConnectionManager, CreateCollectionOptions, TimeSeriesSpec, ValidationRules,
+};
+
+/// Create `name` through the manager and return its listCollections entry.
+async fn create_and_describe(
+    mongo: &MongoTestContainer,
+    name: &'static str,
+    options: CreateCollectionOptions,
+) -> CollectionSpecification {
+    let client = mongo.client.clone();
+    let db = mongo.db_name("create_collection_db");
+    tokio::task::spawn_blocking(move || {
+        let manager = ConnectionManager::new();
+        manager.create_collection(&client, &db, name, &options).expect("create collection");
+        manager
+            .list_collection_specs(&client, &db)
+            .expect("list collections")
+            .into_iter()
+            .find(|spec| spec.name == name)
+            .expect("created collection is listed")
+    })
+    .await
+    .expect("blocking task")
+}
+
+#[tokio::test]
+async fn test_create_capped_collection() {
+    let mongo = MongoTestContainer::start().await;
+    let spec = create_and_describe(
+        &mongo,
+        "capped",
+        CreateCollectionOptions {
+            kind: CollectionKind::Capped { size_bytes: 8192, max_documents: Some(2) },
+            ..Default::default()
+        },
+    )
+    .await;
+    assert_eq!(spec.options.capped, Some(true));
+    assert_eq!(spec.options.max, Some(2));
+
+    let collection = mongo.collection::<Document>("create_collection_db", "capped");
+    for n in 0..3 {
+        collection.insert_one(doc! { "n": n }).await.expect("insert");
+    }
+    assert_eq!(collection.count_documents(doc! {}).await.expect("count"), 2);
+}
+
+#[tokio::test]
+async fn test_create_time_series_collection() {
+    let mongo = MongoTestContainer::start().await;
+    let spec = create_and_describe(
+        &mongo,
+        "readings",
+        CreateCollectionOptions {
+            kind: CollectionKind::TimeSeries(TimeSeriesSpec {
+                time_field: "ts".into(),
+                meta_field: Some("sensor".into()),
+                granularity: Some(TimeseriesGranularity::Minutes),
+                expire_after_seconds: Some(3600),
+            }),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert_eq!(spec.collection_type, CollectionType::Timeseries);
+    let timeseries = spec.options.timeseries.expect("timeseries options");
+    assert_eq!(timeseries.time_field, "ts");
+    assert_eq!(timeseries.meta_field.as_deref(), Some("sensor"));
+    assert_eq!(timeseries.granularity, Some(TimeseriesGranularity::Minutes));
+    assert_eq!(spec.options.expire_after_seconds, Some(Duration::from_secs(3600)));
+}
+
+#[tokio::test]
+async fn test_create_clustered_collection() {
+    let mongo = MongoTestContainer::start().await;
+    let spec = create_and_describe(
+        &mongo,
+        "clustered",
+        CreateCollectionOptions {
+            kind: CollectionKind::Clustered { expire_after_seconds: Some(60) },
+            ..Default::default()
+        },
+    )
+    .await;
+    let clustered = spec.options.clustered_index.expect("clustered index");
+    assert_eq!(clustered.key, doc! { "_id": 1 });
+    assert!(clustered.unique);
+    assert_eq!(spec.options.expire_after_seconds, Some(Duration::from_secs(60)));
+}
+
+#[tokio::test]
+async fn test_create_view() {
+    let mongo = MongoTestContainer::start().await;
+    let source = mongo.collection::<Document>("create_collection_db", "orders");
+    source
+        .insert_many(vec![doc! { "open": true }, doc! { "open": false }])
+        .await
+        .expect("seed orders");
+
+    let spec = create_and_describe(
+        &mongo,
+        "open_orders",
+        CreateCollectionOptions {
+            kind: CollectionKind::View {
+                view_on: "orders".into(),
+                pipeline: vec![doc! { "$match": { "open": true } }],
+            },
+            ..Default::default()
+        },
+    )
+    .await;
+    assert_eq!(spec.collection_type, CollectionType::View);
+    assert_eq!(spec.options.view_on.as_deref(), Some("orders"));
+
+    let view = mongo.collection::<Document>("create_collection_db", "open_orders");
+    assert_eq!(view.count_documents(doc! {}).await.expect("count view"), 1);
+}
+
+#[tokio::test]
+async fn test_create_collection_with_collation_and_validator() {
+    let mongo = MongoTestContainer::start().await;
+    let spec = create_and_describe(
+        &mongo,
+        "people",
+        CreateCollectionOptions {
+            collation: Some(
+                Collation::builder().locale("en").strength(CollationStrength::Secondary).build(),
+            ),
+            validator: Some(doc! { "name": { "$type": "string" } }),
+            validation_level: Some(ValidationLevel::Strict),
+            validation_action: Some(ValidationAction::Error),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert_eq!(spec.options.collation.map(|collation| collation.locale), Some("en".into()));
+    assert_eq!(spec.options.validator, Some(doc! { "name": { "$type": "string" } }));
+    assert_eq!(spec.options.validation_level, Some(ValidationLevel::Strict));
+    assert_eq!(spec.options.validation_action, Some(ValidationAction::Error));
+
+    let collection = mongo.collection::<Document>("create_collection_db", "people");
+    assert!(collection.insert_one(doc! { "name": 42 }).await.is_err());
+    collection.insert_one(doc! { "name": "Ada" }).await.expect("valid insert");
+    let found = collection.count_documents(doc! { "name": "ADA" }).await.expect("count");
+    assert_eq!(found, 1, "default collation is case-insensitive");
+}
+
+#[tokio::test]
+async fn test_create_collection_rejects_invalid_options() {
+    let mongo = MongoTestContainer::start().await;
+    let client = mongo.client.clone();
+    let db = mongo.db_name("create_collection_db");
+    let result = tokio::task::spawn_blocking(move || {
+        ConnectionManager::new().create_collection(
+            &client,
+            &db,
+            "bad",
+            &CreateCollectionOptions {
+                kind: CollectionKind::Capped { size_bytes: 0, max_documents: None },
+                ..Default::default()
+            },
+        )
+    })
+    .await
+    .expect("blocking task");
+    assert!(matches!(result, Err(openmango::error::Error::Parse(_))));
+}
+
+#[tokio::test]
+async fn test_validation_rules_check_and_apply() {
+    let mongo = MongoTestContainer::start().await;
+    let collection = mongo.collection::<Document>("validation_db", "people");
+    collection
+        .insert_many(vec![doc! { "name": "Ada" }, doc! { "name": 7 }, doc! { "age": 3 }])
+        .await
+        .expect("seed people");
+
+    let client = mongo.client.clone();
+    let db = mongo.db_name("validation_db");
+    tokio::task::spawn_blocking(move || {
+        let manager = ConnectionManager::new();
+        let rules = manager.collection_validation(&client, &db, "people").expect("read rules");
+        assert_eq!(rules, ValidationRules::default());
+
+        let validator = doc! { "$jsonSchema": {
+            "required": ["name"],
+            "properties": { "name": { "bsonType": "string" } },
+        } };
+        let check =
+            manager.check_validator(&client, &db, "people", &validator, 100).expect("check");
+        assert_eq!((check.sampled, check.violation_count), (3, 2));
+        assert_eq!(check.violations.len(), 2);
+
+        let rules = ValidationRules {
+            validator: Some(validator.clone()),
+            level: Some(ValidationLevel::Moderate),
+            action: Some(ValidationAction::Warn),
+        };
+        manager.apply_validation(&client, &db, "people", &rules).expect("apply rules");
+        assert_eq!(manager.collection_validation(&client, &db, "people").expect("reread"), rules);
+
+        manager
+            .apply_validation(&client, &db, "people", &ValidationRules::default())
+            .expect("clear rules");
+        let cleared = manager.collection_validation(&client, &db, "people").expect("reread");
+        assert_eq!(cleared.validator, None);
+    })
+    .await
+    .expect("blocking task");
+}
@@ -13,6 +13,8 @@
 #![allow(dead_code)]
 
 pub mod fixtures;
+pub mod replica_set;
+pub mod tls;
 pub mod transport;
 
 use mongodb::bson::{Document, doc};
@@ -0,0 +1,64 @@
+//! Single-node replica set for tests that need multi-document transactions.
+
+use anyhow::{Context, Result, bail};
+use mongodb::Client;
+use mongodb::bson::doc;
+use testcontainers::core::{CmdWaitFor, ExecCommand, IntoContainerPort, WaitFor};
+use testcontainers::runners::AsyncRunner;
+use testcontainers::{ContainerAsync, GenericImage, ImageExt};
+
+/// A mongod started with `--replSet` and initiated as a one-member set.
+pub struct ReplicaSetMongo {
+    _container: ContainerAsync<GenericImage>,
+    pub port: u16,
+}
+
+impl ReplicaSetMongo {
+    pub async fn start() -> Result<Self> {
+        let container = GenericImage::new("mongo", "7.0")
+            .with_exposed_port(27017.tcp())
+            .with_wait_for(WaitFor::message_on_stdout("Waiting for connections"))
+            .with_cmd(["mongod", "--bind_ip_all", "--replSet", "rs0"])
+            .start()
+            .await
+            .context("failed to start replica set mongo container")?;
+        container
+            .exec(
+                ExecCommand::new([
+                    "mongosh",
+                    "--quiet",
+                    "--eval",
+                    "rs.initiate({_id: 'rs0', members: [{_id: 0, host: 'localhost:27017'}]})",
+                ])
+                .with_cmd_ready_condition(CmdWaitFor::exit_code(0)),
+            )
+            .await
+            .context("failed to initiate replica set")?;
+        let port = container
+            .get_host_port_ipv4(27017.tcp())
+            .await
+            .context("failed to read replica set mongo mapped port")?;
+
+        let this = Self { _container: container, port };
+        this.wait_for_primary().await?;
+        Ok(this)
+    }
+
+    /// Direct connection, so the client ignores the member's in-container host.
+    pub fn uri(&self) -> String {
+        format!("mongodb://127.0.0.1:{}/?directConnection=true", self.port)
+    }
+
+    async fn wait_for_primary(&self) -> Result<()> {
+        let client = Client::with_uri_str(self.uri()).await?;
+        for _ in 0..100 {
+            if let Ok(hello) = client.database("admin").run_command(doc! { "hello": 1 }).await
+                && hello.get_bool("isWritablePrimary").unwrap_or(false)
+            {
+                return Ok(());
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        }
+        bail!("replica set member never became primary")
+    }
+}
@@ -0,0 +1,213 @@
+//! TLS integration test helpers: a throwaway PKI and a `requireTLS` mongod.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result, bail};
+use tempfile::TempDir;
+use testcontainers::core::{CmdWaitFor, ExecCommand, IntoContainerPort, WaitFor};
+use testcontainers::runners::AsyncRunner;
+use testcontainers::{ContainerAsync, GenericImage, ImageExt};
+
+use openmango::models::TlsConfig;
+
+/// Subject of the generated client certificate, as MongoDB renders it for `$external` users.
+pub const CLIENT_SUBJECT: &str = "CN=om-client,OU=clients,O=OpenMango";
+
+const CONTAINER_CERT_DIR: &str = "/etc/mongo-tls";
+
+/// CA, server and client certificates generated with the `openssl` CLI.
+pub struct TestPki {
+    dir: TempDir,
+}
+
+impl TestPki {
+    pub fn generate() -> Result<Self> {
+        let dir = TempDir::new().context("failed to create temp dir for TLS certificates")?;
+        let path = |name: &str| dir.path().join(name).to_string_lossy().to_string();
+
+        openssl(&[
+            "req",
+            "-x509",
+            "-newkey",
+            "rsa:2048",
+            "-nodes",
+            "-days",
+            "2",
+            "-subj",
+            "/CN=openmango-test-ca",
+            "-keyout",
+            &path("ca.key"),
+            "-out",
+            &path("ca.pem"),
+        ])?;
+
+        fs::write(
+            dir.path().join("server.ext"),
+            "subjectAltName=DNS:localhost,IP:127.0.0.1\nextendedKeyUsage=serverAuth\n",
+        )?;
+        issue(&dir, "server", "/CN=localhost/OU=servers/O=OpenMango", Some("server.ext"))?;
+
+        fs::write(dir.path().join("client.ext"), "extendedKeyUsage=clientAuth\n")?;
+        issue(&dir, "client", "/CN=om-client/OU=clients/O=OpenMango", Some("client.ext"))?;
+
+        Ok(Self { dir })
+    }
+
+    pub fn ca_file(&self) -> PathBuf {
+        self.dir.path().join("ca.pem")
+    }
+
+    /// Combined certificate + key PEM for the client.
+    pub fn client_pem(&self) -> PathBuf {
+        self.dir.path().join("client.pem")
+    }
+
+    fn server_pem(&self) -> PathBuf {
+        self.dir.path().join("server.pem")
+    }
+}
+
+/// Create `<name>.pem` (certificate followed by key) signed by the test CA.
+fn issue(dir: &TempDir, name: &str, subject: &str, extfile: Option<&str>) -> Result<()> {
+    let path = |file: String| dir.path().join(file).to_string_lossy().to_string();
+    let key = path(format!("{name}.key"));
+    let csr = path(format!("{name}.csr"));
+    let crt = path(format!("{name}.crt"));
+
+    openssl(&[
+        "req", "-newkey", "rsa:2048", "-nodes", "-subj", subject, "-keyout", &key, "-out", &csr,
+    ])?;
+
+    let ca = path("ca.pem".into());
+    let ca_key = path("ca.key".into());
+    let mut args = vec![
+        "x509",
+        "-req",
+        "-in",
+        &csr,
+        "-CA",
+        &ca,
+        "-CAkey",
+        &ca_key,
+        "-CAcreateserial",
+        "-days",
+        "2",
+        "-out",
+        &crt,
+    ];
+    let ext = extfile.map(|file| path(file.to_string()));
+    if let Some(ext) = ext.as_deref() {
+        args.extend(["-extfile", ext]);
+    }
+    openssl(&args)?;
+
+    let mut pem = fs::read_to_string(&crt)?;
+    pem.push_str(&fs::read_to_string(&key)?);
+    fs::write(dir.path().join(format!("{name}.pem")), pem)?;
+    Ok(())
+}
+
+fn openssl(args: &[&str]) -> Result<()> {
+    let output = Command::new("openssl")
+        .args(args)
+        .output()
+        .context("failed to spawn openssl (required for TLS integration tests)")?;
+    if !output.status.success() {
+        bail!(
+            "openssl {} failed: {}",
+            args.first().copied().unwrap_or_default(),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    Ok(())
+}
+
+/// A mongod started with `--tlsMode requireTLS` that trusts the test CA.
+pub struct TlsMongo {
+    container: ContainerAsync<GenericImage>,
+    pub pki: TestPki,
+    pub port: u16,
+}
+
+impl TlsMongo {
+    pub async fn start() -> Result<Self> {
+        let pki = TestPki::generate()?;
+        let server_pem = format!("{CONTAINER_CERT_DIR}/server.pem");
+        let ca_pem = format!("{CONTAINER_CERT_DIR}/ca.pem");
+
+        let container = GenericImage::new("mongo", "7.0")
+            .with_exposed_port(27017.tcp())
+            .with_wait_for(WaitFor::message_on_stdout("Waiting for connections"))
+            .with_copy_to(server_pem.as_str(), fs::read(pki.server_pem())?)
+            .with_copy_to(ca_pem.as_str(), fs::read(pki.ca_file())?)
+            .with_cmd([
+                "mongod",
+                "--bind_ip_all",
+                "--tlsMode",
+                "requireTLS",
+                "--tlsCertificateKeyFile",
+                server_pem.as_str(),
+                "--tlsCAFile",
+                ca_pem.as_str(),
+                "--tlsAllowConnectionsWithoutCertificates",
+            ])
+            .start()
+            .await
+            .context("failed to start requireTLS mongo container")?;
+        let port = container
+            .get_host_port_ipv4(27017.tcp())
+            .await
+            .context("failed to read TLS mongo mapped port")?;
+
+        Ok(Self { container, pki, port })
+    }
+
+    pub fn uri(&self) -> String {
+        format!("mongodb://localhost:{}/?directConnection=true", self.port)
+    }
+
+    /// CA trust plus client certificate, without X.509 authentication.
+    pub fn tls_config(&self) -> TlsConfig {
+        TlsConfig {
+            enabled: true,
+            ca_file: Some(path_string(&self.pki.ca_file())),
+            cert_key_file: Some(path_string(&self.pki.client_pem())),
+            ..TlsConfig::default()
+        }
+    }
+
+    /// Register the client certificate subject as a `$external` user.
+    pub async fn create_x509_user(&self) -> Result<()> {
+        let script = format!(
+            "db.getSiblingDB('$external').runCommand({{createUser: '{CLIENT_SUBJECT}', roles: [{{role: 'readAnyDatabase', db: 'admin'}}]}})"
+        );
+        let server_pem = format!("{CONTAINER_CERT_DIR}/server.pem");
+        let ca_pem = format!("{CONTAINER_CERT_DIR}/ca.pem");
+        self.container
+            .exec(
+                ExecCommand::new([
+                    "mongosh",
+                    "--quiet",
+                    "--tls",
+                    "--host",
+                    "localhost",
+                    "--tlsCAFile",
+                    ca_pem.as_str(),
+                    "--tlsCertificateKeyFile",
+                    server_pem.as_str(),
+                    "--eval",
+                    script.as_str(),
+                ])
+                .with_cmd_ready_condition(CmdWaitFor::exit_code(0)),
+            )
+            .await
+            .context("failed to create X.509 user in TLS mongo container")?;
+        Ok(())
+    }
+}
+
+fn path_string(path: &Path) -> String {
+    path.to_string_lossy().to_string()
+}
@@ -17,7 +17,7 @@ use testcontainers::{ContainerAsync, GenericImage, ImageExt};
 use testcontainers_modules::mongo::Mongo;
 use uuid::Uuid;
 
-use openmango::models::{SshAuth, SshConfig};
+use openmango::models::{SshAuth, SshConfig, SshJumpHost};
 
 pub const KNOWN_HOSTS_ENV: &str = "OPENMANGO_SSH_KNOWN_HOSTS_PATH";
 
@@ -192,6 +192,7 @@ impl TransportStack {
             identity_passphrase: None,
             strict_host_key_checking,
             local_bind_host: "127.0.0.1".to_string(),
+            jump_hosts: Vec::new(),
         }
     }
 
@@ -211,6 +212,27 @@ impl TransportStack {
             identity_passphrase: None,
             strict_host_key_checking,
             local_bind_host: "127.0.0.1".to_string(),
+            jump_hosts: Vec::new(),
+        }
+    }
+
+    /// Reach the sshd container through itself: the mapped port is the jump
+    /// host, and the final hop is the container's own sshd on loopback.
+    pub fn ssh_jump_config(&self, jump_password: &str) -> SshConfig {
+        let jump = SshJumpHost {
+            host: self.ssh_host.clone(),
+            port: self.ssh_port,
+            username: self.ssh_username.clone(),
+            auth: SshAuth::Password,
+            password: Some(jump_password.to_string()),
+            strict_host_key_checking: false,
+            ..SshJumpHost::default()
+        };
+        SshConfig {
+            host: "127.0.0.1".to_string(),
+            port: 22,
+            jump_hosts: vec![jump],
+            ..self.ssh_password_config(false)
         }
     }
 
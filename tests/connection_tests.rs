@@ -131,3 +131,32 @@ async fn test_collection_stats() {
     // Should have count
     assert_eq!(stats.get_i32("count").unwrap_or(0), 100);
 }
+
+/// Test the diagnostic report against a live standalone server.
+#[tokio::test]
+async fn test_diagnose_connection_reports_server_details() {
+    use openmango::connection::ConnectionManager;
+    use openmango::connection::diagnostics::{ProbeOutcome, TopologyKind};
+    use openmango::models::SavedConnection;
+
+    let mongo = MongoTestContainer::start().await;
+    let uri = mongo.connection_string.clone();
+
+    let report = tokio::task::spawn_blocking(move || {
+        let manager = ConnectionManager::new();
+        let saved = SavedConnection::new("diagnostics".to_string(), uri);
+        manager.diagnose_connection(&saved, std::time::Duration::from_secs(10), |_| {})
+    })
+    .await
+    .expect("blocking task panicked");
+
+    assert!(report.success, "diagnostics failed: {:?}", report.error);
+    assert!(report.dns.is_none());
+    assert!(
+        report.hosts.iter().all(|probe| matches!(probe.outcome, ProbeOutcome::Reachable { .. }))
+    );
+    let server = report.server.as_ref().expect("server details missing");
+    assert!(server.version.is_some());
+    assert_eq!(server.topology, TopologyKind::Standalone);
+    assert!(report.to_text().contains("Topology: standalone"));
+}
@@ -133,3 +133,277 @@ async fn test_complex_filter_queries() {
     let count = collection.count_documents(filter).await.expect("Failed to count");
     assert_eq!(count, 5); // indices 0,1,2,3,4
 }
+
+/// Test keyset paging walks a sorted collection without skip or an exact count.
+#[tokio::test]
+async fn test_find_documents_keyset_paging() {
+    use openmango::connection::keyset::{KeysetCursor, keyset_sort};
+    use openmango::connection::{
+        ConnectionManager, CountStrategy, DocumentCount, FindDocumentsOptions, QueryOptions,
+    };
+
+    let mongo = MongoTestContainer::start().await;
+    let collection = mongo.collection::<mongodb::bson::Document>("test_db", "keyset_collection");
+    let docs: Vec<_> = (0..5).map(|i| doc! { "_id": i, "group": i % 2 }).collect();
+    collection.insert_many(docs).await.expect("Failed to insert");
+
+    let db = mongo.db_name("test_db");
+    let client = mongo.client.clone();
+    let pages = tokio::task::spawn_blocking(move || {
+        let manager = ConnectionManager::new();
+        let sort = keyset_sort(Some(&doc! { "group": -1 }));
+        let mut keyset = None;
+        let mut pages = Vec::new();
+        loop {
+            let page = manager
+                .find_documents(
+                    &client,
+                    &db,
+                    "keyset_collection",
+                    FindDocumentsOptions {
+                        filter: None,
+                        sort: Some(sort.clone()),
+                        projection: Some(doc! { "_id": 1 }),
+                        skip: 0,
+                        limit: 2,
+                        count: CountStrategy::Lazy,
+                        keyset: keyset.take(),
+                        options: QueryOptions::default(),
+                    },
+                )
+                .expect("Failed to find page");
+            assert_eq!(page.count, DocumentCount::Unknown);
+            let ids: Vec<i32> =
+                page.documents.iter().map(|doc| doc.get_i32("_id").unwrap()).collect();
+            keyset = page.documents.last().map(|last| KeysetCursor::after(&sort, last));
+            pages.push(ids);
+            if !page.has_more {
+                return pages;
+            }
+        }
+    })
+    .await
+    .expect("blocking task panicked");
+
+    assert_eq!(pages, vec![vec![1, 3], vec![0, 2], vec![4]]);
+}
+
+/// Test keyset paging does not skip documents that lack the sort key.
+#[tokio::test]
+async fn test_find_documents_keyset_paging_sparse_key() {
+    use openmango::connection::keyset::{KeysetCursor, keyset_sort};
+    use openmango::connection::{
+        ConnectionManager, CountStrategy, FindDocumentsOptions, QueryOptions,
+    };
+
+    let mongo = MongoTestContainer::start().await;
+    let collection = mongo.collection::<mongodb::bson::Document>("test_db", "keyset_sparse");
+    let docs: Vec<_> = (0..6)
+        .map(|i| {
+            if i % 2 == 0 {
+                doc! { "_id": i, "rank": i }
+            } else {
+                doc! { "_id": i }
+            }
+        })
+        .collect();
+    collection.insert_many(docs).await.expect("Failed to insert");
+
+    let db = mongo.db_name("test_db");
+    let client = mongo.client.clone();
+    let walks = tokio::task::spawn_blocking(move || {
+        let manager = ConnectionManager::new();
+        [1, -1].map(|direction| {
+            let sort = keyset_sort(Some(&doc! { "rank": direction }));
+            let mut keyset = None;
+            let mut pages = Vec::new();
+            loop {
+                let page = manager
+                    .find_documents(
+                        &client,
+                        &db,
+                        "keyset_sparse",
+                        FindDocumentsOptions {
+                            filter: None,
+                            sort: Some(sort.clone()),
+                            projection: None,
+                            skip: 0,
+                            limit: 2,
+                            count: CountStrategy::Lazy,
+                            keyset: keyset.take(),
+                            options: QueryOptions::default(),
+                        },
+                    )
+                    .expect("Failed to find page");
+                let ids: Vec<i32> =
+                    page.documents.iter().map(|doc| doc.get_i32("_id").unwrap()).collect();
+                keyset = page.documents.last().map(|last| KeysetCursor::after(&sort, last));
+                pages.push(ids);
+                if !page.has_more {
+                    return pages;
+                }
+            }
+        })
+    })
+    .await
+    .expect("blocking task panicked");
+
+    assert_eq!(walks[0], vec![vec![1, 3], vec![5, 0], vec![2, 4]]);
+    assert_eq!(walks[1], vec![vec![4, 2], vec![0, 1], vec![3, 5]]);
+}
+
+/// Test a stream reads the whole collection in batches from one cursor.
+#[tokio::test]
+async fn test_document_stream_reads_batches() {
+    use openmango::connection::{
+        ConnectionManager, CountStrategy, DocumentCount, FindDocumentsOptions, QueryOptions,
+    };
+
+    let mongo = MongoTestContainer::start().await;
+    let collection = mongo.collection::<mongodb::bson::Document>("test_db", "stream_collection");
+    let docs: Vec<_> = (0..5).map(|i| doc! { "_id": i }).collect();
+    collection.insert_many(docs).await.expect("Failed to insert");
+
+    let client = mongo.client.clone();
+    let (count, batches) = tokio::task::spawn_blocking(move || {
+        let manager = ConnectionManager::new();
+        let (mut stream, count) = manager
+            .open_document_stream(
+                &client,
+                "test_db",
+                "stream_collection",
+                FindDocumentsOptions {
+                    filter: None,
+                    sort: Some(doc! { "_id": 1 }),
+                    projection: None,
+                    skip: 0,
+                    limit: 2,
+                    count: CountStrategy::Exact,
+                    keyset: None,
+                    options: QueryOptions::default(),
+                },
+            )
+            .expect("Failed to open stream");
+        let mut batches = Vec::new();
+        while !stream.is_exhausted() {
+            let batch = manager.next_stream_batch(&mut stream, 2).expect("Failed to read batch");
+            batches.push(batch.iter().map(|doc| doc.get_i32("_id").unwrap()).collect::<Vec<_>>());
+        }
+        (count, batches)
+    })
+    .await
+    .expect("blocking task panicked");
+
+    assert_eq!(count, DocumentCount::Exact(5));
+    assert_eq!(batches, vec![vec![0, 1], vec![2, 3], vec![4]]);
+}
+
+/// Test undo is refused, and writes nothing, once a document changed on the server.
+#[tokio::test]
+async fn test_undo_refused_after_server_change() {
+    use openmango::connection::{ConnectionManager, DocumentChange};
+    use openmango::error::Error as AppError;
+
+    let mongo = MongoTestContainer::start().await;
+    let collection = mongo.collection::<mongodb::bson::Document>("test_db", "undo_collection");
+    let docs: Vec<_> = (0..2).map(|i| doc! { "_id": i, "value": 0 }).collect();
+    collection.insert_many(docs).await.expect("Failed to insert");
+
+    let db = mongo.db_name("test_db");
+    let client = mongo.client.clone();
+    let (changes, result) = tokio::task::spawn_blocking(move || {
+        let manager = ConnectionManager::new();
+        let ids = vec![0.into(), 1.into()];
+        let before = manager
+            .find_documents_by_ids(&client, &db, "undo_collection", ids.clone())
+            .expect("Failed to read pre-images");
+        manager
+            .update_many(&client, &db, "undo_collection", doc! {}, doc! { "$set": { "value": 1 } })
+            .expect("Failed to update");
+        let after = manager
+            .find_documents_by_ids(&client, &db, "undo_collection", ids)
+            .expect("Failed to read post-images");
+        let changes = DocumentChange::diff(before, after);
+
+        // Someone else edits a document before the undo runs.
+        manager
+            .update_one(
+                &client,
+                &db,
+                "undo_collection",
+                doc! { "_id": 1 },
+                doc! { "$set": { "value": 99 } },
+            )
+            .expect("Failed to modify document");
+
+        let undo: Vec<_> = changes.iter().rev().map(DocumentChange::inverse).collect();
+        let result = manager.apply_document_changes(&client, &db, "undo_collection", undo);
+        (changes, result)
+    })
+    .await
+    .expect("blocking task panicked");
+
+    assert_eq!(changes.len(), 2);
+    assert!(matches!(result, Err(AppError::Conflict(_))), "got {result:?}");
+    let value = |id: i32| {
+        let collection = collection.clone();
+        async move {
+            collection
+                .find_one(doc! { "_id": id })
+                .await
+                .expect("Failed to find")
+                .and_then(|doc| doc.get_i32("value").ok())
+        }
+    };
+    assert_eq!(value(0).await, Some(1));
+    assert_eq!(value(1).await, Some(99));
+}
+
+/// Test a replay that fails partway on a standalone server reports what it wrote.
+#[tokio::test]
+async fn test_replay_reports_partial_write_without_transactions() {
+    use mongodb::IndexModel;
+    use mongodb::options::IndexOptions;
+    use openmango::connection::{ConnectionManager, DocumentChange, ReplayOutcome};
+    use openmango::error::Error as AppError;
+
+    let mongo = MongoTestContainer::start().await;
+    let collection = mongo.collection::<mongodb::bson::Document>("test_db", "replay_collection");
+    collection.insert_one(doc! { "_id": 1, "code": "a" }).await.expect("Failed to insert");
+    let unique = IndexOptions::builder().unique(true).build();
+    collection
+        .create_index(IndexModel::builder().keys(doc! { "code": 1 }).options(unique).build())
+        .await
+        .expect("Failed to create index");
+
+    let db = mongo.db_name("test_db");
+    let client = mongo.client.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let manager = ConnectionManager::new();
+        // The insert clashes with the first change on the unique index.
+        let changes = vec![
+            DocumentChange {
+                id: 1.into(),
+                before: Some(doc! { "_id": 1, "code": "a" }),
+                after: Some(doc! { "_id": 1, "code": "b" }),
+            },
+            DocumentChange {
+                id: 2.into(),
+                before: None,
+                after: Some(doc! { "_id": 2, "code": "b" }),
+            },
+        ];
+        manager.apply_document_changes(&client, &db, "replay_collection", changes)
+    })
+    .await
+    .expect("blocking task panicked");
+
+    match result {
+        Ok(ReplayOutcome::Partial { applied, error: AppError::Mongo(_) }) => {
+            assert_eq!(applied, vec![mongodb::bson::Bson::Int32(1)]);
+        }
+        other => panic!("expected a partial replay, got {other:?}"),
+    }
+    let current = collection.find_one(doc! { "_id": 1 }).await.expect("Failed to find");
+    assert_eq!(current, Some(doc! { "_id": 1, "code": "b" }));
+}
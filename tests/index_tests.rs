@@ -586,3 +586,235 @@ async fn test_create_many_indexes() {
         .expect("Failed to collect");
     assert_eq!(all_indexes.len(), 4); // _id_ + 3 new indexes
 }
+
+// =============================================================================
+// Index Usage Tests
+// =============================================================================
+
+/// Test reading usage counts and sizes, and flagging unused and covered indexes.
+#[tokio::test]
+async fn test_index_usage_and_flags() {
+    let mongo = MongoTestContainer::start().await;
+    let collection = mongo.collection::<Document>("test_db", "index_usage");
+    collection.insert_many(fixtures::generate_test_documents(20)).await.expect("Failed to insert");
+
+    let named = |keys: Document, name: &str| {
+        IndexModel::builder()
+            .keys(keys)
+            .options(mongodb::options::IndexOptions::builder().name(name.to_string()).build())
+            .build()
+    };
+    collection
+        .create_indexes(vec![
+            named(doc! { "category": 1 }, "category_1"),
+            named(doc! { "category": 1, "value": -1 }, "category_1_value_-1"),
+        ])
+        .await
+        .expect("Failed to create indexes");
+    collection
+        .find_one(doc! { "category": "a", "value": { "$gt": 0 } })
+        .hint(mongodb::options::Hint::Name("category_1_value_-1".to_string()))
+        .await
+        .expect("Failed to query");
+
+    let client = mongo.client.clone();
+    let db = mongo.db_name("test_db");
+    let (indexes, usage) = tokio::task::spawn_blocking(move || {
+        let manager = openmango::connection::ConnectionManager::new();
+        let indexes = manager.list_indexes(&client, &db, "index_usage").expect("list indexes");
+        let usage = manager.index_usage(&client, &db, "index_usage").expect("index usage");
+        (indexes, usage)
+    })
+    .await
+    .expect("blocking task");
+
+    assert_eq!(usage["category_1"].ops, Some(0));
+    assert!(usage["category_1_value_-1"].ops.unwrap_or(0) >= 1);
+    assert!(usage["category_1_value_-1"].since.is_some());
+    assert!(usage["_id_"].size_bytes.is_some_and(|size| size > 0));
+
+    let flags = openmango::connection::ops::index_usage::flag_indexes(&indexes, Some(&usage));
+    assert_eq!(
+        flags["category_1"],
+        vec![
+            openmango::connection::IndexFlag::Unused,
+            openmango::connection::IndexFlag::Redundant {
+                covered_by: "category_1_value_-1".to_string()
+            },
+        ]
+    );
+    assert!(!flags.contains_key("category_1_value_-1"));
+}
+
+/// Test same-key indexes with a different partial filter or collation are not duplicates.
+#[test]
+fn test_flag_duplicates_respect_selection() {
+    use mongodb::options::{Collation, IndexOptions};
+    use openmango::connection::IndexFlag;
+
+    let index = |name: &str, mut options: IndexOptions| {
+        options.name = Some(name.to_string());
+        IndexModel::builder().keys(doc! { "value": 1 }).options(options).build()
+    };
+    let indexes = vec![
+        index("value_1", IndexOptions::default()),
+        index(
+            "value_active",
+            IndexOptions::builder().partial_filter_expression(doc! { "active": true }).build(),
+        ),
+        index(
+            "value_fr",
+            IndexOptions::builder()
+                .collation(Collation::builder().locale("fr".to_string()).build())
+                .build(),
+        ),
+        index("value_copy", IndexOptions::default()),
+    ];
+
+    let flags = openmango::connection::ops::index_usage::flag_indexes(&indexes, None);
+    assert!(!flags.contains_key("value_active"));
+    assert!(!flags.contains_key("value_fr"));
+    assert_eq!(flags["value_copy"], vec![IndexFlag::Duplicate { of: "value_1".to_string() }]);
+}
+
+// =============================================================================
+// Index Modification Tests
+// =============================================================================
+
+/// Test hiding, changing the TTL of and converting an index to unique via collMod.
+#[tokio::test]
+async fn test_modify_index_with_collmod() {
+    use openmango::connection::IndexChange;
+
+    let mongo = MongoTestContainer::start().await;
+    let collection = mongo.collection::<Document>("test_db", "index_collmod");
+    collection.insert_many(fixtures::generate_test_documents(10)).await.expect("Failed to insert");
+    collection
+        .create_indexes(vec![
+            IndexModel::builder()
+                .keys(doc! { "index": 1 })
+                .options(
+                    mongodb::options::IndexOptions::builder().name("index_1".to_string()).build(),
+                )
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "created": 1 })
+                .options(
+                    mongodb::options::IndexOptions::builder()
+                        .name("created_1".to_string())
+                        .expire_after(std::time::Duration::from_secs(3600))
+                        .build(),
+                )
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "category": 1 })
+                .options(
+                    mongodb::options::IndexOptions::builder()
+                        .name("category_1".to_string())
+                        .build(),
+                )
+                .build(),
+        ])
+        .await
+        .expect("Failed to create indexes");
+
+    let client = mongo.client.clone();
+    let db = mongo.db_name("test_db");
+    let (indexes, unique_error, builds) = tokio::task::spawn_blocking(move || {
+        let manager = openmango::connection::ConnectionManager::new();
+        let coll = "index_collmod";
+        manager.modify_index(&client, &db, coll, "index_1", IndexChange::Hide).expect("hide");
+        manager
+            .modify_index(&client, &db, coll, "created_1", IndexChange::ExpireAfter(60))
+            .expect("change ttl");
+        manager
+            .modify_index(&client, &db, coll, "index_1", IndexChange::ConvertToUnique)
+            .expect("convert unique");
+        let unique_error = manager
+            .modify_index(&client, &db, coll, "category_1", IndexChange::ConvertToUnique)
+            .err();
+        let builds = manager.index_builds(&client, &db, coll).expect("index builds");
+        let indexes = manager.list_indexes(&client, &db, coll).expect("list indexes");
+        (indexes, unique_error, builds)
+    })
+    .await
+    .expect("blocking task");
+
+    let options = |name: &str| {
+        indexes
+            .iter()
+            .find(|index| index.options.as_ref().and_then(|o| o.name.as_deref()) == Some(name))
+            .and_then(|index| index.options.clone())
+            .expect("index present")
+    };
+    assert_eq!(options("index_1").hidden, Some(true));
+    assert_eq!(options("index_1").unique, Some(true));
+    assert_eq!(options("created_1").expire_after, Some(std::time::Duration::from_secs(60)));
+    let unique_error = unique_error.expect("category has duplicate values").to_string();
+    assert!(unique_error.contains("\"category\""), "duplicate keys listed: {unique_error}");
+    assert_ne!(options("category_1").unique, Some(true));
+    assert!(builds.is_empty());
+
+    // The refused conversion must not leave prepareUnique behind.
+    let listed = mongo
+        .database("test_db")
+        .run_command(doc! { "listIndexes": "index_collmod" })
+        .await
+        .expect("Failed to list raw indexes");
+    let category = listed
+        .get_document("cursor")
+        .and_then(|cursor| cursor.get_array("firstBatch"))
+        .expect("listIndexes cursor")
+        .iter()
+        .filter_map(|index| index.as_document())
+        .find(|index| index.get_str("name") == Ok("category_1"))
+        .expect("category_1 listed")
+        .clone();
+    assert_ne!(category.get_bool("prepareUnique"), Ok(true));
+}
+
+// =============================================================================
+// Index Advisor Tests
+// =============================================================================
+
+/// Test sampled selectivity and creating an advised index that then serves it.
+#[tokio::test]
+async fn test_index_advice_selectivity_and_create() {
+    let mongo = MongoTestContainer::start().await;
+    let collection = mongo.collection::<Document>("test_db", "index_advice");
+    collection.insert_many(fixtures::generate_test_documents(20)).await.expect("Failed to insert");
+
+    let filter = doc! { "category": "even", "value": { "$gte": 0 } };
+    let suggestion =
+        openmango::state::commands::suggest_index(&filter, Some(&doc! { "index": -1 }))
+            .expect("suggestion");
+    assert_eq!(suggestion.keys, doc! { "category": 1, "index": -1, "value": 1 });
+
+    let client = mongo.client.clone();
+    let db = mongo.db_name("test_db");
+    let (selectivity, before, after) = tokio::task::spawn_blocking(move || {
+        let manager = openmango::connection::ConnectionManager::new();
+        let selectivity = manager
+            .sample_selectivity(&client, &db, "index_advice", &filter, 1000)
+            .expect("sample selectivity");
+        let before = manager.list_indexes(&client, &db, "index_advice").expect("list indexes");
+        manager
+            .create_index(&client, &db, "index_advice", suggestion.index_document())
+            .expect("create index");
+        let after = manager.list_indexes(&client, &db, "index_advice").expect("list indexes");
+        (selectivity, before, after)
+    })
+    .await
+    .expect("blocking task");
+
+    assert_eq!(selectivity.sampled, 20);
+    assert_eq!(selectivity.matched, 10);
+    assert_eq!(selectivity.fraction(), Some(0.5));
+
+    let keys = doc! { "category": 1, "index": -1, "value": 1 };
+    assert_eq!(openmango::state::commands::existing_index_for(&keys, &before), None);
+    assert_eq!(
+        openmango::state::commands::existing_index_for(&keys, &after).as_deref(),
+        Some("category_1_index_-1_value_1")
+    );
+}
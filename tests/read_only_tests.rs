@@ -0,0 +1,262 @@
+//! Integration tests for the read-only guard on mutating connection operations.
+
+mod common;
+
+use std::path::Path;
+
+use futures::future::AbortHandle;
+use mongodb::Client;
+use mongodb::bson::{Document, doc};
+use openmango::connection::manager::mark_read_only_uri;
+use openmango::connection::{
+    AggregatePipelineError, ConnectionManager, CopyOptions, CountStrategy, CreateCollectionOptions,
+    CsvImportOptions, DocumentCount, DocumentReplacement, FindDocumentsOptions, IndexChange,
+    JsonImportOptions, QueryOptions, ValidationRules,
+};
+use openmango::error::Error as AppError;
+use openmango::models::SavedConnection;
+use tempfile::TempDir;
+use uuid::Uuid;
+
+use common::{MongoTestContainer, test_document};
+
+fn assert_read_only<T: std::fmt::Debug>(operation: &str, result: Result<T, AppError>) {
+    match result {
+        Err(AppError::ReadOnly(_)) => {}
+        other => panic!("{operation} should be refused on a read-only connection, got {other:?}"),
+    }
+}
+
+/// Connect through the manager so the client carries the read-only marker.
+fn connect_read_only(manager: &ConnectionManager, uri: &str) -> (Uuid, Client) {
+    let mut saved = SavedConnection::new("read-only".to_string(), uri.to_string());
+    saved.read_only = true;
+    let connection_id = Uuid::new_v4();
+    let (client, _runtime_meta) =
+        manager.connect_managed(connection_id, &saved).expect("read-only connect failed");
+    (connection_id, client)
+}
+
+#[tokio::test]
+async fn read_only_connection_refuses_every_mutating_op() {
+    let mongo = MongoTestContainer::start().await;
+    let db = mongo.db_name("read_only_db");
+    let collection = mongo.collection::<Document>("read_only_db", "items");
+    collection.insert_one(test_document("keep_me")).await.expect("Failed to seed");
+    let seeded_id = collection
+        .find_one(doc! {})
+        .await
+        .expect("Failed to find seed")
+        .and_then(|doc| doc.get("_id").cloned())
+        .expect("Seed has no _id");
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let json_path = temp_dir.path().join("import.jsonl");
+    std::fs::write(&json_path, "{\"name\": \"imported\"}\n").expect("Failed to write JSON");
+    let csv_path = temp_dir.path().join("import.csv");
+    std::fs::write(&csv_path, "name\nimported\n").expect("Failed to write CSV");
+
+    let uri = mongo.connection_string.clone();
+    let writable = mongo.client.clone();
+    tokio::task::spawn_blocking(move || {
+        let manager = ConnectionManager::new();
+        let (connection_id, client) = connect_read_only(&manager, &uri);
+        let id = seeded_id;
+
+        assert_read_only(
+            "insert_document",
+            manager.insert_document(&client, &db, "items", test_document("new")),
+        );
+        assert_read_only(
+            "insert_documents",
+            manager.insert_documents(&client, &db, "items", vec![test_document("new")]),
+        );
+        assert_read_only(
+            "update_one",
+            manager.update_one(&client, &db, "items", doc! {}, doc! { "$set": { "value": 1 } }),
+        );
+        assert_read_only(
+            "update_many",
+            manager.update_many(&client, &db, "items", doc! {}, doc! { "$set": { "value": 1 } }),
+        );
+        assert_read_only(
+            "replace_document",
+            manager.replace_document(&client, &db, "items", &id, test_document("replaced"), None),
+        );
+        assert_read_only("delete_document", manager.delete_document(&client, &db, "items", &id));
+        assert_read_only(
+            "delete_documents",
+            manager.delete_documents(&client, &db, "items", doc! {}),
+        );
+        assert_read_only(
+            "insert_documents_transactional",
+            manager.insert_documents_transactional(
+                &client,
+                &db,
+                "items",
+                vec![test_document("new")],
+            ),
+        );
+        assert_read_only(
+            "replace_documents_transactional",
+            manager.replace_documents_transactional(
+                &client,
+                &db,
+                "items",
+                vec![DocumentReplacement {
+                    id: id.clone(),
+                    replacement: test_document("replaced"),
+                    check: None,
+                }],
+            ),
+        );
+        assert_read_only(
+            "import_collection_json_with_options",
+            manager.import_collection_json_with_options(
+                &client,
+                &db,
+                "items",
+                &json_path,
+                JsonImportOptions::default(),
+            ),
+        );
+        assert_read_only(
+            "import_collection_csv",
+            manager.import_collection_csv(
+                &client,
+                &db,
+                "items",
+                &csv_path,
+                CsvImportOptions::default(),
+            ),
+        );
+        assert_read_only(
+            "create_index",
+            manager.create_index(&client, &db, "items", doc! { "key": { "name": 1 }, "name": "n" }),
+        );
+        assert_read_only(
+            "create_indexes",
+            manager.create_indexes(
+                &client,
+                &db,
+                "items",
+                vec![doc! { "key": { "value": 1 }, "name": "v" }],
+            ),
+        );
+        assert_read_only("drop_index", manager.drop_index(&client, &db, "items", "_id_"));
+        assert_read_only(
+            "modify_index",
+            manager.modify_index(&client, &db, "items", "v", IndexChange::Hide),
+        );
+        assert_read_only(
+            "create_collection",
+            manager.create_collection(&client, &db, "other", &CreateCollectionOptions::default()),
+        );
+        assert_read_only(
+            "apply_validation",
+            manager.apply_validation(&client, &db, "items", &ValidationRules::default()),
+        );
+        assert_read_only(
+            "rename_collection",
+            manager.rename_collection(&client, &db, "items", "renamed"),
+        );
+        assert_read_only("drop_collection", manager.drop_collection(&client, &db, "items"));
+        assert_read_only("drop_database", manager.drop_database(&client, &db));
+        assert_read_only(
+            "copy_collection_with_options",
+            manager.copy_collection_with_options(
+                &writable,
+                &db,
+                "items",
+                &client,
+                &db,
+                "copy",
+                CopyOptions::new(100, false),
+            ),
+        );
+        assert_read_only(
+            "copy_database",
+            manager.copy_database(&writable, &db, &client, &db, 100, false, &[]),
+        );
+
+        let (_abort, registration) = AbortHandle::new_pair();
+        let aggregated = manager.aggregate_pipeline_abortable(
+            &client,
+            &db,
+            "items",
+            vec![doc! { "$out": "items_out" }],
+            None,
+            false,
+            registration,
+        );
+        assert!(
+            matches!(aggregated, Err(AggregatePipelineError::Mongo(AppError::ReadOnly(_)))),
+            "$out pipeline should be refused on a read-only connection"
+        );
+
+        // Reads keep working.
+        let page = manager
+            .find_documents(
+                &client,
+                &db,
+                "items",
+                FindDocumentsOptions {
+                    filter: None,
+                    sort: None,
+                    projection: None,
+                    skip: 0,
+                    limit: 10,
+                    count: CountStrategy::Exact,
+                    keyset: None,
+                    options: QueryOptions::default(),
+                },
+            )
+            .expect("find should work on a read-only connection");
+        assert_eq!((page.documents.len(), page.count), (1, DocumentCount::Exact(1)));
+
+        manager.disconnect(connection_id);
+    })
+    .await
+    .expect("blocking task panicked");
+
+    let remaining = collection.find(doc! {}).await.expect("Failed to find");
+    let remaining: Vec<Document> = futures::TryStreamExt::try_collect(remaining).await.unwrap();
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].get_str("name").unwrap(), "keep_me");
+}
+
+#[tokio::test]
+async fn writable_connection_is_not_guarded() {
+    let mongo = MongoTestContainer::start().await;
+    let db = mongo.db_name("writable_db");
+    let uri = mongo.connection_string.clone();
+
+    let inserted = tokio::task::spawn_blocking(move || {
+        let manager = ConnectionManager::new();
+        let saved = SavedConnection::new("writable".to_string(), uri);
+        let connection_id = Uuid::new_v4();
+        let (client, _runtime_meta) =
+            manager.connect_managed(connection_id, &saved).expect("connect failed");
+        let inserted = manager.insert_document(&client, &db, "items", test_document("ok"));
+        manager.disconnect(connection_id);
+        inserted
+    })
+    .await
+    .expect("blocking task panicked");
+
+    inserted.expect("insert should succeed on a writable connection");
+}
+
+#[test]
+fn read_only_uri_refuses_mongorestore() {
+    let uri = mark_read_only_uri("mongodb://localhost:27017/?w=majority").expect("mark failed");
+    let manager = ConnectionManager::new();
+    assert_read_only(
+        "import_database_bson",
+        manager.import_database_bson(&uri, "db", Path::new("dump"), false),
+    );
+    assert_read_only(
+        "import_database_bson_with_progress",
+        manager.import_database_bson_with_progress(&uri, "db", Path::new("dump"), false, |_| {}),
+    );
+}
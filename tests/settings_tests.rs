@@ -115,6 +115,7 @@ fn test_workspace_tab_forge_roundtrip() {
         filter_raw: String::new(),
         sort_raw: String::new(),
         projection_raw: String::new(),
+        query_options_raw: String::new(),
         aggregation_pipeline: Vec::new(),
         stats_open: false,
         subview: CollectionSubview::Documents,
@@ -0,0 +1,119 @@
+//! Integration tests for typed TLS / X.509 connection settings against a `requireTLS` mongod.
+
+mod common;
+
+use std::time::Duration;
+
+use openmango::connection::ConnectionManager;
+use openmango::error::Error as AppError;
+use openmango::models::{AuthConfig, AuthMechanism, SavedConnection, TlsConfig};
+use uuid::Uuid;
+
+use common::tls::TlsMongo;
+
+fn init_logger() {
+    let _ = env_logger::builder().is_test(true).try_init();
+}
+
+async fn run_blocking<T, F>(f: F) -> T
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .expect("blocking task panicked while running connection-manager operation")
+}
+
+fn connect_and_list(saved: SavedConnection) -> Result<Vec<String>, AppError> {
+    let manager = ConnectionManager::new();
+    let connection_id = Uuid::new_v4();
+    let (client, _runtime_meta) = manager.connect_managed(connection_id, &saved)?;
+    let databases = manager.list_databases(&client);
+    manager.disconnect(connection_id);
+    databases
+}
+
+#[tokio::test]
+async fn tls_ca_and_client_certificate_connect_succeeds() {
+    init_logger();
+    let mongo = TlsMongo::start().await.expect("failed to start TLS mongo");
+    let mut saved = SavedConnection::new("tls".to_string(), mongo.uri());
+    saved.tls = Some(mongo.tls_config());
+
+    let databases = run_blocking(move || connect_and_list(saved))
+        .await
+        .expect("TLS connection with CA and client certificate should succeed");
+
+    assert!(databases.iter().any(|name| name == "admin"));
+}
+
+#[tokio::test]
+async fn tls_x509_authentication_succeeds() {
+    init_logger();
+    let mongo = TlsMongo::start().await.expect("failed to start TLS mongo");
+    mongo.create_x509_user().await.expect("failed to create X.509 user");
+    let mut saved = SavedConnection::new("tls-x509".to_string(), mongo.uri());
+    saved.tls = Some(mongo.tls_config());
+    saved.auth = Some(AuthConfig { mechanism: AuthMechanism::X509, ..AuthConfig::default() });
+
+    run_blocking(move || -> Result<(), AppError> {
+        let manager = ConnectionManager::new();
+        manager.test_connection(&saved, Duration::from_secs(10))?;
+        connect_and_list(saved).map(|_| ())
+    })
+    .await
+    .expect("MONGODB-X509 authentication should succeed");
+}
+
+#[tokio::test]
+async fn tls_without_trusted_ca_fails() {
+    init_logger();
+    let mongo = TlsMongo::start().await.expect("failed to start TLS mongo");
+    let mut saved = SavedConnection::new("tls-untrusted".to_string(), mongo.uri());
+    saved.tls = Some(TlsConfig { ca_file: None, ..mongo.tls_config() });
+
+    let result = run_blocking(move || {
+        let manager = ConnectionManager::new();
+        manager.test_connection(&saved, Duration::from_secs(3))
+    })
+    .await;
+
+    assert!(result.is_err(), "self-signed server certificate should not be trusted by default");
+}
+
+#[tokio::test]
+async fn tls_insecure_skips_certificate_validation() {
+    init_logger();
+    let mongo = TlsMongo::start().await.expect("failed to start TLS mongo");
+    let mut saved = SavedConnection::new("tls-insecure".to_string(), mongo.uri());
+    saved.tls = Some(TlsConfig { ca_file: None, insecure: true, ..mongo.tls_config() });
+
+    run_blocking(move || connect_and_list(saved))
+        .await
+        .expect("tlsInsecure should accept an untrusted server certificate");
+}
+
+#[tokio::test]
+async fn tls_missing_certificate_file_fails_validation() {
+    init_logger();
+    let mut saved = SavedConnection::new(
+        "tls-missing-file".to_string(),
+        "mongodb://localhost:27017/?directConnection=true".to_string(),
+    );
+    saved.tls = Some(TlsConfig {
+        enabled: true,
+        ca_file: Some("/nonexistent/openmango-ca.pem".to_string()),
+        ..TlsConfig::default()
+    });
+
+    let err = run_blocking(move || {
+        let manager = ConnectionManager::new();
+        manager
+            .test_connection(&saved, Duration::from_secs(1))
+            .expect_err("missing CA file should fail validation")
+    })
+    .await;
+
+    assert!(err.to_string().contains("TLS CA file does not exist"), "unexpected error: {err}");
+}
@@ -0,0 +1,140 @@
+//! Integration tests for all-or-nothing batch writes on a replica set.
+
+mod common;
+
+use futures::TryStreamExt;
+use mongodb::bson::{Bson, Document, doc};
+use mongodb::options::IndexOptions;
+use mongodb::{Client, IndexModel};
+use openmango::connection::{ConnectionManager, DocumentChange, DocumentReplacement};
+use openmango::error::Error as AppError;
+use openmango::models::SavedConnection;
+use uuid::Uuid;
+
+use common::replica_set::ReplicaSetMongo;
+
+fn connect(manager: &ConnectionManager, uri: &str) -> Client {
+    let saved = SavedConnection::new("replica-set".to_string(), uri.to_string());
+    let (client, _runtime_meta) =
+        manager.connect_managed(Uuid::new_v4(), &saved).expect("replica set connect failed");
+    client
+}
+
+async fn all_documents(client: &Client, db: &str, collection: &str) -> Vec<Document> {
+    client
+        .database(db)
+        .collection::<Document>(collection)
+        .find(doc! {})
+        .sort(doc! { "_id": 1 })
+        .await
+        .expect("Failed to find")
+        .try_collect()
+        .await
+        .expect("Failed to collect")
+}
+
+/// A duplicate `_id` in the third document rolls back the first two.
+#[tokio::test]
+async fn transactional_insert_writes_nothing_when_a_document_fails() {
+    let mongo = ReplicaSetMongo::start().await.expect("Failed to start replica set");
+    let uri = mongo.uri();
+
+    let result = tokio::task::spawn_blocking(move || {
+        let manager = ConnectionManager::new();
+        let client = connect(&manager, &uri);
+        assert!(manager.supports_transactions(&client).expect("hello failed"));
+
+        let documents = vec![
+            doc! { "_id": 1, "name": "first" },
+            doc! { "_id": 2, "name": "second" },
+            doc! { "_id": 1, "name": "duplicate" },
+        ];
+        manager.insert_documents_transactional(&client, "tx_db", "inserts", documents)
+    })
+    .await
+    .expect("blocking task panicked");
+
+    // One error for the whole batch, which the command layer reports as one failure event.
+    match result {
+        Err(AppError::Mongo(err)) => assert!(err.to_string().contains("duplicate key")),
+        other => panic!("expected a single duplicate key failure, got {other:?}"),
+    }
+    let client = Client::with_uri_str(mongo.uri()).await.expect("Failed to connect");
+    assert!(all_documents(&client, "tx_db", "inserts").await.is_empty());
+}
+
+/// A replacement whose target is gone aborts the batch, leaving earlier ones unapplied.
+#[tokio::test]
+async fn transactional_replace_writes_nothing_when_a_document_is_missing() {
+    let mongo = ReplicaSetMongo::start().await.expect("Failed to start replica set");
+    let seeded = vec![doc! { "_id": 1, "name": "one" }, doc! { "_id": 2, "name": "two" }];
+    let seed_client = Client::with_uri_str(mongo.uri()).await.expect("Failed to connect");
+    seed_client
+        .database("tx_db")
+        .collection::<Document>("replaces")
+        .insert_many(&seeded)
+        .await
+        .expect("Failed to seed");
+    let uri = mongo.uri();
+
+    let result = tokio::task::spawn_blocking(move || {
+        let manager = ConnectionManager::new();
+        let client = connect(&manager, &uri);
+        let replacement = |id: i32, name: &str| DocumentReplacement {
+            id: Bson::Int32(id),
+            replacement: doc! { "_id": id, "name": name },
+            check: None,
+        };
+        manager.replace_documents_transactional(
+            &client,
+            "tx_db",
+            "replaces",
+            vec![replacement(1, "changed"), replacement(2, "changed"), replacement(3, "missing")],
+        )
+    })
+    .await
+    .expect("blocking task panicked");
+
+    assert!(matches!(result, Err(AppError::Conflict(_))), "got {result:?}");
+    assert_eq!(all_documents(&seed_client, "tx_db", "replaces").await, seeded);
+}
+
+/// A replay failing on its second change rolls back the first, so the entry still applies.
+#[tokio::test]
+async fn replay_writes_nothing_when_a_change_fails() {
+    let mongo = ReplicaSetMongo::start().await.expect("Failed to start replica set");
+    let seed_client = Client::with_uri_str(mongo.uri()).await.expect("Failed to connect");
+    let coll = seed_client.database("tx_db").collection::<Document>("replays");
+    coll.insert_one(doc! { "_id": 1, "code": "a" }).await.expect("Failed to seed");
+    let unique = IndexOptions::builder().unique(true).build();
+    coll.create_index(IndexModel::builder().keys(doc! { "code": 1 }).options(unique).build())
+        .await
+        .expect("Failed to create index");
+    let uri = mongo.uri();
+
+    let result = tokio::task::spawn_blocking(move || {
+        let manager = ConnectionManager::new();
+        let client = connect(&manager, &uri);
+        let changes = vec![
+            DocumentChange {
+                id: Bson::Int32(1),
+                before: Some(doc! { "_id": 1, "code": "a" }),
+                after: Some(doc! { "_id": 1, "code": "b" }),
+            },
+            DocumentChange {
+                id: Bson::Int32(2),
+                before: None,
+                after: Some(doc! { "_id": 2, "code": "b" }),
+            },
+        ];
+        manager.apply_document_changes(&client, "tx_db", "replays", changes)
+    })
+    .await
+    .expect("blocking task panicked");
+
+    assert!(matches!(result, Err(AppError::Mongo(_))), "got {result:?}");
+    assert_eq!(
+        all_documents(&seed_client, "tx_db", "replays").await,
+        vec![doc! { "_id": 1, "code": "a" }]
+    );
+}
@@ -23,18 +23,37 @@ use common::transport::{
 
 struct LocalSocksProxy {
     port: u16,
+    credentials: Option<(String, String)>,
     shutdown_tx: Option<oneshot::Sender<()>>,
     handle: tokio::task::JoinHandle<()>,
 }
 
 impl LocalSocksProxy {
     async fn start() -> Self {
+        let config = server::Config::<server::DenyAuthentication>::default();
+        Self::spawn(Arc::new(config), None).await
+    }
+
+    async fn start_with_auth(username: &str, password: &str) -> Self {
+        let config = server::Config::<server::DenyAuthentication>::default().with_authentication(
+            server::SimpleUserPassword {
+                username: username.to_string(),
+                password: password.to_string(),
+            },
+        );
+        Self::spawn(Arc::new(config), Some((username.to_string(), password.to_string()))).await
+    }
+
+    async fn spawn<A>(config: Arc<server::Config<A>>, credentials: Option<(String, String)>) -> Self
+    where
+        A: server::Authentication + 'static,
+        A::Item: Send,
+    {
         let listener = TcpListener::bind(("127.0.0.1", 0))
             .await
             .expect("failed to bind local SOCKS5 listener");
         let port =
             listener.local_addr().expect("failed to read local SOCKS5 listener address").port();
-        let config = Arc::new(server::Config::<server::DenyAuthentication>::default());
         let (shutdown_tx, mut shutdown_rx) = oneshot::channel::<()>();
 
         let handle = tokio::spawn(async move {
@@ -58,7 +77,7 @@ impl LocalSocksProxy {
             }
         });
 
-        Self { port, shutdown_tx: Some(shutdown_tx), handle }
+        Self { port, credentials, shutdown_tx: Some(shutdown_tx), handle }
     }
 
     fn config(&self) -> ProxyConfig {
@@ -67,8 +86,8 @@ impl LocalSocksProxy {
             kind: ProxyKind::Socks5,
             host: "127.0.0.1".to_string(),
             port: self.port,
-            username: None,
-            password: None,
+            username: self.credentials.as_ref().map(|(username, _)| username.clone()),
+            password: self.credentials.as_ref().map(|(_, password)| password.clone()),
         }
     }
 }
@@ -183,6 +202,36 @@ async fn ssh_disconnect_closes_tunnel_endpoint() {
     );
 }
 
+#[tokio::test]
+async fn ssh_check_health_detects_closed_tunnel_and_recovers_after_reconnect() {
+    init_logger();
+    let stack = TransportStack::start().await.expect("failed to start transport stack");
+    let mut saved = make_saved_connection("ssh-health", stack.mongo_uri_for_ssh());
+    saved.ssh = Some(stack.ssh_password_config(false));
+
+    let (healthy, after_close, after_reconnect) = run_blocking(move || -> Result<_, AppError> {
+        let manager = ConnectionManager::new();
+        let connection_id = Uuid::new_v4();
+        let (client, _runtime_meta) = manager.connect_managed(connection_id, &saved)?;
+        let healthy = manager.check_health(connection_id, &client).is_ok();
+
+        manager.disconnect(connection_id);
+        let after_close = manager.check_health(connection_id, &client).is_ok();
+
+        let (client, _runtime_meta) = manager.connect_managed(connection_id, &saved)?;
+        let after_reconnect = manager.check_health(connection_id, &client).is_ok();
+        manager.disconnect(connection_id);
+
+        Ok((healthy, after_close, after_reconnect))
+    })
+    .await
+    .expect("managed SSH connection should succeed");
+
+    assert!(healthy, "expected heartbeat to pass through a running tunnel");
+    assert!(!after_close, "expected heartbeat to fail once the tunnel is gone");
+    assert!(after_reconnect, "expected heartbeat to pass after reconnecting");
+}
+
 #[tokio::test]
 async fn ssh_test_connection_then_connect_managed_succeeds() {
     init_logger();
@@ -343,29 +392,99 @@ async fn socks5_proxy_validation_errors() {
 }
 
 #[tokio::test]
-async fn ssh_and_proxy_enabled_together_rejected() {
+async fn ssh_through_authenticated_socks5_proxy_connect_managed_succeeds() {
     init_logger();
     let stack = TransportStack::start().await.expect("failed to start transport stack");
-    let proxy = LocalSocksProxy::start().await;
+    let proxy = LocalSocksProxy::start_with_auth("proxy-user", "proxy-pass").await;
 
-    let mut saved = make_saved_connection("ssh-proxy-conflict", stack.mongo_uri_for_ssh());
+    let mut saved = make_saved_connection("ssh-via-proxy", stack.mongo_uri_for_ssh());
     saved.ssh = Some(stack.ssh_password_config(false));
     saved.proxy = Some(proxy.config());
 
+    let runtime_meta = run_blocking(move || -> Result<_, AppError> {
+        let manager = ConnectionManager::new();
+        manager.test_connection(&saved, Duration::from_secs(5))?;
+
+        let connection_id = Uuid::new_v4();
+        let (client, runtime_meta) = manager.connect_managed(connection_id, &saved)?;
+        let databases = manager.list_databases(&client)?;
+        assert!(databases.iter().any(|name| name == "admin"));
+        manager.disconnect(connection_id);
+        Ok(runtime_meta)
+    })
+    .await
+    .expect("SSH tunnel chained through SOCKS5 proxy should succeed");
+
+    assert!(runtime_meta.ssh_tunnel_active);
+    assert!(runtime_meta.proxy_active);
+}
+
+#[tokio::test]
+async fn ssh_through_socks5_proxy_with_wrong_credentials_fails() {
+    init_logger();
+    let stack = TransportStack::start().await.expect("failed to start transport stack");
+    let proxy = LocalSocksProxy::start_with_auth("proxy-user", "proxy-pass").await;
+
+    let mut saved = make_saved_connection("ssh-via-proxy-bad-auth", stack.mongo_uri_for_ssh());
+    saved.ssh = Some(stack.ssh_password_config(false));
+    let mut proxy_config = proxy.config();
+    proxy_config.password = Some("not-the-password".to_string());
+    saved.proxy = Some(proxy_config);
+
     let err = run_blocking(move || {
         let manager = ConnectionManager::new();
         manager
-            .test_connection(&saved, Duration::from_secs(5))
-            .expect_err("enabling SSH and SOCKS5 together should fail fast")
+            .connect_managed(Uuid::new_v4(), &saved)
+            .expect_err("wrong proxy credentials should fail before SSH handshake")
     })
     .await;
 
     assert!(
-        err.to_string().contains("cannot be enabled together"),
-        "unexpected conflict error: {err}"
+        err.to_string().contains("SOCKS5 proxy authentication failed"),
+        "unexpected proxy auth error: {err}"
     );
 }
 
+#[tokio::test]
+async fn ssh_through_jump_host_connect_managed_succeeds() {
+    init_logger();
+    let stack = TransportStack::start().await.expect("failed to start transport stack");
+    let mut saved = make_saved_connection("ssh-jump", stack.mongo_uri_for_ssh());
+    saved.ssh = Some(stack.ssh_jump_config(&stack.ssh_password));
+
+    let runtime_meta = run_blocking(move || -> Result<_, AppError> {
+        let manager = ConnectionManager::new();
+        let connection_id = Uuid::new_v4();
+        let (client, runtime_meta) = manager.connect_managed(connection_id, &saved)?;
+        let databases = manager.list_databases(&client)?;
+        assert!(databases.iter().any(|name| name == "admin"));
+        manager.disconnect(connection_id);
+        Ok(runtime_meta)
+    })
+    .await
+    .expect("SSH tunnel through a jump host should succeed");
+
+    assert!(runtime_meta.ssh_tunnel_active);
+}
+
+#[tokio::test]
+async fn ssh_jump_host_auth_failure_names_the_hop() {
+    init_logger();
+    let stack = TransportStack::start().await.expect("failed to start transport stack");
+    let mut saved = make_saved_connection("ssh-jump-bad-auth", stack.mongo_uri_for_ssh());
+    saved.ssh = Some(stack.ssh_jump_config("not-the-password"));
+
+    let err = run_blocking(move || {
+        let manager = ConnectionManager::new();
+        manager
+            .connect_managed(Uuid::new_v4(), &saved)
+            .expect_err("wrong jump host password should fail")
+    })
+    .await;
+
+    assert!(err.to_string().contains("SSH jump host"), "unexpected jump host error: {err}");
+}
+
 #[tokio::test]
 async fn unmanaged_connect_rejects_enabled_ssh() {
     init_logger();